@@ -22,7 +22,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
 async fn run(client_id: String, client_secret: String) -> Result<(), Box<dyn std::error::Error>> {
     let flow = Flow::new(IsahcClient::new()?);
-    let provider = AppleProviderForSearchAdsApi::new(client_id, client_secret)?;
+    let provider = AppleProviderForSearchAdsApi::new(client_id, client_secret.into())?;
 
     let access_token_body = flow.execute(&provider, None).await?;
 
@@ -22,8 +22,11 @@ async fn run(client_id: String, client_secret: String) -> Result<(), Box<dyn std
     let scopes = vec![MastodonScope::Read, MastodonScope::Write];
 
     let flow = Flow::new(IsahcClient::new()?);
-    let provider =
-        MastodonProviderForApplications::new(BASE_URL_MASTODON_SOCIAL, client_id, client_secret)?;
+    let provider = MastodonProviderForApplications::new(
+        BASE_URL_MASTODON_SOCIAL,
+        client_id,
+        client_secret.into(),
+    )?;
 
     let access_token_body = flow.execute(&provider, scopes).await?;
 
@@ -31,7 +31,8 @@ async fn run(client_id: String, client_secret: String) -> Result<(), Box<dyn std
     let scopes = vec![GoogleScope::Email];
 
     let flow = Flow::new(IsahcClient::new()?);
-    let provider = GoogleProviderForDesktopApps::new(client_id, client_secret, RedirectUri::Oob)?;
+    let provider =
+        GoogleProviderForDesktopApps::new(client_id, client_secret.into(), RedirectUri::Oob)?;
 
     let authorization_url = flow.build_authorization_url(&provider, scopes, None)?;
 
@@ -77,7 +78,7 @@ async fn run(client_id: String, client_secret: String) -> Result<(), Box<dyn std
     code = code.trim_end().to_owned();
     println!("code: {code:?}");
 
-    let access_token_body = flow.handle_callback(&provider, code, None).await?;
+    let access_token_body = flow.handle_callback(&provider, code.into(), None).await?;
 
     println!("access_token_body: {access_token_body:?}");
 
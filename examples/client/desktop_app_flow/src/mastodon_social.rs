@@ -34,7 +34,7 @@ async fn run(client_id: String, client_secret: String) -> Result<(), Box<dyn std
     let provider = MastodonProviderForEndUsers::new(
         BASE_URL_MASTODON_SOCIAL,
         client_id,
-        client_secret,
+        client_secret.into(),
         RedirectUri::Oob,
     )?;
 
@@ -82,7 +82,7 @@ async fn run(client_id: String, client_secret: String) -> Result<(), Box<dyn std
     code = code.trim_end().to_owned();
     println!("code: {code:?}");
 
-    let access_token_body = flow.handle_callback(&provider, code, None).await?;
+    let access_token_body = flow.handle_callback(&provider, code.into(), None).await?;
 
     println!("access_token_body: {access_token_body:?}");
 
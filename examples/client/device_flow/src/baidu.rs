@@ -22,7 +22,7 @@ async fn run(app_key: String, secret_key: String) -> Result<(), Box<dyn std::err
     let scopes = vec![BaiduScope::Basic, BaiduScope::Netdisk];
 
     let flow = Flow::new(IsahcClient::new()?, IsahcClient::new()?);
-    let provider = BaiduProviderWithDevice::new(app_key, secret_key)?;
+    let provider = BaiduProviderWithDevice::new(app_key, secret_key.into())?;
 
     let access_token_body = flow
         .execute(
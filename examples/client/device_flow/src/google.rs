@@ -22,7 +22,7 @@ async fn run(client_id: String, client_secret: String) -> Result<(), Box<dyn std
     let scopes = vec![GoogleScope::Email, GoogleScope::DriveFile];
 
     let flow = Flow::new(IsahcClient::new()?, IsahcClient::new()?);
-    let provider = GoogleProviderForTvAndDeviceApps::new(client_id, client_secret)?;
+    let provider = GoogleProviderForTvAndDeviceApps::new(client_id, client_secret.into())?;
 
     let access_token_body = flow
         .execute(
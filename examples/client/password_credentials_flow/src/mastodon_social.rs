@@ -30,7 +30,7 @@ async fn run(
 
     let flow = Flow::new(IsahcClient::new()?);
     let provider =
-        MastodonProviderForBots::new(BASE_URL_MASTODON_SOCIAL, client_id, client_secret)?;
+        MastodonProviderForBots::new(BASE_URL_MASTODON_SOCIAL, client_id, client_secret.into())?;
 
     let access_token_body = flow.execute(&provider, scopes, username, password).await?;
 
@@ -17,15 +17,6 @@ use axum_sessions::{
 };
 use futures_util::future;
 use log::info;
-use oauth2_signin::{
-    oauth2_client::{
-        oauth2_core::utils::gen_code_challenge,
-        utils::{gen_code_verifier, gen_nonce, gen_state},
-    },
-    web_app::{
-        SigninFlowBuildAuthorizationUrlConfiguration, SigninFlowHandleCallbackByQueryConfiguration,
-    },
-};
 use rand::{thread_rng, Rng};
 
 use oauth2_client_web_app_flow_example::{config::Config, context::Context, helpers::*};
@@ -78,35 +69,10 @@ async fn auth_handler(
 ) -> Result<Redirect, Infallible> {
     let flow = ctx.signin_flow_map.get(provider.as_str()).unwrap();
 
-    let mut config = SigninFlowBuildAuthorizationUrlConfiguration::new();
-
-    let state = gen_state(10);
+    let (url, ticket) = flow.build_authorization_url_with_state_store(None).unwrap();
     session
-        .insert(state_session_key(&provider).as_str(), state.to_owned())
+        .insert(signin_ticket_session_key(&provider).as_str(), ticket)
         .unwrap();
-    config.set_state(state);
-
-    if flow.is_oidc_enabled() {
-        let nonce = gen_nonce(32);
-        session
-            .insert(nonce_session_key(&provider).as_str(), nonce.to_owned())
-            .unwrap();
-        config.set_nonce(nonce);
-    }
-
-    if flow.is_pkce_enabled() {
-        let code_verifier = gen_code_verifier(64);
-        session
-            .insert(
-                code_verifier_session_key(&provider).as_str(),
-                code_verifier.to_owned(),
-            )
-            .unwrap();
-        let (code_challenge, code_challenge_method) = gen_code_challenge(code_verifier, None);
-        config.set_code_challenge(code_challenge, code_challenge_method);
-    }
-
-    let url = flow.build_authorization_url(config).unwrap();
 
     info!("{provider} authorization_url {}", url.as_str());
 
@@ -123,34 +89,14 @@ async fn auth_callback_handler(
 
     let flow = ctx.signin_flow_map.get(provider.as_str()).unwrap();
 
-    let mut config = SigninFlowHandleCallbackByQueryConfiguration::new();
-
-    let state = session.get::<String>(state_session_key(&provider).as_str());
-    session.remove(state_session_key(&provider).as_str());
-    info!("{provider} state {state:?}");
-    if let Some(state) = state {
-        config.set_state(state);
-    }
-
-    if flow.is_oidc_enabled() {
-        let nonce = session.get::<String>(nonce_session_key(&provider).as_str());
-        session.remove(nonce_session_key(&provider).as_str());
-        info!("{provider} nonce {nonce:?}");
-        if let Some(nonce) = nonce {
-            config.set_nonce(nonce);
-        }
-    }
-
-    if flow.is_pkce_enabled() {
-        let code_verifier = session.get::<String>(code_verifier_session_key(&provider).as_str());
-        session.remove(code_verifier_session_key(&provider).as_str());
-        info!("{provider} code_verifier {code_verifier:?}");
-        if let Some(code_verifier) = code_verifier {
-            config.set_code_verifier(code_verifier);
-        }
-    }
-
-    let ret = flow.handle_callback_by_query(query_raw, config).await;
+    let ticket = session
+        .get::<String>(signin_ticket_session_key(&provider).as_str())
+        .unwrap_or_default();
+    session.remove(signin_ticket_session_key(&provider).as_str());
+
+    let ret = flow
+        .handle_callback_by_query_with_state_store(query_raw, ticket)
+        .await;
 
     info!("{provider} {ret:?}");
 
@@ -1,9 +1,6 @@
-use std::{env, fs, path::PathBuf};
+use std::{collections::HashMap, env, fs, path::PathBuf};
 
-use serde::Deserialize;
-use serde_json::{Map, Value};
-
-use oauth2_signin::oauth2_client::re_exports::{ClientId, ClientSecret, RedirectUri};
+use oauth2_signin::registry::ProviderConfig;
 
 #[derive(Debug, Clone)]
 pub struct Config {
@@ -34,38 +31,5 @@ impl Config {
 }
 
 //
-#[derive(Deserialize, Debug, Clone)]
-pub struct ClientsConfig {
-    pub github: ClientConfig,
-    pub google: ClientConfig,
-    pub twitch: ClientConfig,
-    pub mastodon_social: ClientConfig,
-    pub apple: ClientConfig,
-    pub instagram: ClientConfig,
-    pub facebook: ClientConfig,
-    pub amazon: ClientConfig,
-    pub gitlab: ClientConfig,
-    pub bitbucket: ClientConfig,
-    pub digitalocean: ClientConfig,
-    pub dropbox: ClientConfig,
-    pub linkedin: ClientConfig,
-    pub microsoft: ClientConfig,
-    pub yahoo: ClientConfig,
-    pub okta: ClientConfig,
-    pub pinterest: ClientConfig,
-    pub baidu: ClientConfig,
-    pub twitter: ClientConfig,
-    pub tiktok: ClientConfig,
-    pub zoho: ClientConfig,
-    pub linode: ClientConfig,
-}
-
-#[derive(Deserialize, Debug, Clone)]
-pub struct ClientConfig {
-    pub client_id: ClientId,
-    pub client_secret: ClientSecret,
-    pub redirect_uri: RedirectUri,
-    //
-    #[serde(flatten)]
-    pub extra: Map<String, Value>,
-}
+/// Keyed by the `/auth/{provider}` path segment.
+pub type ClientsConfig = HashMap<String, ProviderConfig>;
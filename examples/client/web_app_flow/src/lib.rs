@@ -3,15 +3,7 @@ pub mod context;
 
 //
 pub mod helpers {
-    pub fn state_session_key(provider: &str) -> String {
-        format!("state_{provider}")
-    }
-
-    pub fn nonce_session_key(provider: &str) -> String {
-        format!("nonce_{provider}")
-    }
-
-    pub fn code_verifier_session_key(provider: &str) -> String {
-        format!("code_verifier_{provider}")
+    pub fn signin_ticket_session_key(provider: &str) -> String {
+        format!("signin_ticket_{provider}")
     }
 }
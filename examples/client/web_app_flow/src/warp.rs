@@ -6,15 +6,6 @@ use std::sync::Arc;
 
 use futures_util::future;
 use log::info;
-use oauth2_signin::{
-    oauth2_client::{
-        oauth2_core::utils::gen_code_challenge,
-        utils::{gen_code_verifier, gen_nonce, gen_state},
-    },
-    web_app::{
-        SigninFlowBuildAuthorizationUrlConfiguration, SigninFlowHandleCallbackByQueryConfiguration,
-    },
-};
 use warp::{http::Uri, Filter};
 use warp_sessions::{MemoryStore, SessionWithStore};
 
@@ -78,38 +69,11 @@ async fn auth_handler(
 ) -> Result<(impl warp::Reply, SessionWithStore<MemoryStore>), warp::Rejection> {
     let flow = ctx.signin_flow_map.get(provider.as_str()).unwrap();
 
-    let mut config = SigninFlowBuildAuthorizationUrlConfiguration::new();
-
-    let state = gen_state(10);
+    let (url, ticket) = flow.build_authorization_url_with_state_store(None).unwrap();
     session_with_store
         .session
-        .insert(state_session_key(&provider).as_str(), state.to_owned())
+        .insert(signin_ticket_session_key(&provider).as_str(), ticket)
         .unwrap();
-    config.set_state(state);
-
-    if flow.is_oidc_enabled() {
-        let nonce = gen_nonce(32);
-        session_with_store
-            .session
-            .insert(nonce_session_key(&provider).as_str(), nonce.to_owned())
-            .unwrap();
-        config.set_nonce(nonce);
-    }
-
-    if flow.is_pkce_enabled() {
-        let code_verifier = gen_code_verifier(64);
-        session_with_store
-            .session
-            .insert(
-                code_verifier_session_key(&provider).as_str(),
-                code_verifier.to_owned(),
-            )
-            .unwrap();
-        let (code_challenge, code_challenge_method) = gen_code_challenge(code_verifier, None);
-        config.set_code_challenge(code_challenge, code_challenge_method);
-    }
-
-    let url = flow.build_authorization_url(config).unwrap();
 
     info!("{provider} authorization_url {}", url.as_str());
 
@@ -129,46 +93,17 @@ async fn auth_callback_handler(
 
     let flow = ctx.signin_flow_map.get(provider.as_str()).unwrap();
 
-    let mut config = SigninFlowHandleCallbackByQueryConfiguration::new();
-
-    let state = session_with_store
+    let ticket = session_with_store
         .session
-        .get::<String>(state_session_key(&provider).as_str());
+        .get::<String>(signin_ticket_session_key(&provider).as_str())
+        .unwrap_or_default();
     session_with_store
         .session
-        .remove(state_session_key(&provider).as_str());
-    info!("{provider} state {state:?}");
-    if let Some(state) = state {
-        config.set_state(state);
-    }
-
-    if flow.is_oidc_enabled() {
-        let nonce = session_with_store
-            .session
-            .get::<String>(nonce_session_key(&provider).as_str());
-        session_with_store
-            .session
-            .remove(nonce_session_key(&provider).as_str());
-        info!("{provider} nonce {nonce:?}");
-        if let Some(nonce) = nonce {
-            config.set_nonce(nonce);
-        }
-    }
-
-    if flow.is_pkce_enabled() {
-        let code_verifier = session_with_store
-            .session
-            .get::<String>(code_verifier_session_key(&provider).as_str());
-        session_with_store
-            .session
-            .remove(code_verifier_session_key(&provider).as_str());
-        info!("{provider} code_verifier {code_verifier:?}");
-        if let Some(code_verifier) = code_verifier {
-            config.set_code_verifier(code_verifier);
-        }
-    }
-
-    let ret = flow.handle_callback_by_query(query_raw, config).await;
+        .remove(signin_ticket_session_key(&provider).as_str());
+
+    let ret = flow
+        .handle_callback_by_query_with_state_store(query_raw, ticket)
+        .await;
 
     info!("{provider} {ret:?}");
 
@@ -10,7 +10,7 @@ use oauth2_core::{
     },
     http::{
         header::{ACCEPT, CONTENT_TYPE},
-        Error as HttpError,
+        Error as HttpError, StatusCode,
     },
     serde::{de::DeserializeOwned, Serialize},
     types::{Code, CodeVerifier, Scope},
@@ -18,6 +18,8 @@ use oauth2_core::{
 use serde_json::{Error as SerdeJsonError, Map, Value};
 use serde_urlencoded::ser::Error as SerdeUrlencodedSerError;
 
+#[cfg(feature = "with-tracing")]
+use crate::trace;
 use crate::ProviderExtAuthorizationCodeGrant;
 
 //
@@ -70,6 +72,13 @@ where
     type ParseResponseError = AccessTokenEndpointError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span(
+            self.provider.name(),
+            Some(trace::grant_type::AUTHORIZATION_CODE),
+            trace::endpoint::ACCESS_TOKEN,
+        );
+
         let mut body = BodyWithAuthorizationCodeGrant::new(
             self.code.to_owned(),
             self.provider.redirect_uri().map(|x| x.to_string()),
@@ -86,9 +95,11 @@ where
         }
 
         if let Some(request_ret) = self.provider.access_token_request_rendering(&body) {
-            let request = request_ret
-                .map_err(|err| AccessTokenEndpointError::CustomRenderingRequestFailed(err))?;
+            let request =
+                request_ret.map_err(AccessTokenEndpointError::CustomRenderingRequestFailed)?;
 
+            #[cfg(feature = "with-tracing")]
+            trace::request_rendered(&request);
             return Ok(request);
         }
 
@@ -106,6 +117,8 @@ where
             .body(body_str.as_bytes().to_vec())
             .map_err(AccessTokenEndpointError::MakeRequestFailed)?;
 
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
         Ok(request)
     }
 
@@ -113,9 +126,18 @@ where
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span(
+            self.provider.name(),
+            Some(trace::grant_type::AUTHORIZATION_CODE),
+            trace::endpoint::ACCESS_TOKEN,
+        );
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         if let Some(body_ret_ret) = self.provider.access_token_response_parsing(&response) {
-            let body_ret = body_ret_ret
-                .map_err(|err| AccessTokenEndpointError::CustomParsingResponseFailed(err))?;
+            let body_ret =
+                body_ret_ret.map_err(AccessTokenEndpointError::CustomParsingResponseFailed)?;
 
             return Ok(body_ret);
         }
@@ -132,8 +154,9 @@ where
             }
         }
 
-        let body = serde_json::from_slice::<RES_ErrorBody>(response.body())
-            .map_err(AccessTokenEndpointError::DeResponseBodyFailed)?;
+        let body = serde_json::from_slice::<RES_ErrorBody>(response.body()).map_err(|err| {
+            AccessTokenEndpointError::DeErrorResponseBodyFailed(response.status(), err)
+        })?;
         Ok(Err(body))
     }
 }
@@ -153,4 +176,6 @@ pub enum AccessTokenEndpointError {
     //
     #[error("DeResponseBodyFailed {0}")]
     DeResponseBodyFailed(SerdeJsonError),
+    #[error("DeErrorResponseBodyFailed {0} {1}")]
+    DeErrorResponseBodyFailed(StatusCode, SerdeJsonError),
 }
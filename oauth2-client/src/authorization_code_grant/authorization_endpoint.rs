@@ -4,7 +4,9 @@ use http_api_client_endpoint::{Body, Endpoint, Request, Response};
 use oauth2_core::{
     access_token_response::GENERAL_ERROR_BODY_KEY_ERROR,
     authorization_code_grant::{
-        authorization_request::{Query as REQ_Query, METHOD as REQ_METHOD},
+        authorization_request::{
+            AuthenticationParameters, Query as REQ_Query, METHOD as REQ_METHOD,
+        },
         authorization_response::{
             ErrorQuery as RES_ErrorQuery, SuccessfulQuery as RES_SuccessfulQuery,
         },
@@ -31,6 +33,7 @@ where
     pub state: Option<State>,
     pub code_challenge: Option<(CodeChallenge, CodeChallengeMethod)>,
     pub nonce: Option<Nonce>,
+    pub authentication: AuthenticationParameters,
 }
 impl<'a, SCOPE> AuthorizationEndpoint<'a, SCOPE>
 where
@@ -46,6 +49,7 @@ where
             state: None,
             code_challenge: None,
             nonce: None,
+            authentication: Default::default(),
         }
     }
 
@@ -72,6 +76,10 @@ where
     pub fn set_nonce(&mut self, nonce: Nonce) {
         self.nonce = Some(nonce);
     }
+
+    pub fn set_authentication(&mut self, authentication: AuthenticationParameters) {
+        self.authentication = authentication;
+    }
 }
 
 impl<'a, SCOPE> Endpoint for AuthorizationEndpoint<'a, SCOPE>
@@ -98,8 +106,15 @@ where
             query.code_challenge_method = Some(code_challenge_method.to_owned());
         }
         query.nonce = self.nonce.to_owned();
-
-        if let Some(extra) = self.provider.authorization_request_query_extra() {
+        query.authentication = self.authentication.to_owned();
+
+        if let Some(mut extra) = self.provider.authorization_request_query_extra() {
+            // The typed parameters win over the provider's, e.g. Zoho `prompt`.
+            if let Ok(Value::Object(authentication)) = serde_json::to_value(&self.authentication) {
+                for key in authentication.keys() {
+                    extra.remove(key);
+                }
+            }
             query.set_extra(extra);
         }
 
@@ -107,8 +122,7 @@ where
             .provider
             .authorization_request_query_serializing(&query)
         {
-            query_str_ret
-                .map_err(|err| AuthorizationEndpointError::CustomSerRequestQueryFailed(err))?
+            query_str_ret.map_err(AuthorizationEndpointError::CustomSerRequestQueryFailed)?
         } else {
             serde_qs::to_string(&query)
                 .map_err(AuthorizationEndpointError::SerRequestQueryFailed)?
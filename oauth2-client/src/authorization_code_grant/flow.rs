@@ -1,5 +1,6 @@
-use http_api_client::{Client, ClientRespondEndpointError};
-use http_api_client_endpoint::Endpoint as _;
+use std::sync::Arc;
+
+use http_api_client::{Body, Client, Request, RetryableClient};
 use oauth2_core::{
     authorization_code_grant::{
         access_token_response::{
@@ -8,16 +9,19 @@ use oauth2_core::{
         authorization_response::ErrorQuery as A_RES_ErrorQuery,
     },
     serde::{de::DeserializeOwned, Serialize},
-    types::{Code, CodeChallenge, CodeChallengeMethod, CodeVerifier, Nonce, Scope, State},
-    url::{ParseError as UrlParseError, Url},
+    types::{Code, CodeVerifier, Scope, State},
+    url::Url,
 };
 
-use crate::ProviderExtAuthorizationCodeGrant;
+use crate::{
+    retry::{self, RetryPolicy, RetrySleeper},
+    ProviderExtAuthorizationCodeGrant,
+};
 
-use super::{
-    parse_redirect_uri_query, AccessTokenEndpoint, AccessTokenEndpointError, AuthorizationEndpoint,
-    AuthorizationEndpointError, ParseRedirectUriQueryError,
+pub use super::machine::{
+    build_authorization_url, FlowBuildAuthorizationUrlConfiguration, FlowBuildAuthorizationUrlError,
 };
+use super::{AccessTokenEndpointError, Machine, MachineError, ParseRedirectUriQueryError};
 
 //
 //
@@ -28,13 +32,49 @@ where
     C: Client,
 {
     pub client_with_token: C,
+    /// The code exchange is never resent after the server may have processed it.
+    pub retry_policy: RetryPolicy,
+    /// Without it, each request is sent once.
+    pub retry_sleeper: Option<Arc<dyn RetrySleeper>>,
 }
 impl<C> Flow<C>
 where
     C: Client,
 {
+    /// Sends each request once, see [`Self::with_retry`].
     pub fn new(client_with_token: C) -> Self {
-        Self { client_with_token }
+        Self {
+            client_with_token,
+            retry_policy: RetryPolicy::disabled(),
+            retry_sleeper: None,
+        }
+    }
+
+    /// Retries with the default `retry_policy`, sleeping with the client.
+    pub fn with_retry(client_with_token: C) -> Self
+    where
+        C: RetryableClient + Clone + Send + Sync + 'static,
+    {
+        Self::new(client_with_token.clone()).configure(|x| {
+            x.retry_policy = RetryPolicy::default();
+            x.set_retry_sleeper(client_with_token.clone());
+        })
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    /// Retries only if `retry_policy` allows it.
+    pub fn set_retry_sleeper<S>(&mut self, retry_sleeper: S)
+    where
+        S: RetrySleeper + 'static,
+    {
+        self.retry_sleeper = Some(Arc::new(retry_sleeper));
     }
 }
 
@@ -71,25 +111,16 @@ where
         SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
     {
         // Step 3
-        let query = parse_redirect_uri_query(query.as_ref())
-            .map_err(FlowHandleCallbackError::ParseRedirectUriQueryError)?;
-
-        let query = query.map_err(FlowHandleCallbackError::AuthorizationFailed)?;
-
         let config: FlowHandleCallbackByQueryConfiguration = config.into().unwrap_or_default();
 
-        if let Some(ref state) = &config.state {
-            if let Some(query_state) = &query.state {
-                if state != query_state {
-                    return Err(FlowHandleCallbackError::StateMismatch);
-                }
-            } else {
-                return Err(FlowHandleCallbackError::StateMissing);
-            }
-        }
+        let mut machine = Machine::new(provider).configure(|x| {
+            x.state = config.state.to_owned();
+            x.code_verifier = config.code_verifier.to_owned();
+        });
 
-        self.handle_callback(provider, query.code, Some(config.into()))
-            .await
+        let request = machine.handle_callback_by_query(query)?;
+
+        self.respond(&machine, request).await
     }
 
     pub async fn handle_callback<SCOPE>(
@@ -103,30 +134,34 @@ where
     {
         let config: FlowHandleCallbackConfiguration = config.into().unwrap_or_default();
 
-        let mut access_token_endpoint = AccessTokenEndpoint::new(provider, code);
+        let mut machine = Machine::new(provider).configure(|x| {
+            x.code_verifier = config.code_verifier.to_owned();
+        });
 
-        if let Some(code_verifier) = &config.code_verifier {
-            access_token_endpoint.set_code_verifier(code_verifier.to_owned());
-        }
+        let request = machine.handle_callback(code)?;
 
-        let access_token_ret = self
-            .client_with_token
-            .respond_endpoint(&access_token_endpoint)
-            .await
-            .map_err(|err| match err {
-                ClientRespondEndpointError::RespondFailed(err) => {
-                    FlowHandleCallbackError::AccessTokenEndpointRespondFailed(Box::new(err))
-                }
-                ClientRespondEndpointError::EndpointRenderRequestFailed(err) => {
-                    FlowHandleCallbackError::AccessTokenEndpointError(err)
-                }
-                ClientRespondEndpointError::EndpointParseResponseFailed(err) => {
-                    FlowHandleCallbackError::AccessTokenEndpointError(err)
-                }
-            })?;
-
-        let access_token_successful_body =
-            access_token_ret.map_err(FlowHandleCallbackError::AccessTokenFailed)?;
+        self.respond(&machine, request).await
+    }
+
+    async fn respond<SCOPE>(
+        &self,
+        machine: &Machine<'_, SCOPE>,
+        request: Request<Body>,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, FlowHandleCallbackError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
+        let response = retry::respond(
+            &self.client_with_token,
+            self.retry_sleeper.as_deref(),
+            &self.retry_policy,
+            false,
+            request,
+        )
+        .await
+        .map_err(|err| FlowHandleCallbackError::AccessTokenEndpointRespondFailed(Box::new(err)))?;
+
+        let access_token_successful_body = machine.handle_response(response)?;
 
         Ok(access_token_successful_body)
     }
@@ -152,45 +187,24 @@ pub enum FlowHandleCallbackError {
     AccessTokenFailed(AT_RES_ErrorBody),
 }
 
-//
-//
-//
-#[derive(Debug, Clone, Default)]
-pub struct FlowBuildAuthorizationUrlConfiguration {
-    pub state: Option<State>,
-    pub code_challenge: Option<(CodeChallenge, CodeChallengeMethod)>,
-    pub nonce: Option<Nonce>,
-}
-impl FlowBuildAuthorizationUrlConfiguration {
-    pub fn new() -> Self {
-        Self::default()
-    }
-
-    pub fn configure<F>(mut self, mut f: F) -> Self
-    where
-        F: FnMut(&mut Self),
-    {
-        f(&mut self);
-        self
-    }
-
-    pub fn set_state(&mut self, state: State) {
-        self.state = Some(state);
-    }
-
-    pub fn set_code_challenge(
-        &mut self,
-        code_challenge: CodeChallenge,
-        code_challenge_method: CodeChallengeMethod,
-    ) {
-        self.code_challenge = Some((code_challenge, code_challenge_method));
-    }
-
-    pub fn set_nonce(&mut self, nonce: Nonce) {
-        self.nonce = Some(nonce);
+impl From<MachineError> for FlowHandleCallbackError {
+    fn from(err: MachineError) -> Self {
+        match err {
+            MachineError::ParseRedirectUriQueryError(err) => Self::ParseRedirectUriQueryError(err),
+            MachineError::AuthorizationFailed(query) => Self::AuthorizationFailed(query),
+            MachineError::StateMismatch => Self::StateMismatch,
+            MachineError::StateMissing => Self::StateMissing,
+            MachineError::AccessTokenEndpointError(err) => Self::AccessTokenEndpointError(err),
+            MachineError::AccessTokenFailed(body) => Self::AccessTokenFailed(body),
+            MachineError::UnexpectedResponse => {
+                unreachable!("the access token request is always rendered first")
+            }
+        }
     }
 }
 
+//
+//
 //
 #[derive(Debug, Clone, Default)]
 pub struct FlowHandleCallbackByQueryConfiguration {
@@ -250,52 +264,86 @@ impl From<FlowHandleCallbackByQueryConfiguration> for FlowHandleCallbackConfigur
     }
 }
 
-//
-//
-//
-pub fn build_authorization_url<SCOPE>(
-    provider: &dyn ProviderExtAuthorizationCodeGrant<Scope = SCOPE>,
-    scopes: impl Into<Option<Vec<SCOPE>>>,
-    config: impl Into<Option<FlowBuildAuthorizationUrlConfiguration>>,
-) -> Result<Url, FlowBuildAuthorizationUrlError>
-where
-    SCOPE: Scope + Serialize,
-{
-    let scopes = scopes.into().or_else(|| provider.scopes_default());
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    let config: FlowBuildAuthorizationUrlConfiguration = config.into().unwrap_or_default();
+    use core::time::Duration;
 
-    let mut authorization_endpoint = AuthorizationEndpoint::new(provider, scopes);
+    use crate::{
+        re_exports::RedirectUri,
+        test_utils::{response, TestClient, TestProvider, TestResponse},
+    };
 
-    if let Some(state) = &config.state {
-        authorization_endpoint.set_state(state.to_owned());
+    fn provider() -> TestProvider {
+        TestProvider::new().configure(|x| {
+            x.client_secret = None;
+            x.redirect_uri = Some(RedirectUri::new("https://client.example.com/cb").unwrap());
+        })
     }
 
-    if let Some((code_challenge, code_challenge_method)) = &config.code_challenge {
-        authorization_endpoint
-            .set_code_challenge(code_challenge.to_owned(), code_challenge_method.to_owned());
+    fn flow_and_client(responses: Vec<TestResponse>) -> (Flow<TestClient>, TestClient) {
+        let client = TestClient::new(responses);
+        let flow = Flow::with_retry(client.clone()).configure(|x| {
+            x.retry_policy = RetryPolicy::new().configure(|x| {
+                x.max_retries = 3;
+                x.jitter = false;
+            })
+        });
+        (flow, client)
     }
 
-    if let Some(nonce) = &config.nonce {
-        authorization_endpoint.set_nonce(nonce.to_owned());
+    #[test]
+    fn test_handle_callback_with_retry() {
+        let (flow, client) = flow_and_client(vec![
+            Some(response(503, "")),
+            Some(response(429, "")),
+            Some(response(400, r#"{"error":"temporarily_unavailable"}"#)),
+            Some(response(
+                200,
+                r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+            )),
+        ]);
+
+        match futures_executor::block_on(flow.handle_callback(
+            &provider(),
+            "CODE".to_owned().into(),
+            None,
+        )) {
+            Ok(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            Err(err) => panic!("{err}"),
+        }
+        assert_eq!(client.request_count(), 4);
+        assert_eq!(
+            client.sleeps(),
+            vec![
+                Duration::from_millis(500),
+                Duration::from_secs(1),
+                Duration::from_secs(2)
+            ]
+        );
     }
 
-    let authorization_endpoint_request = authorization_endpoint
-        .render_request()
-        .map_err(FlowBuildAuthorizationUrlError::AuthorizationEndpointError)?;
-
-    let url = authorization_endpoint_request.uri();
-
-    let url = Url::parse(url.to_string().as_str())
-        .map_err(FlowBuildAuthorizationUrlError::ToUrlFailed)?;
-
-    Ok(url)
-}
-
-#[derive(thiserror::Error, Debug)]
-pub enum FlowBuildAuthorizationUrlError {
-    #[error("AuthorizationEndpointError {0}")]
-    AuthorizationEndpointError(AuthorizationEndpointError),
-    #[error("ToUrlFailed {0}")]
-    ToUrlFailed(UrlParseError),
+    #[test]
+    fn test_handle_callback_without_retry() {
+        for responses in [
+            vec![Some(response(500, "")), Some(response(503, ""))],
+            vec![
+                Some(response(400, r#"{"error":"server_error"}"#)),
+                Some(response(503, "")),
+            ],
+            vec![None, Some(response(503, ""))],
+        ] {
+            let (flow, client) = flow_and_client(responses);
+
+            assert!(futures_executor::block_on(flow.handle_callback(
+                &provider(),
+                "CODE".to_owned().into(),
+                None,
+            ))
+            .is_err());
+            assert_eq!(client.request_count(), 1);
+            assert!(client.sleeps().is_empty());
+        }
+    }
 }
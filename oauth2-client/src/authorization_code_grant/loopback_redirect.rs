@@ -0,0 +1,607 @@
+//! https://datatracker.ietf.org/doc/html/rfc8252#section-7.3
+
+use core::time::Duration;
+use std::{
+    io::{ErrorKind as IoErrorKind, Read as _, Write as _},
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, TcpListener, TcpStream},
+    process::Command,
+    thread,
+    time::Instant,
+};
+
+use futures_channel::oneshot;
+use http_api_client::Client;
+use oauth2_core::{
+    authorization_code_grant::{
+        access_token_response::SuccessfulBody as AT_RES_SuccessfulBody,
+        authorization_response::ErrorQuery as A_RES_ErrorQuery,
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    types::{Code, RedirectUri, Scope, State},
+    url::Url,
+    utils::gen_code_challenge,
+};
+
+use crate::{
+    utils::{gen_code_verifier, gen_state},
+    ProviderExtAuthorizationCodeGrant,
+};
+
+use super::{
+    parse_redirect_uri_query, provider_ext::ProviderExtAuthorizationCodeGrantPkceSupportType, Flow,
+    FlowBuildAuthorizationUrlConfiguration, FlowBuildAuthorizationUrlError,
+    FlowHandleCallbackConfiguration, FlowHandleCallbackError, ParseRedirectUriQueryError,
+};
+
+pub const TIMEOUT_DEFAULT: Duration = Duration::from_secs(300);
+pub const SUCCESS_HTML_DEFAULT: &str = r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>Authorization succeeded</title></head><body><p>Authorization succeeded. You can close this window and return to the application.</p></body></html>"#;
+pub const FAILURE_HTML_DEFAULT: &str = r#"<!DOCTYPE html><html><head><meta charset="utf-8"><title>Authorization failed</title></head><body><p>Authorization failed. You can close this window and return to the application.</p></body></html>"#;
+
+const REQUEST_HEAD_LEN_MAX: usize = 8 * 1024;
+const REQUEST_READ_TIMEOUT: Duration = Duration::from_secs(5);
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+//
+//
+//
+pub trait BrowserLauncher {
+    fn launch(&self, url: &Url) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+impl<F> BrowserLauncher for F
+where
+    F: Fn(&Url) -> Result<(), Box<dyn std::error::Error + Send + Sync>>,
+{
+    fn launch(&self, url: &Url) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        self(url)
+    }
+}
+
+//
+#[derive(Debug, Clone, Default)]
+pub struct SystemBrowserLauncher;
+
+impl BrowserLauncher for SystemBrowserLauncher {
+    fn launch(&self, url: &Url) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let mut command = if cfg!(target_os = "macos") {
+            Command::new("open")
+        } else if cfg!(target_os = "windows") {
+            let mut command = Command::new("rundll32");
+            command.arg("url.dll,FileProtocolHandler");
+            command
+        } else {
+            Command::new("xdg-open")
+        };
+
+        let status = command.arg(url.as_str()).status()?;
+        if !status.success() {
+            return Err(format!("launch browser failed, {status}").into());
+        }
+
+        Ok(())
+    }
+}
+
+//
+//
+//
+#[derive(Debug)]
+pub struct LoopbackRedirect {
+    listener: TcpListener,
+    redirect_uri: RedirectUri,
+    pub success_html: String,
+    pub failure_html: String,
+    pub timeout: Option<Duration>,
+}
+
+impl LoopbackRedirect {
+    /// Binds `127.0.0.1:0`.
+    pub fn bind_v4() -> Result<Self, LoopbackRedirectError> {
+        Self::bind(IpAddr::V4(Ipv4Addr::LOCALHOST))
+    }
+
+    /// Binds `[::1]:0`.
+    pub fn bind_v6() -> Result<Self, LoopbackRedirectError> {
+        Self::bind(IpAddr::V6(Ipv6Addr::LOCALHOST))
+    }
+
+    pub fn bind(ip: IpAddr) -> Result<Self, LoopbackRedirectError> {
+        if !ip.is_loopback() {
+            return Err(LoopbackRedirectError::NotLoopbackAddress(ip));
+        }
+
+        let listener =
+            TcpListener::bind(SocketAddr::new(ip, 0)).map_err(LoopbackRedirectError::BindFailed)?;
+        let local_addr = listener
+            .local_addr()
+            .map_err(LoopbackRedirectError::BindFailed)?;
+
+        let redirect_uri = RedirectUri::new_loopback_ip(format!("http://{local_addr}/"))
+            .map_err(LoopbackRedirectError::MakeRedirectUriFailed)?;
+
+        Ok(Self {
+            listener,
+            redirect_uri,
+            success_html: SUCCESS_HTML_DEFAULT.to_owned(),
+            failure_html: FAILURE_HTML_DEFAULT.to_owned(),
+            timeout: Some(TIMEOUT_DEFAULT),
+        })
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn set_path(&mut self, path: impl AsRef<str>) {
+        if let RedirectUri::LoopbackIp(url) = &mut self.redirect_uri {
+            url.set_path(path.as_ref());
+        }
+    }
+
+    pub fn set_success_html(&mut self, success_html: impl Into<String>) {
+        self.success_html = success_html.into();
+    }
+
+    pub fn set_failure_html(&mut self, failure_html: impl Into<String>) {
+        self.failure_html = failure_html.into();
+    }
+
+    pub fn set_timeout(&mut self, timeout: impl Into<Option<Duration>>) {
+        self.timeout = timeout.into();
+    }
+
+    /// The provider must be built with this `redirect_uri`.
+    pub fn redirect_uri(&self) -> &RedirectUri {
+        &self.redirect_uri
+    }
+
+    fn path(&self) -> &str {
+        self.redirect_uri.as_url().map(|x| x.path()).unwrap_or("/")
+    }
+
+    /// Blocking.
+    ///
+    /// Requests to other paths (e.g. `/favicon.ico`) are answered with 404,
+    /// the first request to the redirect path is the only callback accepted.
+    pub fn wait_for_callback(self, state: Option<&State>) -> Result<Code, LoopbackRedirectError> {
+        let deadline = self.timeout.map(|x| Instant::now() + x);
+        if deadline.is_some() {
+            self.listener
+                .set_nonblocking(true)
+                .map_err(LoopbackRedirectError::AcceptFailed)?;
+        }
+
+        loop {
+            match deadline {
+                Some(deadline) if Instant::now() >= deadline => {
+                    return Err(LoopbackRedirectError::TimedOut)
+                }
+                _ => {}
+            }
+
+            let mut stream = match self.listener.accept() {
+                Ok((stream, _)) => stream,
+                Err(err) if err.kind() == IoErrorKind::WouldBlock => {
+                    thread::sleep(ACCEPT_POLL_INTERVAL);
+                    continue;
+                }
+                Err(err) => return Err(LoopbackRedirectError::AcceptFailed(err)),
+            };
+
+            let target = match read_request_target(&mut stream) {
+                Some(x) => x,
+                None => {
+                    let _ = write_response(&mut stream, "400 Bad Request", "");
+                    continue;
+                }
+            };
+
+            let (path, query) = target.split_once('?').unwrap_or((target.as_str(), ""));
+            if path != self.path() {
+                let _ = write_response(&mut stream, "404 Not Found", "");
+                continue;
+            }
+
+            let ret = check_callback_query(query, state);
+
+            let _ = match &ret {
+                Ok(_) => write_response(&mut stream, "200 OK", &self.success_html),
+                Err(_) => write_response(&mut stream, "400 Bad Request", &self.failure_html),
+            };
+
+            return ret;
+        }
+    }
+
+    /// Generates `state` and PKCE `code_verifier`, opens the authorization url through `browser_launcher`,
+    /// waits for the callback and then exchanges the code via `Flow::handle_callback`.
+    ///
+    /// PKCE is skipped only if the provider explicitly does not support it.
+    pub async fn authorize<C, SCOPE>(
+        self,
+        flow: &Flow<C>,
+        provider: &(dyn ProviderExtAuthorizationCodeGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        browser_launcher: &dyn BrowserLauncher,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, LoopbackRedirectError>
+    where
+        C: Client + Send + Sync,
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
+        if provider.redirect_uri() != Some(&self.redirect_uri) {
+            return Err(LoopbackRedirectError::RedirectUriMismatch);
+        }
+
+        let state = gen_state(None);
+        let code_verifier = match provider.pkce_support_type() {
+            Some(ProviderExtAuthorizationCodeGrantPkceSupportType::No) => None,
+            _ => Some(gen_code_verifier(None)),
+        };
+
+        let url = flow
+            .build_authorization_url(
+                provider,
+                scopes,
+                FlowBuildAuthorizationUrlConfiguration::new().configure(|x| {
+                    x.set_state(state.to_owned());
+                    if let Some(code_verifier) = &code_verifier {
+                        let (code_challenge, code_challenge_method) =
+                            gen_code_challenge(code_verifier.to_owned(), None);
+                        x.set_code_challenge(code_challenge, code_challenge_method);
+                    }
+                }),
+            )
+            .map_err(LoopbackRedirectError::FlowBuildAuthorizationUrlError)?;
+
+        browser_launcher
+            .launch(&url)
+            .map_err(LoopbackRedirectError::BrowserLaunchFailed)?;
+
+        let (tx, rx) = oneshot::channel();
+        thread::spawn(move || {
+            let _ = tx.send(self.wait_for_callback(Some(&state)));
+        });
+        let code = rx
+            .await
+            .map_err(|_| LoopbackRedirectError::CallbackWaitingAborted)??;
+
+        flow.handle_callback(
+            provider,
+            code,
+            FlowHandleCallbackConfiguration::new().configure(|x| {
+                if let Some(code_verifier) = &code_verifier {
+                    x.set_code_verifier(code_verifier.to_owned());
+                }
+            }),
+        )
+        .await
+        .map_err(|err| LoopbackRedirectError::FlowHandleCallbackError(Box::new(err)))
+    }
+}
+
+fn read_request_target(stream: &mut TcpStream) -> Option<String> {
+    stream.set_nonblocking(false).ok()?;
+    stream.set_read_timeout(Some(REQUEST_READ_TIMEOUT)).ok()?;
+
+    let mut buf = vec![];
+    let mut chunk = [0; 1024];
+    while !buf.windows(4).any(|x| x == b"\r\n\r\n") {
+        if buf.len() > REQUEST_HEAD_LEN_MAX {
+            return None;
+        }
+        let n = stream.read(&mut chunk).ok()?;
+        if n == 0 {
+            return None;
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    }
+
+    let head = core::str::from_utf8(&buf).ok()?;
+    let mut request_line = head.lines().next()?.split(' ');
+    match (request_line.next(), request_line.next()) {
+        (Some("GET"), Some(target)) => Some(target.to_owned()),
+        _ => None,
+    }
+}
+
+fn write_response(stream: &mut TcpStream, status: &str, html: &str) -> std::io::Result<()> {
+    write!(
+        stream,
+        "HTTP/1.1 {status}\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{html}",
+        html.len()
+    )?;
+    stream.flush()
+}
+
+fn check_callback_query(query: &str, state: Option<&State>) -> Result<Code, LoopbackRedirectError> {
+    let query = parse_redirect_uri_query(query)
+        .map_err(LoopbackRedirectError::ParseRedirectUriQueryError)?
+        .map_err(|err| LoopbackRedirectError::AuthorizationFailed(Box::new(err)))?;
+
+    if let Some(state) = state {
+        match &query.state {
+            Some(query_state) if query_state == state => {}
+            Some(_) => return Err(LoopbackRedirectError::StateMismatch),
+            None => return Err(LoopbackRedirectError::StateMissing),
+        }
+    }
+
+    Ok(query.code)
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LoopbackRedirectError {
+    #[error("NotLoopbackAddress {0}")]
+    NotLoopbackAddress(IpAddr),
+    #[error("BindFailed {0}")]
+    BindFailed(std::io::Error),
+    #[error("MakeRedirectUriFailed {0}")]
+    MakeRedirectUriFailed(String),
+    #[error("RedirectUriMismatch")]
+    RedirectUriMismatch,
+    //
+    #[error("FlowBuildAuthorizationUrlError {0}")]
+    FlowBuildAuthorizationUrlError(FlowBuildAuthorizationUrlError),
+    #[error("BrowserLaunchFailed {0}")]
+    BrowserLaunchFailed(Box<dyn std::error::Error + Send + Sync>),
+    //
+    #[error("AcceptFailed {0}")]
+    AcceptFailed(std::io::Error),
+    #[error("TimedOut")]
+    TimedOut,
+    #[error("CallbackWaitingAborted")]
+    CallbackWaitingAborted,
+    #[error("ParseRedirectUriQueryError {0}")]
+    ParseRedirectUriQueryError(ParseRedirectUriQueryError),
+    #[error("AuthorizationFailed {0:?}")]
+    AuthorizationFailed(Box<A_RES_ErrorQuery>),
+    #[error("StateMismatch")]
+    StateMismatch,
+    #[error("StateMissing")]
+    StateMissing,
+    //
+    #[error("FlowHandleCallbackError {0}")]
+    FlowHandleCallbackError(Box<FlowHandleCallbackError>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::{response, TestClient, TestProvider};
+
+    fn send_request(redirect_uri: &RedirectUri, path_and_query: &str) -> String {
+        let url = redirect_uri.as_url().unwrap();
+        let mut stream = TcpStream::connect(url.authority()).unwrap();
+        write!(
+            stream,
+            "GET {path_and_query} HTTP/1.1\r\nHost: {}\r\n\r\n",
+            url.authority()
+        )
+        .unwrap();
+        let mut s = String::new();
+        stream.read_to_string(&mut s).unwrap();
+        s
+    }
+
+    #[test]
+    fn test_bind() -> Result<(), Box<dyn std::error::Error>> {
+        let loopback_redirect = LoopbackRedirect::bind_v4()?;
+        match loopback_redirect.redirect_uri() {
+            RedirectUri::LoopbackIp(url) => {
+                assert_eq!(url.host_str(), Some("127.0.0.1"));
+                assert_ne!(url.port(), Some(0));
+                assert_eq!(url.path(), "/");
+            }
+            x => panic!("{x:?}"),
+        }
+
+        let loopback_redirect = loopback_redirect.configure(|x| x.set_path("/callback"));
+        assert!(loopback_redirect
+            .redirect_uri()
+            .to_string()
+            .ends_with("/callback"));
+
+        match LoopbackRedirect::bind("192.0.2.1".parse()?) {
+            Err(LoopbackRedirectError::NotLoopbackAddress(_)) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_callback() -> Result<(), Box<dyn std::error::Error>> {
+        let loopback_redirect = LoopbackRedirect::bind_v4()?;
+        let redirect_uri = loopback_redirect.redirect_uri().to_owned();
+
+        let handle = thread::spawn(move || {
+            (
+                send_request(&redirect_uri, "/favicon.ico"),
+                send_request(&redirect_uri, "/?code=CODE&state=STATE"),
+            )
+        });
+
+        match loopback_redirect.wait_for_callback(Some(&"STATE".into())) {
+            Ok(code) => assert_eq!(code, "CODE"),
+            Err(err) => panic!("{err}"),
+        }
+
+        let (res_favicon, res_callback) = handle.join().unwrap();
+        assert!(res_favicon.starts_with("HTTP/1.1 404"));
+        assert!(res_callback.starts_with("HTTP/1.1 200"));
+        assert!(res_callback.ends_with(SUCCESS_HTML_DEFAULT));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_callback_with_invalid_query() -> Result<(), Box<dyn std::error::Error>> {
+        for (query, state) in [
+            ("/?code=CODE&state=OTHER", Some(State::from("STATE"))),
+            ("/?code=CODE", Some(State::from("STATE"))),
+            ("/?error=access_denied&state=STATE", None),
+        ] {
+            let loopback_redirect = LoopbackRedirect::bind_v4()?;
+            let redirect_uri = loopback_redirect.redirect_uri().to_owned();
+
+            let handle = thread::spawn(move || send_request(&redirect_uri, query));
+
+            match loopback_redirect.wait_for_callback(state.as_ref()) {
+                Err(LoopbackRedirectError::StateMismatch) => assert!(query.contains("OTHER")),
+                Err(LoopbackRedirectError::StateMissing) => assert!(!query.contains("state")),
+                Err(LoopbackRedirectError::AuthorizationFailed(_)) => {
+                    assert!(query.contains("error"))
+                }
+                x => panic!("{x:?}"),
+            }
+
+            let res = handle.join().unwrap();
+            assert!(res.starts_with("HTTP/1.1 400"));
+            assert!(res.ends_with(FAILURE_HTML_DEFAULT));
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_callback_with_timeout() -> Result<(), Box<dyn std::error::Error>> {
+        let loopback_redirect =
+            LoopbackRedirect::bind_v4()?.configure(|x| x.set_timeout(Duration::from_millis(100)));
+
+        match loopback_redirect.wait_for_callback(None) {
+            Err(LoopbackRedirectError::TimedOut) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_wait_for_callback_with_timeout_and_other_requests(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        let loopback_redirect =
+            LoopbackRedirect::bind_v4()?.configure(|x| x.set_timeout(Duration::from_millis(100)));
+        let redirect_uri = loopback_redirect.redirect_uri().to_owned();
+
+        let now = Instant::now();
+        let handles = (0..4)
+            .map(|_| {
+                let url = redirect_uri.as_url().unwrap().to_owned();
+                thread::spawn(move || {
+                    let mut count = 0;
+                    while let Ok(mut stream) = TcpStream::connect(url.authority()) {
+                        let mut s = String::new();
+                        if write!(stream, "GET /favicon.ico HTTP/1.1\r\n\r\n").is_err()
+                            || stream.read_to_string(&mut s).is_err()
+                            || !s.starts_with("HTTP/1.1 404")
+                        {
+                            break;
+                        }
+                        count += 1;
+                    }
+                    count
+                })
+            })
+            .collect::<Vec<_>>();
+
+        match loopback_redirect.wait_for_callback(None) {
+            Err(LoopbackRedirectError::TimedOut) => {}
+            x => panic!("{x:?}"),
+        }
+        assert!(now.elapsed() < Duration::from_secs(1));
+
+        let count = handles
+            .into_iter()
+            .map(|x| x.join().unwrap())
+            .sum::<usize>();
+        assert!(count > 0);
+
+        Ok(())
+    }
+
+    //
+    fn provider(redirect_uri: RedirectUri) -> TestProvider {
+        TestProvider::new().configure(|x| {
+            x.client_secret = None;
+            x.redirect_uri = Some(redirect_uri.to_owned());
+        })
+    }
+
+    #[test]
+    fn test_authorize() -> Result<(), Box<dyn std::error::Error>> {
+        let loopback_redirect = LoopbackRedirect::bind_v4()?;
+        let provider = provider(loopback_redirect.redirect_uri().to_owned());
+        let client = TestClient::new([Some(response(
+            200,
+            r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+        ))]);
+        let flow = Flow::new(client.clone());
+
+        let redirect_uri = loopback_redirect.redirect_uri().to_owned();
+        let browser_launcher =
+            move |url: &Url| -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                let query = url.query_pairs().collect::<Vec<_>>();
+                assert!(query
+                    .iter()
+                    .any(|(k, v)| k == "redirect_uri" && v == redirect_uri.to_string().as_str()));
+                assert!(query
+                    .iter()
+                    .any(|(k, v)| k == "code_challenge_method" && v == "S256"));
+                let state = query
+                    .iter()
+                    .find(|(k, _)| k == "state")
+                    .map(|(_, v)| v.to_string())
+                    .ok_or("state missing")?;
+
+                let redirect_uri = redirect_uri.to_owned();
+                thread::spawn(move || {
+                    send_request(&redirect_uri, format!("/?code=CODE&state={state}").as_str())
+                });
+                Ok(())
+            };
+
+        match futures_executor::block_on(loopback_redirect.authorize(
+            &flow,
+            &provider,
+            None,
+            &browser_launcher,
+        )) {
+            Ok(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            Err(err) => panic!("{err}"),
+        }
+
+        let request_bodies = client.request_bodies.lock().unwrap();
+        assert_eq!(request_bodies.len(), 1);
+        assert!(request_bodies[0].contains("code=CODE"));
+        assert!(request_bodies[0].contains("code_verifier="));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_authorize_with_redirect_uri_mismatch() -> Result<(), Box<dyn std::error::Error>> {
+        let loopback_redirect = LoopbackRedirect::bind_v4()?;
+        let provider = provider("http://127.0.0.1:1/".parse()?);
+        let flow = Flow::new(TestClient::default());
+
+        let browser_launcher =
+            |_: &Url| -> Result<(), Box<dyn std::error::Error + Send + Sync>> { panic!() };
+
+        match futures_executor::block_on(loopback_redirect.authorize(
+            &flow,
+            &provider,
+            None,
+            &browser_launcher,
+        )) {
+            Err(LoopbackRedirectError::RedirectUriMismatch) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,296 @@
+//! Sans-IO, the caller sends the request and passes the response back.
+
+use http_api_client_endpoint::{Body, Endpoint as _, Request, Response};
+use oauth2_core::{
+    authorization_code_grant::authorization_request::AuthenticationParameters,
+    authorization_code_grant::{
+        access_token_response::{
+            ErrorBody as AT_RES_ErrorBody, SuccessfulBody as AT_RES_SuccessfulBody,
+        },
+        authorization_response::ErrorQuery as A_RES_ErrorQuery,
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    types::{Code, CodeChallenge, CodeChallengeMethod, CodeVerifier, Nonce, Scope, State},
+    url::{ParseError as UrlParseError, Url},
+};
+
+use crate::ProviderExtAuthorizationCodeGrant;
+
+use super::{
+    parse_redirect_uri_query, AccessTokenEndpoint, AccessTokenEndpointError, AuthorizationEndpoint,
+    AuthorizationEndpointError, ParseRedirectUriQueryError,
+};
+
+//
+//
+//
+/// Step 1 is `build_authorization_url`, the machine starts from the callback.
+#[derive(Clone)]
+pub struct Machine<'a, SCOPE>
+where
+    SCOPE: Scope,
+{
+    provider: &'a (dyn ProviderExtAuthorizationCodeGrant<Scope = SCOPE> + Send + Sync),
+    pub state: Option<State>,
+    pub code_verifier: Option<CodeVerifier>,
+    //
+    access_token_endpoint: Option<AccessTokenEndpoint<'a, SCOPE>>,
+}
+impl<'a, SCOPE> Machine<'a, SCOPE>
+where
+    SCOPE: Scope + Serialize + DeserializeOwned,
+{
+    pub fn new(
+        provider: &'a (dyn ProviderExtAuthorizationCodeGrant<Scope = SCOPE> + Send + Sync),
+    ) -> Self {
+        Self {
+            provider,
+            state: None,
+            code_verifier: None,
+            access_token_endpoint: None,
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn set_state(&mut self, state: State) {
+        self.state = Some(state);
+    }
+
+    pub fn set_code_verifier(&mut self, code_verifier: CodeVerifier) {
+        self.code_verifier = Some(code_verifier);
+    }
+
+    /// Returns the access token request.
+    #[allow(clippy::result_large_err)]
+    pub fn handle_callback_by_query(
+        &mut self,
+        query: impl AsRef<str>,
+    ) -> Result<Request<Body>, MachineError> {
+        let query = parse_redirect_uri_query(query.as_ref())
+            .map_err(MachineError::ParseRedirectUriQueryError)?;
+
+        let query = query.map_err(MachineError::AuthorizationFailed)?;
+
+        if let Some(state) = &self.state {
+            if let Some(query_state) = &query.state {
+                if state != query_state {
+                    return Err(MachineError::StateMismatch);
+                }
+            } else {
+                return Err(MachineError::StateMissing);
+            }
+        }
+
+        self.handle_callback(query.code)
+    }
+
+    /// Returns the access token request.
+    #[allow(clippy::result_large_err)]
+    pub fn handle_callback(&mut self, code: Code) -> Result<Request<Body>, MachineError> {
+        let mut access_token_endpoint = AccessTokenEndpoint::new(self.provider, code);
+
+        if let Some(code_verifier) = &self.code_verifier {
+            access_token_endpoint.set_code_verifier(code_verifier.to_owned());
+        }
+
+        let request = access_token_endpoint
+            .render_request()
+            .map_err(MachineError::AccessTokenEndpointError)?;
+
+        self.access_token_endpoint = Some(access_token_endpoint);
+
+        Ok(request)
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn handle_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, MachineError> {
+        let access_token_endpoint = self
+            .access_token_endpoint
+            .as_ref()
+            .ok_or(MachineError::UnexpectedResponse)?;
+
+        access_token_endpoint
+            .parse_response(response)
+            .map_err(MachineError::AccessTokenEndpointError)?
+            .map_err(MachineError::AccessTokenFailed)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum MachineError {
+    #[error("ParseRedirectUriQueryError {0}")]
+    ParseRedirectUriQueryError(ParseRedirectUriQueryError),
+    //
+    #[error("AuthorizationFailed {0:?}")]
+    AuthorizationFailed(A_RES_ErrorQuery),
+    #[error("StateMismatch")]
+    StateMismatch,
+    #[error("StateMissing")]
+    StateMissing,
+    //
+    #[error("AccessTokenEndpointError {0}")]
+    AccessTokenEndpointError(AccessTokenEndpointError),
+    #[error("AccessTokenFailed {0:?}")]
+    AccessTokenFailed(AT_RES_ErrorBody),
+    //
+    #[error("UnexpectedResponse")]
+    UnexpectedResponse,
+}
+
+//
+//
+//
+#[derive(Debug, Clone, Default)]
+pub struct FlowBuildAuthorizationUrlConfiguration {
+    pub state: Option<State>,
+    pub code_challenge: Option<(CodeChallenge, CodeChallengeMethod)>,
+    pub nonce: Option<Nonce>,
+    pub authentication: AuthenticationParameters,
+}
+impl FlowBuildAuthorizationUrlConfiguration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn set_state(&mut self, state: State) {
+        self.state = Some(state);
+    }
+
+    pub fn set_code_challenge(
+        &mut self,
+        code_challenge: CodeChallenge,
+        code_challenge_method: CodeChallengeMethod,
+    ) {
+        self.code_challenge = Some((code_challenge, code_challenge_method));
+    }
+
+    pub fn set_nonce(&mut self, nonce: Nonce) {
+        self.nonce = Some(nonce);
+    }
+
+    pub fn set_authentication(&mut self, authentication: AuthenticationParameters) {
+        self.authentication = authentication;
+    }
+}
+
+//
+//
+//
+pub fn build_authorization_url<SCOPE>(
+    provider: &dyn ProviderExtAuthorizationCodeGrant<Scope = SCOPE>,
+    scopes: impl Into<Option<Vec<SCOPE>>>,
+    config: impl Into<Option<FlowBuildAuthorizationUrlConfiguration>>,
+) -> Result<Url, FlowBuildAuthorizationUrlError>
+where
+    SCOPE: Scope + Serialize,
+{
+    let scopes = scopes.into().or_else(|| provider.scopes_default());
+
+    let config: FlowBuildAuthorizationUrlConfiguration = config.into().unwrap_or_default();
+
+    let mut authorization_endpoint = AuthorizationEndpoint::new(provider, scopes);
+
+    if let Some(state) = &config.state {
+        authorization_endpoint.set_state(state.to_owned());
+    }
+
+    if let Some((code_challenge, code_challenge_method)) = &config.code_challenge {
+        authorization_endpoint
+            .set_code_challenge(code_challenge.to_owned(), code_challenge_method.to_owned());
+    }
+
+    if let Some(nonce) = &config.nonce {
+        authorization_endpoint.set_nonce(nonce.to_owned());
+    }
+
+    authorization_endpoint.set_authentication(config.authentication.to_owned());
+
+    let authorization_endpoint_request = authorization_endpoint
+        .render_request()
+        .map_err(FlowBuildAuthorizationUrlError::AuthorizationEndpointError)?;
+
+    let url = authorization_endpoint_request.uri();
+
+    let url = Url::parse(url.to_string().as_str())
+        .map_err(FlowBuildAuthorizationUrlError::ToUrlFailed)?;
+
+    Ok(url)
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum FlowBuildAuthorizationUrlError {
+    #[error("AuthorizationEndpointError {0}")]
+    AuthorizationEndpointError(AuthorizationEndpointError),
+    #[error("ToUrlFailed {0}")]
+    ToUrlFailed(UrlParseError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::TestProvider;
+
+    #[test]
+    fn test_machine() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = TestProvider::new();
+
+        let mut machine = Machine::new(&provider).configure(|x| {
+            x.set_state("STATE".into());
+            x.set_code_verifier("CODE_VERIFIER".into());
+        });
+
+        match machine.handle_response(Response::new(vec![])) {
+            Err(MachineError::UnexpectedResponse) => {}
+            ret => panic!("{ret:?}"),
+        }
+
+        match machine.handle_callback_by_query("code=CODE&state=xxx") {
+            Err(MachineError::StateMismatch) => {}
+            ret => panic!("{ret:?}"),
+        }
+        match machine.handle_callback_by_query("code=CODE") {
+            Err(MachineError::StateMissing) => {}
+            ret => panic!("{ret:?}"),
+        }
+        match machine.handle_callback_by_query("error=access_denied&state=STATE") {
+            Err(MachineError::AuthorizationFailed(query)) => {
+                assert_eq!(query.error.to_string(), "access_denied")
+            }
+            ret => panic!("{ret:?}"),
+        }
+
+        let request = machine.handle_callback_by_query("code=CODE&state=STATE")?;
+        assert_eq!(request.uri(), "https://example.com/token");
+        assert_eq!(
+            request.body(),
+            b"grant_type=authorization_code&code=CODE&client_id=CLIENT_ID&client_secret=CLIENT_SECRET&code_verifier=CODE_VERIFIER"
+        );
+
+        let response = Response::builder()
+            .status(200)
+            .body(br#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#.to_vec())?;
+        let body = machine.handle_response(response)?;
+        assert_eq!(body.access_token, "ACCESS_TOKEN");
+
+        Ok(())
+    }
+}
@@ -11,12 +11,27 @@ pub use authorization_endpoint::{
     ParseRedirectUriQueryError,
 };
 
+//
+pub mod machine;
+
+pub use machine::{
+    build_authorization_url, FlowBuildAuthorizationUrlConfiguration,
+    FlowBuildAuthorizationUrlError, Machine, MachineError,
+};
+
 //
 #[cfg(feature = "with-flow")]
 pub mod flow;
 #[cfg(feature = "with-flow")]
 pub use flow::{
-    build_authorization_url, Flow, FlowBuildAuthorizationUrlConfiguration,
-    FlowBuildAuthorizationUrlError, FlowHandleCallbackByQueryConfiguration,
-    FlowHandleCallbackConfiguration, FlowHandleCallbackError,
+    Flow, FlowHandleCallbackByQueryConfiguration, FlowHandleCallbackConfiguration,
+    FlowHandleCallbackError,
+};
+
+//
+#[cfg(feature = "with-flow")]
+pub mod loopback_redirect;
+#[cfg(feature = "with-flow")]
+pub use loopback_redirect::{
+    BrowserLauncher, LoopbackRedirect, LoopbackRedirectError, SystemBrowserLauncher,
 };
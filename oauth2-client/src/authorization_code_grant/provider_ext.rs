@@ -14,28 +14,20 @@ use crate::{
 };
 
 //
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub enum ProviderExtAuthorizationCodeGrantOidcSupportType {
+    #[default]
     No,
     Yes,
     Force,
 }
-impl Default for ProviderExtAuthorizationCodeGrantOidcSupportType {
-    fn default() -> Self {
-        Self::No
-    }
-}
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
 pub enum ProviderExtAuthorizationCodeGrantPkceSupportType {
+    #[default]
     No,
     Yes,
 }
-impl Default for ProviderExtAuthorizationCodeGrantPkceSupportType {
-    fn default() -> Self {
-        Self::No
-    }
-}
 
 //
 pub trait ProviderExtAuthorizationCodeGrant: Provider + DynClone {
@@ -157,10 +149,18 @@ where
         self.inner.token_endpoint_url()
     }
 
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
     fn extra(&self) -> Option<Map<String, Value>> {
         self.inner.extra()
     }
 
+    fn principal(&self) -> Option<String> {
+        self.inner.principal()
+    }
+
     // Note
 }
 
@@ -226,7 +226,7 @@ where
         self.inner.access_token_request_rendering(body)
     }
 
-    #[allow(clippy::type_complexity)]
+    #[allow(clippy::type_complexity, clippy::result_large_err)]
     fn access_token_response_parsing(
         &self,
         response: &Response<Body>,
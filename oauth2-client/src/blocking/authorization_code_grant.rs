@@ -0,0 +1,139 @@
+use std::sync::Arc;
+
+use futures_executor::block_on;
+use oauth2_core::{
+    authorization_code_grant::access_token_response::SuccessfulBody as AT_RES_SuccessfulBody,
+    serde::{de::DeserializeOwned, Serialize},
+    types::{Code, Scope},
+    url::Url,
+};
+
+use crate::{
+    authorization_code_grant::{
+        build_authorization_url, Flow as AsyncFlow, FlowBuildAuthorizationUrlConfiguration,
+        FlowBuildAuthorizationUrlError, FlowHandleCallbackByQueryConfiguration,
+        FlowHandleCallbackConfiguration, FlowHandleCallbackError,
+    },
+    ProviderExtAuthorizationCodeGrant,
+};
+
+use super::{BlockingClient, BlockingClientAdapter};
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct Flow<C>
+where
+    C: BlockingClient + Send + Sync,
+{
+    pub inner: AsyncFlow<BlockingClientAdapter<Arc<C>>>,
+}
+impl<C> Flow<C>
+where
+    C: BlockingClient + Send + Sync,
+{
+    /// Sends each request once, see [`Self::with_retry`].
+    pub fn new(client_with_token: C) -> Self {
+        Self {
+            inner: AsyncFlow::new(BlockingClientAdapter(Arc::new(client_with_token))),
+        }
+    }
+
+    /// Retries with the default `retry_policy`, sleeping with [`BlockingClient::sleep`].
+    pub fn with_retry(client_with_token: C) -> Self
+    where
+        C: 'static,
+    {
+        Self {
+            inner: AsyncFlow::with_retry(BlockingClientAdapter(Arc::new(client_with_token))),
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    /// Don't require state if for Mobile & Desktop Apps
+    pub fn build_authorization_url<SCOPE>(
+        &self,
+        provider: &dyn ProviderExtAuthorizationCodeGrant<Scope = SCOPE>,
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        config: impl Into<Option<FlowBuildAuthorizationUrlConfiguration>>,
+    ) -> Result<Url, FlowBuildAuthorizationUrlError>
+    where
+        SCOPE: Scope + Serialize,
+    {
+        build_authorization_url(provider, scopes, config)
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn handle_callback_by_query<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtAuthorizationCodeGrant<Scope = SCOPE> + Send + Sync),
+        query: impl AsRef<str>,
+        config: impl Into<Option<FlowHandleCallbackByQueryConfiguration>>,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, FlowHandleCallbackError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
+        block_on(self.inner.handle_callback_by_query(provider, query, config))
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn handle_callback<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtAuthorizationCodeGrant<Scope = SCOPE> + Send + Sync),
+        code: Code,
+        config: impl Into<Option<FlowHandleCallbackConfiguration>>,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, FlowHandleCallbackError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
+        block_on(self.inner.handle_callback(provider, code, config))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+
+    use crate::test_utils::{response, TestClient, TestProvider};
+
+    #[test]
+    fn test_handle_callback() {
+        let client = TestClient::new([
+            Some(response(503, "")),
+            Some(response(503, "")),
+            Some(response(
+                200,
+                r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+            )),
+        ]);
+        let provider = TestProvider::new().configure(|x| {
+            x.redirect_uri = Some("https://client.example.com/cb".parse().unwrap());
+        });
+
+        assert!(Flow::new(client.clone())
+            .handle_callback(&provider, "CODE".to_owned().into(), None)
+            .is_err());
+        assert_eq!(client.request_count(), 1);
+        assert!(client.sleeps().is_empty());
+
+        let flow = Flow::with_retry(client.clone()).configure(|x| {
+            x.inner.retry_policy.jitter = false;
+        });
+        match flow.handle_callback(&provider, "CODE".to_owned().into(), None) {
+            Ok(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            Err(err) => panic!("{err}"),
+        }
+        assert_eq!(client.request_count(), 3);
+        assert_eq!(client.sleeps(), vec![Duration::from_millis(500)]);
+    }
+}
@@ -0,0 +1,62 @@
+use core::time::Duration;
+use std::sync::Arc;
+
+use http_api_client::{async_trait, Client, RetryableClient};
+use http_api_client_endpoint::{Body, Request, Response};
+
+//
+//
+//
+pub trait BlockingClient {
+    type RespondError: std::error::Error + Send + Sync + 'static;
+
+    fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError>;
+
+    fn sleep(&self, dur: Duration) {
+        std::thread::sleep(dur)
+    }
+}
+
+impl<C> BlockingClient for Arc<C>
+where
+    C: BlockingClient + ?Sized,
+{
+    type RespondError = C::RespondError;
+
+    fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError> {
+        C::respond(self, request)
+    }
+
+    fn sleep(&self, dur: Duration) {
+        C::sleep(self, dur)
+    }
+}
+
+//
+//
+//
+/// Lets the async flows run over a [`BlockingClient`].
+#[derive(Debug, Clone)]
+pub struct BlockingClientAdapter<C>(pub C);
+
+#[async_trait]
+impl<C> Client for BlockingClientAdapter<C>
+where
+    C: BlockingClient + Send + Sync,
+{
+    type RespondError = C::RespondError;
+
+    async fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError> {
+        self.0.respond(request)
+    }
+}
+
+#[async_trait]
+impl<C> RetryableClient for BlockingClientAdapter<C>
+where
+    C: BlockingClient + Send + Sync,
+{
+    async fn sleep(&self, dur: Duration) {
+        self.0.sleep(dur)
+    }
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use futures_executor::block_on;
+use oauth2_core::{
+    device_authorization_grant::{
+        device_access_token_response::SuccessfulBody as DAT_RES_SuccessfulBody,
+        device_authorization_response::{UserCode, VerificationUri, VerificationUriComplete},
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    types::Scope,
+};
+
+use crate::{
+    device_authorization_grant::{
+        Flow as AsyncFlow, FlowExecuteConfiguration as AsyncFlowExecuteConfiguration,
+        FlowExecuteError, FlowExecuteStatus, UserInteraction,
+    },
+    ProviderExtDeviceAuthorizationGrant, RetryPolicy,
+};
+
+use super::{BlockingClient, BlockingClientAdapter};
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct Flow<C1, C2>
+where
+    C1: BlockingClient + Send + Sync,
+    C2: BlockingClient + Send + Sync,
+{
+    pub inner: AsyncFlow<BlockingClientAdapter<Arc<C1>>, BlockingClientAdapter<Arc<C2>>>,
+}
+impl<C1, C2> Flow<C1, C2>
+where
+    C1: BlockingClient + Send + Sync,
+    C2: BlockingClient + Send + Sync,
+{
+    /// Sends each request once, see [`Self::with_retry`]. Polls still wait for `interval`.
+    pub fn new(client_with_auth: C1, client_with_token: C2) -> Self {
+        Self::with_retry(client_with_auth, client_with_token)
+            .configure(|x| x.inner.retry_policy = RetryPolicy::disabled())
+    }
+
+    /// Retries with the default `retry_policy`, sleeping with [`BlockingClient::sleep`].
+    pub fn with_retry(client_with_auth: C1, client_with_token: C2) -> Self {
+        Self {
+            inner: AsyncFlow::new(
+                BlockingClientAdapter(Arc::new(client_with_auth)),
+                BlockingClientAdapter(Arc::new(client_with_token)),
+            ),
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    /// Polls until the user finishes, sleeping on the current thread in between.
+    #[allow(clippy::result_large_err)]
+    pub fn execute<SCOPE, UI>(
+        &self,
+        provider: &(dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        user_interaction: UI,
+    ) -> Result<DAT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+        UI: FnOnce(UserCode, VerificationUri, Option<VerificationUriComplete>) + Send,
+    {
+        block_on(self.inner.execute(provider, scopes, user_interaction))
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn execute_with_configuration<SCOPE, UI>(
+        &self,
+        provider: &(dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        user_interaction: UI,
+        config: impl Into<Option<FlowExecuteConfiguration>>,
+    ) -> Result<DAT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+        UI: FnOnce(UserCode, VerificationUri, Option<VerificationUriComplete>) + Send,
+    {
+        block_on(self.inner.execute_with_configuration(
+            provider,
+            scopes,
+            user_interaction,
+            config.into().map(AsyncFlowExecuteConfiguration::from),
+        ))
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn execute_with_user_interaction<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        user_interaction: &(dyn UserInteraction + Send + Sync),
+        config: impl Into<Option<FlowExecuteConfiguration>>,
+    ) -> Result<DAT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
+        block_on(self.inner.execute_with_user_interaction(
+            provider,
+            scopes,
+            user_interaction,
+            config.into().map(AsyncFlowExecuteConfiguration::from),
+        ))
+    }
+}
+
+//
+//
+//
+/// Has no cancellation, as the thread is blocked in [`BlockingClient::sleep`] between polls.
+#[derive(Default)]
+pub struct FlowExecuteConfiguration {
+    pub on_status: Option<Box<dyn Fn(FlowExecuteStatus) + Send + Sync>>,
+}
+impl core::fmt::Debug for FlowExecuteConfiguration {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FlowExecuteConfiguration")
+            .field("on_status", &self.on_status.is_some())
+            .finish()
+    }
+}
+impl FlowExecuteConfiguration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn set_on_status<F>(&mut self, on_status: F)
+    where
+        F: Fn(FlowExecuteStatus) + Send + Sync + 'static,
+    {
+        self.on_status = Some(Box::new(on_status));
+    }
+}
+
+impl From<FlowExecuteConfiguration> for AsyncFlowExecuteConfiguration {
+    fn from(config: FlowExecuteConfiguration) -> Self {
+        Self {
+            cancellation: None,
+            on_status: config.on_status,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+    use std::sync::{Arc, Mutex};
+
+    use crate::test_utils::{response, TestClient, TestProvider};
+
+    #[test]
+    fn test_execute() {
+        let client = TestClient::new([
+            Some(response(
+                200,
+                r#"{"device_code":"DEVICE_CODE","user_code":"USER_CODE","verification_uri":"https://example.com/device","expires_in":1800,"interval":5}"#,
+            )),
+            Some(response(400, r#"{"error":"authorization_pending"}"#)),
+            Some(response(503, "")),
+            Some(response(
+                200,
+                r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+            )),
+        ]);
+        let flow = Flow::with_retry(client.clone(), client.clone()).configure(|x| {
+            x.inner.retry_policy.jitter = false;
+        });
+
+        let provider = TestProvider::new();
+
+        let statuses = Arc::new(Mutex::new(vec![]));
+        let statuses_t = statuses.clone();
+        match flow.execute_with_configuration(
+            &provider,
+            None,
+            |user_code, _, _| assert_eq!(user_code, "USER_CODE"),
+            FlowExecuteConfiguration::new().configure(|x| {
+                let statuses_t = statuses_t.clone();
+                x.set_on_status(move |status| statuses_t.lock().unwrap().push(status))
+            }),
+        ) {
+            Ok(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            Err(err) => panic!("{err}"),
+        }
+        assert_eq!(
+            statuses.lock().unwrap()[1..],
+            [FlowExecuteStatus::AuthorizationPending {
+                next_poll_in: Duration::from_secs(5)
+            }]
+        );
+
+        assert_eq!(
+            client.sleeps(),
+            vec![Duration::from_secs(5), Duration::from_millis(500)]
+        );
+    }
+}
@@ -0,0 +1,118 @@
+use std::sync::Arc;
+
+use futures_executor::block_on;
+use oauth2_core::{
+    jwt_authorization_grant::access_token_response::SuccessfulBody as AT_RES_SuccessfulBody,
+    serde::{de::DeserializeOwned, Serialize},
+    types::Scope,
+};
+
+use crate::{
+    jwt_authorization_grant::{Flow as AsyncFlow, FlowExecuteError},
+    token_cache::TokenCache,
+    ProviderExtJwtAuthorizationGrant,
+};
+
+use super::{BlockingClient, BlockingClientAdapter};
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct Flow<C>
+where
+    C: BlockingClient + Send + Sync,
+{
+    pub inner: AsyncFlow<BlockingClientAdapter<Arc<C>>>,
+}
+impl<C> Flow<C>
+where
+    C: BlockingClient + Send + Sync,
+{
+    /// Sends each request once, see [`Self::with_retry`].
+    pub fn new(client_with_token: C) -> Self {
+        Self {
+            inner: AsyncFlow::new(BlockingClientAdapter(Arc::new(client_with_token))),
+        }
+    }
+
+    /// Retries with the default `retry_policy`, sleeping with [`BlockingClient::sleep`].
+    pub fn with_retry(client_with_token: C) -> Self
+    where
+        C: 'static,
+    {
+        Self {
+            inner: AsyncFlow::with_retry(BlockingClientAdapter(Arc::new(client_with_token))),
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn execute<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtJwtAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
+        block_on(self.inner.execute(provider, scopes))
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn execute_with_cache<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtJwtAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        token_cache: &TokenCache,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
+        block_on(self.inner.execute_with_cache(provider, scopes, token_cache))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+
+    use crate::test_utils::{response, TestClient, TestProvider};
+
+    #[test]
+    fn test_execute() {
+        let client = TestClient::new([
+            Some(response(503, "")),
+            Some(response(503, "")),
+            Some(response(
+                200,
+                r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+            )),
+        ]);
+        let provider = TestProvider::new();
+
+        assert!(Flow::new(client.clone()).execute(&provider, None).is_err());
+        assert_eq!(client.request_count(), 1);
+        assert!(client.sleeps().is_empty());
+
+        let flow = Flow::with_retry(client.clone()).configure(|x| {
+            x.inner.retry_policy.jitter = false;
+        });
+        match flow.execute(&provider, None) {
+            Ok(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            Err(err) => panic!("{err}"),
+        }
+        assert_eq!(client.request_count(), 3);
+        assert_eq!(client.sleeps(), vec![Duration::from_millis(500)]);
+        assert!(client.request_bodies.lock().unwrap()[2].contains("assertion=ASSERTION"));
+    }
+}
@@ -0,0 +1,16 @@
+//! Synchronous flows for callers without an async runtime.
+//!
+//! Each flow drives its async counterpart to completion on the current thread, sleeping
+//! with [`BlockingClient::sleep`] between retries and device polls.
+
+//
+pub mod client;
+
+pub use client::{BlockingClient, BlockingClientAdapter};
+
+//
+pub mod authorization_code_grant;
+pub mod client_credentials_grant;
+pub mod device_authorization_grant;
+pub mod jwt_authorization_grant;
+pub mod resource_owner_password_credentials_grant;
@@ -0,0 +1,134 @@
+//! What a provider supports, every provider crate exports one as `CAPABILITIES`.
+
+use serde::Serialize;
+
+use crate::oauth2_core::types::scope::SCOPE_PARAMETER_DELIMITATION;
+
+//
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum GrantType {
+    AuthorizationCode,
+    ClientCredentials,
+    DeviceCode,
+    JwtBearer,
+    Password,
+}
+
+impl core::fmt::Display for GrantType {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::AuthorizationCode => write!(f, "authorization_code"),
+            Self::ClientCredentials => write!(f, "client_credentials"),
+            Self::DeviceCode => write!(f, "device_code"),
+            Self::JwtBearer => write!(f, "jwt_bearer"),
+            Self::Password => write!(f, "password"),
+        }
+    }
+}
+
+/// How the client authenticates at the token endpoint.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientAuthMethod {
+    ClientSecretPost,
+    ClientSecretBasic,
+    // e.g. Baidu, WeChat
+    ClientSecretQuery,
+    PrivateKeyJwt,
+    // Public clients, e.g. device flows
+    None,
+}
+
+impl core::fmt::Display for ClientAuthMethod {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Self::ClientSecretPost => write!(f, "client_secret_post"),
+            Self::ClientSecretBasic => write!(f, "client_secret_basic"),
+            Self::ClientSecretQuery => write!(f, "client_secret_query"),
+            Self::PrivateKeyJwt => write!(f, "private_key_jwt"),
+            Self::None => write!(f, "none"),
+        }
+    }
+}
+
+//
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+pub struct ProviderCapabilities {
+    pub grants: &'static [GrantType],
+    pub client_auth_methods: &'static [ClientAuthMethod],
+    pub pkce: bool,
+    pub oidc: bool,
+    pub refresh_token: bool,
+    pub revocation: bool,
+    /// Whether the crate's `ExtensionsBuilder` can obtain `UserInfo`.
+    pub user_info: bool,
+    pub scope_delimiter: char,
+}
+
+impl ProviderCapabilities {
+    /// Authorization code grant with `client_secret_post`, everything else off.
+    pub const MINIMAL: Self = Self {
+        grants: &[GrantType::AuthorizationCode],
+        client_auth_methods: &[ClientAuthMethod::ClientSecretPost],
+        pkce: false,
+        oidc: false,
+        refresh_token: false,
+        revocation: false,
+        user_info: false,
+        scope_delimiter: SCOPE_PARAMETER_DELIMITATION,
+    };
+
+    pub fn supports_grant(&self, grant: GrantType) -> bool {
+        self.grants.contains(&grant)
+    }
+
+    pub fn supports_client_auth_method(&self, method: ClientAuthMethod) -> bool {
+        self.client_auth_methods.contains(&method)
+    }
+
+    pub fn join_scopes<S>(&self, scopes: &[S]) -> String
+    where
+        S: ToString,
+    {
+        scopes
+            .iter()
+            .map(|x| x.to_string())
+            .collect::<Vec<_>>()
+            .join(self.scope_delimiter.to_string().as_str())
+    }
+}
+
+impl Default for ProviderCapabilities {
+    fn default() -> Self {
+        Self::MINIMAL
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ser() -> Result<(), Box<dyn std::error::Error>> {
+        let capabilities = ProviderCapabilities {
+            grants: &[GrantType::AuthorizationCode, GrantType::DeviceCode],
+            client_auth_methods: &[ClientAuthMethod::ClientSecretBasic, ClientAuthMethod::None],
+            pkce: true,
+            scope_delimiter: ',',
+            ..ProviderCapabilities::MINIMAL
+        };
+
+        assert_eq!(
+            serde_json::to_string(&capabilities)?,
+            r#"{"grants":["authorization_code","device_code"],"client_auth_methods":["client_secret_basic","none"],"pkce":true,"oidc":false,"refresh_token":false,"revocation":false,"user_info":false,"scope_delimiter":","}"#
+        );
+        assert!(capabilities.supports_grant(GrantType::DeviceCode));
+        assert!(!capabilities.supports_grant(GrantType::Password));
+        assert!(capabilities.supports_client_auth_method(ClientAuthMethod::None));
+        assert_eq!(capabilities.join_scopes(&["a", "b"]), "a,b");
+        assert_eq!(GrantType::JwtBearer.to_string(), "jwt_bearer");
+
+        Ok(())
+    }
+}
@@ -10,7 +10,7 @@ use oauth2_core::{
     },
     http::{
         header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
-        Error as HttpError,
+        Error as HttpError, StatusCode,
     },
     serde::{de::DeserializeOwned, Serialize},
     types::{ClientPassword, Scope},
@@ -18,6 +18,8 @@ use oauth2_core::{
 use serde_json::{Error as SerdeJsonError, Map, Value};
 use serde_urlencoded::ser::Error as SerdeUrlencodedSerError;
 
+#[cfg(feature = "with-tracing")]
+use crate::trace;
 use crate::ProviderExtClientCredentialsGrant;
 
 //
@@ -56,6 +58,13 @@ where
     type ParseResponseError = AccessTokenEndpointError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span(
+            self.provider.name(),
+            Some(trace::grant_type::CLIENT_CREDENTIALS),
+            trace::endpoint::ACCESS_TOKEN,
+        );
+
         let client_password = ClientPassword::new(
             self.provider
                 .client_id()
@@ -115,6 +124,8 @@ where
         //
         self.provider.access_token_request_modifying(&mut request);
 
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
         Ok(request)
     }
 
@@ -122,6 +133,15 @@ where
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span(
+            self.provider.name(),
+            Some(trace::grant_type::CLIENT_CREDENTIALS),
+            trace::endpoint::ACCESS_TOKEN,
+        );
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         //
         if response.status().is_success() {
             let map = serde_json::from_slice::<Map<String, Value>>(response.body())
@@ -134,8 +154,9 @@ where
             }
         }
 
-        let body = serde_json::from_slice::<RES_ErrorBody>(response.body())
-            .map_err(AccessTokenEndpointError::DeResponseBodyFailed)?;
+        let body = serde_json::from_slice::<RES_ErrorBody>(response.body()).map_err(|err| {
+            AccessTokenEndpointError::DeErrorResponseBodyFailed(response.status(), err)
+        })?;
         Ok(Err(body))
     }
 }
@@ -157,4 +178,6 @@ pub enum AccessTokenEndpointError {
     //
     #[error("DeResponseBodyFailed {0}")]
     DeResponseBodyFailed(SerdeJsonError),
+    #[error("DeErrorResponseBodyFailed {0} {1}")]
+    DeErrorResponseBodyFailed(StatusCode, SerdeJsonError),
 }
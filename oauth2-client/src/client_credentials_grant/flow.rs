@@ -1,15 +1,21 @@
-use http_api_client::{Client, ClientRespondEndpointError};
+use std::sync::Arc;
+
+use http_api_client::{Client, RetryableClient};
 use oauth2_core::{
     client_credentials_grant::access_token_response::{
         ErrorBody as AT_RES_ErrorBody, SuccessfulBody as AT_RES_SuccessfulBody,
     },
     serde::{de::DeserializeOwned, Serialize},
-    types::Scope,
+    types::{Scope, ScopeParameter},
 };
 
-use crate::ProviderExtClientCredentialsGrant;
+use crate::{
+    retry::{self, RetryPolicy, RetrySleeper},
+    token_cache::{TokenCache, TokenCacheKey},
+    ProviderExtClientCredentialsGrant,
+};
 
-use super::{AccessTokenEndpoint, AccessTokenEndpointError};
+use super::{AccessTokenEndpointError, Machine, MachineError};
 
 //
 //
@@ -20,13 +26,48 @@ where
     C: Client,
 {
     pub client_with_token: C,
+    pub retry_policy: RetryPolicy,
+    /// Without it, each request is sent once.
+    pub retry_sleeper: Option<Arc<dyn RetrySleeper>>,
 }
 impl<C> Flow<C>
 where
     C: Client,
 {
+    /// Sends each request once, see [`Self::with_retry`].
     pub fn new(client_with_token: C) -> Self {
-        Self { client_with_token }
+        Self {
+            client_with_token,
+            retry_policy: RetryPolicy::disabled(),
+            retry_sleeper: None,
+        }
+    }
+
+    /// Retries with the default `retry_policy`, sleeping with the client.
+    pub fn with_retry(client_with_token: C) -> Self
+    where
+        C: RetryableClient + Clone + Send + Sync + 'static,
+    {
+        Self::new(client_with_token.clone()).configure(|x| {
+            x.retry_policy = RetryPolicy::default();
+            x.set_retry_sleeper(client_with_token.clone());
+        })
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    /// Retries only if `retry_policy` allows it.
+    pub fn set_retry_sleeper<S>(&mut self, retry_sleeper: S)
+    where
+        S: RetrySleeper + 'static,
+    {
+        self.retry_sleeper = Some(Arc::new(retry_sleeper));
     }
 }
 
@@ -43,28 +84,49 @@ where
         SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
     {
         // Step 1
+        let machine = Machine::new(provider, scopes);
+
+        let request = machine.request()?;
+
+        let response = retry::respond(
+            &self.client_with_token,
+            self.retry_sleeper.as_deref(),
+            &self.retry_policy,
+            true,
+            request,
+        )
+        .await
+        .map_err(|err| FlowExecuteError::AccessTokenEndpointRespondFailed(Box::new(err)))?;
+
+        let access_token_successful_body = machine.handle_response(response)?;
+
+        Ok(access_token_successful_body)
+    }
+
+    /// Reuse the token in `token_cache` until it nears `expires_in`.
+    pub async fn execute_with_cache<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtClientCredentialsGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        token_cache: &TokenCache,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
         let scopes = scopes.into().or_else(|| provider.scopes_default());
 
-        let access_token_endpoint = AccessTokenEndpoint::new(provider, scopes);
-
-        let access_token_ret = self
-            .client_with_token
-            .respond_endpoint(&access_token_endpoint)
-            .await
-            .map_err(|err| match err {
-                ClientRespondEndpointError::RespondFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointRespondFailed(Box::new(err))
-                }
-                ClientRespondEndpointError::EndpointRenderRequestFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointError(err)
-                }
-                ClientRespondEndpointError::EndpointParseResponseFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointError(err)
-                }
-            })?;
-
-        let access_token_successful_body =
-            access_token_ret.map_err(FlowExecuteError::AccessTokenFailed)?;
+        let key = TokenCacheKey::with_provider(
+            provider,
+            scopes.to_owned().map(ScopeParameter::from).as_ref(),
+        );
+
+        if let Some(access_token_successful_body) = token_cache.get(&key) {
+            return Ok(access_token_successful_body.into());
+        }
+
+        let access_token_successful_body = self.execute(provider, scopes).await?;
+
+        token_cache.insert(&key, &access_token_successful_body.to_owned().into());
 
         Ok(access_token_successful_body)
     }
@@ -79,3 +141,124 @@ pub enum FlowExecuteError {
     #[error("AccessTokenFailed {0:?}")]
     AccessTokenFailed(AT_RES_ErrorBody),
 }
+
+impl From<MachineError> for FlowExecuteError {
+    fn from(err: MachineError) -> Self {
+        match err {
+            MachineError::AccessTokenEndpointError(err) => Self::AccessTokenEndpointError(err),
+            MachineError::AccessTokenFailed(body) => Self::AccessTokenFailed(body),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use core::time::Duration;
+
+    use http_api_client::Response;
+
+    use crate::test_utils::{response, TestClient, TestProvider, TestResponse};
+
+    fn flow_and_client(responses: Vec<TestResponse>) -> (Flow<TestClient>, TestClient) {
+        let client = TestClient::new(responses);
+        let flow = Flow::with_retry(client.clone()).configure(|x| {
+            x.retry_policy = RetryPolicy::new().configure(|x| {
+                x.max_retries = 3;
+                x.jitter = false;
+            })
+        });
+        (flow, client)
+    }
+
+    fn response_with_retry_after(status: u16, retry_after: &str) -> TestResponse {
+        Response::builder()
+            .status(status)
+            .header("Retry-After", retry_after)
+            .body(vec![])
+            .ok()
+    }
+
+    #[test]
+    fn test_execute_with_retry() {
+        let (flow, client) = flow_and_client(vec![
+            response_with_retry_after(503, "3"),
+            None,
+            Some(response(400, r#"{"error":"temporarily_unavailable"}"#)),
+            Some(response(
+                200,
+                r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+            )),
+        ]);
+
+        match futures_executor::block_on(flow.execute(&TestProvider::new(), None)) {
+            Ok(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            Err(err) => panic!("{err}"),
+        }
+        assert_eq!(client.request_count(), 4);
+        assert_eq!(
+            client.sleeps(),
+            vec![
+                Duration::from_secs(3),
+                Duration::from_secs(1),
+                Duration::from_secs(2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_execute_with_retry_exhausted() {
+        let (flow, client) = flow_and_client(
+            (0..4)
+                .map(|_| Some(response(400, r#"{"error":"server_error"}"#)))
+                .collect(),
+        );
+
+        match futures_executor::block_on(flow.execute(&TestProvider::new(), None)) {
+            Err(FlowExecuteError::AccessTokenFailed(body)) => {
+                assert_eq!(body.error.to_string(), "server_error")
+            }
+            ret => panic!("{ret:?}"),
+        }
+        assert_eq!(client.request_count(), 4);
+        assert_eq!(client.sleeps().len(), 3);
+    }
+
+    #[test]
+    fn test_execute_without_retry() {
+        let (flow, client) =
+            flow_and_client(vec![Some(response(400, r#"{"error":"invalid_client"}"#))]);
+
+        match futures_executor::block_on(flow.execute(&TestProvider::new(), None)) {
+            Err(FlowExecuteError::AccessTokenFailed(body)) => {
+                assert_eq!(body.error.to_string(), "invalid_client")
+            }
+            ret => panic!("{ret:?}"),
+        }
+        assert_eq!(client.request_count(), 1);
+
+        let (flow, client) = flow_and_client(vec![response_with_retry_after(429, "120")]);
+
+        match futures_executor::block_on(flow.execute(&TestProvider::new(), None)) {
+            Err(FlowExecuteError::AccessTokenEndpointError(_)) => {}
+            ret => panic!("{ret:?}"),
+        }
+        assert_eq!(client.request_count(), 1);
+        assert!(client.sleeps().is_empty());
+    }
+
+    #[test]
+    fn test_execute_without_retry_sleeper() {
+        let client = TestClient::new([Some(response(503, "")), Some(response(503, ""))]);
+        let flow = Flow::new(client.clone());
+        assert_eq!(flow.retry_policy, RetryPolicy::disabled());
+
+        match futures_executor::block_on(flow.execute(&TestProvider::new(), None)) {
+            Err(FlowExecuteError::AccessTokenEndpointError(_)) => {}
+            ret => panic!("{ret:?}"),
+        }
+        assert_eq!(client.request_count(), 1);
+        assert!(client.sleeps().is_empty());
+    }
+}
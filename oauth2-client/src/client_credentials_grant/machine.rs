@@ -0,0 +1,102 @@
+//! Sans-IO, the caller sends the request and passes the response back.
+
+use http_api_client_endpoint::{Body, Endpoint as _, Request, Response};
+use oauth2_core::{
+    client_credentials_grant::access_token_response::{
+        ErrorBody as AT_RES_ErrorBody, SuccessfulBody as AT_RES_SuccessfulBody,
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    types::Scope,
+};
+
+use crate::ProviderExtClientCredentialsGrant;
+
+use super::{AccessTokenEndpoint, AccessTokenEndpointError};
+
+//
+//
+//
+#[derive(Clone)]
+pub struct Machine<'a, SCOPE>
+where
+    SCOPE: Scope,
+{
+    access_token_endpoint: AccessTokenEndpoint<'a, SCOPE>,
+}
+impl<'a, SCOPE> Machine<'a, SCOPE>
+where
+    SCOPE: Scope + Serialize + DeserializeOwned,
+{
+    pub fn new(
+        provider: &'a (dyn ProviderExtClientCredentialsGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+    ) -> Self {
+        let scopes = scopes.into().or_else(|| provider.scopes_default());
+
+        Self {
+            access_token_endpoint: AccessTokenEndpoint::new(provider, scopes),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn request(&self) -> Result<Request<Body>, MachineError> {
+        self.access_token_endpoint
+            .render_request()
+            .map_err(MachineError::AccessTokenEndpointError)
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn handle_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, MachineError> {
+        self.access_token_endpoint
+            .parse_response(response)
+            .map_err(MachineError::AccessTokenEndpointError)?
+            .map_err(MachineError::AccessTokenFailed)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum MachineError {
+    #[error("AccessTokenEndpointError {0}")]
+    AccessTokenEndpointError(AccessTokenEndpointError),
+    #[error("AccessTokenFailed {0:?}")]
+    AccessTokenFailed(AT_RES_ErrorBody),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::TestProvider;
+
+    #[test]
+    fn test_machine() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = TestProvider::new();
+
+        let machine = Machine::new(&provider, vec!["read".to_owned()]);
+
+        let request = machine.request()?;
+        assert_eq!(request.uri(), "https://example.com/token");
+        assert_eq!(request.body(), b"grant_type=client_credentials&scope=read");
+
+        let response = Response::builder()
+            .status(200)
+            .body(br#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#.to_vec())?;
+        let body = machine.handle_response(response)?;
+        assert_eq!(body.access_token, "ACCESS_TOKEN");
+
+        let response = Response::builder()
+            .status(400)
+            .body(br#"{"error":"invalid_client"}"#.to_vec())?;
+        match machine.handle_response(response) {
+            Err(MachineError::AccessTokenFailed(body)) => {
+                assert_eq!(body.error.to_string(), "invalid_client")
+            }
+            ret => panic!("{ret:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -6,6 +6,11 @@ pub mod provider_ext;
 
 pub use access_token_endpoint::{AccessTokenEndpoint, AccessTokenEndpointError};
 
+//
+pub mod machine;
+
+pub use machine::{Machine, MachineError};
+
 //
 #[cfg(feature = "with-flow")]
 pub mod flow;
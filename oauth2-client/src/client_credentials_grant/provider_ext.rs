@@ -82,10 +82,18 @@ where
         self.inner.token_endpoint_url()
     }
 
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
     fn extra(&self) -> Option<Map<String, Value>> {
         self.inner.extra()
     }
 
+    fn principal(&self) -> Option<String> {
+        self.inner.principal()
+    }
+
     // Note
 }
 
@@ -1,4 +1,9 @@
-use core::{cmp::max, time::Duration};
+use core::{
+    cmp::max,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use std::time::Instant;
 
 use http_api_client_endpoint::{
     Body, Request, Response, RetryableEndpoint, RetryableEndpointRetry,
@@ -16,12 +21,12 @@ use oauth2_core::{
             ErrorBody as RES_ErrorBody, SuccessfulBody as RES_SuccessfulBody,
         },
         device_authorization_response::{
-            SuccessfulBody as DA_RES_SuccessfulBody, INTERVAL_DEFAULT,
+            SuccessfulBody as DA_RES_SuccessfulBody, INTERVAL_DEFAULT, INTERVAL_SLOW_DOWN_INCREMENT,
         },
     },
     http::{
         header::{ACCEPT, CONTENT_TYPE},
-        Error as HttpError,
+        Error as HttpError, StatusCode,
     },
     serde::{de::DeserializeOwned, Serialize},
     types::Scope,
@@ -29,10 +34,11 @@ use oauth2_core::{
 use serde_json::{Error as SerdeJsonError, Map, Value};
 use serde_urlencoded::ser::Error as SerdeUrlencodedSerError;
 
+#[cfg(feature = "with-tracing")]
+use crate::trace;
 use crate::ProviderExtDeviceAuthorizationGrant;
 
 //
-#[derive(Clone)]
 pub struct DeviceAccessTokenEndpoint<'a, SCOPE>
 where
     SCOPE: Scope,
@@ -40,6 +46,24 @@ where
     provider: &'a (dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
     device_authorization_response_successful_body: DA_RES_SuccessfulBody,
     interval: Duration,
+    expires_at: Instant,
+    slow_down_count: AtomicUsize,
+}
+impl<'a, SCOPE> Clone for DeviceAccessTokenEndpoint<'a, SCOPE>
+where
+    SCOPE: Scope,
+{
+    fn clone(&self) -> Self {
+        Self {
+            provider: self.provider,
+            device_authorization_response_successful_body: self
+                .device_authorization_response_successful_body
+                .to_owned(),
+            interval: self.interval,
+            expires_at: self.expires_at,
+            slow_down_count: AtomicUsize::new(self.slow_down_count.load(Ordering::SeqCst)),
+        }
+    }
 }
 impl<'a, SCOPE> DeviceAccessTokenEndpoint<'a, SCOPE>
 where
@@ -53,12 +77,32 @@ where
             device_authorization_response_successful_body.interval(),
             Duration::from_secs(INTERVAL_DEFAULT as u64),
         );
+        let expires_at =
+            Instant::now() + device_authorization_response_successful_body.expires_in();
         Self {
             provider,
             device_authorization_response_successful_body,
             interval,
+            expires_at,
+            slow_down_count: AtomicUsize::new(0),
         }
     }
+
+    /// https://datatracker.ietf.org/doc/html/rfc8628#section-3.5
+    /// The interval is increased by 5 seconds for every `slow_down`.
+    pub fn interval(&self) -> Duration {
+        self.interval
+            + Duration::from_secs(INTERVAL_SLOW_DOWN_INCREMENT as u64)
+                * self.slow_down_count.load(Ordering::SeqCst) as u32
+    }
+
+    pub fn expires_at(&self) -> Instant {
+        self.expires_at
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Instant::now() >= self.expires_at
+    }
 }
 
 impl<'a, SCOPE> RetryableEndpoint for DeviceAccessTokenEndpoint<'a, SCOPE>
@@ -76,6 +120,13 @@ where
         &self,
         _retry: Option<&RetryableEndpointRetry<Self::RetryReason>>,
     ) -> Result<Request<Body>, Self::RenderRequestError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span(
+            self.provider.name(),
+            Some(trace::grant_type::DEVICE_CODE),
+            trace::endpoint::DEVICE_ACCESS_TOKEN,
+        );
+
         let mut body = BodyWithDeviceAuthorizationGrant::new(
             self.device_authorization_response_successful_body
                 .device_code
@@ -95,8 +146,10 @@ where
             &self.device_authorization_response_successful_body,
         ) {
             let request = request_ret
-                .map_err(|err| DeviceAccessTokenEndpointError::CustomRenderingRequestFailed(err))?;
+                .map_err(DeviceAccessTokenEndpointError::CustomRenderingRequestFailed)?;
 
+            #[cfg(feature = "with-tracing")]
+            trace::request_rendered(&request);
             return Ok(request);
         }
 
@@ -113,6 +166,8 @@ where
             .body(body_str.as_bytes().to_vec())
             .map_err(DeviceAccessTokenEndpointError::MakeRequestFailed)?;
 
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
         Ok(request)
     }
 
@@ -122,12 +177,21 @@ where
         _retry: Option<&RetryableEndpointRetry<Self::RetryReason>>,
     ) -> Result<Result<Self::ParseResponseOutput, Self::RetryReason>, Self::ParseResponseError>
     {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span(
+            self.provider.name(),
+            Some(trace::grant_type::DEVICE_CODE),
+            trace::endpoint::DEVICE_ACCESS_TOKEN,
+        );
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         if let Some(body_ret_ret) = self
             .provider
             .device_access_token_response_parsing(&response)
         {
             let body_ret = body_ret_ret
-                .map_err(|err| DeviceAccessTokenEndpointError::CustomParsingResponseFailed(err))?;
+                .map_err(DeviceAccessTokenEndpointError::CustomParsingResponseFailed)?;
 
             return Ok(body_ret);
         }
@@ -143,8 +207,9 @@ where
             }
         }
 
-        let body = serde_json::from_slice::<RES_ErrorBody>(response.body())
-            .map_err(DeviceAccessTokenEndpointError::DeResponseBodyFailed)?;
+        let body = serde_json::from_slice::<RES_ErrorBody>(response.body()).map_err(|err| {
+            DeviceAccessTokenEndpointError::DeErrorResponseBodyFailed(response.status(), err)
+        })?;
         match body.error {
             ErrorBodyError::AuthorizationPending => {
                 return Ok(Err(
@@ -152,23 +217,27 @@ where
                 ))
             }
             ErrorBodyError::SlowDown => {
-                return Ok(Err(DeviceAccessTokenEndpointRetryReason::SlowDown))
+                self.slow_down_count.fetch_add(1, Ordering::SeqCst);
+                return Ok(Err(DeviceAccessTokenEndpointRetryReason::SlowDown));
             }
             _ => {}
         }
         Ok(Ok(Err(body)))
     }
 
-    fn next_retry_in(&self, retry: &RetryableEndpointRetry<Self::RetryReason>) -> Duration {
-        match retry.reason {
-            DeviceAccessTokenEndpointRetryReason::AuthorizationPending => self.interval,
-            DeviceAccessTokenEndpointRetryReason::SlowDown => self.interval,
-        }
+    fn next_retry_in(&self, _retry: &RetryableEndpointRetry<Self::RetryReason>) -> Duration {
+        self.interval()
     }
 
     fn max_retry_count(&self) -> usize {
-        // 1800 / 5
-        360
+        // e.g. 1800 / 5
+        max(
+            self.device_authorization_response_successful_body
+                .expires_in()
+                .as_secs()
+                / self.interval.as_secs(),
+            1,
+        ) as usize
     }
 }
 
@@ -193,4 +262,67 @@ pub enum DeviceAccessTokenEndpointError {
     //
     #[error("DeResponseBodyFailed {0}")]
     DeResponseBodyFailed(SerdeJsonError),
+    #[error("DeErrorResponseBodyFailed {0} {1}")]
+    DeErrorResponseBodyFailed(StatusCode, SerdeJsonError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_core::re_exports::AccessTokenResponseErrorBody;
+
+    use crate::test_utils::TestProvider;
+
+    #[test]
+    fn test_interval_with_slow_down() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = TestProvider::new();
+
+        let endpoint = DeviceAccessTokenEndpoint::new(
+            &provider,
+            DA_RES_SuccessfulBody::new(
+                "DEVICE_CODE".to_owned(),
+                "USER_CODE".to_owned(),
+                "https://example.com/device".parse()?,
+                None,
+                1800,
+                Some(5),
+            ),
+        );
+        assert_eq!(endpoint.interval(), Duration::from_secs(5));
+        assert_eq!(endpoint.max_retry_count(), 360);
+        assert!(!endpoint.is_expired());
+
+        let error_response = |error: ErrorBodyError| -> Result<Response<Body>, HttpError> {
+            let body: AccessTokenResponseErrorBody =
+                AccessTokenResponseErrorBody::new(error, None, None);
+            Response::builder()
+                .status(400)
+                .body(serde_json::to_vec(&body).unwrap())
+        };
+
+        for (error, interval) in [
+            (ErrorBodyError::AuthorizationPending, 5),
+            (ErrorBodyError::SlowDown, 10),
+            (ErrorBodyError::AuthorizationPending, 10),
+            (ErrorBodyError::SlowDown, 15),
+        ] {
+            match endpoint.parse_response(error_response(error)?, None)? {
+                Err(reason) => {
+                    assert_eq!(
+                        endpoint.next_retry_in(&RetryableEndpointRetry::new(1, reason)),
+                        Duration::from_secs(interval)
+                    );
+                }
+                Ok(ret) => panic!("{ret:?}"),
+            }
+        }
+
+        match endpoint.parse_response(error_response(ErrorBodyError::ExpiredToken)?, None)? {
+            Ok(Err(body)) => assert_eq!(body.error, ErrorBodyError::ExpiredToken),
+            ret => panic!("{ret:?}"),
+        }
+
+        Ok(())
+    }
 }
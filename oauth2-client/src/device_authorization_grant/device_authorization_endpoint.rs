@@ -12,7 +12,7 @@ use oauth2_core::{
     },
     http::{
         header::{ACCEPT, CONTENT_TYPE},
-        Error as HttpError,
+        Error as HttpError, StatusCode,
     },
     serde::Serialize,
     types::Scope,
@@ -20,6 +20,8 @@ use oauth2_core::{
 use serde_json::{Error as SerdeJsonError, Map, Value};
 use serde_urlencoded::ser::Error as SerdeUrlencodedSerError;
 
+#[cfg(feature = "with-tracing")]
+use crate::trace;
 use crate::ProviderExtDeviceAuthorizationGrant;
 
 //
@@ -56,6 +58,13 @@ where
     type ParseResponseError = DeviceAuthorizationEndpointError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span(
+            self.provider.name(),
+            Some(trace::grant_type::DEVICE_CODE),
+            trace::endpoint::DEVICE_AUTHORIZATION,
+        );
+
         let mut body = REQ_Body::new(
             self.provider.client_id().cloned(),
             self.scopes.to_owned().map(Into::into),
@@ -68,6 +77,8 @@ where
             let request = request_ret.map_err(|err| {
                 DeviceAuthorizationEndpointError::CustomRenderingRequestFailed(err)
             })?;
+            #[cfg(feature = "with-tracing")]
+            trace::request_rendered(&request);
             return Ok(request);
         }
 
@@ -82,6 +93,8 @@ where
             .body(body_str.as_bytes().to_vec())
             .map_err(DeviceAuthorizationEndpointError::MakeRequestFailed)?;
 
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
         Ok(request)
     }
 
@@ -89,6 +102,15 @@ where
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span(
+            self.provider.name(),
+            Some(trace::grant_type::DEVICE_CODE),
+            trace::endpoint::DEVICE_AUTHORIZATION,
+        );
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         if let Some(body_ret_ret) = self
             .provider
             .device_authorization_response_parsing(&response)
@@ -111,8 +133,9 @@ where
             }
         }
 
-        let body = serde_json::from_slice::<RES_ErrorBody>(response.body())
-            .map_err(DeviceAuthorizationEndpointError::DeResponseBodyFailed)?;
+        let body = serde_json::from_slice::<RES_ErrorBody>(response.body()).map_err(|err| {
+            DeviceAuthorizationEndpointError::DeErrorResponseBodyFailed(response.status(), err)
+        })?;
         Ok(Err(body))
     }
 }
@@ -132,4 +155,6 @@ pub enum DeviceAuthorizationEndpointError {
     //
     #[error("DeResponseBodyFailed {0}")]
     DeResponseBodyFailed(SerdeJsonError),
+    #[error("DeErrorResponseBodyFailed {0} {1}")]
+    DeErrorResponseBodyFailed(StatusCode, SerdeJsonError),
 }
@@ -1,7 +1,8 @@
-use http_api_client::{
-    Client, ClientRespondEndpointError, RetryableClient,
-    RetryableClientRespondEndpointUntilDoneError,
-};
+use core::{future::Future, pin::Pin, time::Duration};
+use std::time::Instant;
+
+use futures_util::future::{self, Either};
+use http_api_client::{Client, RetryableClient};
 use oauth2_core::{
     device_authorization_grant::{
         device_access_token_response::{
@@ -15,11 +16,16 @@ use oauth2_core::{
     types::Scope,
 };
 
-use crate::ProviderExtDeviceAuthorizationGrant;
+use crate::{
+    retry::{self, RetryPolicy},
+    ProviderExtDeviceAuthorizationGrant,
+};
 
 use super::{
-    DeviceAccessTokenEndpoint, DeviceAccessTokenEndpointError, DeviceAuthorizationEndpoint,
-    DeviceAuthorizationEndpointError,
+    device_access_token_endpoint::DeviceAccessTokenEndpointRetryReason,
+    user_interaction::{FnOnceUserInteraction, UserInteraction},
+    DeviceAccessTokenEndpointError, DeviceAuthorizationEndpointError, Machine, MachineAction,
+    MachineError,
 };
 
 //
@@ -33,6 +39,7 @@ where
 {
     pub client_with_auth: C1,
     pub client_with_token: C2,
+    pub retry_policy: RetryPolicy,
 }
 impl<C1, C2> Flow<C1, C2>
 where
@@ -43,8 +50,17 @@ where
         Self {
             client_with_auth,
             client_with_token,
+            retry_policy: RetryPolicy::default(),
         }
     }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
 }
 
 impl<C1, C2> Flow<C1, C2>
@@ -60,70 +76,149 @@ where
     ) -> Result<DAT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
     where
         SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
-        UI: FnOnce(UserCode, VerificationUri, Option<VerificationUriComplete>),
+        UI: FnOnce(UserCode, VerificationUri, Option<VerificationUriComplete>) + Send,
+    {
+        self.execute_with_configuration(provider, scopes, user_interaction, None)
+            .await
+    }
+
+    pub async fn execute_with_configuration<SCOPE, UI>(
+        &self,
+        provider: &(dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        user_interaction: UI,
+        config: impl Into<Option<FlowExecuteConfiguration>>,
+    ) -> Result<DAT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+        UI: FnOnce(UserCode, VerificationUri, Option<VerificationUriComplete>) + Send,
+    {
+        self.execute_with_user_interaction(
+            provider,
+            scopes,
+            &FnOnceUserInteraction::new(user_interaction),
+            config,
+        )
+        .await
+    }
+
+    pub async fn execute_with_user_interaction<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        user_interaction: &(dyn UserInteraction + Send + Sync),
+        config: impl Into<Option<FlowExecuteConfiguration>>,
+    ) -> Result<DAT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
+        let FlowExecuteConfiguration {
+            cancellation,
+            on_status,
+        } = config.into().unwrap_or_default();
+        let cancellation = cancellation.unwrap_or_else(|| Box::pin(future::pending()));
+
+        let execute = self.execute_inner(provider, scopes, user_interaction, on_status);
+
+        match future::select(cancellation, Box::pin(execute)).await {
+            Either::Left(((), _)) => Err(FlowExecuteError::Cancelled),
+            Either::Right((ret, _)) => ret,
+        }
+    }
+
+    async fn execute_inner<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        user_interaction: &(dyn UserInteraction + Send + Sync),
+        on_status: Option<Box<dyn Fn(FlowExecuteStatus) + Send + Sync>>,
+    ) -> Result<DAT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
     {
+        let on_status = |status: FlowExecuteStatus| {
+            if let Some(on_status) = &on_status {
+                on_status(status)
+            }
+        };
+
         // Step 1
-        let scopes = scopes.into().or_else(|| provider.scopes_default());
+        let mut machine = Machine::new(provider, scopes);
 
-        let device_authorization_endpoint = DeviceAuthorizationEndpoint::new(provider, scopes);
+        let request = machine.request()?;
 
-        let device_authorization_ret = self
-            .client_with_auth
-            .respond_endpoint(&device_authorization_endpoint)
-            .await
-            .map_err(|err| match err {
-                ClientRespondEndpointError::RespondFailed(err) => {
-                    FlowExecuteError::DeviceAuthorizationEndpointRespondFailed(Box::new(err))
-                }
-                ClientRespondEndpointError::EndpointRenderRequestFailed(err) => {
-                    FlowExecuteError::DeviceAuthorizationEndpointError(err)
-                }
-                ClientRespondEndpointError::EndpointParseResponseFailed(err) => {
-                    FlowExecuteError::DeviceAuthorizationEndpointError(err)
-                }
-            })?;
+        let response = retry::respond(
+            &self.client_with_auth,
+            Some(&self.client_with_token),
+            &self.retry_policy,
+            true,
+            request,
+        )
+        .await
+        .map_err(|err| FlowExecuteError::DeviceAuthorizationEndpointRespondFailed(Box::new(err)))?;
 
-        let device_authorization_successful_body =
-            device_authorization_ret.map_err(FlowExecuteError::DeviceAuthorizationFailed)?;
+        let (device_authorization_successful_body, expires_at, interval) =
+            match machine.handle_response(response, Instant::now())? {
+                MachineAction::UserInteraction {
+                    device_authorization_response_body,
+                    expires_at,
+                    interval,
+                } => (device_authorization_response_body, expires_at, interval),
+                _ => unreachable!("the device authorization response comes first"),
+            };
 
         // Step 2
-        user_interaction(
-            device_authorization_successful_body.user_code.to_owned(),
-            device_authorization_successful_body
-                .verification_uri
-                .to_owned(),
-            device_authorization_successful_body
-                .verification_uri_complete
-                .to_owned(),
-        );
+        user_interaction
+            .interact(&device_authorization_successful_body)
+            .await
+            .map_err(FlowExecuteError::UserInteractionFailed)?;
 
         // Step 3
-        let device_access_token_endpoint =
-            DeviceAccessTokenEndpoint::new(provider, device_authorization_successful_body);
+        on_status(FlowExecuteStatus::WaitingForUser {
+            expires_at,
+            interval,
+        });
 
-        let device_access_token_ret = self
-            .client_with_token
-            .respond_endpoint_until_done(&device_access_token_endpoint)
+        loop {
+            let request = machine.request()?;
+
+            let response = retry::respond(
+                &self.client_with_token,
+                Some(&self.client_with_token),
+                &self.retry_policy,
+                true,
+                request,
+            )
             .await
-            .map_err(|err| match err {
-                RetryableClientRespondEndpointUntilDoneError::RespondFailed(err) => {
-                    FlowExecuteError::DeviceAccessTokenEndpointRespondFailed(Box::new(err))
+            .map_err(|err| {
+                FlowExecuteError::DeviceAccessTokenEndpointRespondFailed(Box::new(err))
+            })?;
+
+            let (next_poll_in, reason) = match machine.handle_response(response, Instant::now())? {
+                MachineAction::Done(device_access_token_successful_body) => {
+                    return Ok(device_access_token_successful_body);
                 }
-                RetryableClientRespondEndpointUntilDoneError::EndpointRenderRequestFailed(err) => {
-                    FlowExecuteError::DeviceAccessTokenEndpointError(err)
+                MachineAction::PollAt {
+                    next_poll_in,
+                    reason,
+                    ..
+                } => (next_poll_in, reason),
+                MachineAction::UserInteraction { .. } => {
+                    unreachable!("the device authorization response comes first")
                 }
-                RetryableClientRespondEndpointUntilDoneError::EndpointParseResponseFailed(err) => {
-                    FlowExecuteError::DeviceAccessTokenEndpointError(err)
+            };
+
+            on_status(match reason {
+                DeviceAccessTokenEndpointRetryReason::AuthorizationPending => {
+                    FlowExecuteStatus::AuthorizationPending { next_poll_in }
                 }
-                RetryableClientRespondEndpointUntilDoneError::ReachedMaxRetries => {
-                    FlowExecuteError::DeviceAccessTokenEndpointErrorWithReachedMaxRetries
+                DeviceAccessTokenEndpointRetryReason::SlowDown => {
+                    FlowExecuteStatus::SlowDown { next_poll_in }
                 }
-            })?;
-
-        let device_access_token_successful_body =
-            device_access_token_ret.map_err(FlowExecuteError::DeviceAccessTokenFailed)?;
+            });
 
-        Ok(device_access_token_successful_body)
+            self.client_with_token.sleep(next_poll_in).await;
+        }
     }
 }
 
@@ -136,12 +231,238 @@ pub enum FlowExecuteError {
     #[error("DeviceAuthorizationFailed {0:?}")]
     DeviceAuthorizationFailed(DA_RES_ErrorBody),
     //
+    #[error("UserInteractionFailed {0}")]
+    UserInteractionFailed(Box<dyn std::error::Error + Send + Sync>),
+    //
     #[error("DeviceAccessTokenEndpointRespondFailed {0}")]
     DeviceAccessTokenEndpointRespondFailed(Box<dyn std::error::Error + Send + Sync>),
     #[error("DeviceAccessTokenEndpointError {0}")]
     DeviceAccessTokenEndpointError(DeviceAccessTokenEndpointError),
-    #[error("DeviceAccessTokenEndpointErrorWithReachedMaxRetries")]
-    DeviceAccessTokenEndpointErrorWithReachedMaxRetries,
+    #[error("DeviceCodeExpired")]
+    DeviceCodeExpired,
     #[error("DeviceAccessTokenFailed {0:?}")]
     DeviceAccessTokenFailed(DAT_RES_ErrorBody),
+    //
+    #[error("Cancelled")]
+    Cancelled,
+}
+
+impl From<MachineError> for FlowExecuteError {
+    fn from(err: MachineError) -> Self {
+        match err {
+            MachineError::DeviceAuthorizationEndpointError(err) => {
+                Self::DeviceAuthorizationEndpointError(err)
+            }
+            MachineError::DeviceAuthorizationFailed(body) => Self::DeviceAuthorizationFailed(body),
+            MachineError::DeviceAccessTokenEndpointError(err) => {
+                Self::DeviceAccessTokenEndpointError(err)
+            }
+            MachineError::DeviceCodeExpired => Self::DeviceCodeExpired,
+            MachineError::DeviceAccessTokenFailed(body) => Self::DeviceAccessTokenFailed(body),
+            MachineError::Finished => unreachable!("the flow returns once finished"),
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FlowExecuteStatus {
+    WaitingForUser {
+        expires_at: Instant,
+        interval: Duration,
+    },
+    AuthorizationPending {
+        next_poll_in: Duration,
+    },
+    SlowDown {
+        next_poll_in: Duration,
+    },
+}
+
+#[derive(Default)]
+pub struct FlowExecuteConfiguration {
+    pub cancellation: Option<Pin<Box<dyn Future<Output = ()> + Send>>>,
+    pub on_status: Option<Box<dyn Fn(FlowExecuteStatus) + Send + Sync>>,
+}
+impl core::fmt::Debug for FlowExecuteConfiguration {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("FlowExecuteConfiguration")
+            .field("cancellation", &self.cancellation.is_some())
+            .field("on_status", &self.on_status.is_some())
+            .finish()
+    }
+}
+impl FlowExecuteConfiguration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    /// e.g. `tokio_util::sync::CancellationToken::cancelled_owned()`
+    pub fn set_cancellation<F>(&mut self, cancellation: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.cancellation = Some(Box::pin(cancellation));
+    }
+
+    pub fn set_on_status<F>(&mut self, on_status: F)
+    where
+        F: Fn(FlowExecuteStatus) + Send + Sync + 'static,
+    {
+        self.on_status = Some(Box::new(on_status));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::{Arc, Mutex};
+
+    use http_api_client::async_trait;
+    use oauth2_core::device_authorization_grant::device_authorization_response::SuccessfulBody as DA_RES_SuccessfulBody;
+
+    use crate::test_utils::{response, TestClient, TestProvider};
+
+    fn client(expires_in: usize, responses: Vec<(u16, &str)>) -> TestClient {
+        let device_authorization_response = format!(
+            r#"{{"device_code":"DEVICE_CODE","user_code":"USER_CODE","verification_uri":"https://example.com/device","expires_in":{expires_in},"interval":5}}"#
+        );
+        TestClient::new(
+            [(200, device_authorization_response.as_str())]
+                .into_iter()
+                .chain(responses)
+                .map(|(status, body)| Some(response(status, body))),
+        )
+    }
+
+    #[test]
+    fn test_execute_with_slow_down() {
+        let client = client(
+            1800,
+            vec![
+                (400, r#"{"error":"authorization_pending"}"#),
+                (400, r#"{"error":"slow_down"}"#),
+                (400, r#"{"error":"authorization_pending"}"#),
+                (
+                    200,
+                    r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+                ),
+            ],
+        );
+        let flow = Flow::new(client.clone(), client.clone());
+
+        let statuses = Arc::new(Mutex::new(vec![]));
+        let statuses_cloned = statuses.clone();
+
+        let ret = futures_executor::block_on(flow.execute_with_configuration(
+            &TestProvider::new(),
+            None,
+            |user_code, _, _| assert_eq!(user_code, "USER_CODE"),
+            FlowExecuteConfiguration::new().configure(|x| {
+                let statuses = statuses_cloned.clone();
+                x.set_on_status(move |status| statuses.lock().unwrap().push(status))
+            }),
+        ));
+        match ret {
+            Ok(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            Err(err) => panic!("{err}"),
+        }
+
+        assert_eq!(
+            client.sleeps(),
+            vec![
+                Duration::from_secs(5),
+                Duration::from_secs(10),
+                Duration::from_secs(10)
+            ]
+        );
+
+        let statuses = statuses.lock().unwrap();
+        assert_eq!(statuses.len(), 4);
+        assert!(matches!(
+            statuses[0],
+            FlowExecuteStatus::WaitingForUser { .. }
+        ));
+        assert_eq!(
+            statuses[2],
+            FlowExecuteStatus::SlowDown {
+                next_poll_in: Duration::from_secs(10)
+            }
+        );
+    }
+
+    #[test]
+    fn test_execute_with_expired() {
+        let client = client(6, vec![(400, r#"{"error":"slow_down"}"#)]);
+        let flow = Flow::new(client.clone(), client.clone());
+
+        match futures_executor::block_on(flow.execute(&TestProvider::new(), None, |_, _, _| {})) {
+            Err(FlowExecuteError::DeviceCodeExpired) => {}
+            ret => panic!("{ret:?}"),
+        }
+        assert!(client.sleeps().is_empty());
+    }
+
+    #[test]
+    fn test_execute_with_cancellation() {
+        let client = client(1800, vec![]);
+        let flow = Flow::new(client.clone(), client);
+
+        match futures_executor::block_on(flow.execute_with_configuration(
+            &TestProvider::new(),
+            None,
+            |_, _, _| {},
+            FlowExecuteConfiguration::new().configure(|x| x.set_cancellation(async {})),
+        )) {
+            Err(FlowExecuteError::Cancelled) => {}
+            ret => panic!("{ret:?}"),
+        }
+    }
+
+    #[test]
+    fn test_execute_with_user_interaction() {
+        struct TestUserInteraction(Arc<Mutex<Option<usize>>>);
+        #[async_trait]
+        impl UserInteraction for TestUserInteraction {
+            async fn interact(
+                &self,
+                device_authorization_response_body: &DA_RES_SuccessfulBody,
+            ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+                *self.0.lock().unwrap() = Some(device_authorization_response_body.expires_in);
+                Ok(())
+            }
+        }
+
+        let client = client(
+            1800,
+            vec![(
+                200,
+                r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+            )],
+        );
+        let flow = Flow::new(client.clone(), client);
+
+        let expires_in = Arc::new(Mutex::new(None));
+        match futures_executor::block_on(flow.execute_with_user_interaction(
+            &TestProvider::new(),
+            None,
+            &TestUserInteraction(expires_in.clone()),
+            None,
+        )) {
+            Ok(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            Err(err) => panic!("{err}"),
+        }
+        assert_eq!(*expires_in.lock().unwrap(), Some(1800));
+    }
 }
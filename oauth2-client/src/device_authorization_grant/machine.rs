@@ -0,0 +1,330 @@
+//! Sans-IO, the caller sends the request, passes the response back and waits until the
+//! returned wake-up time before polling again.
+
+use core::time::Duration;
+use std::time::Instant;
+
+use http_api_client_endpoint::{
+    Body, Endpoint as _, Request, Response, RetryableEndpoint as _, RetryableEndpointRetry,
+};
+use oauth2_core::{
+    device_authorization_grant::{
+        device_access_token_response::{
+            ErrorBody as DAT_RES_ErrorBody, SuccessfulBody as DAT_RES_SuccessfulBody,
+        },
+        device_authorization_response::{
+            ErrorBody as DA_RES_ErrorBody, SuccessfulBody as DA_RES_SuccessfulBody,
+        },
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    types::Scope,
+};
+
+use crate::ProviderExtDeviceAuthorizationGrant;
+
+use super::{
+    device_access_token_endpoint::DeviceAccessTokenEndpointRetryReason, DeviceAccessTokenEndpoint,
+    DeviceAccessTokenEndpointError, DeviceAuthorizationEndpoint, DeviceAuthorizationEndpointError,
+};
+
+//
+//
+//
+/// `request` -> `handle_response` -> `MachineAction`, until `MachineAction::Done`.
+pub struct Machine<'a, SCOPE>
+where
+    SCOPE: Scope,
+{
+    provider: &'a (dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+    state: MachineState<'a, SCOPE>,
+}
+
+#[allow(clippy::large_enum_variant)]
+enum MachineState<'a, SCOPE>
+where
+    SCOPE: Scope,
+{
+    DeviceAuthorization(DeviceAuthorizationEndpoint<'a, SCOPE>),
+    DeviceAccessToken {
+        endpoint: DeviceAccessTokenEndpoint<'a, SCOPE>,
+        expires_at: Instant,
+        retry: Option<RetryableEndpointRetry<DeviceAccessTokenEndpointRetryReason>>,
+    },
+    Finished,
+}
+
+impl<'a, SCOPE> Machine<'a, SCOPE>
+where
+    SCOPE: Scope + Serialize + DeserializeOwned,
+{
+    pub fn new(
+        provider: &'a (dyn ProviderExtDeviceAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+    ) -> Self {
+        let scopes = scopes.into().or_else(|| provider.scopes_default());
+
+        Self {
+            provider,
+            state: MachineState::DeviceAuthorization(DeviceAuthorizationEndpoint::new(
+                provider, scopes,
+            )),
+        }
+    }
+
+    /// The device authorization request first, then the device access token requests.
+    #[allow(clippy::result_large_err)]
+    pub fn request(&self) -> Result<Request<Body>, MachineError> {
+        match &self.state {
+            MachineState::DeviceAuthorization(endpoint) => endpoint
+                .render_request()
+                .map_err(MachineError::DeviceAuthorizationEndpointError),
+            MachineState::DeviceAccessToken {
+                endpoint, retry, ..
+            } => endpoint
+                .render_request(retry.as_ref())
+                .map_err(MachineError::DeviceAccessTokenEndpointError),
+            MachineState::Finished => Err(MachineError::Finished),
+        }
+    }
+
+    /// `now` is when the response was received.
+    #[allow(clippy::result_large_err)]
+    pub fn handle_response(
+        &mut self,
+        response: Response<Body>,
+        now: Instant,
+    ) -> Result<MachineAction<SCOPE>, MachineError> {
+        match &mut self.state {
+            MachineState::DeviceAuthorization(endpoint) => {
+                let device_authorization_successful_body = endpoint
+                    .parse_response(response)
+                    .map_err(MachineError::DeviceAuthorizationEndpointError)?
+                    .map_err(MachineError::DeviceAuthorizationFailed)?;
+
+                let expires_at = now + device_authorization_successful_body.expires_in();
+                let endpoint = DeviceAccessTokenEndpoint::new(
+                    self.provider,
+                    device_authorization_successful_body.to_owned(),
+                );
+                let interval = endpoint.interval();
+
+                self.state = MachineState::DeviceAccessToken {
+                    endpoint,
+                    expires_at,
+                    retry: None,
+                };
+
+                Ok(MachineAction::UserInteraction {
+                    device_authorization_response_body: device_authorization_successful_body,
+                    expires_at,
+                    interval,
+                })
+            }
+            MachineState::DeviceAccessToken {
+                endpoint,
+                expires_at,
+                retry,
+            } => {
+                let reason = match endpoint
+                    .parse_response(response, retry.as_ref())
+                    .map_err(MachineError::DeviceAccessTokenEndpointError)?
+                {
+                    Ok(device_access_token_ret) => {
+                        let device_access_token_successful_body = device_access_token_ret
+                            .map_err(MachineError::DeviceAccessTokenFailed)?;
+
+                        self.state = MachineState::Finished;
+
+                        return Ok(MachineAction::Done(device_access_token_successful_body));
+                    }
+                    Err(reason) => reason,
+                };
+
+                let x = retry.get_or_insert(RetryableEndpointRetry::new(0, reason));
+                x.count += 1;
+                x.reason = reason;
+
+                let next_poll_in = endpoint.interval();
+                let wake_at = now + next_poll_in;
+                if wake_at >= *expires_at {
+                    return Err(MachineError::DeviceCodeExpired);
+                }
+
+                Ok(MachineAction::PollAt {
+                    wake_at,
+                    next_poll_in,
+                    reason,
+                })
+            }
+            MachineState::Finished => Err(MachineError::Finished),
+        }
+    }
+
+    pub fn expires_at(&self) -> Option<Instant> {
+        match &self.state {
+            MachineState::DeviceAccessToken { expires_at, .. } => Some(*expires_at),
+            _ => None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        matches!(self.state, MachineState::Finished)
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub enum MachineAction<SCOPE>
+where
+    SCOPE: Scope,
+{
+    /// Show the user code, then send the next `request` right away.
+    UserInteraction {
+        device_authorization_response_body: DA_RES_SuccessfulBody,
+        expires_at: Instant,
+        interval: Duration,
+    },
+    /// Send the next `request` at `wake_at`.
+    PollAt {
+        wake_at: Instant,
+        next_poll_in: Duration,
+        reason: DeviceAccessTokenEndpointRetryReason,
+    },
+    Done(DAT_RES_SuccessfulBody<SCOPE>),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum MachineError {
+    #[error("DeviceAuthorizationEndpointError {0}")]
+    DeviceAuthorizationEndpointError(DeviceAuthorizationEndpointError),
+    #[error("DeviceAuthorizationFailed {0:?}")]
+    DeviceAuthorizationFailed(DA_RES_ErrorBody),
+    //
+    #[error("DeviceAccessTokenEndpointError {0}")]
+    DeviceAccessTokenEndpointError(DeviceAccessTokenEndpointError),
+    #[error("DeviceCodeExpired")]
+    DeviceCodeExpired,
+    #[error("DeviceAccessTokenFailed {0:?}")]
+    DeviceAccessTokenFailed(DAT_RES_ErrorBody),
+    //
+    #[error("Finished")]
+    Finished,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::test_utils::{response, TestProvider};
+
+    #[test]
+    fn test_machine() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = TestProvider::new();
+
+        let mut machine = Machine::new(&provider, None);
+        let now = Instant::now();
+
+        //
+        let request = machine.request()?;
+        assert_eq!(request.uri(), "https://example.com/device");
+
+        match machine.handle_response(
+            response(
+                200,
+                r#"{"device_code":"DEVICE_CODE","user_code":"USER_CODE","verification_uri":"https://example.com/device","expires_in":1800,"interval":5}"#,
+            ),
+            now,
+        )? {
+            MachineAction::UserInteraction {
+                device_authorization_response_body,
+                expires_at,
+                interval,
+            } => {
+                assert_eq!(device_authorization_response_body.user_code, "USER_CODE");
+                assert_eq!(expires_at, now + Duration::from_secs(1800));
+                assert_eq!(interval, Duration::from_secs(5));
+            }
+            action => panic!("{action:?}"),
+        }
+        assert_eq!(machine.expires_at(), Some(now + Duration::from_secs(1800)));
+
+        //
+        let request = machine.request()?;
+        assert_eq!(request.uri(), "https://example.com/token");
+
+        match machine.handle_response(response(400, r#"{"error":"authorization_pending"}"#), now)? {
+            MachineAction::PollAt {
+                wake_at,
+                next_poll_in,
+                reason,
+            } => {
+                assert_eq!(wake_at, now + Duration::from_secs(5));
+                assert_eq!(next_poll_in, Duration::from_secs(5));
+                assert_eq!(
+                    reason,
+                    DeviceAccessTokenEndpointRetryReason::AuthorizationPending
+                );
+            }
+            action => panic!("{action:?}"),
+        }
+
+        let now = now + Duration::from_secs(5);
+        match machine.handle_response(response(400, r#"{"error":"slow_down"}"#), now)? {
+            MachineAction::PollAt {
+                wake_at, reason, ..
+            } => {
+                assert_eq!(wake_at, now + Duration::from_secs(10));
+                assert_eq!(reason, DeviceAccessTokenEndpointRetryReason::SlowDown);
+            }
+            action => panic!("{action:?}"),
+        }
+
+        let now = now + Duration::from_secs(10);
+        match machine.handle_response(
+            response(
+                200,
+                r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer"}"#,
+            ),
+            now,
+        )? {
+            MachineAction::Done(body) => assert_eq!(body.access_token, "ACCESS_TOKEN"),
+            action => panic!("{action:?}"),
+        }
+        assert!(machine.is_finished());
+
+        match machine.request() {
+            Err(MachineError::Finished) => {}
+            ret => panic!("{ret:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_machine_with_expired() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = TestProvider::new();
+
+        let mut machine = Machine::new(&provider, None);
+        let now = Instant::now();
+
+        machine.handle_response(
+            response(
+                200,
+                r#"{"device_code":"DEVICE_CODE","user_code":"USER_CODE","verification_uri":"https://example.com/device","expires_in":30,"interval":5}"#,
+            ),
+            now,
+        )?;
+
+        match machine.handle_response(
+            response(400, r#"{"error":"authorization_pending"}"#),
+            now + Duration::from_secs(25),
+        ) {
+            Err(MachineError::DeviceCodeExpired) => {}
+            ret => panic!("{ret:?}"),
+        }
+
+        Ok(())
+    }
+}
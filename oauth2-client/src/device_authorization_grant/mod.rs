@@ -10,8 +10,19 @@ pub use device_authorization_endpoint::{
     DeviceAuthorizationEndpoint, DeviceAuthorizationEndpointError,
 };
 
+//
+pub mod machine;
+
+pub use machine::{Machine, MachineAction, MachineError};
+
 //
 #[cfg(feature = "with-flow")]
 pub mod flow;
 #[cfg(feature = "with-flow")]
-pub use flow::{Flow, FlowExecuteError};
+pub mod user_interaction;
+#[cfg(feature = "with-flow")]
+pub use flow::{Flow, FlowExecuteConfiguration, FlowExecuteError, FlowExecuteStatus};
+#[cfg(feature = "with-qr-code")]
+pub use user_interaction::TerminalQrCodeUserInteraction;
+#[cfg(feature = "with-flow")]
+pub use user_interaction::UserInteraction;
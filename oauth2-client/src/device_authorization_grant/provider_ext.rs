@@ -142,10 +142,18 @@ where
         self.inner.token_endpoint_url()
     }
 
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
     fn extra(&self) -> Option<Map<String, Value>> {
         self.inner.extra()
     }
 
+    fn principal(&self) -> Option<String> {
+        self.inner.principal()
+    }
+
     // Note
 }
 
@@ -213,7 +221,7 @@ where
             .device_access_token_request_rendering(body, device_authorization_response_body)
     }
 
-    #[allow(clippy::type_complexity)]
+    #[allow(clippy::type_complexity, clippy::result_large_err)]
     fn device_access_token_response_parsing(
         &self,
         response: &Response<Body>,
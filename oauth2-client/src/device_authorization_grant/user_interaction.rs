@@ -0,0 +1,157 @@
+use std::sync::Mutex;
+
+use http_api_client::async_trait;
+use oauth2_core::device_authorization_grant::device_authorization_response::{
+    SuccessfulBody as DA_RES_SuccessfulBody, UserCode, VerificationUri, VerificationUriComplete,
+};
+
+//
+//
+//
+/// https://datatracker.ietf.org/doc/html/rfc8628#section-3.3
+#[async_trait]
+pub trait UserInteraction {
+    async fn interact(
+        &self,
+        device_authorization_response_body: &DA_RES_SuccessfulBody,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>>;
+}
+
+//
+//
+//
+pub(crate) struct FnOnceUserInteraction<F>(Mutex<Option<F>>);
+
+impl<F> FnOnceUserInteraction<F> {
+    pub(crate) fn new(f: F) -> Self {
+        Self(Mutex::new(Some(f)))
+    }
+}
+
+#[async_trait]
+impl<F> UserInteraction for FnOnceUserInteraction<F>
+where
+    F: FnOnce(UserCode, VerificationUri, Option<VerificationUriComplete>) + Send,
+{
+    async fn interact(
+        &self,
+        device_authorization_response_body: &DA_RES_SuccessfulBody,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let f = self
+            .0
+            .lock()
+            .map_err(|err| err.to_string())?
+            .take()
+            .ok_or("called more than once")?;
+
+        f(
+            device_authorization_response_body.user_code.to_owned(),
+            device_authorization_response_body
+                .verification_uri
+                .to_owned(),
+            device_authorization_response_body
+                .verification_uri_complete
+                .to_owned(),
+        );
+
+        Ok(())
+    }
+}
+
+//
+//
+//
+#[cfg(feature = "with-qr-code")]
+#[derive(Debug, Clone, Default)]
+pub struct TerminalQrCodeUserInteraction;
+
+#[cfg(feature = "with-qr-code")]
+impl TerminalQrCodeUserInteraction {
+    pub fn render(
+        device_authorization_response_body: &DA_RES_SuccessfulBody,
+    ) -> Result<String, qrcode::types::QrError> {
+        use qrcode::{render::unicode::Dense1x2, QrCode};
+
+        let mut s = String::new();
+
+        if let Some(verification_uri_complete) =
+            &device_authorization_response_body.verification_uri_complete
+        {
+            let qr_code = QrCode::new(verification_uri_complete.as_str())?;
+            s.push_str(
+                qr_code
+                    .render::<Dense1x2>()
+                    .dark_color(Dense1x2::Light)
+                    .light_color(Dense1x2::Dark)
+                    .build()
+                    .as_str(),
+            );
+            s.push('\n');
+            s.push_str(format!("Scan the QR code, or open {verification_uri_complete}\n").as_str());
+            s.push_str(
+                format!(
+                    "Or open {} and enter the code: {}\n",
+                    device_authorization_response_body.verification_uri,
+                    device_authorization_response_body.user_code
+                )
+                .as_str(),
+            );
+        } else {
+            s.push_str(
+                format!(
+                    "Open {} and enter the code: {}\n",
+                    device_authorization_response_body.verification_uri,
+                    device_authorization_response_body.user_code
+                )
+                .as_str(),
+            );
+        }
+
+        s.push_str(
+            format!(
+                "The code expires in {} seconds\n",
+                device_authorization_response_body.expires_in
+            )
+            .as_str(),
+        );
+
+        Ok(s)
+    }
+}
+
+#[cfg(feature = "with-qr-code")]
+#[async_trait]
+impl UserInteraction for TerminalQrCodeUserInteraction {
+    async fn interact(
+        &self,
+        device_authorization_response_body: &DA_RES_SuccessfulBody,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        println!("{}", Self::render(device_authorization_response_body)?);
+
+        Ok(())
+    }
+}
+
+#[cfg(all(test, feature = "with-qr-code"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render() -> Result<(), Box<dyn std::error::Error>> {
+        let body = DA_RES_SuccessfulBody::new(
+            "DEVICE_CODE".to_owned(),
+            "WDJB-MJHT".to_owned(),
+            "https://example.com/device".parse()?,
+            Some("https://example.com/device?user_code=WDJB-MJHT".parse()?),
+            1800,
+            Some(5),
+        );
+
+        let s = TerminalQrCodeUserInteraction::render(&body)?;
+        assert!(s.contains('█') || s.contains('▀') || s.contains('▄'));
+        assert!(s.contains("https://example.com/device?user_code=WDJB-MJHT"));
+        assert!(s.contains("WDJB-MJHT"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,587 @@
+//! One error type for every grant, classified by `ErrorKind`.
+
+use oauth2_core::{
+    access_token_response::{ErrorBody, ErrorBodyError},
+    http::StatusCode,
+};
+
+use crate::{
+    authorization_code_grant::{
+        AccessTokenEndpointError as AC_AccessTokenEndpointError, AuthorizationEndpointError,
+        FlowBuildAuthorizationUrlError, MachineError as AC_MachineError,
+    },
+    client_credentials_grant::{
+        AccessTokenEndpointError as CC_AccessTokenEndpointError, MachineError as CC_MachineError,
+    },
+    device_authorization_grant::{
+        DeviceAccessTokenEndpointError, DeviceAuthorizationEndpointError,
+        MachineError as DA_MachineError,
+    },
+    extensions::EndpointExecuteError,
+    jwt_authorization_grant::{
+        AccessTokenEndpointError as JWT_AccessTokenEndpointError, MachineError as JWT_MachineError,
+    },
+    resource_owner_password_credentials_grant::{
+        AccessTokenEndpointError as ROPC_AccessTokenEndpointError,
+        MachineError as ROPC_MachineError,
+    },
+};
+
+//
+//
+//
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// e.g. `access_denied`
+    UserDenied,
+    /// e.g. `invalid_client`, `invalid_scope`, a missing client_secret
+    InvalidClientConfig,
+    /// e.g. `invalid_grant`, `expired_token`, an expired device code
+    InvalidGrant,
+    /// e.g. `server_error`, `temporarily_unavailable`, HTTP 429 or 5xx
+    TransientServerError,
+    Network,
+    /// e.g. an undecodable response body, a state mismatch
+    Protocol,
+    Cancelled,
+    Other,
+}
+
+impl ErrorKind {
+    /// Whether the same request may succeed later.
+    pub fn is_retryable(&self) -> bool {
+        matches!(self, Self::TransientServerError | Self::Network)
+    }
+}
+
+impl From<StatusCode> for ErrorKind {
+    /// For an error response without a decodable body.
+    fn from(status: StatusCode) -> Self {
+        if status == StatusCode::TOO_MANY_REQUESTS || status.is_server_error() {
+            Self::TransientServerError
+        } else {
+            Self::Protocol
+        }
+    }
+}
+
+impl From<&ErrorBodyError> for ErrorKind {
+    fn from(error: &ErrorBodyError) -> Self {
+        match error {
+            ErrorBodyError::AccessDenied => Self::UserDenied,
+            ErrorBodyError::InvalidRequest
+            | ErrorBodyError::InvalidClient
+            | ErrorBodyError::UnauthorizedClient
+            | ErrorBodyError::UnsupportedGrantType
+            | ErrorBodyError::InvalidScope
+            | ErrorBodyError::UnsupportedResponseType => Self::InvalidClientConfig,
+            ErrorBodyError::InvalidGrant | ErrorBodyError::ExpiredToken => Self::InvalidGrant,
+            ErrorBodyError::ServerError
+            | ErrorBodyError::TemporarilyUnavailable
+            | ErrorBodyError::AuthorizationPending
+            | ErrorBodyError::SlowDown => Self::TransientServerError,
+            ErrorBodyError::Other(_) => Self::Other,
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Debug)]
+pub struct Error {
+    kind: ErrorKind,
+    status: Option<StatusCode>,
+    body: Option<Box<ErrorBody>>,
+    source: Option<Box<dyn std::error::Error + Send + Sync>>,
+}
+
+impl Error {
+    pub fn new(
+        kind: ErrorKind,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self {
+            kind,
+            status: None,
+            body: None,
+            source: Some(source.into()),
+        }
+    }
+
+    fn with_status(
+        status: StatusCode,
+        source: impl Into<Box<dyn std::error::Error + Send + Sync>>,
+    ) -> Self {
+        Self {
+            status: Some(status),
+            ..Self::new(status.into(), source)
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    /// The HTTP status of an error response whose body could not be decoded.
+    pub fn status(&self) -> Option<StatusCode> {
+        self.status
+    }
+
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == Some(StatusCode::TOO_MANY_REQUESTS)
+    }
+
+    /// The error response of the authorization server, if any.
+    pub fn body(&self) -> Option<&ErrorBody> {
+        self.body.as_deref()
+    }
+
+    pub fn into_body(self) -> Option<ErrorBody> {
+        self.body.map(|x| *x)
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.kind.is_retryable()
+    }
+}
+
+impl core::fmt::Display for Error {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.kind)?;
+        if let Some(status) = &self.status {
+            write!(f, " {status}")?;
+        }
+        if let Some(body) = &self.body {
+            write!(f, " {}", body.error)?;
+            if let Some(error_description) = &body.error_description {
+                write!(f, " {error_description}")?;
+            }
+        }
+        if let Some(source) = &self.source {
+            write!(f, " {source}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.source
+            .as_deref()
+            .map(|x| x as &(dyn std::error::Error + 'static))
+    }
+}
+
+impl From<ErrorBody> for Error {
+    fn from(body: ErrorBody) -> Self {
+        Self {
+            kind: (&body.error).into(),
+            status: None,
+            body: Some(Box::new(body)),
+            source: None,
+        }
+    }
+}
+
+//
+// Endpoints
+//
+impl From<AuthorizationEndpointError> for Error {
+    fn from(err: AuthorizationEndpointError) -> Self {
+        Self::new(ErrorKind::InvalidClientConfig, err)
+    }
+}
+
+impl From<AC_AccessTokenEndpointError> for Error {
+    fn from(err: AC_AccessTokenEndpointError) -> Self {
+        let kind = match &err {
+            AC_AccessTokenEndpointError::CustomRenderingRequestFailed(_)
+            | AC_AccessTokenEndpointError::SerRequestBodyFailed(_)
+            | AC_AccessTokenEndpointError::MakeRequestFailed(_) => ErrorKind::InvalidClientConfig,
+            AC_AccessTokenEndpointError::CustomParsingResponseFailed(_)
+            | AC_AccessTokenEndpointError::DeResponseBodyFailed(_) => ErrorKind::Protocol,
+            AC_AccessTokenEndpointError::DeErrorResponseBodyFailed(status, _) => {
+                return Self::with_status(*status, err)
+            }
+        };
+        Self::new(kind, err)
+    }
+}
+
+impl From<CC_AccessTokenEndpointError> for Error {
+    fn from(err: CC_AccessTokenEndpointError) -> Self {
+        let kind = match &err {
+            CC_AccessTokenEndpointError::ClientIdMissing
+            | CC_AccessTokenEndpointError::ClientSecretMissing
+            | CC_AccessTokenEndpointError::MakeRequestBodyExtraFailed(_)
+            | CC_AccessTokenEndpointError::SerRequestBodyFailed(_)
+            | CC_AccessTokenEndpointError::MakeRequestFailed(_) => ErrorKind::InvalidClientConfig,
+            CC_AccessTokenEndpointError::DeResponseBodyFailed(_) => ErrorKind::Protocol,
+            CC_AccessTokenEndpointError::DeErrorResponseBodyFailed(status, _) => {
+                return Self::with_status(*status, err)
+            }
+        };
+        Self::new(kind, err)
+    }
+}
+
+impl From<DeviceAuthorizationEndpointError> for Error {
+    fn from(err: DeviceAuthorizationEndpointError) -> Self {
+        let kind = match &err {
+            DeviceAuthorizationEndpointError::CustomRenderingRequestFailed(_)
+            | DeviceAuthorizationEndpointError::SerRequestBodyFailed(_)
+            | DeviceAuthorizationEndpointError::MakeRequestFailed(_) => {
+                ErrorKind::InvalidClientConfig
+            }
+            DeviceAuthorizationEndpointError::CustomParsingResponseFailed(_)
+            | DeviceAuthorizationEndpointError::DeResponseBodyFailed(_) => ErrorKind::Protocol,
+            DeviceAuthorizationEndpointError::DeErrorResponseBodyFailed(status, _) => {
+                return Self::with_status(*status, err)
+            }
+        };
+        Self::new(kind, err)
+    }
+}
+
+impl From<DeviceAccessTokenEndpointError> for Error {
+    fn from(err: DeviceAccessTokenEndpointError) -> Self {
+        let kind = match &err {
+            DeviceAccessTokenEndpointError::CustomRenderingRequestFailed(_)
+            | DeviceAccessTokenEndpointError::SerRequestBodyFailed(_)
+            | DeviceAccessTokenEndpointError::MakeRequestFailed(_) => {
+                ErrorKind::InvalidClientConfig
+            }
+            DeviceAccessTokenEndpointError::CustomParsingResponseFailed(_)
+            | DeviceAccessTokenEndpointError::DeResponseBodyFailed(_) => ErrorKind::Protocol,
+            DeviceAccessTokenEndpointError::DeErrorResponseBodyFailed(status, _) => {
+                return Self::with_status(*status, err)
+            }
+        };
+        Self::new(kind, err)
+    }
+}
+
+impl From<JWT_AccessTokenEndpointError> for Error {
+    fn from(err: JWT_AccessTokenEndpointError) -> Self {
+        let kind = match &err {
+            JWT_AccessTokenEndpointError::MakeAssertionFailed(_)
+            | JWT_AccessTokenEndpointError::MakeRequestBodyExtraFailed(_)
+            | JWT_AccessTokenEndpointError::SerRequestBodyFailed(_)
+            | JWT_AccessTokenEndpointError::MakeRequestFailed(_) => ErrorKind::InvalidClientConfig,
+            JWT_AccessTokenEndpointError::DeResponseBodyFailed(_) => ErrorKind::Protocol,
+            JWT_AccessTokenEndpointError::DeErrorResponseBodyFailed(status, _) => {
+                return Self::with_status(*status, err)
+            }
+        };
+        Self::new(kind, err)
+    }
+}
+
+impl From<ROPC_AccessTokenEndpointError> for Error {
+    fn from(err: ROPC_AccessTokenEndpointError) -> Self {
+        let kind = match &err {
+            ROPC_AccessTokenEndpointError::ClientIdMissing
+            | ROPC_AccessTokenEndpointError::ClientSecretMissing
+            | ROPC_AccessTokenEndpointError::MakeRequestBodyExtraFailed(_)
+            | ROPC_AccessTokenEndpointError::SerRequestBodyFailed(_)
+            | ROPC_AccessTokenEndpointError::MakeRequestFailed(_) => ErrorKind::InvalidClientConfig,
+            ROPC_AccessTokenEndpointError::DeResponseBodyFailed(_) => ErrorKind::Protocol,
+            ROPC_AccessTokenEndpointError::DeErrorResponseBodyFailed(status, _) => {
+                return Self::with_status(*status, err)
+            }
+        };
+        Self::new(kind, err)
+    }
+}
+
+impl From<EndpointExecuteError> for Error {
+    fn from(err: EndpointExecuteError) -> Self {
+        let kind = match &err {
+            EndpointExecuteError::RenderRequestError(_) => ErrorKind::InvalidClientConfig,
+            EndpointExecuteError::RespondFailed(_) => ErrorKind::Network,
+            EndpointExecuteError::ParseResponseError(_) => ErrorKind::Protocol,
+        };
+        Self::new(kind, err)
+    }
+}
+
+//
+// Machines
+//
+impl From<FlowBuildAuthorizationUrlError> for Error {
+    fn from(err: FlowBuildAuthorizationUrlError) -> Self {
+        match err {
+            FlowBuildAuthorizationUrlError::AuthorizationEndpointError(err) => err.into(),
+            err @ FlowBuildAuthorizationUrlError::ToUrlFailed(_) => {
+                Self::new(ErrorKind::InvalidClientConfig, err)
+            }
+        }
+    }
+}
+
+impl From<AC_MachineError> for Error {
+    fn from(err: AC_MachineError) -> Self {
+        match err {
+            AC_MachineError::AuthorizationFailed(body)
+            | AC_MachineError::AccessTokenFailed(body) => body.into(),
+            AC_MachineError::AccessTokenEndpointError(err) => err.into(),
+            err @ (AC_MachineError::ParseRedirectUriQueryError(_)
+            | AC_MachineError::StateMismatch
+            | AC_MachineError::StateMissing
+            | AC_MachineError::UnexpectedResponse) => Self::new(ErrorKind::Protocol, err),
+        }
+    }
+}
+
+impl From<CC_MachineError> for Error {
+    fn from(err: CC_MachineError) -> Self {
+        match err {
+            CC_MachineError::AccessTokenEndpointError(err) => err.into(),
+            CC_MachineError::AccessTokenFailed(body) => body.into(),
+        }
+    }
+}
+
+impl From<DA_MachineError> for Error {
+    fn from(err: DA_MachineError) -> Self {
+        match err {
+            DA_MachineError::DeviceAuthorizationEndpointError(err) => err.into(),
+            DA_MachineError::DeviceAccessTokenEndpointError(err) => err.into(),
+            DA_MachineError::DeviceAuthorizationFailed(body)
+            | DA_MachineError::DeviceAccessTokenFailed(body) => body.into(),
+            err @ DA_MachineError::DeviceCodeExpired => Self::new(ErrorKind::InvalidGrant, err),
+            err @ DA_MachineError::Finished => Self::new(ErrorKind::Protocol, err),
+        }
+    }
+}
+
+impl From<JWT_MachineError> for Error {
+    fn from(err: JWT_MachineError) -> Self {
+        match err {
+            JWT_MachineError::AccessTokenEndpointError(err) => err.into(),
+            JWT_MachineError::AccessTokenFailed(body) => body.into(),
+        }
+    }
+}
+
+impl From<ROPC_MachineError> for Error {
+    fn from(err: ROPC_MachineError) -> Self {
+        match err {
+            ROPC_MachineError::AccessTokenEndpointError(err) => err.into(),
+            ROPC_MachineError::AccessTokenFailed(body) => body.into(),
+        }
+    }
+}
+
+//
+// Flows
+//
+#[cfg(feature = "with-flow")]
+mod flow {
+    use super::*;
+
+    use crate::{
+        authorization_code_grant::{FlowHandleCallbackError, LoopbackRedirectError},
+        client_credentials_grant::FlowExecuteError as CC_FlowExecuteError,
+        device_authorization_grant::FlowExecuteError as DA_FlowExecuteError,
+        jwt_authorization_grant::FlowExecuteError as JWT_FlowExecuteError,
+        resource_owner_password_credentials_grant::FlowExecuteError as ROPC_FlowExecuteError,
+    };
+
+    impl From<FlowHandleCallbackError> for Error {
+        fn from(err: FlowHandleCallbackError) -> Self {
+            match err {
+                FlowHandleCallbackError::AuthorizationFailed(body)
+                | FlowHandleCallbackError::AccessTokenFailed(body) => body.into(),
+                FlowHandleCallbackError::AccessTokenEndpointRespondFailed(err) => {
+                    Self::new(ErrorKind::Network, err)
+                }
+                FlowHandleCallbackError::AccessTokenEndpointError(err) => err.into(),
+                err @ (FlowHandleCallbackError::ParseRedirectUriQueryError(_)
+                | FlowHandleCallbackError::StateMismatch
+                | FlowHandleCallbackError::StateMissing) => Self::new(ErrorKind::Protocol, err),
+            }
+        }
+    }
+
+    impl From<LoopbackRedirectError> for Error {
+        fn from(err: LoopbackRedirectError) -> Self {
+            match err {
+                LoopbackRedirectError::FlowBuildAuthorizationUrlError(err) => err.into(),
+                LoopbackRedirectError::AuthorizationFailed(body) => (*body).into(),
+                LoopbackRedirectError::FlowHandleCallbackError(err) => (*err).into(),
+                err @ (LoopbackRedirectError::NotLoopbackAddress(_)
+                | LoopbackRedirectError::BindFailed(_)
+                | LoopbackRedirectError::MakeRedirectUriFailed(_)
+                | LoopbackRedirectError::RedirectUriMismatch) => {
+                    Self::new(ErrorKind::InvalidClientConfig, err)
+                }
+                err @ LoopbackRedirectError::AcceptFailed(_) => Self::new(ErrorKind::Network, err),
+                err @ LoopbackRedirectError::CallbackWaitingAborted => {
+                    Self::new(ErrorKind::Cancelled, err)
+                }
+                err @ (LoopbackRedirectError::ParseRedirectUriQueryError(_)
+                | LoopbackRedirectError::StateMismatch
+                | LoopbackRedirectError::StateMissing) => Self::new(ErrorKind::Protocol, err),
+                err @ (LoopbackRedirectError::BrowserLaunchFailed(_)
+                | LoopbackRedirectError::TimedOut) => Self::new(ErrorKind::Other, err),
+            }
+        }
+    }
+
+    impl From<CC_FlowExecuteError> for Error {
+        fn from(err: CC_FlowExecuteError) -> Self {
+            match err {
+                CC_FlowExecuteError::AccessTokenEndpointRespondFailed(err) => {
+                    Self::new(ErrorKind::Network, err)
+                }
+                CC_FlowExecuteError::AccessTokenEndpointError(err) => err.into(),
+                CC_FlowExecuteError::AccessTokenFailed(body) => body.into(),
+            }
+        }
+    }
+
+    impl From<DA_FlowExecuteError> for Error {
+        fn from(err: DA_FlowExecuteError) -> Self {
+            match err {
+                DA_FlowExecuteError::DeviceAuthorizationEndpointRespondFailed(err)
+                | DA_FlowExecuteError::DeviceAccessTokenEndpointRespondFailed(err) => {
+                    Self::new(ErrorKind::Network, err)
+                }
+                DA_FlowExecuteError::DeviceAuthorizationEndpointError(err) => err.into(),
+                DA_FlowExecuteError::DeviceAccessTokenEndpointError(err) => err.into(),
+                DA_FlowExecuteError::DeviceAuthorizationFailed(body)
+                | DA_FlowExecuteError::DeviceAccessTokenFailed(body) => body.into(),
+                DA_FlowExecuteError::UserInteractionFailed(err) => Self::new(ErrorKind::Other, err),
+                err @ DA_FlowExecuteError::DeviceCodeExpired => {
+                    Self::new(ErrorKind::InvalidGrant, err)
+                }
+                err @ DA_FlowExecuteError::Cancelled => Self::new(ErrorKind::Cancelled, err),
+            }
+        }
+    }
+
+    impl From<JWT_FlowExecuteError> for Error {
+        fn from(err: JWT_FlowExecuteError) -> Self {
+            match err {
+                JWT_FlowExecuteError::AccessTokenEndpointRespondFailed(err) => {
+                    Self::new(ErrorKind::Network, err)
+                }
+                JWT_FlowExecuteError::AccessTokenEndpointError(err) => err.into(),
+                JWT_FlowExecuteError::AccessTokenFailed(body) => body.into(),
+            }
+        }
+    }
+
+    impl From<ROPC_FlowExecuteError> for Error {
+        fn from(err: ROPC_FlowExecuteError) -> Self {
+            match err {
+                ROPC_FlowExecuteError::AccessTokenEndpointRespondFailed(err) => {
+                    Self::new(ErrorKind::Network, err)
+                }
+                ROPC_FlowExecuteError::AccessTokenEndpointError(err) => err.into(),
+                ROPC_FlowExecuteError::AccessTokenFailed(body) => body.into(),
+            }
+        }
+    }
+}
+
+#[cfg(feature = "with-jwt-assertion")]
+impl From<crate::jwt_authorization_grant::JwtAssertionSignerError> for Error {
+    fn from(err: crate::jwt_authorization_grant::JwtAssertionSignerError) -> Self {
+        Self::new(ErrorKind::InvalidClientConfig, err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::error::Error as _;
+
+    #[test]
+    fn test_kind() -> Result<(), Box<dyn std::error::Error>> {
+        let body = serde_json::from_str::<ErrorBody>(
+            r#"{"error":"access_denied","error_description":"The user denied"}"#,
+        )?;
+        let err = Error::from(AC_MachineError::AuthorizationFailed(body));
+        assert_eq!(err.kind(), ErrorKind::UserDenied);
+        assert_eq!(
+            err.body().map(|x| &x.error),
+            Some(&ErrorBodyError::AccessDenied)
+        );
+        assert_eq!(err.to_string(), "UserDenied access_denied The user denied");
+        assert!(err.source().is_none());
+
+        let body = serde_json::from_str::<ErrorBody>(r#"{"error":"temporarily_unavailable"}"#)?;
+        let err = Error::from(CC_MachineError::AccessTokenFailed(body));
+        assert_eq!(err.kind(), ErrorKind::TransientServerError);
+        assert!(err.is_retryable());
+
+        let err = Error::from(DA_MachineError::DeviceCodeExpired);
+        assert_eq!(err.kind(), ErrorKind::InvalidGrant);
+        assert!(err.body().is_none());
+        assert!(err
+            .source()
+            .and_then(|x| x.downcast_ref::<DA_MachineError>())
+            .is_some());
+
+        let err = Error::from(AC_MachineError::StateMismatch);
+        assert_eq!(err.kind(), ErrorKind::Protocol);
+        assert!(!err.is_retryable());
+
+        let err = Error::from(CC_AccessTokenEndpointError::ClientSecretMissing);
+        assert_eq!(err.kind(), ErrorKind::InvalidClientConfig);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_kind_from_status() {
+        let de_err = || serde_json::from_str::<ErrorBody>("<html></html>").unwrap_err();
+
+        let err = Error::from(AC_AccessTokenEndpointError::DeErrorResponseBodyFailed(
+            StatusCode::BAD_GATEWAY,
+            de_err(),
+        ));
+        assert_eq!(err.kind(), ErrorKind::TransientServerError);
+        assert_eq!(err.status(), Some(StatusCode::BAD_GATEWAY));
+        assert!(err.is_retryable());
+        assert!(!err.is_rate_limited());
+
+        let err = Error::from(CC_AccessTokenEndpointError::DeErrorResponseBodyFailed(
+            StatusCode::TOO_MANY_REQUESTS,
+            de_err(),
+        ));
+        assert_eq!(err.kind(), ErrorKind::TransientServerError);
+        assert!(err.is_rate_limited());
+
+        let err = Error::from(DeviceAccessTokenEndpointError::DeErrorResponseBodyFailed(
+            StatusCode::BAD_REQUEST,
+            de_err(),
+        ));
+        assert_eq!(err.kind(), ErrorKind::Protocol);
+        assert!(!err.is_retryable());
+
+        let err = Error::from(JWT_AccessTokenEndpointError::DeResponseBodyFailed(de_err()));
+        assert_eq!(err.kind(), ErrorKind::Protocol);
+        assert_eq!(err.status(), None);
+    }
+
+    #[cfg(feature = "with-flow")]
+    #[test]
+    fn test_kind_from_flow_error() {
+        use crate::device_authorization_grant::FlowExecuteError;
+
+        let err = Error::from(FlowExecuteError::DeviceAccessTokenEndpointRespondFailed(
+            Box::new(std::io::Error::other("connection reset")),
+        ));
+        assert_eq!(err.kind(), ErrorKind::Network);
+        assert!(err.is_retryable());
+
+        let err = Error::from(FlowExecuteError::Cancelled);
+        assert_eq!(err.kind(), ErrorKind::Cancelled);
+    }
+}
@@ -2,7 +2,9 @@ use dyn_clone::{clone_trait_object, DynClone};
 
 use crate::re_exports::Scope;
 
-use super::{AccessTokenResponseSuccessfulBody, GrantInfo, UserInfo, UserInfoEndpointBox};
+use super::{
+    AccessTokenResponseSuccessfulBody, GrantInfo, UserInfo, UserInfoEndpointBox, UserInfoPipeline,
+};
 
 //
 //
@@ -19,10 +21,13 @@ where
 }
 
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum BuilderObtainUserInfoOutput {
     None,
     Static(UserInfo),
     Respond(UserInfoEndpointBox),
+    /// Several requests merged into one `UserInfo`.
+    Pipeline(UserInfoPipeline),
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -0,0 +1,172 @@
+use crate::{
+    id_token::{decode_payload, IdTokenClaims, IdTokenError, IdTokenValidation},
+    re_exports::Scope,
+};
+
+use super::{
+    AccessTokenResponseSuccessfulBody, Builder, BuilderObtainUserInfoError,
+    BuilderObtainUserInfoOutput, DefaultBuilder, GrantInfo, UserInfo,
+};
+
+//
+//
+//
+/// Reads `UserInfo` from the `id_token` claims without an extra request, `fallback` is used when
+/// the access token response has no `id_token`.
+///
+/// `iss`, `aud`, `exp` and `nonce` are validated, the signature is not, see [`crate::id_token`].
+#[derive(Debug, Clone)]
+pub struct IdTokenExtensionsBuilder<F = DefaultBuilder> {
+    /// Empty means any issuer.
+    pub issuers: Vec<String>,
+    pub fallback: F,
+}
+
+impl IdTokenExtensionsBuilder {
+    pub fn new() -> Self {
+        Self {
+            issuers: vec![],
+            fallback: DefaultBuilder,
+        }
+    }
+}
+
+impl Default for IdTokenExtensionsBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<F> IdTokenExtensionsBuilder<F> {
+    pub fn with_issuer(mut self, issuer: impl Into<String>) -> Self {
+        self.issuers.push(issuer.into());
+        self
+    }
+
+    pub fn with_fallback<F2>(self, fallback: F2) -> IdTokenExtensionsBuilder<F2> {
+        IdTokenExtensionsBuilder {
+            issuers: self.issuers,
+            fallback,
+        }
+    }
+}
+
+impl<SCOPE, F> Builder<SCOPE> for IdTokenExtensionsBuilder<F>
+where
+    SCOPE: Scope,
+    F: Builder<SCOPE> + Clone,
+{
+    fn obtain_user_info(
+        &self,
+        grant_info: GrantInfo<SCOPE>,
+        access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
+    ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
+        let id_token = match &access_token.id_token {
+            Some(x) => x,
+            None => return self.fallback.obtain_user_info(grant_info, access_token),
+        };
+
+        let (client_id, nonce) = match &grant_info {
+            GrantInfo::AuthorizationCodeGrant(info) => {
+                (info.provider.client_id(), info.authorization_request_nonce)
+            }
+            GrantInfo::DeviceAuthorizationGrant(info) => (info.provider.client_id(), None),
+        };
+        let validation = IdTokenValidation {
+            issuers: self.issuers.to_owned(),
+            client_id: client_id.map(ToOwned::to_owned),
+            nonce: nonce.map(ToOwned::to_owned),
+            ..Default::default()
+        };
+
+        user_info_from_id_token(id_token, &validation)
+            .map(BuilderObtainUserInfoOutput::Static)
+            .map_err(|err| BuilderObtainUserInfoError::Other(Box::new(err)))
+    }
+}
+
+pub fn user_info_from_id_token(
+    id_token: impl AsRef<str>,
+    validation: &IdTokenValidation,
+) -> Result<UserInfo, IdTokenError> {
+    let raw = decode_payload(id_token)?;
+    let claims = IdTokenClaims::from_payload(raw.to_owned())?;
+    claims.validate(validation)?;
+
+    Ok(UserInfo {
+        uid: claims.sub,
+        raw,
+        ..Default::default()
+    }
+    .with_standard_claims_from_raw())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{
+        extensions::AuthorizationCodeGrantInfo,
+        id_token::tests::{encode, exp_in_one_hour},
+        test_utils::TestProvider,
+    };
+
+    #[test]
+    fn test_obtain_user_info() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = TestProvider::new();
+        let nonce = "NONCE".to_owned();
+        let grant_info = || {
+            GrantInfo::AuthorizationCodeGrant(AuthorizationCodeGrantInfo {
+                provider: &provider,
+                authorization_request_scopes: None,
+                authorization_request_nonce: Some(&nonce),
+            })
+        };
+
+        let exp = exp_in_one_hour();
+        let mut access_token = AccessTokenResponseSuccessfulBody::<String>::new(
+            "ACCESS_TOKEN".into(),
+            Default::default(),
+            None,
+            None,
+            None,
+        );
+        access_token.id_token = Some(encode(&serde_json::json!({
+            "iss": "https://appleid.apple.com",
+            "sub": "001234.abcd",
+            "aud": "CLIENT_ID",
+            "exp": exp,
+            "iat": exp - 3600,
+            "nonce": "NONCE",
+            "email": "foo@privaterelay.appleid.com",
+            "email_verified": "true",
+        })));
+
+        let builder = IdTokenExtensionsBuilder::new().with_issuer("https://appleid.apple.com");
+        match builder.obtain_user_info(grant_info(), &access_token)? {
+            BuilderObtainUserInfoOutput::Static(user_info) => {
+                assert_eq!(user_info.uid, "001234.abcd");
+                assert_eq!(
+                    user_info.email,
+                    Some("foo@privaterelay.appleid.com".to_owned())
+                );
+                assert_eq!(user_info.email_verified, Some(true));
+            }
+            x => panic!("{x:?}"),
+        }
+
+        let builder = IdTokenExtensionsBuilder::new().with_issuer("https://accounts.google.com");
+        match builder.obtain_user_info(grant_info(), &access_token) {
+            Err(BuilderObtainUserInfoError::Other(_)) => {}
+            x => panic!("{x:?}"),
+        }
+
+        access_token.id_token = None;
+        match builder.obtain_user_info(grant_info(), &access_token)? {
+            BuilderObtainUserInfoOutput::None => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -7,8 +7,10 @@ pub use oauth2_core::re_exports::{
 pub mod builder;
 pub mod endpoint_errors;
 pub mod grant_info;
+pub mod id_token_builder;
 pub mod user_info;
 pub mod user_info_endpoint;
+pub mod user_info_pipeline;
 
 pub use builder::{
     Builder, BuilderObtainUserInfoError, BuilderObtainUserInfoOutput, DefaultBuilder,
@@ -17,5 +19,7 @@ pub use endpoint_errors::{
     EndpointExecuteError, EndpointParseResponseError, EndpointRenderRequestError,
 };
 pub use grant_info::{AuthorizationCodeGrantInfo, DeviceAuthorizationGrantInfo, GrantInfo};
+pub use id_token_builder::IdTokenExtensionsBuilder;
 pub use user_info::UserInfo;
 pub use user_info_endpoint::UserInfoEndpointBox;
+pub use user_info_pipeline::{UserInfoPipeline, UserInfoPipelineStep, UserInfoPipelineStepBox};
@@ -1,11 +1,101 @@
 use serde_json::{Map, Value};
 
 //
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct UserInfo {
     pub uid: String,
     pub name: Option<String>,
     pub email: Option<String>,
+    // OpenID Connect Core 1.0 standard claims, mapped from the provider's native response.
+    pub preferred_username: Option<String>,
+    pub given_name: Option<String>,
+    pub family_name: Option<String>,
+    /// Avatar URL, e.g. GitHub `avatar_url`, Twitch `profile_image_url`, WeChat `headimgurl`.
+    pub picture: Option<String>,
+    pub email_verified: Option<bool>,
+    pub locale: Option<String>,
+    pub zoneinfo: Option<String>,
+    /// Profile page URL.
+    pub profile: Option<String>,
+    pub phone_number: Option<String>,
+    /// Seconds since the Unix epoch.
+    pub updated_at: Option<i64>,
     //
     pub raw: Map<String, Value>,
 }
+
+impl UserInfo {
+    /// Fills the standard claims that are still `None` from `raw`, for providers whose
+    /// response already uses the OIDC claim names.
+    pub fn with_standard_claims_from_raw(mut self) -> Self {
+        let str_claim = |raw: &Map<String, Value>, key: &str| {
+            raw.get(key).and_then(|x| x.as_str()).map(ToOwned::to_owned)
+        };
+
+        macro_rules! fill_str {
+            ($($field:ident),+) => {
+                $(
+                    if self.$field.is_none() {
+                        self.$field = str_claim(&self.raw, stringify!($field));
+                    }
+                )+
+            };
+        }
+        fill_str!(
+            name,
+            email,
+            preferred_username,
+            given_name,
+            family_name,
+            picture,
+            locale,
+            zoneinfo,
+            profile,
+            phone_number
+        );
+
+        if self.email_verified.is_none() {
+            self.email_verified = match self.raw.get("email_verified") {
+                Some(Value::Bool(b)) => Some(*b),
+                // e.g. AWS Cognito
+                Some(Value::String(s)) => s.parse().ok(),
+                _ => None,
+            };
+        }
+        if self.updated_at.is_none() {
+            self.updated_at = self.raw.get("updated_at").and_then(|x| x.as_i64());
+        }
+
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_with_standard_claims_from_raw() -> Result<(), Box<dyn std::error::Error>> {
+        let raw = serde_json::from_str::<Map<String, Value>>(
+            r#"{"sub":"1","name":"Jane","picture":"https://example.com/a.png","email_verified":"true","updated_at":1700000000}"#,
+        )?;
+        let user_info = UserInfo {
+            uid: "1".to_owned(),
+            name: Some("Jane Doe".to_owned()),
+            raw,
+            ..Default::default()
+        }
+        .with_standard_claims_from_raw();
+
+        assert_eq!(user_info.name, Some("Jane Doe".to_owned()));
+        assert_eq!(
+            user_info.picture,
+            Some("https://example.com/a.png".to_owned())
+        );
+        assert_eq!(user_info.email_verified, Some(true));
+        assert_eq!(user_info.updated_at, Some(1700000000));
+        assert_eq!(user_info.locale, None);
+
+        Ok(())
+    }
+}
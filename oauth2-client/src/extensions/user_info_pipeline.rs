@@ -0,0 +1,204 @@
+use dyn_clone::{clone_trait_object, DynClone};
+
+use crate::re_exports::{Body, Request, Response};
+
+#[cfg(feature = "with-flow")]
+use super::EndpointExecuteError;
+use super::{
+    EndpointParseResponseError, EndpointRenderRequestError, UserInfo, UserInfoEndpointBox,
+};
+
+//
+//
+//
+/// A follow-up request that fills in what the first user info response is missing, e.g.
+/// GitHub `/user/emails` when the email is private.
+pub trait UserInfoPipelineStep: DynClone {
+    /// `None` skips the step.
+    fn render_request(
+        &self,
+        user_info: &UserInfo,
+    ) -> Result<Option<Request<Body>>, EndpointRenderRequestError>;
+
+    fn merge_response(
+        &self,
+        response: Response<Body>,
+        user_info: &mut UserInfo,
+    ) -> Result<(), EndpointParseResponseError>;
+}
+
+clone_trait_object!(UserInfoPipelineStep);
+
+pub type UserInfoPipelineStepBox = Box<dyn UserInfoPipelineStep + Send + Sync>;
+
+impl core::fmt::Debug for dyn UserInfoPipelineStep + Send + Sync {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("UserInfoPipelineStep").finish()
+    }
+}
+
+//
+//
+//
+#[derive(Debug)]
+pub struct UserInfoPipeline {
+    pub endpoint: UserInfoEndpointBox,
+    pub steps: Vec<UserInfoPipelineStepBox>,
+}
+
+impl UserInfoPipeline {
+    pub fn new(endpoint: UserInfoEndpointBox) -> Self {
+        Self {
+            endpoint,
+            steps: vec![],
+        }
+    }
+
+    pub fn with_step<S>(mut self, step: S) -> Self
+    where
+        S: UserInfoPipelineStep + Send + Sync + 'static,
+    {
+        self.steps.push(Box::new(step));
+        self
+    }
+}
+
+impl From<UserInfoEndpointBox> for UserInfoPipeline {
+    fn from(endpoint: UserInfoEndpointBox) -> Self {
+        Self::new(endpoint)
+    }
+}
+
+#[cfg(feature = "with-flow")]
+impl UserInfoPipeline {
+    /// Steps run in order, each one sees what the previous ones merged.
+    pub async fn execute<C>(&self, client: &C) -> Result<UserInfo, EndpointExecuteError>
+    where
+        C: http_api_client::Client + Send + Sync,
+    {
+        use http_api_client::ClientRespondEndpointError;
+
+        let mut user_info = client
+            .respond_dyn_endpoint(self.endpoint.as_ref())
+            .await
+            .map_err(|err| match err {
+                ClientRespondEndpointError::RespondFailed(err) => {
+                    EndpointExecuteError::RespondFailed(Box::new(err))
+                }
+                ClientRespondEndpointError::EndpointRenderRequestFailed(err) => {
+                    EndpointExecuteError::RenderRequestError(err)
+                }
+                ClientRespondEndpointError::EndpointParseResponseFailed(err) => {
+                    EndpointExecuteError::ParseResponseError(err)
+                }
+            })?;
+
+        for step in self.steps.iter() {
+            let request = match step
+                .render_request(&user_info)
+                .map_err(EndpointExecuteError::RenderRequestError)?
+            {
+                Some(x) => x,
+                None => continue,
+            };
+
+            let response = client
+                .respond(request)
+                .await
+                .map_err(|err| EndpointExecuteError::RespondFailed(Box::new(err)))?;
+
+            step.merge_response(response, &mut user_info)
+                .map_err(EndpointExecuteError::ParseResponseError)?;
+        }
+
+        Ok(user_info)
+    }
+}
+
+#[cfg(all(test, feature = "with-flow"))]
+mod tests {
+    use super::*;
+
+    use crate::{
+        re_exports::{Endpoint, Map},
+        test_utils::{response, TestClient},
+    };
+
+    #[derive(Debug, Clone)]
+    struct UserEndpoint;
+    impl Endpoint for UserEndpoint {
+        type RenderRequestError = EndpointRenderRequestError;
+
+        type ParseResponseOutput = UserInfo;
+        type ParseResponseError = EndpointParseResponseError;
+
+        fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+            Request::builder()
+                .uri("https://example.com/user")
+                .body(vec![])
+                .map_err(EndpointRenderRequestError::MakeRequestFailed)
+        }
+
+        fn parse_response(
+            &self,
+            response: Response<Body>,
+        ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+            let raw = serde_json::from_slice::<Map<String, serde_json::Value>>(response.body())
+                .map_err(EndpointParseResponseError::DeResponseBodyFailed)?;
+            Ok(UserInfo {
+                uid: "1".to_owned(),
+                raw,
+                ..Default::default()
+            })
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    struct EmailStep;
+    impl UserInfoPipelineStep for EmailStep {
+        fn render_request(
+            &self,
+            user_info: &UserInfo,
+        ) -> Result<Option<Request<Body>>, EndpointRenderRequestError> {
+            if user_info.email.is_some() {
+                return Ok(None);
+            }
+            Request::builder()
+                .uri("https://example.com/user/emails")
+                .body(vec![])
+                .map(Some)
+                .map_err(EndpointRenderRequestError::MakeRequestFailed)
+        }
+
+        fn merge_response(
+            &self,
+            response: Response<Body>,
+            user_info: &mut UserInfo,
+        ) -> Result<(), EndpointParseResponseError> {
+            user_info.email = Some(String::from_utf8_lossy(response.body()).to_string());
+            user_info.email_verified = Some(true);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_execute() -> Result<(), Box<dyn std::error::Error>> {
+        futures_executor::block_on(async {
+            let pipeline = UserInfoPipeline::new(Box::new(UserEndpoint))
+                .with_step(EmailStep)
+                .with_step(EmailStep);
+
+            let client = TestClient::new([
+                Some(response(200, r#"{"id":"1"}"#)),
+                Some(response(200, "foo@example.com")),
+            ]);
+            let user_info = pipeline.execute(&client).await?;
+            assert_eq!(user_info.uid, "1");
+            assert_eq!(user_info.email, Some("foo@example.com".to_owned()));
+            assert_eq!(user_info.email_verified, Some(true));
+            assert_eq!(client.request_count(), 2);
+
+            Ok(())
+        })
+    }
+}
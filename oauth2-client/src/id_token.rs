@@ -0,0 +1,284 @@
+//! Claims of an OpenID Connect ID token.
+//!
+//! The signature is not checked, only ID tokens received directly from the token endpoint over
+//! TLS should be decoded here (OpenID Connect Core 1.0 section 3.1.3.7).
+
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Error as SerdeJsonError, Map, Value};
+
+pub const LEEWAY_DEFAULT: Duration = Duration::from_secs(60);
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct IdTokenClaims {
+    pub iss: String,
+    pub sub: String,
+    #[serde(deserialize_with = "deserialize_audience")]
+    pub aud: Vec<String>,
+    pub exp: u64,
+    pub iat: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub azp: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_time: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acr: Option<String>,
+    //
+    #[serde(flatten)]
+    pub _extra: Map<String, Value>,
+}
+
+impl IdTokenClaims {
+    pub fn decode(id_token: impl AsRef<str>) -> Result<Self, IdTokenError> {
+        Self::from_payload(decode_payload(id_token)?)
+    }
+
+    pub fn from_payload(payload: Map<String, Value>) -> Result<Self, IdTokenError> {
+        serde_json::from_value(Value::Object(payload)).map_err(IdTokenError::DeClaimsFailed)
+    }
+
+    pub fn validate(&self, validation: &IdTokenValidation) -> Result<(), IdTokenError> {
+        if !validation.issuers.is_empty() && !validation.issuers.contains(&self.iss) {
+            return Err(IdTokenError::IssuerMismatch(self.iss.to_owned()));
+        }
+
+        if let Some(client_id) = &validation.client_id {
+            if !self.aud.contains(client_id) {
+                return Err(IdTokenError::AudienceMismatch);
+            }
+            if self.aud.len() > 1 && self.azp.as_ref() != Some(client_id) {
+                return Err(IdTokenError::AudienceMismatch);
+            }
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| IdTokenError::SystemTimeBeforeUnixEpoch)?;
+        if Duration::from_secs(self.exp) + validation.leeway <= now {
+            return Err(IdTokenError::Expired);
+        }
+
+        if let Some(nonce) = &validation.nonce {
+            if self.nonce.as_ref() != Some(nonce) {
+                return Err(IdTokenError::NonceMismatch);
+            }
+        }
+
+        // OpenID Connect Core 1.0 section 3.1.3.7 items 11 and 12
+        if let Some(max_age) = validation.max_age {
+            let auth_time = self.auth_time.ok_or(IdTokenError::AuthTimeMissing)?;
+            if Duration::from_secs(auth_time) + max_age + validation.leeway < now {
+                return Err(IdTokenError::AuthTimeTooOld(auth_time));
+            }
+        }
+
+        if !validation.acr_values.is_empty() {
+            match &self.acr {
+                Some(acr) if validation.acr_values.contains(acr) => {}
+                acr => return Err(IdTokenError::AcrMismatch(acr.to_owned())),
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The payload as is, without checking any claim.
+pub fn decode_payload(id_token: impl AsRef<str>) -> Result<Map<String, Value>, IdTokenError> {
+    let mut parts = id_token.as_ref().split('.');
+    let payload = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(_), Some(payload), Some(_), None) => payload,
+        _ => return Err(IdTokenError::Malformed),
+    };
+
+    let payload = general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .map_err(|_| IdTokenError::Malformed)?;
+
+    serde_json::from_slice(&payload).map_err(IdTokenError::DeClaimsFailed)
+}
+
+pub(crate) fn deserialize_audience<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Audience {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Audience::deserialize(deserializer)? {
+        Audience::One(x) => vec![x],
+        Audience::Many(x) => x,
+    })
+}
+
+//
+#[derive(Debug, Clone)]
+pub struct IdTokenValidation {
+    /// Empty means any issuer, e.g. Microsoft `common` tenant.
+    pub issuers: Vec<String>,
+    pub client_id: Option<String>,
+    pub nonce: Option<String>,
+    /// The `max_age` of the authentication request.
+    pub max_age: Option<Duration>,
+    /// The `acr_values` of the authentication request, empty means any.
+    pub acr_values: Vec<String>,
+    pub leeway: Duration,
+}
+impl Default for IdTokenValidation {
+    fn default() -> Self {
+        Self {
+            issuers: vec![],
+            client_id: None,
+            nonce: None,
+            max_age: None,
+            acr_values: vec![],
+            leeway: LEEWAY_DEFAULT,
+        }
+    }
+}
+
+//
+#[derive(thiserror::Error, Debug)]
+pub enum IdTokenError {
+    /// No `id_token` in the access token response.
+    #[error("Missing")]
+    Missing,
+    #[error("Malformed")]
+    Malformed,
+    #[error("DeClaimsFailed {0}")]
+    DeClaimsFailed(SerdeJsonError),
+    //
+    #[error("IssuerMismatch {0}")]
+    IssuerMismatch(String),
+    #[error("AudienceMismatch")]
+    AudienceMismatch,
+    #[error("Expired")]
+    Expired,
+    #[error("NonceMismatch")]
+    NonceMismatch,
+    #[error("AuthTimeMissing")]
+    AuthTimeMissing,
+    #[error("AuthTimeTooOld {0}")]
+    AuthTimeTooOld(u64),
+    #[error("AcrMismatch {0:?}")]
+    AcrMismatch(Option<String>),
+    #[error("SystemTimeBeforeUnixEpoch")]
+    SystemTimeBeforeUnixEpoch,
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    pub(crate) fn encode(claims: &Value) -> String {
+        format!(
+            "eyJhbGciOiJSUzI1NiJ9.{}.c2lnbmF0dXJl",
+            general_purpose::URL_SAFE_NO_PAD.encode(claims.to_string())
+        )
+    }
+
+    pub(crate) fn exp_in_one_hour() -> u64 {
+        (SystemTime::now() + Duration::from_secs(3600))
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs()
+    }
+
+    #[test]
+    fn test_decode_and_validate() -> Result<(), Box<dyn std::error::Error>> {
+        let exp = exp_in_one_hour();
+        let id_token = encode(&serde_json::json!({
+            "iss": "https://accounts.google.com",
+            "sub": "110169484474386276334",
+            "aud": "CLIENT_ID",
+            "exp": exp,
+            "iat": exp - 3600,
+            "nonce": "NONCE",
+            "email": "foo@example.com",
+        }));
+
+        let claims = IdTokenClaims::decode(&id_token)?;
+        assert_eq!(claims.sub, "110169484474386276334");
+        assert_eq!(claims.aud, vec!["CLIENT_ID".to_owned()]);
+        assert_eq!(claims._extra["email"], "foo@example.com");
+
+        let validation = IdTokenValidation {
+            issuers: vec!["https://accounts.google.com".to_owned()],
+            client_id: Some("CLIENT_ID".to_owned()),
+            nonce: Some("NONCE".to_owned()),
+            ..Default::default()
+        };
+        claims.validate(&validation)?;
+
+        match claims.validate(&IdTokenValidation {
+            client_id: Some("OTHER".to_owned()),
+            ..validation.to_owned()
+        }) {
+            Err(IdTokenError::AudienceMismatch) => {}
+            x => panic!("{x:?}"),
+        }
+        match claims.validate(&IdTokenValidation {
+            nonce: Some("OTHER".to_owned()),
+            ..validation.to_owned()
+        }) {
+            Err(IdTokenError::NonceMismatch) => {}
+            x => panic!("{x:?}"),
+        }
+        let expired = IdTokenClaims {
+            exp: 1,
+            ..claims.to_owned()
+        };
+        match expired.validate(&validation) {
+            Err(IdTokenError::Expired) => {}
+            x => panic!("{x:?}"),
+        }
+
+        let validation = IdTokenValidation {
+            max_age: Some(Duration::from_secs(300)),
+            acr_values: vec!["urn:mace:incommon:iap:silver".to_owned()],
+            ..validation
+        };
+        match claims.validate(&validation) {
+            Err(IdTokenError::AuthTimeMissing) => {}
+            x => panic!("{x:?}"),
+        }
+        let old = IdTokenClaims {
+            auth_time: Some(exp - 3600 * 2),
+            ..claims.to_owned()
+        };
+        match old.validate(&validation) {
+            Err(IdTokenError::AuthTimeTooOld(_)) => {}
+            x => panic!("{x:?}"),
+        }
+        let recent = IdTokenClaims {
+            auth_time: Some(exp - 3600),
+            ..claims.to_owned()
+        };
+        match recent.validate(&validation) {
+            Err(IdTokenError::AcrMismatch(None)) => {}
+            x => panic!("{x:?}"),
+        }
+        IdTokenClaims {
+            acr: Some("urn:mace:incommon:iap:silver".to_owned()),
+            ..recent
+        }
+        .validate(&validation)?;
+
+        match IdTokenClaims::decode("foo.bar") {
+            Err(IdTokenError::Malformed) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use oauth2_core::{
     access_token_response::{CONTENT_TYPE as RES_CONTENT_TYPE, GENERAL_ERROR_BODY_KEY_ERROR},
     http::{
         header::{ACCEPT, CONTENT_TYPE},
-        Error as HttpError,
+        Error as HttpError, StatusCode,
     },
     jwt_authorization_grant::access_token_response::{
         ErrorBody as RES_ErrorBody, SuccessfulBody as RES_SuccessfulBody,
@@ -18,6 +18,8 @@ use oauth2_core::{
 use serde_json::{Error as SerdeJsonError, Map, Value};
 use serde_urlencoded::ser::Error as SerdeUrlencodedSerError;
 
+#[cfg(feature = "with-tracing")]
+use crate::trace;
 use crate::ProviderExtJwtAuthorizationGrant;
 
 //
@@ -56,6 +58,13 @@ where
     type ParseResponseError = AccessTokenEndpointError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span(
+            self.provider.name(),
+            Some(trace::grant_type::JWT_BEARER),
+            trace::endpoint::ACCESS_TOKEN,
+        );
+
         //
         let mut url = self.provider.token_endpoint_url().to_owned();
 
@@ -63,8 +72,13 @@ where
         self.provider.access_token_request_url_modifying(&mut url);
 
         //
+        let assertion = self
+            .provider
+            .assertion()
+            .map_err(AccessTokenEndpointError::MakeAssertionFailed)?;
+
         let mut body = BodyWithJwtAuthorizationGrant::new(
-            self.provider.assertion().to_owned(),
+            assertion,
             self.scopes.to_owned().map(Into::into),
             self.provider.client_id().map(Into::into),
         );
@@ -97,6 +111,8 @@ where
         //
         self.provider.access_token_request_modifying(&mut request);
 
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
         Ok(request)
     }
 
@@ -104,6 +120,15 @@ where
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span(
+            self.provider.name(),
+            Some(trace::grant_type::JWT_BEARER),
+            trace::endpoint::ACCESS_TOKEN,
+        );
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         //
         if response.status().is_success() {
             let map = serde_json::from_slice::<Map<String, Value>>(response.body())
@@ -116,14 +141,17 @@ where
             }
         }
 
-        let body = serde_json::from_slice::<RES_ErrorBody>(response.body())
-            .map_err(AccessTokenEndpointError::DeResponseBodyFailed)?;
+        let body = serde_json::from_slice::<RES_ErrorBody>(response.body()).map_err(|err| {
+            AccessTokenEndpointError::DeErrorResponseBodyFailed(response.status(), err)
+        })?;
         Ok(Err(body))
     }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum AccessTokenEndpointError {
+    #[error("MakeAssertionFailed {0}")]
+    MakeAssertionFailed(Box<dyn std::error::Error + Send + Sync>),
     #[error("MakeRequestBodyExtraFailed {0}")]
     MakeRequestBodyExtraFailed(Box<dyn std::error::Error + Send + Sync>),
     //
@@ -134,4 +162,6 @@ pub enum AccessTokenEndpointError {
     //
     #[error("DeResponseBodyFailed {0}")]
     DeResponseBodyFailed(SerdeJsonError),
+    #[error("DeErrorResponseBodyFailed {0} {1}")]
+    DeErrorResponseBodyFailed(StatusCode, SerdeJsonError),
 }
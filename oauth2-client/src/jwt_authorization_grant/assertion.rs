@@ -0,0 +1,369 @@
+//! https://datatracker.ietf.org/doc/html/rfc7523#section-3
+
+use core::time::Duration;
+use std::{
+    sync::{Arc, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use jsonwebtoken::{encode, errors::Error as JsonwebtokenError, Algorithm, EncodingKey, Header};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+use crate::utils::gen_nonce;
+
+pub const LIFETIME_DEFAULT: Duration = Duration::from_secs(3600);
+pub const EARLY_RESIGN_DEFAULT: Duration = Duration::from_secs(60);
+
+//
+//
+//
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct JwtAssertionClaims {
+    pub iss: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    pub aud: String,
+    pub exp: u64,
+    pub iat: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jti: Option<String>,
+
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+//
+//
+//
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum JwtAssertionSigningAlgorithm {
+    Rs256,
+    Es256,
+}
+
+impl From<JwtAssertionSigningAlgorithm> for Algorithm {
+    fn from(alg: JwtAssertionSigningAlgorithm) -> Self {
+        match alg {
+            JwtAssertionSigningAlgorithm::Rs256 => Algorithm::RS256,
+            JwtAssertionSigningAlgorithm::Es256 => Algorithm::ES256,
+        }
+    }
+}
+
+//
+//
+//
+/// Signs and caches the assertion, re-signs it when it is about to expire.
+///
+/// With `with_jti` every assertion is signed fresh, since a `jti` must not be replayed.
+/// Cloned signers share the cached assertion.
+#[derive(Clone)]
+pub struct JwtAssertionSigner {
+    algorithm: JwtAssertionSigningAlgorithm,
+    encoding_key: EncodingKey,
+    pub iss: String,
+    pub aud: String,
+    pub sub: Option<String>,
+    pub kid: Option<String>,
+    pub extra: Map<String, Value>,
+    pub lifetime: Duration,
+    pub early_resign: Duration,
+    pub with_jti: bool,
+    //
+    cached: Arc<RwLock<Option<(String, u64)>>>,
+}
+
+impl core::fmt::Debug for JwtAssertionSigner {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("JwtAssertionSigner")
+            .field("algorithm", &self.algorithm)
+            .field("iss", &self.iss)
+            .field("aud", &self.aud)
+            .field("sub", &self.sub)
+            .field("kid", &self.kid)
+            .field("extra", &self.extra)
+            .field("lifetime", &self.lifetime)
+            .field("early_resign", &self.early_resign)
+            .field("with_jti", &self.with_jti)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JwtAssertionSigner {
+    pub fn new(
+        algorithm: JwtAssertionSigningAlgorithm,
+        encoding_key: EncodingKey,
+        iss: impl Into<String>,
+        aud: impl Into<String>,
+    ) -> Self {
+        Self {
+            algorithm,
+            encoding_key,
+            iss: iss.into(),
+            aud: aud.into(),
+            sub: None,
+            kid: None,
+            extra: Map::new(),
+            lifetime: LIFETIME_DEFAULT,
+            early_resign: EARLY_RESIGN_DEFAULT,
+            with_jti: true,
+            cached: Default::default(),
+        }
+    }
+
+    /// PKCS#8 PEM, or PKCS#1 PEM for RS256.
+    pub fn from_pem(
+        algorithm: JwtAssertionSigningAlgorithm,
+        key: &[u8],
+        iss: impl Into<String>,
+        aud: impl Into<String>,
+    ) -> Result<Self, JwtAssertionSignerError> {
+        let encoding_key = match algorithm {
+            JwtAssertionSigningAlgorithm::Rs256 => EncodingKey::from_rsa_pem(key),
+            JwtAssertionSigningAlgorithm::Es256 => EncodingKey::from_ec_pem(key),
+        }
+        .map_err(JwtAssertionSignerError::InvalidKey)?;
+
+        Ok(Self::new(algorithm, encoding_key, iss, aud))
+    }
+
+    /// PKCS#8 DER for ES256, PKCS#1 DER for RS256.
+    pub fn from_der(
+        algorithm: JwtAssertionSigningAlgorithm,
+        key: &[u8],
+        iss: impl Into<String>,
+        aud: impl Into<String>,
+    ) -> Self {
+        let encoding_key = match algorithm {
+            JwtAssertionSigningAlgorithm::Rs256 => EncodingKey::from_rsa_der(key),
+            JwtAssertionSigningAlgorithm::Es256 => EncodingKey::from_ec_der(key),
+        };
+
+        Self::new(algorithm, encoding_key, iss, aud)
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn set_sub(&mut self, sub: impl Into<String>) {
+        self.sub = Some(sub.into());
+    }
+
+    pub fn set_kid(&mut self, kid: impl Into<String>) {
+        self.kid = Some(kid.into());
+    }
+
+    pub fn set_extra_claim(&mut self, key: impl Into<String>, value: Value) {
+        self.extra.insert(key.into(), value);
+    }
+
+    pub fn set_lifetime(&mut self, lifetime: Duration) {
+        self.lifetime = lifetime;
+    }
+
+    pub fn set_early_resign(&mut self, early_resign: Duration) {
+        self.early_resign = early_resign;
+    }
+
+    pub fn set_with_jti(&mut self, with_jti: bool) {
+        self.with_jti = with_jti;
+    }
+
+    pub fn algorithm(&self) -> JwtAssertionSigningAlgorithm {
+        self.algorithm
+    }
+
+    pub fn claims(&self, iat: u64) -> JwtAssertionClaims {
+        JwtAssertionClaims {
+            iss: self.iss.to_owned(),
+            sub: self.sub.to_owned(),
+            aud: self.aud.to_owned(),
+            exp: iat + self.lifetime.as_secs(),
+            iat,
+            jti: if self.with_jti {
+                Some(gen_nonce(None))
+            } else {
+                None
+            },
+            extra: self.extra.to_owned(),
+        }
+    }
+
+    pub fn sign(&self, claims: &JwtAssertionClaims) -> Result<String, JwtAssertionSignerError> {
+        let mut header = Header::new(self.algorithm.into());
+        header.kid = self.kid.to_owned();
+
+        encode(&header, claims, &self.encoding_key).map_err(JwtAssertionSignerError::SignFailed)
+    }
+
+    /// Signs a new assertion issued now, bypassing the cache.
+    pub fn sign_now(&self) -> Result<String, JwtAssertionSignerError> {
+        self.sign(&self.claims(now_secs()?))
+    }
+
+    /// Returns the cached assertion, or a new one if it expires within `early_resign` or
+    /// `with_jti` is set.
+    pub fn assertion(&self) -> Result<String, JwtAssertionSignerError> {
+        self.assertion_at(now_secs()?)
+    }
+
+    fn assertion_at(&self, now: u64) -> Result<String, JwtAssertionSignerError> {
+        if self.with_jti {
+            return self.sign(&self.claims(now));
+        }
+
+        if let Ok(cached) = self.cached.read() {
+            if let Some((assertion, exp)) = cached.as_ref() {
+                if now + self.early_resign.as_secs() < *exp {
+                    return Ok(assertion.to_owned());
+                }
+            }
+        }
+
+        let claims = self.claims(now);
+        let assertion = self.sign(&claims)?;
+
+        if let Ok(mut cached) = self.cached.write() {
+            *cached = Some((assertion.to_owned(), claims.exp));
+        }
+
+        Ok(assertion)
+    }
+}
+
+fn now_secs() -> Result<u64, JwtAssertionSignerError> {
+    Ok(SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|_| JwtAssertionSignerError::SystemTimeBeforeUnixEpoch)?
+        .as_secs())
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum JwtAssertionSignerError {
+    #[error("InvalidKey {0}")]
+    InvalidKey(JsonwebtokenError),
+    #[error("SignFailed {0}")]
+    SignFailed(JsonwebtokenError),
+    #[error("SystemTimeBeforeUnixEpoch")]
+    SystemTimeBeforeUnixEpoch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use jsonwebtoken::{decode, decode_header, DecodingKey, Validation};
+
+    fn decode_claims(
+        assertion: &str,
+        algorithm: Algorithm,
+        decoding_key: &DecodingKey,
+    ) -> Result<JwtAssertionClaims, Box<dyn std::error::Error>> {
+        let mut validation = Validation::new(algorithm);
+        validation.set_audience(&["https://oauth2.example.com/token"]);
+        validation.validate_exp = false;
+        Ok(decode::<JwtAssertionClaims>(assertion, decoding_key, &validation)?.claims)
+    }
+
+    #[test]
+    fn test_rs256() -> Result<(), Box<dyn std::error::Error>> {
+        let signer = JwtAssertionSigner::from_pem(
+            JwtAssertionSigningAlgorithm::Rs256,
+            include_bytes!("../../tests/keys/rsa_private_key.pem"),
+            "client@example.com",
+            "https://oauth2.example.com/token",
+        )?
+        .configure(|x| {
+            x.set_sub("user@example.com");
+            x.set_kid("KID");
+            x.set_extra_claim("scope", "a b".into());
+        });
+
+        let assertion = signer.assertion_at(1_000_000)?;
+        assert_eq!(decode_header(&assertion)?.kid, Some("KID".to_owned()));
+
+        let claims = decode_claims(
+            &assertion,
+            Algorithm::RS256,
+            &DecodingKey::from_rsa_pem(include_bytes!("../../tests/keys/rsa_public_key.pem"))?,
+        )?;
+        assert_eq!(claims.iss, "client@example.com");
+        assert_eq!(claims.sub, Some("user@example.com".to_owned()));
+        assert_eq!(claims.aud, "https://oauth2.example.com/token");
+        assert_eq!(claims.iat, 1_000_000);
+        assert_eq!(claims.exp, 1_000_000 + 3600);
+        assert!(claims.jti.is_some());
+        assert_eq!(claims.extra.get("scope"), Some(&Value::from("a b")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_es256_and_resign() -> Result<(), Box<dyn std::error::Error>> {
+        let signer = JwtAssertionSigner::from_pem(
+            JwtAssertionSigningAlgorithm::Es256,
+            include_bytes!("../../tests/keys/ec_private_key.pem"),
+            "client",
+            "https://oauth2.example.com/token",
+        )?
+        .configure(|x| {
+            x.set_lifetime(Duration::from_secs(300));
+            x.set_early_resign(Duration::from_secs(30));
+            x.set_with_jti(false);
+        });
+        let decoding_key =
+            DecodingKey::from_ec_pem(include_bytes!("../../tests/keys/ec_public_key.pem"))?;
+
+        let assertion = signer.assertion_at(1_000_000)?;
+        assert_eq!(
+            decode_claims(&assertion, Algorithm::ES256, &decoding_key)?.exp,
+            1_000_300
+        );
+
+        assert_eq!(signer.clone().assertion_at(1_000_269)?, assertion);
+
+        let assertion_resigned = signer.assertion_at(1_000_270)?;
+        assert_ne!(assertion_resigned, assertion);
+        assert_eq!(
+            decode_claims(&assertion_resigned, Algorithm::ES256, &decoding_key)?.iat,
+            1_000_270
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_with_jti_not_cached() -> Result<(), Box<dyn std::error::Error>> {
+        let signer = JwtAssertionSigner::from_pem(
+            JwtAssertionSigningAlgorithm::Es256,
+            include_bytes!("../../tests/keys/ec_private_key.pem"),
+            "client",
+            "https://oauth2.example.com/token",
+        )?;
+        let decoding_key =
+            DecodingKey::from_ec_pem(include_bytes!("../../tests/keys/ec_public_key.pem"))?;
+
+        let jti_1 = decode_claims(
+            &signer.assertion_at(1_000_000)?,
+            Algorithm::ES256,
+            &decoding_key,
+        )?
+        .jti;
+        let jti_2 = decode_claims(
+            &signer.assertion_at(1_000_001)?,
+            Algorithm::ES256,
+            &decoding_key,
+        )?
+        .jti;
+        assert!(jti_1.is_some());
+        assert_ne!(jti_1, jti_2);
+
+        Ok(())
+    }
+}
@@ -1,15 +1,21 @@
-use http_api_client::{Client, ClientRespondEndpointError};
+use std::sync::Arc;
+
+use http_api_client::{Client, RetryableClient};
 use oauth2_core::{
     jwt_authorization_grant::access_token_response::{
         ErrorBody as AT_RES_ErrorBody, SuccessfulBody as AT_RES_SuccessfulBody,
     },
     serde::{de::DeserializeOwned, Serialize},
-    types::Scope,
+    types::{Scope, ScopeParameter},
 };
 
-use crate::ProviderExtJwtAuthorizationGrant;
+use crate::{
+    retry::{self, RetryPolicy, RetrySleeper},
+    token_cache::{TokenCache, TokenCacheKey},
+    ProviderExtJwtAuthorizationGrant,
+};
 
-use super::{AccessTokenEndpoint, AccessTokenEndpointError};
+use super::{AccessTokenEndpointError, Machine, MachineError};
 
 //
 //
@@ -20,13 +26,48 @@ where
     C: Client,
 {
     pub client_with_token: C,
+    pub retry_policy: RetryPolicy,
+    /// Without it, each request is sent once.
+    pub retry_sleeper: Option<Arc<dyn RetrySleeper>>,
 }
 impl<C> Flow<C>
 where
     C: Client,
 {
+    /// Sends each request once, see [`Self::with_retry`].
     pub fn new(client_with_token: C) -> Self {
-        Self { client_with_token }
+        Self {
+            client_with_token,
+            retry_policy: RetryPolicy::disabled(),
+            retry_sleeper: None,
+        }
+    }
+
+    /// Retries with the default `retry_policy`, sleeping with the client.
+    pub fn with_retry(client_with_token: C) -> Self
+    where
+        C: RetryableClient + Clone + Send + Sync + 'static,
+    {
+        Self::new(client_with_token.clone()).configure(|x| {
+            x.retry_policy = RetryPolicy::default();
+            x.set_retry_sleeper(client_with_token.clone());
+        })
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    /// Retries only if `retry_policy` allows it.
+    pub fn set_retry_sleeper<S>(&mut self, retry_sleeper: S)
+    where
+        S: RetrySleeper + 'static,
+    {
+        self.retry_sleeper = Some(Arc::new(retry_sleeper));
     }
 }
 
@@ -34,6 +75,7 @@ impl<C> Flow<C>
 where
     C: Client + Send + Sync,
 {
+    #[allow(clippy::result_large_err)]
     pub async fn execute<SCOPE>(
         &self,
         provider: &(dyn ProviderExtJwtAuthorizationGrant<Scope = SCOPE> + Send + Sync),
@@ -43,28 +85,54 @@ where
         SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
     {
         // Step 1
+        let machine = Machine::new(provider, scopes);
+
+        // A new assertion for every attempt, the `jti` of the last one may have been used.
+        let response = retry::respond_with(
+            &self.client_with_token,
+            self.retry_sleeper.as_deref(),
+            &self.retry_policy,
+            true,
+            || machine.request(),
+        )
+        .await?
+        .map_err(|err| FlowExecuteError::AccessTokenEndpointRespondFailed(Box::new(err)))?;
+
+        let access_token_successful_body = machine.handle_response(response)?;
+
+        Ok(access_token_successful_body)
+    }
+
+    /// Reuse the token in `token_cache` until it nears `expires_in`.
+    ///
+    /// Providers without [`crate::Provider::principal`] bypass the cache, as their assertions
+    /// may stand for different subjects.
+    pub async fn execute_with_cache<SCOPE>(
+        &self,
+        provider: &(dyn ProviderExtJwtAuthorizationGrant<Scope = SCOPE> + Send + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        token_cache: &TokenCache,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, FlowExecuteError>
+    where
+        SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
+    {
         let scopes = scopes.into().or_else(|| provider.scopes_default());
 
-        let access_token_endpoint = AccessTokenEndpoint::new(provider, scopes);
-
-        let access_token_ret = self
-            .client_with_token
-            .respond_endpoint(&access_token_endpoint)
-            .await
-            .map_err(|err| match err {
-                ClientRespondEndpointError::RespondFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointRespondFailed(Box::new(err))
-                }
-                ClientRespondEndpointError::EndpointRenderRequestFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointError(err)
-                }
-                ClientRespondEndpointError::EndpointParseResponseFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointError(err)
-                }
-            })?;
-
-        let access_token_successful_body =
-            access_token_ret.map_err(FlowExecuteError::AccessTokenFailed)?;
+        let key = TokenCacheKey::with_provider(
+            provider,
+            scopes.to_owned().map(ScopeParameter::from).as_ref(),
+        );
+        if key.principal.is_none() {
+            return self.execute(provider, scopes).await;
+        }
+
+        if let Some(access_token_successful_body) = token_cache.get(&key) {
+            return Ok(access_token_successful_body);
+        }
+
+        let access_token_successful_body = self.execute(provider, scopes).await?;
+
+        token_cache.insert(&key, &access_token_successful_body);
 
         Ok(access_token_successful_body)
     }
@@ -79,3 +147,12 @@ pub enum FlowExecuteError {
     #[error("AccessTokenFailed {0:?}")]
     AccessTokenFailed(AT_RES_ErrorBody),
 }
+
+impl From<MachineError> for FlowExecuteError {
+    fn from(err: MachineError) -> Self {
+        match err {
+            MachineError::AccessTokenEndpointError(err) => Self::AccessTokenEndpointError(err),
+            MachineError::AccessTokenFailed(body) => Self::AccessTokenFailed(body),
+        }
+    }
+}
@@ -6,8 +6,21 @@ pub mod provider_ext;
 
 pub use access_token_endpoint::{AccessTokenEndpoint, AccessTokenEndpointError};
 
+//
+pub mod machine;
+
+pub use machine::{Machine, MachineError};
+
 //
 #[cfg(feature = "with-flow")]
 pub mod flow;
 #[cfg(feature = "with-flow")]
 pub use flow::{Flow, FlowExecuteError};
+
+//
+#[cfg(feature = "with-jwt-assertion")]
+pub mod assertion;
+#[cfg(feature = "with-jwt-assertion")]
+pub use assertion::{
+    JwtAssertionClaims, JwtAssertionSigner, JwtAssertionSignerError, JwtAssertionSigningAlgorithm,
+};
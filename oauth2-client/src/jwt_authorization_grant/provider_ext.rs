@@ -8,7 +8,8 @@ use crate::{
 
 //
 pub trait ProviderExtJwtAuthorizationGrant: Provider + DynClone {
-    fn assertion(&self) -> &str;
+    /// Called on every access token request, so signed assertions can be renewed before expiry.
+    fn assertion(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>>;
 
     fn scopes_default(&self) -> Option<Vec<<Self as Provider>::Scope>> {
         None
@@ -80,10 +81,18 @@ where
         self.inner.token_endpoint_url()
     }
 
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
     fn extra(&self) -> Option<Map<String, Value>> {
         self.inner.extra()
     }
 
+    fn principal(&self) -> Option<String> {
+        self.inner.principal()
+    }
+
     // Note
 }
 
@@ -91,7 +100,7 @@ impl<P> ProviderExtJwtAuthorizationGrant for ProviderExtJwtAuthorizationGrantStr
 where
     P: ProviderExtJwtAuthorizationGrant + Clone,
 {
-    fn assertion(&self) -> &str {
+    fn assertion(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
         self.inner.assertion()
     }
 
@@ -4,9 +4,14 @@ pub use oauth2_core;
 pub mod re_exports;
 pub mod utils;
 
+//
+pub mod error;
+
 //
 pub mod provider;
 
+pub mod capabilities;
+
 pub mod authorization_code_grant;
 pub mod client_credentials_grant;
 pub mod device_authorization_grant;
@@ -15,13 +20,39 @@ pub mod resource_owner_password_credentials_grant;
 
 pub mod extensions;
 
+pub mod id_token;
+pub mod logout;
+
+pub mod token_cache;
+
+#[cfg(feature = "with-blocking")]
+pub mod blocking;
+
+pub mod retry;
+
+pub mod redaction;
+#[cfg(feature = "with-tracing")]
+pub mod trace;
+
+#[cfg(test)]
+pub(crate) mod test_utils;
+
 //
+pub use error::{Error, ErrorKind};
 pub use provider::Provider;
 
+pub use capabilities::ProviderCapabilities;
+
 pub use authorization_code_grant::provider_ext::ProviderExtAuthorizationCodeGrant;
 pub use client_credentials_grant::provider_ext::ProviderExtClientCredentialsGrant;
 pub use device_authorization_grant::provider_ext::ProviderExtDeviceAuthorizationGrant;
 pub use jwt_authorization_grant::provider_ext::ProviderExtJwtAuthorizationGrant;
 pub use resource_owner_password_credentials_grant::provider_ext::ProviderExtResourceOwnerPasswordCredentialsGrant;
 
+pub use token_cache::TokenCache;
+
+pub use retry::RetryPolicy;
+#[cfg(feature = "with-flow")]
+pub use retry::RetrySleeper;
+
 pub use extensions::{Builder as ExtensionsBuilder, DefaultBuilder as DefaultExtensionsBuilder};
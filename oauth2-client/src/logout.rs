@@ -0,0 +1,470 @@
+//! OpenID Connect RP-Initiated Logout 1.0 and Back-Channel Logout 1.0.
+//!
+//! Front-Channel Logout 1.0 has nothing to verify, see `oauth2_signin::web_app::logout`.
+
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use oauth2_core::{
+    types::{ClientId, IdToken, RedirectUri, State},
+    url::{form_urlencoded, Url},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Error as SerdeJsonError, Map, Value};
+use serde_urlencoded::ser::Error as SerdeUrlencodedSerError;
+
+use crate::id_token::{deserialize_audience, LEEWAY_DEFAULT};
+
+pub const BACK_CHANNEL_LOGOUT_EVENT: &str = "http://schemas.openid.net/event/backchannel-logout";
+
+//
+//
+//
+/// The URL to redirect the user agent to, the `end_session_endpoint` of the discovery document.
+#[derive(Debug, Clone)]
+pub struct EndSessionRequest {
+    pub end_session_endpoint_url: Url,
+    pub id_token_hint: Option<IdToken>,
+    pub post_logout_redirect_uri: Option<RedirectUri>,
+    pub state: Option<State>,
+    pub client_id: Option<ClientId>,
+    pub logout_hint: Option<String>,
+}
+
+impl EndSessionRequest {
+    pub fn new(end_session_endpoint_url: Url) -> Self {
+        Self {
+            end_session_endpoint_url,
+            id_token_hint: None,
+            post_logout_redirect_uri: None,
+            state: None,
+            client_id: None,
+            logout_hint: None,
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn set_id_token_hint(&mut self, id_token_hint: IdToken) {
+        self.id_token_hint = Some(id_token_hint);
+    }
+
+    pub fn set_post_logout_redirect_uri(&mut self, post_logout_redirect_uri: RedirectUri) {
+        self.post_logout_redirect_uri = Some(post_logout_redirect_uri);
+    }
+
+    pub fn set_state(&mut self, state: State) {
+        self.state = Some(state);
+    }
+
+    pub fn set_client_id(&mut self, client_id: ClientId) {
+        self.client_id = Some(client_id);
+    }
+
+    pub fn set_logout_hint(&mut self, logout_hint: String) {
+        self.logout_hint = Some(logout_hint);
+    }
+
+    pub fn build_url(&self) -> Result<Url, EndSessionRequestError> {
+        // The OP can only check `post_logout_redirect_uri` against a known client.
+        if self.post_logout_redirect_uri.is_some()
+            && self.id_token_hint.is_none()
+            && self.client_id.is_none()
+        {
+            return Err(EndSessionRequestError::ClientUnidentified);
+        }
+
+        let query = EndSessionRequestQuery {
+            id_token_hint: self.id_token_hint.as_deref(),
+            post_logout_redirect_uri: self
+                .post_logout_redirect_uri
+                .as_ref()
+                .map(|x| x.to_string()),
+            state: self.state.as_ref().map(|x| x.expose_secret()),
+            client_id: self.client_id.as_deref(),
+            logout_hint: self.logout_hint.as_deref(),
+        };
+        let query_str = serde_urlencoded::to_string(&query)
+            .map_err(EndSessionRequestError::SerRequestQueryFailed)?;
+
+        let mut url = self.end_session_endpoint_url.to_owned();
+        if !query_str.is_empty() {
+            // Keeps the query of the endpoint, e.g. `?p=<policy>` of Azure AD B2C.
+            url.query_pairs_mut()
+                .extend_pairs(form_urlencoded::parse(query_str.as_bytes()));
+        }
+        Ok(url)
+    }
+}
+
+#[derive(Serialize)]
+struct EndSessionRequestQuery<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id_token_hint: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    post_logout_redirect_uri: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    state: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_id: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    logout_hint: Option<&'a str>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum EndSessionRequestError {
+    /// `post_logout_redirect_uri` needs `id_token_hint` or `client_id`.
+    #[error("ClientUnidentified")]
+    ClientUnidentified,
+    #[error("SerRequestQueryFailed {0}")]
+    SerRequestQueryFailed(SerdeUrlencodedSerError),
+}
+
+//
+//
+//
+/// Back-Channel Logout 1.0 section 2.4.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct LogoutTokenClaims {
+    pub iss: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sub: Option<String>,
+    #[serde(deserialize_with = "deserialize_audience")]
+    pub aud: Vec<String>,
+    pub iat: u64,
+    pub exp: u64,
+    pub jti: String,
+    pub events: Map<String, Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sid: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<String>,
+    //
+    #[serde(flatten)]
+    pub _extra: Map<String, Value>,
+}
+
+impl LogoutTokenClaims {
+    /// Only for payloads whose signature was already verified, see `LogoutTokenVerifier`.
+    pub fn from_payload(payload: Map<String, Value>) -> Result<Self, LogoutTokenError> {
+        serde_json::from_value(Value::Object(payload)).map_err(LogoutTokenError::DeClaimsFailed)
+    }
+
+    /// Back-Channel Logout 1.0 section 2.6, without the signature.
+    pub fn validate(&self, validation: &LogoutTokenValidation) -> Result<(), LogoutTokenError> {
+        if !validation.issuers.is_empty() && !validation.issuers.contains(&self.iss) {
+            return Err(LogoutTokenError::IssuerMismatch(self.iss.to_owned()));
+        }
+
+        if !self.aud.contains(&validation.client_id) {
+            return Err(LogoutTokenError::AudienceMismatch);
+        }
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| LogoutTokenError::SystemTimeBeforeUnixEpoch)?;
+        if Duration::from_secs(self.exp) + validation.leeway <= now {
+            return Err(LogoutTokenError::Expired);
+        }
+
+        match self.events.get(BACK_CHANNEL_LOGOUT_EVENT) {
+            Some(Value::Object(_)) => {}
+            _ => return Err(LogoutTokenError::EventMissing),
+        }
+
+        if self.sub.is_none() && self.sid.is_none() {
+            return Err(LogoutTokenError::SubAndSidMissing);
+        }
+
+        // So that an ID token cannot be passed off as a logout token.
+        if self.nonce.is_some() {
+            return Err(LogoutTokenError::NoncePresent);
+        }
+
+        Ok(())
+    }
+}
+
+//
+/// `client_id` is required, the `aud` of a logout token must contain it.
+#[derive(Debug, Clone)]
+pub struct LogoutTokenValidation {
+    /// Empty means any issuer.
+    pub issuers: Vec<String>,
+    pub client_id: ClientId,
+    pub leeway: Duration,
+}
+impl LogoutTokenValidation {
+    pub fn new(client_id: ClientId) -> Self {
+        Self {
+            issuers: vec![],
+            client_id,
+            leeway: LEEWAY_DEFAULT,
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+}
+
+//
+#[cfg(feature = "with-logout-token")]
+pub use verifier::LogoutTokenVerifier;
+
+#[cfg(feature = "with-logout-token")]
+mod verifier {
+    use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+    use oauth2_core::types::ClientId;
+    use serde_json::{Map, Value};
+
+    use super::{LogoutTokenClaims, LogoutTokenError, LogoutTokenValidation};
+
+    /// Logout tokens are posted by the OP to the RP, unlike ID tokens they must be checked for
+    /// a signature by a key from the OP's `jwks_uri`.
+    #[derive(Clone)]
+    pub struct LogoutTokenVerifier {
+        decoding_key: DecodingKey,
+        algorithms: Vec<Algorithm>,
+        pub validation: LogoutTokenValidation,
+    }
+
+    impl core::fmt::Debug for LogoutTokenVerifier {
+        fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+            f.debug_struct("LogoutTokenVerifier")
+                .field("algorithms", &self.algorithms)
+                .field("validation", &self.validation)
+                .finish()
+        }
+    }
+
+    impl LogoutTokenVerifier {
+        pub fn new(
+            decoding_key: DecodingKey,
+            algorithms: Vec<Algorithm>,
+            client_id: ClientId,
+        ) -> Self {
+            Self {
+                decoding_key,
+                algorithms,
+                validation: LogoutTokenValidation::new(client_id),
+            }
+        }
+
+        pub fn configure<F>(mut self, mut f: F) -> Self
+        where
+            F: FnMut(&mut Self),
+        {
+            f(&mut self);
+            self
+        }
+
+        pub fn verify(
+            &self,
+            logout_token: impl AsRef<str>,
+        ) -> Result<LogoutTokenClaims, LogoutTokenError> {
+            let mut validation = Validation::default();
+            validation.algorithms = self.algorithms.to_owned();
+            // Checked by `LogoutTokenClaims::validate`.
+            validation.required_spec_claims.clear();
+            validation.validate_exp = false;
+            validation.validate_aud = false;
+
+            let payload = decode::<Map<String, Value>>(
+                logout_token.as_ref(),
+                &self.decoding_key,
+                &validation,
+            )
+            .map_err(LogoutTokenError::VerifyFailed)?
+            .claims;
+
+            let claims = LogoutTokenClaims::from_payload(payload)?;
+            claims.validate(&self.validation)?;
+            Ok(claims)
+        }
+    }
+}
+
+//
+#[derive(thiserror::Error, Debug)]
+pub enum LogoutTokenError {
+    #[cfg(feature = "with-logout-token")]
+    #[error("VerifyFailed {0}")]
+    VerifyFailed(jsonwebtoken::errors::Error),
+    #[error("DeClaimsFailed {0}")]
+    DeClaimsFailed(SerdeJsonError),
+    //
+    #[error("IssuerMismatch {0}")]
+    IssuerMismatch(String),
+    #[error("AudienceMismatch")]
+    AudienceMismatch,
+    #[error("Expired")]
+    Expired,
+    #[error("EventMissing")]
+    EventMissing,
+    #[error("SubAndSidMissing")]
+    SubAndSidMissing,
+    #[error("NoncePresent")]
+    NoncePresent,
+    #[error("SystemTimeBeforeUnixEpoch")]
+    SystemTimeBeforeUnixEpoch,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::id_token::tests::exp_in_one_hour;
+
+    #[test]
+    fn test_end_session_request() -> Result<(), Box<dyn std::error::Error>> {
+        let request = EndSessionRequest::new(
+            "https://login.microsoftonline.com/common/oauth2/v2.0/logout".parse()?,
+        )
+        .configure(|x| {
+            x.set_id_token_hint("ID_TOKEN".to_owned());
+            x.set_post_logout_redirect_uri(
+                "https://client.example.com/logged_out".parse().unwrap(),
+            );
+            x.set_state("STATE".into());
+        });
+        assert_eq!(request.build_url()?.as_str(), "https://login.microsoftonline.com/common/oauth2/v2.0/logout?id_token_hint=ID_TOKEN&post_logout_redirect_uri=https%3A%2F%2Fclient.example.com%2Flogged_out&state=STATE");
+
+        match request.configure(|x| x.id_token_hint = None).build_url() {
+            Err(EndSessionRequestError::ClientUnidentified) => {}
+            x => panic!("{x:?}"),
+        }
+
+        let request = EndSessionRequest::new(
+            "https://tenant.b2clogin.com/tenant.onmicrosoft.com/oauth2/v2.0/logout?p=B2C_1_signin"
+                .parse()?,
+        )
+        .configure(|x| {
+            x.set_id_token_hint("ID_TOKEN".to_owned());
+            x.set_state("STATE".into());
+        });
+        assert_eq!(request.build_url()?.as_str(), "https://tenant.b2clogin.com/tenant.onmicrosoft.com/oauth2/v2.0/logout?p=B2C_1_signin&id_token_hint=ID_TOKEN&state=STATE");
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_logout_token_claims() -> Result<(), Box<dyn std::error::Error>> {
+        let exp = exp_in_one_hour();
+        let payload = serde_json::json!({
+            "iss": "https://server.example.com",
+            "aud": "CLIENT_ID",
+            "iat": exp - 3600,
+            "exp": exp,
+            "jti": "bWJq",
+            "sid": "08a5019c-17e1-4977-8f42-65a12843ea02",
+            "events": {
+                "http://schemas.openid.net/event/backchannel-logout": {}
+            }
+        });
+        let claims =
+            LogoutTokenClaims::from_payload(payload.as_object().cloned().unwrap_or_default())?;
+
+        let validation = LogoutTokenValidation::new("CLIENT_ID".to_owned())
+            .configure(|x| x.issuers = vec!["https://server.example.com".to_owned()]);
+        claims.validate(&validation)?;
+
+        let with_nonce = LogoutTokenClaims {
+            nonce: Some("NONCE".to_owned()),
+            ..claims.to_owned()
+        };
+        match with_nonce.validate(&validation) {
+            Err(LogoutTokenError::NoncePresent) => {}
+            x => panic!("{x:?}"),
+        }
+        let without_event = LogoutTokenClaims {
+            events: Map::new(),
+            ..claims.to_owned()
+        };
+        match without_event.validate(&validation) {
+            Err(LogoutTokenError::EventMissing) => {}
+            x => panic!("{x:?}"),
+        }
+        let without_sid = LogoutTokenClaims {
+            sid: None,
+            ..claims.to_owned()
+        };
+        match without_sid.validate(&validation) {
+            Err(LogoutTokenError::SubAndSidMissing) => {}
+            x => panic!("{x:?}"),
+        }
+        match claims.validate(&LogoutTokenValidation {
+            client_id: "OTHER".to_owned(),
+            ..validation.to_owned()
+        }) {
+            Err(LogoutTokenError::AudienceMismatch) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "with-logout-token")]
+    #[test]
+    fn test_logout_token_verifier() -> Result<(), Box<dyn std::error::Error>> {
+        use jsonwebtoken::{encode, Algorithm, DecodingKey, EncodingKey, Header};
+
+        let exp = exp_in_one_hour();
+        let payload = serde_json::json!({
+            "iss": "https://server.example.com",
+            "sub": "248289761001",
+            "aud": ["CLIENT_ID"],
+            "iat": exp - 3600,
+            "exp": exp,
+            "jti": "bWJq",
+            "events": {
+                "http://schemas.openid.net/event/backchannel-logout": {}
+            }
+        });
+        let logout_token = encode(
+            &Header::new(Algorithm::HS256),
+            &payload,
+            &EncodingKey::from_secret(b"SECRET"),
+        )?;
+
+        let verifier = LogoutTokenVerifier::new(
+            DecodingKey::from_secret(b"SECRET"),
+            vec![Algorithm::HS256],
+            "CLIENT_ID".to_owned(),
+        );
+        let claims = verifier.verify(&logout_token)?;
+        assert_eq!(claims.sub, Some("248289761001".to_owned()));
+
+        let verifier = LogoutTokenVerifier::new(
+            DecodingKey::from_secret(b"OTHER"),
+            vec![Algorithm::HS256],
+            "CLIENT_ID".to_owned(),
+        );
+        match verifier.verify(&logout_token) {
+            Err(LogoutTokenError::VerifyFailed(_)) => {}
+            x => panic!("{x:?}"),
+        }
+
+        let verifier = LogoutTokenVerifier::new(
+            DecodingKey::from_secret(b"SECRET"),
+            vec![Algorithm::HS256],
+            "OTHER".to_owned(),
+        );
+        match verifier.verify(&logout_token) {
+            Err(LogoutTokenError::AudienceMismatch) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -14,10 +14,21 @@ pub trait Provider: DynClone {
 
     fn token_endpoint_url(&self) -> &Url;
 
+    /// e.g. `github`, names the provider in tracing spans.
+    fn name(&self) -> &str {
+        self.token_endpoint_url().host_str().unwrap_or_default()
+    }
+
     // e.g. Mastodon's base_url
     fn extra(&self) -> Option<Map<String, Value>> {
         None
     }
+
+    /// Who tokens are issued for when `client_id` does not tell, e.g. the `iss` and `sub` of a
+    /// JWT assertion. Part of [`crate::token_cache::TokenCacheKey::with_provider`].
+    fn principal(&self) -> Option<String> {
+        None
+    }
 }
 
 clone_trait_object!(<SCOPE> Provider<Scope = SCOPE> where SCOPE: Scope + Clone);
@@ -60,9 +71,17 @@ where
         self.inner.token_endpoint_url()
     }
 
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
     fn extra(&self) -> Option<Map<String, Value>> {
         self.inner.extra()
     }
 
+    fn principal(&self) -> Option<String> {
+        self.inner.principal()
+    }
+
     // Note
 }
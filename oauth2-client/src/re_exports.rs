@@ -5,7 +5,10 @@ pub use oauth2_core::{
     http::{self, Error as HttpError},
     serde::{self, de::DeserializeOwned, Deserialize, Serialize},
     serde_enum_str::{self, Deserialize_enum_str, Serialize_enum_str},
-    types::{ClientId, ClientSecret, RedirectUri, Scope},
+    types::{
+        AccessToken, ClientId, ClientSecret, Code, CodeVerifier, PrivateKey, RedirectUri,
+        RefreshToken, Scope, State,
+    },
     url::{self, ParseError as UrlParseError, Url},
 };
 pub use serde_json::{self, Error as SerdeJsonError, Map, Value};
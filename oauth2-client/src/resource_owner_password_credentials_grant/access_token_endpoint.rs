@@ -7,17 +7,19 @@ use oauth2_core::{
     access_token_response::{CONTENT_TYPE as RES_CONTENT_TYPE, GENERAL_ERROR_BODY_KEY_ERROR},
     http::{
         header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
-        Error as HttpError,
+        Error as HttpError, StatusCode,
     },
     resource_owner_password_credentials_grant::access_token_response::{
         ErrorBody as RES_ErrorBody, SuccessfulBody as RES_SuccessfulBody,
     },
     serde::{de::DeserializeOwned, Serialize},
-    types::{ClientPassword, Scope},
+    types::{ClientPassword, Password, Scope},
 };
 use serde_json::{Error as SerdeJsonError, Map, Value};
 use serde_urlencoded::ser::Error as SerdeUrlencodedSerError;
 
+#[cfg(feature = "with-tracing")]
+use crate::trace;
 use crate::ProviderExtResourceOwnerPasswordCredentialsGrant;
 
 //
@@ -32,7 +34,7 @@ where
         &'a (dyn ProviderExtResourceOwnerPasswordCredentialsGrant<Scope = SCOPE> + Send + Sync),
     scopes: Option<Vec<SCOPE>>,
     username: String,
-    password: String,
+    password: Password,
 }
 impl<'a, SCOPE> AccessTokenEndpoint<'a, SCOPE>
 where
@@ -50,7 +52,7 @@ where
             provider,
             scopes: scopes.into(),
             username: username.as_ref().to_owned(),
-            password: password.as_ref().to_owned(),
+            password: Password::new(password.as_ref()),
         }
     }
 }
@@ -65,6 +67,13 @@ where
     type ParseResponseError = AccessTokenEndpointError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span(
+            self.provider.name(),
+            Some(trace::grant_type::PASSWORD),
+            trace::endpoint::ACCESS_TOKEN,
+        );
+
         let client_password = ClientPassword::new(
             self.provider
                 .client_id()
@@ -79,14 +88,14 @@ where
         let mut body = if self.provider.client_password_in_request_body() {
             BodyWithResourceOwnerPasswordCredentialsGrant::new_with_client_password(
                 &self.username,
-                &self.password,
+                self.password.expose_secret(),
                 self.scopes.to_owned().map(Into::into),
                 client_password.to_owned(),
             )
         } else {
             BodyWithResourceOwnerPasswordCredentialsGrant::new(
                 &self.username,
-                &self.password,
+                self.password.expose_secret(),
                 self.scopes.to_owned().map(Into::into),
             )
         };
@@ -119,6 +128,8 @@ where
             .body(body_str.as_bytes().to_vec())
             .map_err(AccessTokenEndpointError::MakeRequestFailed)?;
 
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
         Ok(request)
     }
 
@@ -126,6 +137,15 @@ where
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span(
+            self.provider.name(),
+            Some(trace::grant_type::PASSWORD),
+            trace::endpoint::ACCESS_TOKEN,
+        );
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         //
         if response.status().is_success() {
             let map = serde_json::from_slice::<Map<String, Value>>(response.body())
@@ -138,8 +158,9 @@ where
             }
         }
 
-        let body = serde_json::from_slice::<RES_ErrorBody>(response.body())
-            .map_err(AccessTokenEndpointError::DeResponseBodyFailed)?;
+        let body = serde_json::from_slice::<RES_ErrorBody>(response.body()).map_err(|err| {
+            AccessTokenEndpointError::DeErrorResponseBodyFailed(response.status(), err)
+        })?;
         Ok(Err(body))
     }
 }
@@ -161,4 +182,6 @@ pub enum AccessTokenEndpointError {
     //
     #[error("DeResponseBodyFailed {0}")]
     DeResponseBodyFailed(SerdeJsonError),
+    #[error("DeErrorResponseBodyFailed {0} {1}")]
+    DeErrorResponseBodyFailed(StatusCode, SerdeJsonError),
 }
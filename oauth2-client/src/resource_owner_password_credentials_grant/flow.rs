@@ -1,4 +1,6 @@
-use http_api_client::{Client, ClientRespondEndpointError};
+use std::sync::Arc;
+
+use http_api_client::{Client, RetryableClient};
 use oauth2_core::{
     resource_owner_password_credentials_grant::access_token_response::{
         ErrorBody as AT_RES_ErrorBody, SuccessfulBody as AT_RES_SuccessfulBody,
@@ -7,9 +9,12 @@ use oauth2_core::{
     types::Scope,
 };
 
-use crate::ProviderExtResourceOwnerPasswordCredentialsGrant;
+use crate::{
+    retry::{self, RetryPolicy, RetrySleeper},
+    ProviderExtResourceOwnerPasswordCredentialsGrant,
+};
 
-use super::{AccessTokenEndpoint, AccessTokenEndpointError};
+use super::{AccessTokenEndpointError, Machine, MachineError};
 
 //
 //
@@ -20,13 +25,48 @@ where
     C: Client,
 {
     pub client_with_token: C,
+    pub retry_policy: RetryPolicy,
+    /// Without it, each request is sent once.
+    pub retry_sleeper: Option<Arc<dyn RetrySleeper>>,
 }
 impl<C> Flow<C>
 where
     C: Client,
 {
+    /// Sends each request once, see [`Self::with_retry`].
     pub fn new(client_with_token: C) -> Self {
-        Self { client_with_token }
+        Self {
+            client_with_token,
+            retry_policy: RetryPolicy::disabled(),
+            retry_sleeper: None,
+        }
+    }
+
+    /// Retries with the default `retry_policy`, sleeping with the client.
+    pub fn with_retry(client_with_token: C) -> Self
+    where
+        C: RetryableClient + Clone + Send + Sync + 'static,
+    {
+        Self::new(client_with_token.clone()).configure(|x| {
+            x.retry_policy = RetryPolicy::default();
+            x.set_retry_sleeper(client_with_token.clone());
+        })
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    /// Retries only if `retry_policy` allows it.
+    pub fn set_retry_sleeper<S>(&mut self, retry_sleeper: S)
+    where
+        S: RetrySleeper + 'static,
+    {
+        self.retry_sleeper = Some(Arc::new(retry_sleeper));
     }
 }
 
@@ -47,28 +87,21 @@ where
         SCOPE: Scope + Serialize + DeserializeOwned + Send + Sync,
     {
         // Step 1
-        let scopes = scopes.into().or_else(|| provider.scopes_default());
+        let machine = Machine::new(provider, scopes, username, password);
 
-        let access_token_endpoint = AccessTokenEndpoint::new(provider, scopes, username, password);
+        let request = machine.request()?;
 
-        let access_token_ret = self
-            .client_with_token
-            .respond_endpoint(&access_token_endpoint)
-            .await
-            .map_err(|err| match err {
-                ClientRespondEndpointError::RespondFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointRespondFailed(Box::new(err))
-                }
-                ClientRespondEndpointError::EndpointRenderRequestFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointError(err)
-                }
-                ClientRespondEndpointError::EndpointParseResponseFailed(err) => {
-                    FlowExecuteError::AccessTokenEndpointError(err)
-                }
-            })?;
+        let response = retry::respond(
+            &self.client_with_token,
+            self.retry_sleeper.as_deref(),
+            &self.retry_policy,
+            true,
+            request,
+        )
+        .await
+        .map_err(|err| FlowExecuteError::AccessTokenEndpointRespondFailed(Box::new(err)))?;
 
-        let access_token_successful_body =
-            access_token_ret.map_err(FlowExecuteError::AccessTokenFailed)?;
+        let access_token_successful_body = machine.handle_response(response)?;
 
         Ok(access_token_successful_body)
     }
@@ -83,3 +116,12 @@ pub enum FlowExecuteError {
     #[error("AccessTokenFailed {0:?}")]
     AccessTokenFailed(AT_RES_ErrorBody),
 }
+
+impl From<MachineError> for FlowExecuteError {
+    fn from(err: MachineError) -> Self {
+        match err {
+            MachineError::AccessTokenEndpointError(err) => Self::AccessTokenEndpointError(err),
+            MachineError::AccessTokenFailed(body) => Self::AccessTokenFailed(body),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+//! Sans-IO, the caller sends the request and passes the response back.
+
+use http_api_client_endpoint::{Body, Endpoint as _, Request, Response};
+use oauth2_core::{
+    resource_owner_password_credentials_grant::access_token_response::{
+        ErrorBody as AT_RES_ErrorBody, SuccessfulBody as AT_RES_SuccessfulBody,
+    },
+    serde::{de::DeserializeOwned, Serialize},
+    types::Scope,
+};
+
+use crate::ProviderExtResourceOwnerPasswordCredentialsGrant;
+
+use super::{AccessTokenEndpoint, AccessTokenEndpointError};
+
+//
+//
+//
+#[derive(Clone)]
+pub struct Machine<'a, SCOPE>
+where
+    SCOPE: Scope,
+{
+    access_token_endpoint: AccessTokenEndpoint<'a, SCOPE>,
+}
+impl<'a, SCOPE> Machine<'a, SCOPE>
+where
+    SCOPE: Scope + Serialize + DeserializeOwned,
+{
+    pub fn new(
+        provider: &'a (dyn ProviderExtResourceOwnerPasswordCredentialsGrant<Scope = SCOPE>
+                 + Send
+                 + Sync),
+        scopes: impl Into<Option<Vec<SCOPE>>>,
+        username: impl AsRef<str>,
+        password: impl AsRef<str>,
+    ) -> Self {
+        let scopes = scopes.into().or_else(|| provider.scopes_default());
+
+        Self {
+            access_token_endpoint: AccessTokenEndpoint::new(provider, scopes, username, password),
+        }
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn request(&self) -> Result<Request<Body>, MachineError> {
+        self.access_token_endpoint
+            .render_request()
+            .map_err(MachineError::AccessTokenEndpointError)
+    }
+
+    #[allow(clippy::result_large_err)]
+    pub fn handle_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<AT_RES_SuccessfulBody<SCOPE>, MachineError> {
+        self.access_token_endpoint
+            .parse_response(response)
+            .map_err(MachineError::AccessTokenEndpointError)?
+            .map_err(MachineError::AccessTokenFailed)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum MachineError {
+    #[error("AccessTokenEndpointError {0}")]
+    AccessTokenEndpointError(AccessTokenEndpointError),
+    #[error("AccessTokenFailed {0:?}")]
+    AccessTokenFailed(AT_RES_ErrorBody),
+}
@@ -0,0 +1,398 @@
+//! Retry on 429, 5xx, `temporarily_unavailable`, `server_error` and respond failures.
+
+use core::{cmp::min, time::Duration};
+use std::time::SystemTime;
+
+#[cfg(feature = "with-flow")]
+use http_api_client::{async_trait, Client, Request, RetryableClient};
+use http_api_client_endpoint::{
+    http::{header::RETRY_AFTER, StatusCode},
+    Body, Response,
+};
+use oauth2_core::access_token_response::{ErrorBody, ErrorBodyError};
+
+//
+//
+//
+/// Flows only retry with a `RetrySleeper`, and without one default to
+/// [`RetryPolicy::disabled`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_retries: usize,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    /// Sleep a random duration between half of and the full backoff.
+    ///
+    /// Ignored when no entropy is available.
+    pub jitter: bool,
+    /// Give up when the server asks to wait longer than this.
+    pub max_retry_after: Duration,
+}
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 2,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(10),
+            jitter: true,
+            max_retry_after: Duration::from_secs(60),
+        }
+    }
+}
+impl RetryPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn disabled() -> Self {
+        Self::default().configure(|x| x.max_retries = 0)
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    /// `attempt` starts from 0, the backoff doubles each attempt up to `max_backoff`.
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let factor = 1_u32.checked_shl(attempt as u32).unwrap_or(u32::MAX);
+        min(
+            self.initial_backoff.saturating_mul(factor),
+            self.max_backoff,
+        )
+    }
+
+    /// Returns `None` when the request should not be sent again.
+    ///
+    /// A non-idempotent request (e.g. an authorization code exchange) is only sent again
+    /// when the server refused it without processing it.
+    pub fn next_retry_in(
+        &self,
+        attempt: usize,
+        reason: RetryReason,
+        idempotent: bool,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_retries {
+            return None;
+        }
+        if !idempotent && !reason.is_unprocessed() {
+            return None;
+        }
+
+        if let Some(retry_after) = retry_after {
+            return (retry_after <= self.max_retry_after).then_some(retry_after);
+        }
+
+        let backoff = self.backoff(attempt);
+        match self.jitter.then(random_f64).flatten() {
+            Some(random) => Some(backoff / 2 + backoff.mul_f64(random / 2.0)),
+            None => Some(backoff),
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RetryReason {
+    TooManyRequests,
+    ServiceUnavailable,
+    TemporarilyUnavailable,
+    ServerError,
+    RespondFailed,
+}
+impl RetryReason {
+    pub fn from_response(response: &Response<Body>) -> Option<Self> {
+        match response.status() {
+            StatusCode::TOO_MANY_REQUESTS => return Some(Self::TooManyRequests),
+            StatusCode::SERVICE_UNAVAILABLE => return Some(Self::ServiceUnavailable),
+            status if status.is_server_error() => return Some(Self::ServerError),
+            status if status.is_success() => return None,
+            _ => {}
+        }
+
+        match serde_json::from_slice::<ErrorBody>(response.body()).map(|x| x.error) {
+            Ok(ErrorBodyError::TemporarilyUnavailable) => Some(Self::TemporarilyUnavailable),
+            Ok(ErrorBodyError::ServerError) => Some(Self::ServerError),
+            _ => None,
+        }
+    }
+
+    /// The server did not act on the request, so sending it again is always safe.
+    pub fn is_unprocessed(&self) -> bool {
+        matches!(
+            self,
+            Self::TooManyRequests | Self::ServiceUnavailable | Self::TemporarilyUnavailable
+        )
+    }
+}
+
+/// `Retry-After` as delay-seconds or an HTTP-date.
+pub fn parse_retry_after(response: &Response<Body>, now: SystemTime) -> Option<Duration> {
+    let value = response.headers().get(RETRY_AFTER)?.to_str().ok()?.trim();
+
+    if let Ok(secs) = value.parse::<u64>() {
+        return Some(Duration::from_secs(secs));
+    }
+
+    let at = httpdate::parse_http_date(value).ok()?;
+    Some(at.duration_since(now).unwrap_or_default())
+}
+
+fn random_f64() -> Option<f64> {
+    let mut buf = [0_u8; 8];
+    getrandom::getrandom(&mut buf).ok()?;
+    Some((u64::from_le_bytes(buf) >> 11) as f64 / (1_u64 << 53) as f64)
+}
+
+//
+//
+//
+/// Waits between attempts, flows without one send each request once.
+///
+/// Every `RetryableClient` is one.
+#[cfg(feature = "with-flow")]
+#[async_trait]
+pub trait RetrySleeper: Send + Sync {
+    async fn sleep(&self, dur: Duration);
+}
+
+#[cfg(feature = "with-flow")]
+#[async_trait]
+impl<C> RetrySleeper for C
+where
+    C: RetryableClient + Send + Sync,
+{
+    async fn sleep(&self, dur: Duration) {
+        RetryableClient::sleep(self, dur).await
+    }
+}
+
+#[cfg(feature = "with-flow")]
+impl core::fmt::Debug for dyn RetrySleeper {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("RetrySleeper").finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "with-flow")]
+fn clone_request(request: &Request<Body>) -> Request<Body> {
+    let mut cloned = Request::new(request.body().to_owned());
+    *cloned.method_mut() = request.method().to_owned();
+    *cloned.uri_mut() = request.uri().to_owned();
+    *cloned.version_mut() = request.version();
+    *cloned.headers_mut() = request.headers().to_owned();
+    cloned
+}
+
+/// Sends `request` with `client`, and sleeps with `sleeper` between attempts. Without
+/// `sleeper` the request is sent once.
+///
+/// The last response or respond error is returned as-is once the policy gives up.
+#[cfg(feature = "with-flow")]
+pub(crate) async fn respond<C>(
+    client: &C,
+    sleeper: Option<&dyn RetrySleeper>,
+    policy: &RetryPolicy,
+    idempotent: bool,
+    request: Request<Body>,
+) -> Result<Response<Body>, C::RespondError>
+where
+    C: Client + Send + Sync,
+{
+    let sleeper = match sleeper {
+        Some(x) => x,
+        None => return client.respond(request).await,
+    };
+
+    match respond_with(client, Some(sleeper), policy, idempotent, || {
+        Ok::<_, core::convert::Infallible>(clone_request(&request))
+    })
+    .await
+    {
+        Ok(ret) => ret,
+        Err(err) => match err {},
+    }
+}
+
+/// Like [`respond`], but renders a new request for every attempt, e.g. with a fresh assertion.
+#[cfg(feature = "with-flow")]
+pub(crate) async fn respond_with<C, F, E>(
+    client: &C,
+    sleeper: Option<&dyn RetrySleeper>,
+    policy: &RetryPolicy,
+    idempotent: bool,
+    mut render_request: F,
+) -> Result<Result<Response<Body>, C::RespondError>, E>
+where
+    C: Client + Send + Sync,
+    F: FnMut() -> Result<Request<Body>, E> + Send,
+{
+    let sleeper = match sleeper {
+        Some(x) => x,
+        None => return Ok(client.respond(render_request()?).await),
+    };
+
+    let mut attempt = 0;
+    loop {
+        let ret = client.respond(render_request()?).await;
+
+        let (reason, retry_after) = match &ret {
+            Ok(response) => (
+                RetryReason::from_response(response),
+                parse_retry_after(response, SystemTime::now()),
+            ),
+            Err(_) => (Some(RetryReason::RespondFailed), None),
+        };
+
+        match reason
+            .and_then(|reason| policy.next_retry_in(attempt, reason, idempotent, retry_after))
+        {
+            Some(dur) => {
+                sleeper.sleep(dur).await;
+                attempt += 1;
+            }
+            None => return Ok(ret),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(status: u16, retry_after: Option<&str>, body: &str) -> Response<Body> {
+        let mut builder = Response::builder().status(status);
+        if let Some(retry_after) = retry_after {
+            builder = builder.header(RETRY_AFTER, retry_after);
+        }
+        builder.body(body.as_bytes().to_vec()).unwrap()
+    }
+
+    #[test]
+    fn test_retry_reason() {
+        for (status, body, reason) in [
+            (429, "", Some(RetryReason::TooManyRequests)),
+            (503, "", Some(RetryReason::ServiceUnavailable)),
+            (502, "", Some(RetryReason::ServerError)),
+            (
+                400,
+                r#"{"error":"temporarily_unavailable"}"#,
+                Some(RetryReason::TemporarilyUnavailable),
+            ),
+            (
+                400,
+                r#"{"error":"server_error"}"#,
+                Some(RetryReason::ServerError),
+            ),
+            (400, r#"{"error":"invalid_grant"}"#, None),
+            (400, "", None),
+            (200, r#"{"error":"server_error"}"#, None),
+        ] {
+            assert_eq!(
+                RetryReason::from_response(&response(status, None, body)),
+                reason
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        let now = SystemTime::UNIX_EPOCH + Duration::from_secs(784111767);
+
+        assert_eq!(
+            parse_retry_after(&response(503, Some("120"), ""), now),
+            Some(Duration::from_secs(120))
+        );
+        assert_eq!(
+            parse_retry_after(
+                &response(503, Some("Sun, 06 Nov 1994 08:49:37 GMT"), ""),
+                now
+            ),
+            Some(Duration::from_secs(10))
+        );
+        assert_eq!(
+            parse_retry_after(
+                &response(503, Some("Sun, 06 Nov 1994 08:49:17 GMT"), ""),
+                now
+            ),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after(&response(503, Some("x"), ""), now), None);
+        assert_eq!(parse_retry_after(&response(503, None, ""), now), None);
+    }
+
+    #[test]
+    fn test_next_retry_in() {
+        let policy = RetryPolicy::new().configure(|x| {
+            x.max_retries = 5;
+            x.jitter = false;
+        });
+
+        assert_eq!(
+            (0..6)
+                .map(|i| policy.next_retry_in(i, RetryReason::ServerError, true, None))
+                .collect::<Vec<_>>(),
+            vec![
+                Some(Duration::from_millis(500)),
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                Some(Duration::from_secs(4)),
+                Some(Duration::from_secs(8)),
+                None,
+            ]
+        );
+        assert_eq!(policy.backoff(10), Duration::from_secs(10));
+        assert_eq!(policy.backoff(100), Duration::from_secs(10));
+
+        assert_eq!(
+            policy.next_retry_in(
+                0,
+                RetryReason::TooManyRequests,
+                true,
+                Some(Duration::from_secs(30))
+            ),
+            Some(Duration::from_secs(30))
+        );
+        assert_eq!(
+            policy.next_retry_in(
+                0,
+                RetryReason::TooManyRequests,
+                true,
+                Some(Duration::from_secs(61))
+            ),
+            None
+        );
+
+        assert_eq!(
+            policy.next_retry_in(0, RetryReason::ServiceUnavailable, false, None),
+            Some(Duration::from_millis(500))
+        );
+        assert_eq!(
+            policy.next_retry_in(0, RetryReason::ServerError, false, None),
+            None
+        );
+        assert_eq!(
+            policy.next_retry_in(0, RetryReason::RespondFailed, false, None),
+            None
+        );
+
+        assert_eq!(
+            RetryPolicy::disabled().next_retry_in(0, RetryReason::TooManyRequests, true, None),
+            None
+        );
+
+        let policy = RetryPolicy::new().configure(|x| x.max_retries = 1);
+        for _ in 0..100 {
+            let dur = policy
+                .next_retry_in(0, RetryReason::ServerError, true, None)
+                .unwrap();
+            assert!(dur >= Duration::from_millis(250) && dur <= Duration::from_millis(500));
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use http_api_client_endpoint::{Body, Response};
+
+use crate::{
+    re_exports::{ClientId, ClientSecret, RedirectUri, Url},
+    Provider, ProviderExtAuthorizationCodeGrant, ProviderExtClientCredentialsGrant,
+    ProviderExtDeviceAuthorizationGrant, ProviderExtJwtAuthorizationGrant,
+    ProviderExtResourceOwnerPasswordCredentialsGrant,
+};
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub(crate) struct TestProvider {
+    pub(crate) client_id: Option<ClientId>,
+    pub(crate) client_secret: Option<ClientSecret>,
+    pub(crate) token_endpoint_url: Url,
+    pub(crate) authorization_endpoint_url: Url,
+    pub(crate) device_authorization_endpoint_url: Url,
+    pub(crate) redirect_uri: Option<RedirectUri>,
+}
+impl TestProvider {
+    pub(crate) fn new() -> Self {
+        Self {
+            client_id: Some("CLIENT_ID".to_owned()),
+            client_secret: Some("CLIENT_SECRET".into()),
+            token_endpoint_url: "https://example.com/token".parse().unwrap(),
+            authorization_endpoint_url: "https://example.com/authorize".parse().unwrap(),
+            device_authorization_endpoint_url: "https://example.com/device".parse().unwrap(),
+            redirect_uri: None,
+        }
+    }
+
+    #[cfg(feature = "with-flow")]
+    pub(crate) fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+}
+impl Provider for TestProvider {
+    type Scope = String;
+
+    fn client_id(&self) -> Option<&ClientId> {
+        self.client_id.as_ref()
+    }
+
+    fn client_secret(&self) -> Option<&ClientSecret> {
+        self.client_secret.as_ref()
+    }
+
+    fn token_endpoint_url(&self) -> &Url {
+        &self.token_endpoint_url
+    }
+}
+impl ProviderExtAuthorizationCodeGrant for TestProvider {
+    fn redirect_uri(&self) -> Option<&RedirectUri> {
+        self.redirect_uri.as_ref()
+    }
+
+    fn authorization_endpoint_url(&self) -> &Url {
+        &self.authorization_endpoint_url
+    }
+}
+impl ProviderExtClientCredentialsGrant for TestProvider {}
+impl ProviderExtDeviceAuthorizationGrant for TestProvider {
+    fn device_authorization_endpoint_url(&self) -> &Url {
+        &self.device_authorization_endpoint_url
+    }
+}
+impl ProviderExtResourceOwnerPasswordCredentialsGrant for TestProvider {}
+impl ProviderExtJwtAuthorizationGrant for TestProvider {
+    fn assertion(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok("ASSERTION".to_owned())
+    }
+}
+
+//
+//
+//
+pub(crate) fn response(status: u16, body: &str) -> Response<Body> {
+    Response::builder()
+        .status(status)
+        .body(body.as_bytes().to_vec())
+        .unwrap()
+}
+
+#[cfg(feature = "with-flow")]
+pub(crate) use client::{TestClient, TestResponse};
+
+#[cfg(feature = "with-flow")]
+mod client {
+    use core::time::Duration;
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+    };
+
+    use http_api_client::{async_trait, Client, RetryableClient};
+    use http_api_client_endpoint::{Body, Request, Response};
+
+    /// `None` fails like a connection reset.
+    pub(crate) type TestResponse = Option<Response<Body>>;
+
+    #[derive(Debug, Clone, Default)]
+    pub(crate) struct TestClient {
+        pub(crate) responses: Arc<Mutex<VecDeque<TestResponse>>>,
+        pub(crate) request_bodies: Arc<Mutex<Vec<String>>>,
+        pub(crate) sleeps: Arc<Mutex<Vec<Duration>>>,
+    }
+    impl TestClient {
+        pub(crate) fn new(responses: impl IntoIterator<Item = TestResponse>) -> Self {
+            let this = Self::default();
+            this.responses.lock().unwrap().extend(responses);
+            this
+        }
+
+        pub(crate) fn request_count(&self) -> usize {
+            self.request_bodies.lock().unwrap().len()
+        }
+
+        pub(crate) fn sleeps(&self) -> Vec<Duration> {
+            self.sleeps.lock().unwrap().to_owned()
+        }
+
+        fn respond_inner(&self, request: Request<Body>) -> Result<Response<Body>, std::io::Error> {
+            self.request_bodies
+                .lock()
+                .unwrap()
+                .push(String::from_utf8_lossy(request.body()).to_string());
+
+            self.responses
+                .lock()
+                .unwrap()
+                .pop_front()
+                .flatten()
+                .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::ConnectionReset))
+        }
+    }
+    #[async_trait]
+    impl Client for TestClient {
+        type RespondError = std::io::Error;
+
+        async fn respond(
+            &self,
+            request: Request<Body>,
+        ) -> Result<Response<Body>, Self::RespondError> {
+            self.respond_inner(request)
+        }
+    }
+    #[async_trait]
+    impl RetryableClient for TestClient {
+        async fn sleep(&self, dur: Duration) {
+            self.sleeps.lock().unwrap().push(dur);
+        }
+    }
+
+    #[cfg(feature = "with-blocking")]
+    impl crate::blocking::BlockingClient for TestClient {
+        type RespondError = std::io::Error;
+
+        fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError> {
+            self.respond_inner(request)
+        }
+
+        fn sleep(&self, dur: Duration) {
+            self.sleeps.lock().unwrap().push(dur);
+        }
+    }
+}
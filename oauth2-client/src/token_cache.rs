@@ -0,0 +1,304 @@
+use core::time::Duration;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+    time::Instant,
+};
+
+use oauth2_core::{
+    access_token_response::SuccessfulBody,
+    types::{Scope, ScopeParameter},
+};
+
+use crate::Provider;
+
+pub const EARLY_REFRESH_DEFAULT: Duration = Duration::from_secs(60);
+
+//
+//
+//
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TokenCacheKey {
+    pub provider: String,
+    /// See [`Provider::principal`].
+    pub principal: Option<String>,
+    pub scope: Vec<String>,
+}
+impl TokenCacheKey {
+    pub fn new<SCOPE>(provider: impl Into<String>, scopes: Option<&ScopeParameter<SCOPE>>) -> Self
+    where
+        SCOPE: Scope,
+    {
+        Self {
+            provider: provider.into(),
+            principal: None,
+            scope: scopes.map(|x| x.to_normalized().0).unwrap_or_default(),
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn set_principal(&mut self, principal: impl Into<String>) {
+        self.principal = Some(principal.into());
+    }
+
+    /// The provider part is made of `token_endpoint_url`, `client_id` and `extra`, the principal
+    /// is [`Provider::principal`].
+    pub fn with_provider<P>(provider: &P, scopes: Option<&ScopeParameter<P::Scope>>) -> Self
+    where
+        P: Provider + ?Sized,
+    {
+        let mut provider_key = provider.token_endpoint_url().to_string();
+        if let Some(client_id) = provider.client_id() {
+            provider_key.push('#');
+            provider_key.push_str(client_id);
+        }
+        if let Some(extra) = provider.extra() {
+            provider_key.push('#');
+            provider_key.push_str(serde_json::Value::Object(extra).to_string().as_str());
+        }
+
+        let mut this = Self::new(provider_key, scopes);
+        this.principal = provider.principal();
+        this
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Clone)]
+struct TokenCacheEntry {
+    body: SuccessfulBody<String>,
+    expires_at: Instant,
+}
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct TokenCache {
+    entries: Arc<RwLock<HashMap<TokenCacheKey, TokenCacheEntry>>>,
+    pub early_refresh: Duration,
+}
+impl Default for TokenCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+impl TokenCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Default::default(),
+            early_refresh: EARLY_REFRESH_DEFAULT,
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn set_early_refresh(&mut self, early_refresh: Duration) {
+        self.early_refresh = early_refresh;
+    }
+
+    pub fn get<SCOPE>(&self, key: &TokenCacheKey) -> Option<SuccessfulBody<SCOPE>>
+    where
+        SCOPE: Scope,
+    {
+        self.get_at(key, Instant::now())
+    }
+
+    fn get_at<SCOPE>(&self, key: &TokenCacheKey, now: Instant) -> Option<SuccessfulBody<SCOPE>>
+    where
+        SCOPE: Scope,
+    {
+        let entries = self.entries.read().ok()?;
+        let is_fresh = |entry: &&TokenCacheEntry| now + self.early_refresh < entry.expires_at;
+
+        let entry = match entries.get(key).filter(is_fresh) {
+            Some(x) => x,
+            None if key.scope.is_empty() => return None,
+            None => {
+                let scope = ScopeParameter::from(key.scope.to_owned());
+                entries
+                    .iter()
+                    .filter(|(k, _)| {
+                        k.provider == key.provider
+                            && k.principal == key.principal
+                            && ScopeParameter::from(k.scope.to_owned()).contains_all(&scope)
+                    })
+                    .map(|(_, v)| v)
+                    .filter(is_fresh)
+                    .max_by_key(|x| x.expires_at)?
+            }
+        };
+
+        let mut body = SuccessfulBody::<SCOPE>::try_from_t_with_string(&entry.body).ok()?;
+        body.expires_in = Some(entry.expires_at.saturating_duration_since(now).as_secs() as usize);
+        Some(body)
+    }
+
+    /// Responses without `expires_in` are not cached.
+    ///
+    /// Stored under the granted `scope`, so a narrower grant is not served for the requested
+    /// one, and a wider grant also serves its subsets.
+    pub fn insert<SCOPE>(&self, key: &TokenCacheKey, body: &SuccessfulBody<SCOPE>)
+    where
+        SCOPE: Scope,
+    {
+        self.insert_at(key, body, Instant::now())
+    }
+
+    fn insert_at<SCOPE>(&self, key: &TokenCacheKey, body: &SuccessfulBody<SCOPE>, now: Instant)
+    where
+        SCOPE: Scope,
+    {
+        let expires_in = match body.expires_in {
+            Some(x) => Duration::from_secs(x as u64),
+            None => return,
+        };
+
+        let key = TokenCacheKey {
+            scope: match &body.scope {
+                Some(x) => x.to_normalized().0,
+                // https://datatracker.ietf.org/doc/html/rfc6749#section-5.1
+                None => key.scope.to_owned(),
+            },
+            ..key.to_owned()
+        };
+
+        let entry = TokenCacheEntry {
+            body: body.into(),
+            expires_at: now + expires_in,
+        };
+
+        if let Ok(mut entries) = self.entries.write() {
+            entries.insert(key, entry);
+        }
+    }
+
+    pub fn remove(&self, key: &TokenCacheKey) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.remove(key);
+        }
+    }
+
+    pub fn clear(&self) {
+        if let Ok(mut entries) = self.entries.write() {
+            entries.clear();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_core::types::AccessTokenType;
+
+    fn body(expires_in: Option<usize>, scope: Option<&str>) -> SuccessfulBody<String> {
+        SuccessfulBody::new(
+            "ACCESS_TOKEN".into(),
+            AccessTokenType::Bearer,
+            expires_in,
+            None,
+            scope.map(|x| x.parse().unwrap()),
+        )
+    }
+
+    #[test]
+    fn test_get_and_insert() {
+        let cache = TokenCache::new().configure(|x| x.set_early_refresh(Duration::from_secs(10)));
+        let now = Instant::now();
+
+        let scope: ScopeParameter<String> = "b a".parse().unwrap();
+        let key = TokenCacheKey::new("p", Some(&scope));
+        assert_eq!(
+            key,
+            TokenCacheKey::new(
+                "p",
+                Some(&"a b a".parse::<ScopeParameter<String>>().unwrap())
+            )
+        );
+
+        assert!(cache.get_at::<String>(&key, now).is_none());
+
+        cache.insert_at(&key, &body(Some(100), None), now);
+        match cache.get_at::<String>(&key, now + Duration::from_secs(30)) {
+            Some(body) => {
+                assert_eq!(body.access_token, "ACCESS_TOKEN");
+                assert_eq!(body.expires_in, Some(70));
+            }
+            None => panic!(),
+        }
+        assert!(cache
+            .get_at::<String>(&key, now + Duration::from_secs(90))
+            .is_none());
+
+        assert!(cache
+            .get_at::<String>(&TokenCacheKey::new::<String>("p", None), now)
+            .is_none());
+
+        //
+        cache.insert_at(&key, &body(None, None), now);
+        assert!(cache.get_at::<String>(&key, now).is_some());
+        cache.remove(&key);
+        assert!(cache.get_at::<String>(&key, now).is_none());
+    }
+
+    #[test]
+    fn test_insert_with_granted_scope() {
+        let cache = TokenCache::new();
+        let now = Instant::now();
+
+        let scope = |s: &str| s.parse::<ScopeParameter<String>>().unwrap();
+        let key_a = TokenCacheKey::new("p", Some(&scope("a")));
+        let key_a_b = TokenCacheKey::new("p", Some(&scope("a b")));
+
+        cache.insert_at(&key_a_b, &body(Some(3600), Some("a")), now);
+        assert!(cache.get_at::<String>(&key_a_b, now).is_none());
+        assert!(cache.get_at::<String>(&key_a, now).is_some());
+
+        cache.insert_at(&key_a, &body(Some(3600), Some("b a c")), now);
+        assert!(cache.get_at::<String>(&key_a_b, now).is_some());
+        assert!(cache
+            .get_at::<String>(&TokenCacheKey::new("p", Some(&scope("c b"))), now)
+            .is_some());
+        assert!(cache
+            .get_at::<String>(&TokenCacheKey::new("p", Some(&scope("a d"))), now)
+            .is_none());
+        assert!(cache
+            .get_at::<String>(&TokenCacheKey::new("q", Some(&scope("a b"))), now)
+            .is_none());
+    }
+
+    #[test]
+    fn test_principal() {
+        let cache = TokenCache::new();
+        let now = Instant::now();
+
+        let scope: ScopeParameter<String> = "a".parse().unwrap();
+        let key_1 =
+            TokenCacheKey::new("p", Some(&scope)).configure(|x| x.set_principal("iss#sub_1"));
+        let key_2 =
+            TokenCacheKey::new("p", Some(&scope)).configure(|x| x.set_principal("iss#sub_2"));
+
+        cache.insert_at(&key_1, &body(Some(3600), Some("a b")), now);
+        assert!(cache.get_at::<String>(&key_1, now).is_some());
+        assert!(cache.get_at::<String>(&key_2, now).is_none());
+        assert!(cache
+            .get_at::<String>(&TokenCacheKey::new("p", Some(&scope)), now)
+            .is_none());
+    }
+}
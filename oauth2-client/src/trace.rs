@@ -0,0 +1,101 @@
+//! Spans and events for endpoint render/parse, bodies are only logged at `TRACE` and
+//! always through [`crate::redaction`].
+
+use http_api_client_endpoint::{Body, Request, Response};
+use oauth2_core::{access_token_response::GENERAL_ERROR_BODY_KEY_ERROR, url::Url};
+use serde_json::{Map, Value};
+use tracing::{field, span::EnteredSpan};
+
+use crate::redaction::{redact_body, redact_url};
+
+pub const TARGET: &str = "oauth2_client";
+
+//
+pub mod grant_type {
+    pub const AUTHORIZATION_CODE: &str = "authorization_code";
+    pub const CLIENT_CREDENTIALS: &str = "client_credentials";
+    pub const DEVICE_CODE: &str = "device_code";
+    pub const JWT_BEARER: &str = "jwt_bearer";
+    pub const PASSWORD: &str = "password";
+}
+
+pub mod endpoint {
+    pub const ACCESS_TOKEN: &str = "access_token";
+    pub const DEVICE_AUTHORIZATION: &str = "device_authorization";
+    pub const DEVICE_ACCESS_TOKEN: &str = "device_access_token";
+    pub const USER_INFO: &str = "user_info";
+}
+
+//
+pub fn render_request_span(
+    provider: &str,
+    grant_type: Option<&str>,
+    endpoint: &str,
+) -> EnteredSpan {
+    let span = tracing::debug_span!(
+        target: TARGET,
+        "render_request",
+        provider,
+        endpoint,
+        grant_type = field::Empty
+    );
+    if let Some(grant_type) = grant_type {
+        span.record("grant_type", grant_type);
+    }
+    span.entered()
+}
+
+pub fn parse_response_span(
+    provider: &str,
+    grant_type: Option<&str>,
+    endpoint: &str,
+) -> EnteredSpan {
+    let span = tracing::debug_span!(
+        target: TARGET,
+        "parse_response",
+        provider,
+        endpoint,
+        grant_type = field::Empty
+    );
+    if let Some(grant_type) = grant_type {
+        span.record("grant_type", grant_type);
+    }
+    span.entered()
+}
+
+//
+pub fn request_rendered(request: &Request<Body>) {
+    let url = request
+        .uri()
+        .to_string()
+        .parse::<Url>()
+        .map(|x| redact_url(&x))
+        .unwrap_or_default();
+
+    tracing::debug!(target: TARGET, method = %request.method(), url, "request rendered");
+    tracing::trace!(target: TARGET, body = redact_body(request.body()), "request body");
+}
+
+pub fn response_received(response: &Response<Body>) {
+    let error = serde_json::from_slice::<Map<String, Value>>(response.body())
+        .ok()
+        .and_then(|map| {
+            map.get(GENERAL_ERROR_BODY_KEY_ERROR)
+                .and_then(|x| x.as_str().map(ToOwned::to_owned))
+        });
+
+    match error {
+        Some(error) => tracing::debug!(
+            target: TARGET,
+            status = response.status().as_u16(),
+            error,
+            "response received"
+        ),
+        None => tracing::debug!(
+            target: TARGET,
+            status = response.status().as_u16(),
+            "response received"
+        ),
+    }
+    tracing::trace!(target: TARGET, body = redact_body(response.body()), "response body");
+}
@@ -4,17 +4,23 @@
 //! https://datatracker.ietf.org/doc/html/rfc8628#section-3.4
 //! https://datatracker.ietf.org/doc/html/rfc7523#section-2.1
 
+use alloc::{borrow::ToOwned, string::String};
+
+#[cfg(feature = "std")]
 use http::Method;
+#[cfg(feature = "std")]
 use mime::Mime;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{Map, Value};
 
 use crate::types::{
-    ClientId, ClientPassword, ClientSecret, Code, CodeVerifier, Scope, ScopeFromStrError,
-    ScopeParameter,
+    Assertion, ClientId, ClientPassword, ClientSecret, Code, CodeVerifier, Password, Scope,
+    ScopeFromStrError, ScopeParameter,
 };
 
+#[cfg(feature = "std")]
 pub const METHOD: Method = Method::POST;
+#[cfg(feature = "std")]
 pub const CONTENT_TYPE: Mime = mime::APPLICATION_WWW_FORM_URLENCODED;
 pub const GRANT_TYPE_WITH_AUTHORIZATION_CODE_GRANT: &str = "authorization_code";
 
@@ -45,7 +51,11 @@ where
 
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct BodyWithAuthorizationCodeGrant {
+#[serde(bound(deserialize = "EXTRA: DeserializeOwned"))]
+pub struct BodyWithAuthorizationCodeGrant<EXTRA = Map<String, Value>>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub code: Code,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub redirect_uri: Option<String>,
@@ -60,10 +70,13 @@ pub struct BodyWithAuthorizationCodeGrant {
     pub code_verifier: Option<CodeVerifier>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
 
-impl BodyWithAuthorizationCodeGrant {
+impl<EXTRA> BodyWithAuthorizationCodeGrant<EXTRA>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub fn new(
         code: Code,
         redirect_uri: Option<String>,
@@ -90,17 +103,21 @@ impl BodyWithAuthorizationCodeGrant {
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
 }
 
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct BodyWithDeviceAuthorizationGrant {
+#[serde(bound(deserialize = "EXTRA: DeserializeOwned"))]
+pub struct BodyWithDeviceAuthorizationGrant<EXTRA = Map<String, Value>>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub device_code: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_id: Option<ClientId>,
@@ -109,10 +126,13 @@ pub struct BodyWithDeviceAuthorizationGrant {
     pub client_secret: Option<ClientSecret>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
 
-impl BodyWithDeviceAuthorizationGrant {
+impl<EXTRA> BodyWithDeviceAuthorizationGrant<EXTRA>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub fn new(
         device_code: String,
         client_id: Option<ClientId>,
@@ -126,19 +146,21 @@ impl BodyWithDeviceAuthorizationGrant {
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
 }
 
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct BodyWithClientCredentialsGrant<SCOPE>
+#[serde(bound(deserialize = "SCOPE: Scope, EXTRA: DeserializeOwned"))]
+pub struct BodyWithClientCredentialsGrant<SCOPE, EXTRA = Map<String, Value>>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scope: Option<ScopeParameter<SCOPE>>,
@@ -146,12 +168,13 @@ where
     pub client_password: Option<ClientPassword>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
 
-impl<SCOPE> BodyWithClientCredentialsGrant<SCOPE>
+impl<SCOPE, EXTRA> BodyWithClientCredentialsGrant<SCOPE, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub fn new(scope: Option<ScopeParameter<SCOPE>>) -> Self {
         Self {
@@ -172,16 +195,19 @@ where
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
 
     pub fn try_from_t_with_string(
-        body: &BodyWithClientCredentialsGrant<String>,
-    ) -> Result<Self, ScopeFromStrError> {
+        body: &BodyWithClientCredentialsGrant<String, EXTRA>,
+    ) -> Result<Self, ScopeFromStrError>
+    where
+        EXTRA: Clone,
+    {
         let scope = if let Some(x) = &body.scope {
             Some(ScopeParameter::<SCOPE>::try_from_t_with_string(x)?)
         } else {
@@ -199,12 +225,14 @@ where
 
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct BodyWithResourceOwnerPasswordCredentialsGrant<SCOPE>
+#[serde(bound(deserialize = "SCOPE: Scope, EXTRA: DeserializeOwned"))]
+pub struct BodyWithResourceOwnerPasswordCredentialsGrant<SCOPE, EXTRA = Map<String, Value>>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub username: String,
-    pub password: String,
+    pub password: Password,
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scope: Option<ScopeParameter<SCOPE>>,
@@ -212,12 +240,13 @@ where
     pub client_password: Option<ClientPassword>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
 
-impl<SCOPE> BodyWithResourceOwnerPasswordCredentialsGrant<SCOPE>
+impl<SCOPE, EXTRA> BodyWithResourceOwnerPasswordCredentialsGrant<SCOPE, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub fn new(
         username: impl AsRef<str>,
@@ -226,7 +255,7 @@ where
     ) -> Self {
         Self {
             username: username.as_ref().to_owned(),
-            password: password.as_ref().to_owned(),
+            password: Password::new(password.as_ref()),
             scope,
             client_password: None,
             _extra: None,
@@ -241,30 +270,33 @@ where
     ) -> Self {
         Self {
             username: username.as_ref().to_owned(),
-            password: password.as_ref().to_owned(),
+            password: Password::new(password.as_ref()),
             scope,
             client_password: Some(client_password),
             _extra: None,
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
 
     pub fn try_from_t_with_string(
-        body: &BodyWithResourceOwnerPasswordCredentialsGrant<String>,
-    ) -> Result<Self, ScopeFromStrError> {
+        body: &BodyWithResourceOwnerPasswordCredentialsGrant<String, EXTRA>,
+    ) -> Result<Self, ScopeFromStrError>
+    where
+        EXTRA: Clone,
+    {
         let scope = if let Some(x) = &body.scope {
             Some(ScopeParameter::<SCOPE>::try_from_t_with_string(x)?)
         } else {
             None
         };
 
-        let mut this = Self::new(&body.username, &body.password, scope);
+        let mut this = Self::new(&body.username, body.password.expose_secret(), scope);
         this.client_password = body.client_password.to_owned();
         if let Some(extra) = body.extra() {
             this.set_extra(extra.to_owned());
@@ -275,47 +307,53 @@ where
 
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct BodyWithJwtAuthorizationGrant<SCOPE>
+#[serde(bound(deserialize = "SCOPE: Scope, EXTRA: DeserializeOwned"))]
+pub struct BodyWithJwtAuthorizationGrant<SCOPE, EXTRA = Map<String, Value>>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
-    pub assertion: String,
+    pub assertion: Assertion,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scope: Option<ScopeParameter<SCOPE>>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_id: Option<ClientId>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
 
-impl<SCOPE> BodyWithJwtAuthorizationGrant<SCOPE>
+impl<SCOPE, EXTRA> BodyWithJwtAuthorizationGrant<SCOPE, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub fn new(
-        assertion: String,
+        assertion: impl Into<Assertion>,
         scope: Option<ScopeParameter<SCOPE>>,
         client_id: Option<ClientId>,
     ) -> Self {
         Self {
-            assertion,
+            assertion: assertion.into(),
             scope,
             client_id,
             _extra: None,
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
 
     pub fn try_from_t_with_string(
-        body: &BodyWithJwtAuthorizationGrant<String>,
-    ) -> Result<Self, ScopeFromStrError> {
+        body: &BodyWithJwtAuthorizationGrant<String, EXTRA>,
+    ) -> Result<Self, ScopeFromStrError>
+    where
+        EXTRA: Clone,
+    {
         let scope = if let Some(x) = &body.scope {
             Some(ScopeParameter::<SCOPE>::try_from_t_with_string(x)?)
         } else {
@@ -388,7 +426,7 @@ mod tests_with_device_authorization_grant {
         let mut body = BodyWithDeviceAuthorizationGrant::new(
             "your_device_code".to_owned(),
             Some("your_client_id".to_owned()),
-            Some("your_client_secret".to_owned()),
+            Some("your_client_secret".into()),
         );
         body.set_extra(extra.to_owned());
         let body = Body::<String>::DeviceAuthorizationGrant(body);
@@ -524,6 +562,21 @@ mod tests_with_resource_owner_password_credentials_grant {
             Err(err) => panic!("{err}"),
         }
     }
+
+    #[test]
+    fn test_debug_redacted() {
+        let body = BodyWithResourceOwnerPasswordCredentialsGrant::<String>::new(
+            "USERNAME", "PASSWORD", None,
+        );
+        assert!(!format!("{body:?}").contains("PASSWORD"));
+        assert_eq!(
+            serde_urlencoded::to_string(&body).unwrap(),
+            "username=USERNAME&password=PASSWORD"
+        );
+
+        let body = BodyWithJwtAuthorizationGrant::<String>::new("ASSERTION", None, None);
+        assert!(!format!("{body:?}").contains("ASSERTION"));
+    }
 }
 
 #[cfg(test)]
@@ -1,13 +1,23 @@
 //! https://datatracker.ietf.org/doc/html/rfc6749#section-5
 
+use alloc::{borrow::ToOwned, string::String};
+
+#[cfg(feature = "std")]
 use mime::Mime;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
-use serde_json::{Map, Value};
+use serde_json::{Error as SerdeJsonError, Map, Value};
 use url::Url;
 
-use crate::types::{AccessTokenType, IdToken, Scope, ScopeFromStrError, ScopeParameter};
+use crate::{
+    types::{
+        AccessToken, AccessTokenType, IdToken, RefreshToken, Scope, ScopeFromStrError,
+        ScopeParameter,
+    },
+    utils::cast_extra,
+};
 
+#[cfg(feature = "std")]
 pub const CONTENT_TYPE: Mime = mime::APPLICATION_JSON;
 pub const GENERAL_ERROR_BODY_KEY_ERROR: &str = "error";
 
@@ -15,18 +25,20 @@ pub const GENERAL_ERROR_BODY_KEY_ERROR: &str = "error";
 //
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SuccessfulBody<SCOPE>
+#[serde(bound(deserialize = "SCOPE: Scope, EXTRA: DeserializeOwned"))]
+pub struct SuccessfulBody<SCOPE, EXTRA = Map<String, Value>>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
-    pub access_token: String,
+    pub access_token: AccessToken,
     // e.g. instagram {"access_token":"xxx", "user_id":0}
     #[serde(default)]
     pub token_type: AccessTokenType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_in: Option<usize>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub refresh_token: Option<String>,
+    pub refresh_token: Option<RefreshToken>,
     #[serde(skip_serializing_if = "Option::is_none")]
     // e.g. bitbucket {"scopes": "account repository"}
     // e.g. linode {"scope": "account:read_only linodes:read_only", "scopes": "account:read_only linodes:read_only"}
@@ -38,17 +50,18 @@ where
     pub id_token: Option<IdToken>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    pub _extra: Option<Map<String, Value>>,
+    pub _extra: Option<EXTRA>,
 }
-impl<SCOPE> SuccessfulBody<SCOPE>
+impl<SCOPE, EXTRA> SuccessfulBody<SCOPE, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub fn new(
-        access_token: String,
+        access_token: AccessToken,
         token_type: AccessTokenType,
         expires_in: Option<usize>,
-        refresh_token: Option<String>,
+        refresh_token: Option<RefreshToken>,
         scope: Option<ScopeParameter<SCOPE>>,
     ) -> Self {
         Self {
@@ -62,16 +75,35 @@ where
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
 
+    /// e.g. from `Map<String, Value>` to a provider's typed extra, or back.
+    pub fn try_cast_extra<T>(self) -> Result<SuccessfulBody<SCOPE, T>, SerdeJsonError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Ok(SuccessfulBody {
+            access_token: self.access_token,
+            token_type: self.token_type,
+            expires_in: self.expires_in,
+            refresh_token: self.refresh_token,
+            scope: self.scope,
+            id_token: self.id_token,
+            _extra: cast_extra(self._extra)?,
+        })
+    }
+
     pub fn try_from_t_with_string(
-        body: &SuccessfulBody<String>,
-    ) -> Result<Self, ScopeFromStrError> {
+        body: &SuccessfulBody<String, EXTRA>,
+    ) -> Result<Self, ScopeFromStrError>
+    where
+        EXTRA: Clone,
+    {
         let scope = if let Some(x) = &body.scope {
             Some(ScopeParameter::<SCOPE>::try_from_t_with_string(x)?)
         } else {
@@ -92,11 +124,12 @@ where
     }
 }
 
-impl<SCOPE> From<&SuccessfulBody<SCOPE>> for SuccessfulBody<String>
+impl<SCOPE, EXTRA> From<&SuccessfulBody<SCOPE, EXTRA>> for SuccessfulBody<String, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned + Clone,
 {
-    fn from(body: &SuccessfulBody<SCOPE>) -> Self {
+    fn from(body: &SuccessfulBody<SCOPE, EXTRA>) -> Self {
         let mut this = Self::new(
             body.access_token.to_owned(),
             body.token_type.to_owned(),
@@ -117,7 +150,11 @@ where
 //
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct ErrorBody {
+#[serde(bound(deserialize = "EXTRA: DeserializeOwned"))]
+pub struct ErrorBody<EXTRA = Map<String, Value>>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     // e.g. twitch {"status":400, "message":"Invalid authorization code"}
     #[serde(default)]
     pub error: ErrorBodyError,
@@ -127,9 +164,12 @@ pub struct ErrorBody {
     pub error_uri: Option<Url>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
-impl ErrorBody {
+impl<EXTRA> ErrorBody<EXTRA>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub fn new(
         error: ErrorBodyError,
         error_description: Option<String>,
@@ -143,12 +183,24 @@ impl ErrorBody {
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
+
+    pub fn try_cast_extra<T>(self) -> Result<ErrorBody<T>, SerdeJsonError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Ok(ErrorBody {
+            error: self.error,
+            error_description: self.error_description,
+            error_uri: self.error_uri,
+            _extra: cast_extra(self._extra)?,
+        })
+    }
 }
 
 #[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
@@ -215,6 +267,31 @@ impl Default for ErrorBodyError {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cast_extra() -> Result<(), Box<dyn std::error::Error>> {
+        #[derive(Serialize, Deserialize, Debug, Clone)]
+        struct Extra {
+            openid: String,
+        }
+
+        let body_str = r#"{"access_token":"ACCESS_TOKEN","token_type":"bearer","openid":"OPENID"}"#;
+
+        let body = serde_json::from_str::<SuccessfulBody<String>>(body_str)?;
+        assert_eq!(
+            body.extra().and_then(|x| x.get("openid")),
+            Some(&Value::from("OPENID"))
+        );
+
+        let body = body.try_cast_extra::<Extra>()?;
+        assert_eq!(body.extra().map(|x| x.openid.as_str()), Some("OPENID"));
+
+        let body = serde_json::from_str::<SuccessfulBody<String, Extra>>(body_str)?;
+        assert_eq!(body.extra().map(|x| x.openid.as_str()), Some("OPENID"));
+        assert_eq!(serde_json::to_string(&body)?, body_str);
+
+        Ok(())
+    }
+
     #[test]
     fn test_ser_de_error_body() {
         let body_str = r#"
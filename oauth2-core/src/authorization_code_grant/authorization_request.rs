@@ -1,14 +1,21 @@
 //! https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.1
 
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString as _},
+};
+
+#[cfg(feature = "std")]
 use http::Method;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize, Serializer};
 use serde_json::{Map, Value};
 
 use crate::types::{
-    ClientId, CodeChallenge, CodeChallengeMethod, Nonce, Scope, ScopeFromStrError, ScopeParameter,
-    State,
+    ClientId, CodeChallenge, CodeChallengeMethod, DisplayMode, IdToken, Nonce, Prompt, Scope,
+    ScopeFromStrError, ScopeParameter, SpaceDelimited, State,
 };
 
+#[cfg(feature = "std")]
 pub const METHOD: Method = Method::GET;
 pub const RESPONSE_TYPE: &str = "code";
 
@@ -16,9 +23,11 @@ pub const RESPONSE_TYPE: &str = "code";
 //
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Query<SCOPE>
+#[serde(bound(deserialize = "SCOPE: Scope, EXTRA: DeserializeOwned"))]
+pub struct Query<SCOPE, EXTRA = Map<String, Value>>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub response_type: String,
     pub client_id: ClientId,
@@ -38,13 +47,16 @@ where
     // OIDC
     #[serde(skip_serializing_if = "Option::is_none")]
     pub nonce: Option<Nonce>,
+    #[serde(flatten)]
+    pub authentication: AuthenticationParameters,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
-impl<SCOPE> Query<SCOPE>
+impl<SCOPE, EXTRA> Query<SCOPE, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub fn new(
         client_id: ClientId,
@@ -72,18 +84,22 @@ where
             code_challenge: code_challenge.to_owned().map(|x| x.0),
             code_challenge_method: code_challenge.map(|x| x.1),
             nonce,
+            authentication: Default::default(),
             _extra: None,
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
 
-    pub fn try_from_t_with_string(query: &Query<String>) -> Result<Self, ScopeFromStrError> {
+    pub fn try_from_t_with_string(query: &Query<String, EXTRA>) -> Result<Self, ScopeFromStrError>
+    where
+        EXTRA: Clone,
+    {
         let scope = if let Some(x) = &query.scope {
             Some(ScopeParameter::<SCOPE>::try_from_t_with_string(x)?)
         } else {
@@ -105,6 +121,7 @@ where
             code_challenge,
             query.nonce.to_owned(),
         );
+        this.authentication = query.authentication.to_owned();
         if let Some(extra) = query.extra() {
             this.set_extra(extra.to_owned());
         }
@@ -112,11 +129,12 @@ where
     }
 }
 
-impl<SCOPE> From<&Query<SCOPE>> for Query<String>
+impl<SCOPE, EXTRA> From<&Query<SCOPE, EXTRA>> for Query<String, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned + Clone,
 {
-    fn from(query: &Query<SCOPE>) -> Self {
+    fn from(query: &Query<SCOPE, EXTRA>) -> Self {
         let mut code_challenge = None;
         if let Some(cc) = &query.code_challenge {
             if let Some(ccm) = &query.code_challenge_method {
@@ -135,6 +153,7 @@ where
             code_challenge,
             query.nonce.to_owned(),
         );
+        this.authentication = query.authentication.to_owned();
         if let Some(extra) = query.extra() {
             this.set_extra(extra.to_owned());
         }
@@ -142,17 +161,111 @@ where
     }
 }
 
+//
+//
+//
+/// OpenID Connect Core 1.0 section 3.1.2.1 parameters other than `nonce`.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuthenticationParameters {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt: Option<SpaceDelimited<Prompt>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub login_hint: Option<String>,
+    /// Seconds, the ID token `auth_time` must not be older.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        deserialize_with = "deserialize_max_age"
+    )]
+    pub max_age: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acr_values: Option<SpaceDelimited<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui_locales: Option<SpaceDelimited<String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub display: Option<DisplayMode>,
+    /// Sent as a JSON string.
+    #[serde(
+        default,
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_claims",
+        deserialize_with = "deserialize_claims"
+    )]
+    pub claims: Option<Map<String, Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id_token_hint: Option<IdToken>,
+}
+
+impl AuthenticationParameters {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+}
+
+fn serialize_claims<S>(
+    claims: &Option<Map<String, Value>>,
+    serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match claims {
+        Some(x) => serializer.serialize_str(Value::Object(x.to_owned()).to_string().as_str()),
+        None => serializer.serialize_none(),
+    }
+}
+
+fn deserialize_claims<'de, D>(deserializer: D) -> Result<Option<Map<String, Value>>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let s = String::deserialize(deserializer)?;
+    serde_json::from_str(&s)
+        .map(Some)
+        .map_err(serde::de::Error::custom)
+}
+
+// Query strings carry numbers as strings.
+fn deserialize_max_age<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum MaxAge {
+        Number(u64),
+        String(String),
+    }
+
+    match MaxAge::deserialize(deserializer)? {
+        MaxAge::Number(x) => Ok(Some(x)),
+        MaxAge::String(s) => s.parse().map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     #[test]
     fn test_ser_de() {
-        let query = Query::new(
+        let query = Query::<String>::new(
             "your_client_id".to_owned(),
             Some("https://client.example.com/cb".parse().unwrap()),
             Some(vec!["email".to_owned(), "profile".to_owned()].into()),
-            Some("STATE".to_owned()),
+            Some("STATE".into()),
         );
         match serde_qs::to_string(&query) {
             Ok(query_str) => {
@@ -161,4 +274,33 @@ mod tests {
             Err(err) => panic!("{err}"),
         }
     }
+
+    #[test]
+    fn test_ser_de_with_authentication() {
+        use alloc::vec;
+
+        let mut query = Query::<String>::new(
+            "your_client_id".to_owned(),
+            None,
+            Some(vec!["openid".to_owned()].into()),
+            None,
+        );
+        query.nonce = Some("NONCE".to_owned());
+        query.authentication = AuthenticationParameters::new().configure(|x| {
+            x.prompt = Some(vec![Prompt::Login, Prompt::Consent].into());
+            x.login_hint = Some("foo@example.com".to_owned());
+            x.max_age = Some(300);
+            x.acr_values = Some(vec!["urn:mace:incommon:iap:silver".to_owned()].into());
+            x.display = Some(DisplayMode::Popup);
+            x.claims =
+                serde_json::from_str(r#"{"id_token":{"auth_time":{"essential":true}}}"#).ok();
+        });
+
+        let query_str = serde_qs::to_string(&query).unwrap();
+        assert_eq!(query_str, "response_type=code&client_id=your_client_id&scope=openid&nonce=NONCE&prompt=login+consent&login_hint=foo%40example.com&max_age=300&acr_values=urn%3Amace%3Aincommon%3Aiap%3Asilver&display=popup&claims=%7B%22id_token%22%3A%7B%22auth_time%22%3A%7B%22essential%22%3Atrue%7D%7D%7D");
+
+        let de = serde_qs::from_str::<Query<String>>(&query_str).unwrap();
+        assert_eq!(de.authentication, query.authentication);
+        assert!(de.extra().map(|x| x.is_empty()).unwrap_or(true));
+    }
 }
@@ -1,23 +1,35 @@
 //! https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2
 
-use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use alloc::string::String;
 
-use crate::types::{Code, State};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Error as SerdeJsonError, Map, Value};
+
+use crate::{
+    types::{Code, State},
+    utils::cast_extra,
+};
 
 //
 //
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SuccessfulQuery {
+#[serde(bound(deserialize = "EXTRA: DeserializeOwned"))]
+pub struct SuccessfulQuery<EXTRA = Map<String, Value>>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub code: Code,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub state: Option<State>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
-impl SuccessfulQuery {
+impl<EXTRA> SuccessfulQuery<EXTRA>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub fn new(code: Code, state: Option<State>) -> Self {
         Self {
             code,
@@ -26,12 +38,23 @@ impl SuccessfulQuery {
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
+
+    pub fn try_cast_extra<T>(self) -> Result<SuccessfulQuery<T>, SerdeJsonError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Ok(SuccessfulQuery {
+            code: self.code,
+            state: self.state,
+            _extra: cast_extra(self._extra)?,
+        })
+    }
 }
 
 //
@@ -55,7 +78,7 @@ mod tests {
         match serde_qs::from_str::<SuccessfulQuery>(query_str) {
             Ok(query) => {
                 assert_eq!(query.code, "SplxlOBeZQQYbYS6WxSbIA");
-                assert_eq!(query.state, Some("xyz".to_owned()));
+                assert_eq!(query.state, Some("xyz".into()));
             }
             Err(err) => panic!("{err}"),
         }
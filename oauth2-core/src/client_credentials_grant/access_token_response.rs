@@ -1,17 +1,24 @@
-use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use alloc::{borrow::ToOwned, string::String};
 
-use crate::types::{AccessTokenType, Scope, ScopeParameter};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Error as SerdeJsonError, Map, Value};
+
+use crate::{
+    types::{AccessToken, AccessTokenType, Scope, ScopeParameter},
+    utils::cast_extra,
+};
 
 //
 //
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SuccessfulBody<SCOPE>
+#[serde(bound(deserialize = "SCOPE: Scope, EXTRA: DeserializeOwned"))]
+pub struct SuccessfulBody<SCOPE, EXTRA = Map<String, Value>>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
-    pub access_token: String,
+    pub access_token: AccessToken,
     pub token_type: AccessTokenType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_in: Option<usize>,
@@ -19,15 +26,16 @@ where
     pub scope: Option<ScopeParameter<SCOPE>>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    pub _extra: Option<Map<String, Value>>,
+    pub _extra: Option<EXTRA>,
 }
 
-impl<SCOPE> SuccessfulBody<SCOPE>
+impl<SCOPE, EXTRA> SuccessfulBody<SCOPE, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub fn new(
-        access_token: String,
+        access_token: AccessToken,
         token_type: AccessTokenType,
         expires_in: Option<usize>,
         scope: Option<ScopeParameter<SCOPE>>,
@@ -41,19 +49,34 @@ where
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
+
+    pub fn try_cast_extra<T>(self) -> Result<SuccessfulBody<SCOPE, T>, SerdeJsonError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Ok(SuccessfulBody {
+            access_token: self.access_token,
+            token_type: self.token_type,
+            expires_in: self.expires_in,
+            scope: self.scope,
+            _extra: cast_extra(self._extra)?,
+        })
+    }
 }
 
-impl<SCOPE> From<SuccessfulBody<SCOPE>> for crate::access_token_response::SuccessfulBody<SCOPE>
+impl<SCOPE, EXTRA> From<SuccessfulBody<SCOPE, EXTRA>>
+    for crate::access_token_response::SuccessfulBody<SCOPE, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned + Clone,
 {
-    fn from(body: SuccessfulBody<SCOPE>) -> Self {
+    fn from(body: SuccessfulBody<SCOPE, EXTRA>) -> Self {
         let mut this = Self::new(
             body.access_token.to_owned(),
             body.token_type.to_owned(),
@@ -68,6 +91,26 @@ where
     }
 }
 
+impl<SCOPE, EXTRA> From<crate::access_token_response::SuccessfulBody<SCOPE, EXTRA>>
+    for SuccessfulBody<SCOPE, EXTRA>
+where
+    SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned + Clone,
+{
+    fn from(body: crate::access_token_response::SuccessfulBody<SCOPE, EXTRA>) -> Self {
+        let mut this = Self::new(
+            body.access_token.to_owned(),
+            body.token_type.to_owned(),
+            body.expires_in.to_owned(),
+            body.scope.to_owned(),
+        );
+        if let Some(extra) = body.extra() {
+            this.set_extra(extra.to_owned());
+        }
+        this
+    }
+}
+
 //
 //
 //
@@ -1,13 +1,19 @@
 //! https://datatracker.ietf.org/doc/html/rfc8628#section-3.1
 
+use alloc::{borrow::ToOwned, string::String};
+
+#[cfg(feature = "std")]
 use http::Method;
+#[cfg(feature = "std")]
 use mime::Mime;
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use serde_json::{Map, Value};
 
 use crate::types::{ClientId, Scope, ScopeFromStrError, ScopeParameter};
 
+#[cfg(feature = "std")]
 pub const METHOD: Method = Method::POST;
+#[cfg(feature = "std")]
 pub const CONTENT_TYPE: Mime = mime::APPLICATION_WWW_FORM_URLENCODED;
 pub const RESPONSE_TYPE: &str = "device_code";
 
@@ -15,9 +21,11 @@ pub const RESPONSE_TYPE: &str = "device_code";
 //
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Body<SCOPE>
+#[serde(bound(deserialize = "SCOPE: Scope, EXTRA: DeserializeOwned"))]
+pub struct Body<SCOPE, EXTRA = Map<String, Value>>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_id: Option<ClientId>,
@@ -25,12 +33,13 @@ where
     pub scope: Option<ScopeParameter<SCOPE>>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
 
-impl<SCOPE> Body<SCOPE>
+impl<SCOPE, EXTRA> Body<SCOPE, EXTRA>
 where
     SCOPE: Scope,
+    EXTRA: Serialize + DeserializeOwned,
 {
     pub fn new(client_id: Option<ClientId>, scope: Option<ScopeParameter<SCOPE>>) -> Self {
         Self {
@@ -40,14 +49,17 @@ where
         }
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
 
-    pub fn try_from_t_with_string(body: &Body<String>) -> Result<Self, ScopeFromStrError> {
+    pub fn try_from_t_with_string(body: &Body<String, EXTRA>) -> Result<Self, ScopeFromStrError>
+    where
+        EXTRA: Clone,
+    {
         let scope = if let Some(x) = &body.scope {
             Some(ScopeParameter::<SCOPE>::try_from_t_with_string(x)?)
         } else {
@@ -68,7 +80,7 @@ mod tests {
 
     #[test]
     fn ser() {
-        let body = Body::new(
+        let body = Body::<String>::new(
             Some("your_client_id".to_owned()),
             Some(vec!["email".to_owned(), "profile".to_owned()].into()),
         );
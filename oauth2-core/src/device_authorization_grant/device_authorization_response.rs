@@ -1,14 +1,21 @@
 //! https://datatracker.ietf.org/doc/html/rfc8628#section-3.2
 
+use alloc::string::String;
 use core::time::Duration;
 
+#[cfg(feature = "std")]
 use mime::Mime;
-use serde::{Deserialize, Serialize};
-use serde_json::{Map, Value};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Error as SerdeJsonError, Map, Value};
 use url::Url;
 
+use crate::utils::cast_extra;
+
+#[cfg(feature = "std")]
 pub const CONTENT_TYPE: Mime = mime::APPLICATION_JSON;
 pub const INTERVAL_DEFAULT: usize = 5;
+/// https://datatracker.ietf.org/doc/html/rfc8628#section-3.5
+pub const INTERVAL_SLOW_DOWN_INCREMENT: usize = 5;
 pub type DeviceCode = String;
 pub type UserCode = String;
 pub type VerificationUri = Url;
@@ -18,7 +25,11 @@ pub type VerificationUriComplete = Url;
 //
 //
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct SuccessfulBody {
+#[serde(bound(deserialize = "EXTRA: DeserializeOwned"))]
+pub struct SuccessfulBody<EXTRA = Map<String, Value>>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub device_code: DeviceCode,
     pub user_code: UserCode,
     // e.g. google
@@ -34,10 +45,13 @@ pub struct SuccessfulBody {
     pub interval: Option<usize>,
 
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
-    _extra: Option<Map<String, Value>>,
+    _extra: Option<EXTRA>,
 }
 
-impl SuccessfulBody {
+impl<EXTRA> SuccessfulBody<EXTRA>
+where
+    EXTRA: Serialize + DeserializeOwned,
+{
     pub fn new(
         device_code: DeviceCode,
         user_code: UserCode,
@@ -61,12 +75,31 @@ impl SuccessfulBody {
         Duration::from_secs(self.interval.unwrap_or(INTERVAL_DEFAULT) as u64)
     }
 
-    pub fn set_extra(&mut self, extra: Map<String, Value>) {
+    pub fn expires_in(&self) -> Duration {
+        Duration::from_secs(self.expires_in as u64)
+    }
+
+    pub fn set_extra(&mut self, extra: EXTRA) {
         self._extra = Some(extra);
     }
-    pub fn extra(&self) -> Option<&Map<String, Value>> {
+    pub fn extra(&self) -> Option<&EXTRA> {
         self._extra.as_ref()
     }
+
+    pub fn try_cast_extra<T>(self) -> Result<SuccessfulBody<T>, SerdeJsonError>
+    where
+        T: Serialize + DeserializeOwned,
+    {
+        Ok(SuccessfulBody {
+            device_code: self.device_code,
+            user_code: self.user_code,
+            verification_uri: self.verification_uri,
+            verification_uri_complete: self.verification_uri_complete,
+            expires_in: self.expires_in,
+            interval: self.interval,
+            _extra: cast_extra(self._extra)?,
+        })
+    }
 }
 
 //
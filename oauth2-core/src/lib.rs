@@ -1,4 +1,10 @@
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
 pub use http;
+#[cfg(feature = "std")]
 pub use mime;
 pub use serde;
 pub use serde_enum_str;
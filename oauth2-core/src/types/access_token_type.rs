@@ -1,9 +1,12 @@
 //! https://datatracker.ietf.org/doc/html/rfc6749#section-7.1
 
+use alloc::string::String;
+
 use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
 
-#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq, Default)]
 pub enum AccessTokenType {
+    #[default]
     #[serde(rename = "bearer")]
     #[serde(alias = "Bearer")]
     Bearer,
@@ -13,8 +16,3 @@ pub enum AccessTokenType {
     #[serde(other)]
     Other(String),
 }
-impl Default for AccessTokenType {
-    fn default() -> Self {
-        Self::Bearer
-    }
-}
@@ -1,5 +1,7 @@
 //! https://datatracker.ietf.org/doc/html/rfc6749#section-2.3.1
 
+use alloc::{borrow::ToOwned, format, string::String};
+
 use base64::{engine::general_purpose, Engine as _};
 use serde::{Deserialize, Serialize};
 
@@ -25,7 +27,11 @@ impl ClientPassword {
     pub fn header_authorization(&self) -> String {
         format!(
             "{HEADER_AUTHORIZATION_PREFIX}{}",
-            general_purpose::STANDARD.encode(format!("{}:{}", self.client_id, self.client_secret))
+            general_purpose::STANDARD.encode(format!(
+                "{}:{}",
+                self.client_id,
+                self.client_secret.expose_secret()
+            ))
         )
     }
 
@@ -43,7 +49,7 @@ impl ClientPassword {
 
         let mut s = s.split(':');
         let client_id = s.next().ok_or("Missing client_id")?.to_owned();
-        let client_secret = s.next().ok_or("Missing client_secret")?.to_owned();
+        let client_secret = s.next().ok_or("Missing client_secret")?.into();
         if s.next().is_some() {
             return Err("Invalid");
         }
@@ -62,7 +68,7 @@ mod tests {
     #[test]
     fn test_header_authorization() {
         assert_eq!(
-            ClientPassword::new("s6BhdRkqt3".to_owned(), "7Fjfp0ZBr1KtDRbnfVdmIw".to_owned())
+            ClientPassword::new("s6BhdRkqt3".to_owned(), "7Fjfp0ZBr1KtDRbnfVdmIw".into())
                 .header_authorization(),
             "Basic czZCaGRSa3F0Mzo3RmpmcDBaQnIxS3REUmJuZlZkbUl3"
         );
@@ -72,7 +78,7 @@ mod tests {
                 "Basic czZCaGRSa3F0Mzo3RmpmcDBaQnIxS3REUmJuZlZkbUl3"
             )
             .unwrap(),
-            ClientPassword::new("s6BhdRkqt3".to_owned(), "7Fjfp0ZBr1KtDRbnfVdmIw".to_owned())
+            ClientPassword::new("s6BhdRkqt3".to_owned(), "7Fjfp0ZBr1KtDRbnfVdmIw".into())
         );
     }
 }
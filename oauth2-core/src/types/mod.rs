@@ -1,24 +1,29 @@
+use alloc::string::String;
+
 pub mod access_token_type;
 pub mod client_password;
 pub mod code_challenge_method;
 pub mod code_verifier;
+pub mod oidc;
 pub mod redirect_uri;
 pub mod scope;
+pub mod secret;
 
 pub use access_token_type::AccessTokenType;
 pub use client_password::ClientPassword;
 pub use code_challenge_method::CodeChallengeMethod;
-pub use code_verifier::CodeVerifier;
+pub use oidc::{DisplayMode, Prompt, SpaceDelimited};
 pub use redirect_uri::RedirectUri;
 pub use scope::{Scope, ScopeFromStrError, ScopeParameter};
+pub use secret::{
+    AccessToken, Assertion, ClientSecret, Code, CodeVerifier, Password, PrivateKey, RefreshToken,
+    State,
+};
 
 //
 //
 //
 pub type ClientId = String;
-pub type ClientSecret = String;
-pub type State = String;
-pub type Code = String;
 
 pub type CodeChallenge = String;
 
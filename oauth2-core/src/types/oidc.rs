@@ -0,0 +1,108 @@
+//! https://openid.net/specs/openid-connect-core-1_0.html#AuthRequest
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::str::FromStr;
+
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use serde_enum_str::{Deserialize_enum_str, Serialize_enum_str};
+
+//
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Prompt {
+    None,
+    Login,
+    Consent,
+    SelectAccount,
+    #[serde(other)]
+    Other(String),
+}
+
+/// The `display` parameter.
+#[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DisplayMode {
+    Page,
+    Popup,
+    Touch,
+    Wap,
+    #[serde(other)]
+    Other(String),
+}
+
+//
+/// Space delimited like `scope`, e.g. `prompt`, `ui_locales`, `acr_values`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpaceDelimited<T>(pub Vec<T>);
+
+impl<T> From<Vec<T>> for SpaceDelimited<T> {
+    fn from(v: Vec<T>) -> Self {
+        Self(v)
+    }
+}
+
+impl<T> Serialize for SpaceDelimited<T>
+where
+    T: ToString,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(
+            self.0
+                .iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+                .as_str(),
+        )
+    }
+}
+
+impl<'de, T> Deserialize<'de> for SpaceDelimited<T>
+where
+    T: FromStr,
+    T::Err: core::fmt::Display,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s = String::deserialize(deserializer)?;
+        s.split(' ')
+            .filter(|x| !x.is_empty())
+            .map(|x| T::from_str(x).map_err(de::Error::custom))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::{borrow::ToOwned as _, vec};
+
+    #[test]
+    fn test_ser_de() {
+        let prompt: SpaceDelimited<Prompt> = vec![Prompt::Consent, Prompt::SelectAccount].into();
+        assert_eq!(
+            serde_json::to_string(&prompt).unwrap(),
+            r#""consent select_account""#
+        );
+        assert_eq!(
+            serde_json::from_str::<SpaceDelimited<Prompt>>(r#""login  none""#).unwrap(),
+            vec![Prompt::Login, Prompt::None].into()
+        );
+
+        assert_eq!(DisplayMode::Popup.to_string(), "popup");
+        assert_eq!(
+            "foo".parse::<DisplayMode>().unwrap(),
+            DisplayMode::Other("foo".to_owned())
+        );
+    }
+}
@@ -1,14 +1,24 @@
 //! https://datatracker.ietf.org/doc/html/rfc6749#section-3.1.2
 
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+};
+
 use serde::{
     de::{self, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use url::Url;
+use url::{Host, Url};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum RedirectUri {
     Url(Url),
+    /// https://datatracker.ietf.org/doc/html/rfc8252#section-7.3
+    LoopbackIp(Url),
+    /// https://datatracker.ietf.org/doc/html/rfc8252#section-7.1
+    PrivateUseScheme(Url),
     /// https://developers.google.com/identity/protocols/oauth2/native-app
     Oob,
     /// https://developers.google.com/identity/protocols/oauth2/native-app
@@ -20,11 +30,93 @@ impl RedirectUri {
     pub fn new(url: impl AsRef<str>) -> Result<Self, String> {
         url.as_ref().parse()
     }
+
+    pub fn new_loopback_ip(url: impl AsRef<str>) -> Result<Self, String> {
+        let url = Url::parse(url.as_ref()).map_err(|err| err.to_string())?;
+        if !is_loopback_ip(&url) {
+            return Err(format!("Invalid loopback ip redirect uri: {url}"));
+        }
+
+        Ok(Self::LoopbackIp(url))
+    }
+
+    pub fn new_private_use_scheme(url: impl AsRef<str>) -> Result<Self, String> {
+        let url = Url::parse(url.as_ref()).map_err(|err| err.to_string())?;
+        check_private_use_scheme(&url)?;
+
+        Ok(Self::PrivateUseScheme(url))
+    }
+
+    pub fn as_url(&self) -> Option<&Url> {
+        match self {
+            Self::Url(url) | Self::LoopbackIp(url) | Self::PrivateUseScheme(url) => Some(url),
+            Self::Oob | Self::OobAuto | Self::Other(_) => None,
+        }
+    }
+
+    /// https://datatracker.ietf.org/doc/html/rfc8252#section-7
+    ///
+    /// `Url` is only allowed as a claimed `https` uri or on `localhost`.
+    pub fn is_allowed_for_native_apps(&self) -> bool {
+        match self {
+            Self::Url(url) => url.scheme() == "https" || url.host_str() == Some("localhost"),
+            Self::LoopbackIp(_) | Self::PrivateUseScheme(_) | Self::Oob | Self::OobAuto => true,
+            Self::Other(_) => false,
+        }
+    }
+
+    /// Whether `self` (used in the request) matches the registered redirect uri.
+    ///
+    /// https://datatracker.ietf.org/doc/html/rfc8252#section-7.3
+    /// For loopback ip redirect uris, any port is allowed.
+    pub fn matches(&self, registered: &Self) -> bool {
+        match (self, registered) {
+            (Self::LoopbackIp(a), Self::LoopbackIp(b)) => {
+                a.scheme() == b.scheme()
+                    && a.host() == b.host()
+                    && a.path() == b.path()
+                    && a.query() == b.query()
+            }
+            (a, b) => a == b,
+        }
+    }
+}
+
+fn is_loopback_ip(url: &Url) -> bool {
+    url.scheme() == "http"
+        && match url.host() {
+            Some(Host::Ipv4(ip)) => ip.is_loopback(),
+            Some(Host::Ipv6(ip)) => ip.is_loopback(),
+            _ => false,
+        }
+}
+
+fn check_private_use_scheme(url: &Url) -> Result<(), String> {
+    let scheme = url.scheme();
+    if scheme.split('.').count() < 2 || scheme.split('.').any(|x| x.is_empty()) {
+        return Err(format!(
+            "Invalid private-use scheme: {scheme}, should be a reverse domain name"
+        ));
+    }
+    if url.as_str()[scheme.len() + 1..].starts_with("//") {
+        return Err(format!(
+            "Invalid private-use scheme redirect uri: {url}, should not contain an authority"
+        ));
+    }
+    if url.fragment().is_some() {
+        return Err(format!(
+            "Invalid private-use scheme redirect uri: {url}, should not contain a fragment"
+        ));
+    }
+
+    Ok(())
 }
 
 impl core::str::FromStr for RedirectUri {
     type Err = String;
 
+    /// Private-use scheme uris failing the validation are kept as `Other`,
+    /// use `RedirectUri::new_private_use_scheme` for strict parsing.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         if s.starts_with("http://") || s.starts_with("https://") {
             let url = Url::parse(s).map_err(|err| err.to_string())?;
@@ -34,7 +126,12 @@ impl core::str::FromStr for RedirectUri {
             match s {
                 "urn:ietf:wg:oauth:2.0:oob" => Ok(Self::Oob),
                 "urn:ietf:wg:oauth:2.0:oob:auto" => Ok(Self::OobAuto),
-                s => Ok(Self::Other(s.to_owned())),
+                s => match Url::parse(s) {
+                    Ok(url) if check_private_use_scheme(&url).is_ok() => {
+                        Ok(Self::PrivateUseScheme(url))
+                    }
+                    _ => Ok(Self::Other(s.to_owned())),
+                },
             }
         }
     }
@@ -42,7 +139,9 @@ impl core::str::FromStr for RedirectUri {
 impl core::fmt::Display for RedirectUri {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self {
-            Self::Url(url) => write!(f, "{}", url.as_str()),
+            Self::Url(url) | Self::LoopbackIp(url) | Self::PrivateUseScheme(url) => {
+                write!(f, "{}", url.as_str())
+            }
             Self::Oob => write!(f, "urn:ietf:wg:oauth:2.0:oob"),
             Self::OobAuto => write!(f, "urn:ietf:wg:oauth:2.0:oob:auto"),
             Self::Other(s) => write!(f, "{s}"),
@@ -54,11 +153,16 @@ impl TryFrom<Url> for RedirectUri {
     type Error = String;
 
     fn try_from(url: Url) -> Result<Self, Self::Error> {
-        if !vec!["http", "https"].contains(&url.scheme()) {
-            return Err(format!("Invalid scheme: {}", url.scheme()));
+        if ["http", "https"].contains(&url.scheme()) {
+            if is_loopback_ip(&url) {
+                return Ok(Self::LoopbackIp(url));
+            }
+            return Ok(Self::Url(url));
         }
 
-        Ok(Self::Url(url))
+        check_private_use_scheme(&url).map_err(|_| format!("Invalid scheme: {}", url.scheme()))?;
+
+        Ok(Self::PrivateUseScheme(url))
     }
 }
 
@@ -135,7 +239,9 @@ mod tests {
         }
 
         match serde_json::to_string(&Foo {
-            redirect_uri: RedirectUri::Other("com.example.app:redirect_uri_path".to_owned()),
+            redirect_uri: RedirectUri::PrivateUseScheme(
+                "com.example.app:redirect_uri_path".parse().unwrap(),
+            ),
         }) {
             Ok(v) => {
                 assert_eq!(v, r#"{"redirect_uri":"com.example.app:redirect_uri_path"}"#);
@@ -175,10 +281,72 @@ mod tests {
             Ok(v) => {
                 assert_eq!(
                     v.redirect_uri,
-                    RedirectUri::Other("com.example.app:redirect_uri_path".to_owned())
+                    RedirectUri::PrivateUseScheme(
+                        "com.example.app:redirect_uri_path".parse().unwrap()
+                    )
                 );
             }
             Err(err) => panic!("{err}"),
         }
     }
+
+    #[test]
+    fn test_native_apps() -> Result<(), Box<dyn std::error::Error>> {
+        match RedirectUri::new("http://127.0.0.1:8080/cb")? {
+            RedirectUri::LoopbackIp(url) => assert_eq!(url.port(), Some(8080)),
+            x => panic!("{x:?}"),
+        }
+        match RedirectUri::new("http://[::1]/cb")? {
+            RedirectUri::LoopbackIp(_) => {}
+            x => panic!("{x:?}"),
+        }
+        assert!(matches!(
+            RedirectUri::new("https://127.0.0.1/cb")?,
+            RedirectUri::Url(_)
+        ));
+        assert!(matches!(
+            RedirectUri::new("http://localhost/cb")?,
+            RedirectUri::Url(_)
+        ));
+        assert!(RedirectUri::new_loopback_ip("http://192.0.2.1/cb").is_err());
+
+        match RedirectUri::new("com.example.app:/oauth2redirect")? {
+            RedirectUri::PrivateUseScheme(url) => {
+                assert_eq!(url.as_str(), "com.example.app:/oauth2redirect")
+            }
+            x => panic!("{x:?}"),
+        }
+        assert!(RedirectUri::new_private_use_scheme("com.example.app:/oauth2redirect").is_ok());
+        assert!(RedirectUri::new_private_use_scheme("myapp:/oauth2redirect").is_err());
+        assert!(RedirectUri::new_private_use_scheme("com.example.app://oauth2redirect").is_err());
+        assert!(matches!(
+            RedirectUri::new("com.example.app://oauth2redirect")?,
+            RedirectUri::Other(_)
+        ));
+
+        assert!(RedirectUri::new("http://127.0.0.1/cb")?.is_allowed_for_native_apps());
+        assert!(RedirectUri::new("com.example.app:/cb")?.is_allowed_for_native_apps());
+        assert!(RedirectUri::Oob.is_allowed_for_native_apps());
+        assert!(!RedirectUri::new("http://client.example.com/cb")?.is_allowed_for_native_apps());
+        assert!(!RedirectUri::new("myapp://cb")?.is_allowed_for_native_apps());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_matches() -> Result<(), Box<dyn std::error::Error>> {
+        let registered = RedirectUri::new("http://127.0.0.1/cb")?;
+        assert!(RedirectUri::new("http://127.0.0.1:51004/cb")?.matches(&registered));
+        assert!(!RedirectUri::new("http://127.0.0.1:51004/other")?.matches(&registered));
+        assert!(!RedirectUri::new("http://[::1]:51004/cb")?.matches(&registered));
+
+        let registered = RedirectUri::new("com.example.app:/oauth2redirect")?;
+        assert!(RedirectUri::new("com.example.app:/oauth2redirect")?.matches(&registered));
+        assert!(!RedirectUri::new("com.example.app:/other")?.matches(&registered));
+
+        let registered = RedirectUri::new("https://client.example.com/cb")?;
+        assert!(!RedirectUri::new("https://client.example.com:8443/cb")?.matches(&registered));
+
+        Ok(())
+    }
 }
@@ -1,5 +1,11 @@
 //! https://datatracker.ietf.org/doc/html/rfc6749#section-3.3
 
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use core::{marker::PhantomData, str::FromStr};
 
 use serde::{
@@ -132,6 +138,18 @@ impl<T> ScopeParameter<T>
 where
     T: Scope,
 {
+    /// Sorted and deduplicated, so that `"b a a"` and `"a b"` are treated as the same scope set.
+    pub fn to_normalized(&self) -> ScopeParameter<String> {
+        let mut inner = self.0.iter().map(|x| x.to_string()).collect::<Vec<_>>();
+        inner.sort_unstable();
+        inner.dedup();
+        inner.into()
+    }
+
+    pub fn contains_all(&self, other: &ScopeParameter<T>) -> bool {
+        other.0.iter().all(|x| self.0.contains(x))
+    }
+
     pub fn try_from_t_with_string(v: &ScopeParameter<String>) -> Result<Self, ScopeFromStrError> {
         let mut inner = vec![];
         for s in v.0.iter() {
@@ -150,6 +168,7 @@ impl core::fmt::Display for ScopeFromStrError {
         write!(f, "{self:?}")
     }
 }
+#[cfg(feature = "std")]
 impl std::error::Error for ScopeFromStrError {}
 
 #[cfg(test)]
@@ -193,4 +212,16 @@ mod tests {
             Err(err) => panic!("{err}"),
         }
     }
+
+    #[test]
+    fn test_to_normalized() {
+        let scope: ScopeParameter<String> = "b a a".parse().unwrap();
+        assert_eq!(
+            scope.to_normalized(),
+            vec!["a".to_owned(), "b".to_owned()].into()
+        );
+
+        assert!(scope.contains_all(&"a".parse().unwrap()));
+        assert!(!scope.contains_all(&"a c".parse().unwrap()));
+    }
 }
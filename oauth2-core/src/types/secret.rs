@@ -0,0 +1,158 @@
+use alloc::{borrow::ToOwned, string::String};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use zeroize::Zeroize as _;
+
+pub const REDACTED: &str = "[REDACTED]";
+
+/// The value is only reachable via `expose_secret`, `Debug` and `Display` print `[REDACTED]`,
+/// and the memory is zeroed on drop.
+macro_rules! secret_string {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone, PartialEq, Eq, Hash)]
+        pub struct $name(String);
+
+        impl $name {
+            pub fn new(s: impl Into<String>) -> Self {
+                Self(s.into())
+            }
+
+            pub fn expose_secret(&self) -> &str {
+                self.0.as_str()
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                self.0.zeroize();
+            }
+        }
+
+        impl core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.debug_tuple(stringify!($name)).field(&REDACTED).finish()
+            }
+        }
+
+        impl core::fmt::Display for $name {
+            fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+                f.write_str(REDACTED)
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(s: String) -> Self {
+                Self(s)
+            }
+        }
+
+        impl From<&str> for $name {
+            fn from(s: &str) -> Self {
+                Self(s.to_owned())
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl Serialize for $name {
+            fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: Serializer,
+            {
+                serializer.serialize_str(self.0.as_str())
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+            where
+                D: Deserializer<'de>,
+            {
+                String::deserialize(deserializer).map(Self)
+            }
+        }
+    };
+}
+
+secret_string!(
+    /// https://datatracker.ietf.org/doc/html/rfc6749#section-2.3.1
+    ClientSecret
+);
+secret_string!(
+    /// https://datatracker.ietf.org/doc/html/rfc6749#section-4.1.2
+    Code
+);
+secret_string!(
+    /// https://datatracker.ietf.org/doc/html/rfc7636#section-4.1
+    CodeVerifier
+);
+secret_string!(
+    /// https://datatracker.ietf.org/doc/html/rfc6749#section-10.12
+    State
+);
+secret_string!(
+    /// https://datatracker.ietf.org/doc/html/rfc6749#section-1.4
+    AccessToken
+);
+secret_string!(
+    /// https://datatracker.ietf.org/doc/html/rfc6749#section-1.5
+    RefreshToken
+);
+secret_string!(
+    /// A PEM private key, e.g. of a service account.
+    PrivateKey
+);
+secret_string!(
+    /// https://datatracker.ietf.org/doc/html/rfc6749#section-4.3.2
+    Password
+);
+secret_string!(
+    /// https://datatracker.ietf.org/doc/html/rfc7523#section-2.1
+    Assertion
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug)]
+    struct Foo {
+        access_token: AccessToken,
+        refresh_token: Option<RefreshToken>,
+    }
+
+    #[test]
+    fn test_redacted() {
+        let access_token = AccessToken::new("ACCESS_TOKEN");
+        assert_eq!(format!("{access_token:?}"), r#"AccessToken("[REDACTED]")"#);
+        assert_eq!(format!("{access_token}"), "[REDACTED]");
+        assert_eq!(access_token.expose_secret(), "ACCESS_TOKEN");
+        assert_eq!(access_token, "ACCESS_TOKEN");
+    }
+
+    #[test]
+    fn test_ser_de() {
+        let foo = serde_json::from_str::<Foo>(
+            r#"{"access_token":"ACCESS_TOKEN","refresh_token":"REFRESH_TOKEN"}"#,
+        )
+        .unwrap();
+        assert_eq!(foo.access_token.expose_secret(), "ACCESS_TOKEN");
+        assert!(!format!("{foo:?}").contains("ACCESS_TOKEN"));
+
+        assert_eq!(
+            serde_json::to_string(&foo).unwrap(),
+            r#"{"access_token":"ACCESS_TOKEN","refresh_token":"REFRESH_TOKEN"}"#
+        );
+    }
+}
@@ -1,19 +1,32 @@
+use alloc::borrow::ToOwned;
+#[cfg(feature = "with-getrandom")]
+use alloc::string::String;
+#[cfg(feature = "with-getrandom")]
+use core::cmp::{max, min};
+
 use base64::{engine::general_purpose, Engine as _};
+use serde::{de::DeserializeOwned, Serialize};
 use sha2::{Digest as _, Sha256};
 
+#[cfg(feature = "with-getrandom")]
+use crate::types::{code_verifier::CODE_VERIFIER_CHARSET, Nonce, State};
 use crate::types::{
     code_verifier::{CODE_VERIFIER_LEN_MAX, CODE_VERIFIER_LEN_MIN},
     CodeChallenge, CodeChallengeMethod, CodeVerifier,
 };
 
+#[cfg(feature = "with-getrandom")]
+const ALPHANUMERIC_CHARSET: &[u8] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789";
+
 // Ref https://github.com/ramosbugs/oauth2-rs/blob/4.1.0/src/types.rs#L498
 pub fn gen_code_challenge(
     code_verifier: CodeVerifier,
     code_challenge_method: impl Into<Option<CodeChallengeMethod>>,
 ) -> (CodeChallenge, CodeChallengeMethod) {
     assert!(
-        code_verifier.len() >= CODE_VERIFIER_LEN_MIN
-            && code_verifier.len() <= CODE_VERIFIER_LEN_MAX
+        code_verifier.expose_secret().len() >= CODE_VERIFIER_LEN_MIN
+            && code_verifier.expose_secret().len() <= CODE_VERIFIER_LEN_MAX
     );
 
     let code_challenge_method: CodeChallengeMethod =
@@ -21,11 +34,93 @@ pub fn gen_code_challenge(
 
     let code_challenge = match code_challenge_method {
         CodeChallengeMethod::Sha256 => {
-            let digest = Sha256::digest(code_verifier.as_bytes());
+            let digest = Sha256::digest(code_verifier.expose_secret().as_bytes());
             general_purpose::URL_SAFE_NO_PAD.encode(digest)
         }
-        CodeChallengeMethod::Plain => code_verifier,
+        CodeChallengeMethod::Plain => code_verifier.expose_secret().to_owned(),
     };
 
     (code_challenge, code_challenge_method)
 }
+
+/// Converts an `_extra` between the untyped map and a typed struct by way of JSON.
+pub fn cast_extra<FROM, TO>(extra: Option<FROM>) -> Result<Option<TO>, serde_json::Error>
+where
+    FROM: Serialize,
+    TO: DeserializeOwned,
+{
+    extra
+        .map(|x| serde_json::to_value(x).and_then(serde_json::from_value))
+        .transpose()
+}
+
+//
+//
+//
+#[cfg(feature = "with-getrandom")]
+pub fn gen_state(length: impl Into<Option<usize>>) -> State {
+    gen_string(ALPHANUMERIC_CHARSET, length.into().unwrap_or(10)).into()
+}
+
+#[cfg(feature = "with-getrandom")]
+pub fn gen_nonce(length: impl Into<Option<usize>>) -> Nonce {
+    gen_string(ALPHANUMERIC_CHARSET, length.into().unwrap_or(22))
+}
+
+#[cfg(feature = "with-getrandom")]
+pub fn gen_code_verifier(length: impl Into<Option<usize>>) -> CodeVerifier {
+    let length = length.into().unwrap_or(64);
+    let length = min(CODE_VERIFIER_LEN_MAX, length);
+    let length = max(CODE_VERIFIER_LEN_MIN, length);
+
+    gen_string(CODE_VERIFIER_CHARSET, length).into()
+}
+
+/// Panics if the OS random source (`crypto.getRandomValues` on wasm32 with
+/// `with-getrandom-js`) fails, like `rand::thread_rng` does.
+#[cfg(feature = "with-getrandom")]
+fn gen_string(charset: &[u8], length: usize) -> String {
+    // Rejection sampling, so that every char is equally likely.
+    let limit = 256 - 256 % charset.len();
+
+    let mut s = String::with_capacity(length);
+    let mut buf = [0_u8; 64];
+    while s.len() < length {
+        getrandom::getrandom(&mut buf).expect("getrandom failed");
+
+        for b in buf.iter().map(|b| *b as usize).filter(|b| *b < limit) {
+            if s.len() == length {
+                break;
+            }
+            s.push(charset[b % charset.len()] as char);
+        }
+    }
+    s
+}
+
+#[cfg(all(test, feature = "with-getrandom"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gen() {
+        let state = gen_state(None);
+        assert_eq!(state.expose_secret().len(), 10);
+        assert!(state
+            .expose_secret()
+            .bytes()
+            .all(|b| ALPHANUMERIC_CHARSET.contains(&b)));
+        assert_ne!(gen_state(None), state);
+
+        assert_eq!(gen_nonce(None).len(), 22);
+
+        let code_verifier = gen_code_verifier(None);
+        assert_eq!(code_verifier.expose_secret().len(), 64);
+        assert!(code_verifier
+            .expose_secret()
+            .bytes()
+            .all(|b| CODE_VERIFIER_CHARSET.contains(&b)));
+        assert_eq!(gen_code_verifier(1).expose_secret().len(), 43);
+        assert_eq!(gen_code_verifier(200).expose_secret().len(), 128);
+    }
+}
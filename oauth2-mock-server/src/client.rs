@@ -0,0 +1,52 @@
+use std::time::Duration;
+
+use oauth2_client::re_exports::{
+    http_api_client::async_trait, Body, Client, Request, Response, RetryableClient,
+};
+
+use crate::server::MockServer;
+
+//
+//
+//
+/// Routes requests to a [`MockServer`] instead of the network, sleeps are recorded only.
+#[derive(Debug, Clone)]
+pub struct MockClient {
+    server: MockServer,
+}
+impl MockClient {
+    pub fn new(server: MockServer) -> Self {
+        Self { server }
+    }
+
+    pub fn server(&self) -> &MockServer {
+        &self.server
+    }
+}
+
+#[async_trait]
+impl Client for MockClient {
+    type RespondError = MockClientError;
+
+    async fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError> {
+        self.server
+            .handle(request)
+            .ok_or(MockClientError::RespondFailed)
+    }
+}
+
+#[async_trait]
+impl RetryableClient for MockClient {
+    async fn sleep(&self, dur: Duration) {
+        self.server.record_sleep(dur)
+    }
+}
+
+//
+//
+//
+#[derive(thiserror::Error, Debug)]
+pub enum MockClientError {
+    #[error("RespondFailed")]
+    RespondFailed,
+}
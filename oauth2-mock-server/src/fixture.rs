@@ -0,0 +1,481 @@
+//! Record real request/response pairs with secrets scrubbed, and replay them in CI.
+//!
+//! Set `OAUTH2_FIXTURES_UPDATE=1` to rewrite the recorded requests of a fixture from
+//! what the endpoints render now, the recorded responses are kept.
+
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fs,
+    path::{Path, PathBuf},
+    sync::Mutex,
+    time::Duration,
+};
+
+use oauth2_client::{
+    re_exports::{
+        http::{
+            header::{AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION, SET_COOKIE},
+            HeaderMap, HeaderName, StatusCode,
+        },
+        http_api_client::async_trait,
+        Body, Client, ClientRespondEndpointError, Endpoint, Request, Response, RetryableClient,
+        Url,
+    },
+    redaction::{redact_body, redact_json, redact_url, REDACTED_VALUE},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+pub const UPDATE_ENV: &str = "OAUTH2_FIXTURES_UPDATE";
+
+/// `tests/fixtures/$name` in the calling crate.
+#[macro_export]
+macro_rules! fixture_path {
+    ($name:literal) => {
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/", $name)
+    };
+}
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone, Default, PartialEq)]
+pub struct Fixture {
+    pub interactions: Vec<Interaction>,
+}
+impl Fixture {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, FixtureError> {
+        let path = path.as_ref();
+        let bytes = fs::read(path).map_err(|err| FixtureError::ReadFailed(path.into(), err))?;
+        serde_json::from_slice(&bytes).map_err(|err| FixtureError::DeFailed(path.into(), err))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), FixtureError> {
+        let path = path.as_ref();
+        let mut bytes = serde_json::to_vec_pretty(self).map_err(FixtureError::SerFailed)?;
+        bytes.push(b'\n');
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| FixtureError::WriteFailed(path.into(), err))?;
+        }
+        fs::write(path, bytes).map_err(|err| FixtureError::WriteFailed(path.into(), err))
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct Interaction {
+    pub request: RecordedRequest,
+    pub response: RecordedResponse,
+}
+
+//
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RecordedRequest {
+    pub method: String,
+    pub url: String,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub body: String,
+}
+impl RecordedRequest {
+    pub fn new(request: &Request<Body>) -> Self {
+        let url = request.uri().to_string();
+        Self {
+            method: request.method().to_string(),
+            url: url.parse::<Url>().map(|x| redact_url(&x)).unwrap_or(url),
+            headers: scrub_headers(request.headers()),
+            body: redact_body(request.body()),
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RecordedResponse {
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub headers: BTreeMap<String, String>,
+    /// JSON bodies are kept as JSON, anything else as a string.
+    #[serde(default)]
+    pub body: Value,
+}
+impl RecordedResponse {
+    pub fn new(response: &Response<Body>) -> Self {
+        let body = match serde_json::from_slice::<Value>(response.body()) {
+            Ok(mut value) => {
+                redact_json(&mut value);
+                value
+            }
+            Err(_) if response.body().is_empty() => Value::Null,
+            Err(_) => Value::String(String::from_utf8_lossy(response.body()).to_string()),
+        };
+
+        Self {
+            status: response.status().as_u16(),
+            headers: scrub_headers(response.headers()),
+            body,
+        }
+    }
+
+    pub fn to_response(&self) -> Result<Response<Body>, FixtureError> {
+        let body = match &self.body {
+            Value::Null => vec![],
+            Value::String(s) => s.as_bytes().to_vec(),
+            value => value.to_string().into_bytes(),
+        };
+
+        let mut builder = Response::builder().status(
+            StatusCode::from_u16(self.status)
+                .map_err(|_| FixtureError::InvalidStatus(self.status))?,
+        );
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(body).map_err(FixtureError::MakeResponseFailed)
+    }
+}
+
+/// e.g. Amazon's `x-amz-access-token`.
+fn is_secret_header(name: &HeaderName) -> bool {
+    [AUTHORIZATION, PROXY_AUTHORIZATION, COOKIE, SET_COOKIE].contains(name)
+        || ["token", "secret", "key"]
+            .iter()
+            .any(|x| name.as_str().contains(x))
+}
+
+fn scrub_headers(headers: &HeaderMap) -> BTreeMap<String, String> {
+    headers
+        .iter()
+        .map(|(name, value)| {
+            let value = String::from_utf8_lossy(value.as_bytes()).to_string();
+            let value = if is_secret_header(name) {
+                match value.split_once(' ') {
+                    Some((scheme, _)) => format!("{scheme} {REDACTED_VALUE}"),
+                    None => REDACTED_VALUE.to_owned(),
+                }
+            } else {
+                value
+            };
+            (name.to_string(), value)
+        })
+        .collect()
+}
+
+//
+//
+//
+/// Forwards to `inner` and appends every exchange to the fixture at `path`.
+#[derive(Debug)]
+pub struct RecordingClient<C> {
+    inner: C,
+    path: PathBuf,
+    fixture: Mutex<Fixture>,
+}
+impl<C> RecordingClient<C> {
+    pub fn new(inner: C, path: impl Into<PathBuf>) -> Self {
+        Self {
+            inner,
+            path: path.into(),
+            fixture: Default::default(),
+        }
+    }
+
+    pub fn fixture(&self) -> Fixture {
+        self.fixture.lock().expect("poisoned").to_owned()
+    }
+}
+
+#[async_trait]
+impl<C> Client for RecordingClient<C>
+where
+    C: Client + Send + Sync,
+{
+    type RespondError = RecordingClientError<C::RespondError>;
+
+    async fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError> {
+        let recorded_request = RecordedRequest::new(&request);
+
+        let response = self
+            .inner
+            .respond(request)
+            .await
+            .map_err(RecordingClientError::RespondFailed)?;
+
+        let fixture = {
+            let mut fixture = self.fixture.lock().expect("poisoned");
+            fixture.interactions.push(Interaction {
+                request: recorded_request,
+                response: RecordedResponse::new(&response),
+            });
+            fixture.to_owned()
+        };
+        fixture
+            .save(&self.path)
+            .map_err(RecordingClientError::FixtureError)?;
+
+        Ok(response)
+    }
+}
+
+#[async_trait]
+impl<C> RetryableClient for RecordingClient<C>
+where
+    C: RetryableClient + Send + Sync,
+{
+    async fn sleep(&self, dur: Duration) {
+        self.inner.sleep(dur).await
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RecordingClientError<E>
+where
+    E: std::error::Error + 'static,
+{
+    #[error("RespondFailed {0}")]
+    RespondFailed(E),
+    #[error("FixtureError {0}")]
+    FixtureError(FixtureError),
+}
+
+//
+//
+//
+/// Serves the recorded responses in order, after checking the request matches the
+/// recorded one.
+#[derive(Debug)]
+pub struct ReplayClient {
+    path: PathBuf,
+    fixture: Mutex<Fixture>,
+    remaining: Mutex<VecDeque<usize>>,
+}
+impl ReplayClient {
+    pub fn open(path: impl Into<PathBuf>) -> Result<Self, FixtureError> {
+        let path = path.into();
+        let fixture = Fixture::load(&path)?;
+        Ok(Self {
+            remaining: Mutex::new((0..fixture.interactions.len()).collect()),
+            fixture: Mutex::new(fixture),
+            path,
+        })
+    }
+
+    /// For tests without an async runtime.
+    #[allow(clippy::type_complexity)]
+    pub fn replay<EP>(
+        &self,
+        endpoint: &EP,
+    ) -> Result<
+        EP::ParseResponseOutput,
+        ClientRespondEndpointError<FixtureError, EP::RenderRequestError, EP::ParseResponseError>,
+    >
+    where
+        EP: Endpoint + Send + Sync,
+    {
+        futures_executor::block_on(self.respond_endpoint(endpoint))
+    }
+
+    /// For a `RetryableEndpoint`, whose `parse_response` also takes the retry state.
+    pub fn replay_request(&self, request: Request<Body>) -> Result<Response<Body>, FixtureError> {
+        futures_executor::block_on(self.respond(request))
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.remaining.lock().expect("poisoned").is_empty()
+    }
+}
+
+#[async_trait]
+impl Client for ReplayClient {
+    type RespondError = FixtureError;
+
+    async fn respond(&self, request: Request<Body>) -> Result<Response<Body>, Self::RespondError> {
+        let index = self
+            .remaining
+            .lock()
+            .expect("poisoned")
+            .pop_front()
+            .ok_or_else(|| FixtureError::Exhausted(self.path.to_owned()))?;
+
+        let actual = RecordedRequest::new(&request);
+
+        let mut fixture = self.fixture.lock().expect("poisoned");
+        let interaction = &mut fixture.interactions[index];
+        if interaction.request != actual {
+            if std::env::var_os(UPDATE_ENV).is_some() {
+                interaction.request = actual;
+                fixture.save(&self.path)?;
+            } else {
+                return Err(FixtureError::RequestMismatch {
+                    path: self.path.to_owned(),
+                    index,
+                    expected: Box::new(interaction.request.to_owned()),
+                    actual: Box::new(actual),
+                });
+            }
+        }
+
+        fixture.interactions[index].response.to_response()
+    }
+}
+
+#[async_trait]
+impl RetryableClient for ReplayClient {
+    async fn sleep(&self, _dur: Duration) {}
+}
+
+//
+//
+//
+#[derive(thiserror::Error, Debug)]
+pub enum FixtureError {
+    #[error("ReadFailed {0:?} {1}")]
+    ReadFailed(PathBuf, std::io::Error),
+    #[error("WriteFailed {0:?} {1}")]
+    WriteFailed(PathBuf, std::io::Error),
+    #[error("DeFailed {0:?} {1}")]
+    DeFailed(PathBuf, serde_json::Error),
+    #[error("SerFailed {0}")]
+    SerFailed(serde_json::Error),
+    //
+    #[error("InvalidStatus {0}")]
+    InvalidStatus(u16),
+    #[error("MakeResponseFailed {0}")]
+    MakeResponseFailed(oauth2_client::re_exports::HttpError),
+    //
+    #[error("Exhausted {0:?}")]
+    Exhausted(PathBuf),
+    #[error("RequestMismatch {path:?} #{index}, expected {expected:?}, actual {actual:?}")]
+    RequestMismatch {
+        path: PathBuf,
+        index: usize,
+        expected: Box<RecordedRequest>,
+        actual: Box<RecordedRequest>,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_client::re_exports::http::{header::CONTENT_TYPE, Method};
+
+    use crate::{MockEndpoint, MockServer};
+
+    fn token_request(server: &MockServer, client_secret: &str) -> Request<Body> {
+        Request::builder()
+            .method(Method::POST)
+            .uri(server.endpoint_url(MockEndpoint::Token).as_str())
+            .header(CONTENT_TYPE, "application/x-www-form-urlencoded")
+            .body(
+                format!("grant_type=client_credentials&client_id=mock-client-id&client_secret={client_secret}")
+                    .into_bytes(),
+            )
+            .unwrap()
+    }
+
+    #[test]
+    fn test_record_and_replay() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!(
+            "oauth2-mock-server-fixture-{}.json",
+            std::process::id()
+        ));
+
+        let server = MockServer::default();
+        let recorder = RecordingClient::new(server.client(), &path);
+        let response = futures_executor::block_on(
+            recorder.respond(token_request(&server, "mock-client-secret")),
+        )?;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let fixture = Fixture::load(&path)?;
+        assert_eq!(fixture, recorder.fixture());
+        let interaction = &fixture.interactions[0];
+        assert_eq!(
+            interaction.request.body,
+            "grant_type=client_credentials&client_id=mock-client-id&client_secret=***"
+        );
+        assert_eq!(interaction.response.body["access_token"], REDACTED_VALUE);
+        assert_eq!(interaction.response.body["token_type"], "Bearer");
+
+        let replayer = ReplayClient::open(&path)?;
+        let response =
+            futures_executor::block_on(replayer.respond(token_request(&server, "another-secret")))?;
+        assert_eq!(
+            serde_json::from_slice::<Value>(response.body())?["token_type"],
+            "Bearer"
+        );
+        assert!(replayer.is_done());
+        match futures_executor::block_on(replayer.respond(token_request(&server, "x"))) {
+            Err(FixtureError::Exhausted(_)) => {}
+            x => panic!("{x:?}"),
+        }
+
+        if std::env::var_os(UPDATE_ENV).is_none() {
+            let replayer = ReplayClient::open(&path)?;
+            let mut request = token_request(&server, "mock-client-secret");
+            *request.body_mut() = b"grant_type=password".to_vec();
+            match futures_executor::block_on(replayer.respond(request)) {
+                Err(FixtureError::RequestMismatch { index: 0, .. }) => {}
+                x => panic!("{x:?}"),
+            }
+        }
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_record_credentials() -> Result<(), Box<dyn std::error::Error>> {
+        let path = std::env::temp_dir().join(format!(
+            "oauth2-mock-server-fixture-credentials-{}.json",
+            std::process::id()
+        ));
+
+        let server = MockServer::default();
+        let recorder = RecordingClient::new(server.client(), &path);
+        for body in [
+            "grant_type=password&username=USERNAME&password=PASSWORD&client_id=mock-client-id",
+            "grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Ajwt-bearer&assertion=ASSERTION&client_assertion=CLIENT_ASSERTION",
+        ] {
+            let mut request = token_request(&server, "mock-client-secret");
+            *request.body_mut() = body.as_bytes().to_vec();
+            futures_executor::block_on(recorder.respond(request))?;
+        }
+
+        let fixture = Fixture::load(&path)?;
+        assert_eq!(
+            fixture.interactions[0].request.body,
+            "grant_type=password&username=USERNAME&password=***&client_id=mock-client-id"
+        );
+        assert_eq!(
+            fixture.interactions[1].request.body,
+            "grant_type=urn%3Aietf%3Aparams%3Aoauth%3Agrant-type%3Ajwt-bearer&assertion=***&client_assertion=***"
+        );
+        let written = fs::read_to_string(&path)?;
+        assert!(!written.contains("PASSWORD") && !written.contains("ASSERTION"));
+
+        fs::remove_file(&path)?;
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_scrub_headers() {
+        let mut headers = HeaderMap::new();
+        headers.insert(AUTHORIZATION, "Bearer TOKEN".parse().unwrap());
+        headers.insert(COOKIE, "session=S".parse().unwrap());
+        headers.insert("x-amz-access-token", "TOKEN".parse().unwrap());
+        headers.insert(CONTENT_TYPE, "application/json".parse().unwrap());
+
+        assert_eq!(
+            scrub_headers(&headers),
+            BTreeMap::from([
+                ("authorization".to_owned(), "Bearer ***".to_owned()),
+                ("content-type".to_owned(), "application/json".to_owned()),
+                ("cookie".to_owned(), "***".to_owned()),
+                ("x-amz-access-token".to_owned(), "***".to_owned()),
+            ])
+        );
+    }
+}
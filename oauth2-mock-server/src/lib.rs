@@ -0,0 +1,414 @@
+//! An in-process OAuth 2.0 / OpenID Connect authorization server, so flows can be tested
+//! end to end without the network.
+
+pub use oauth2_client;
+
+pub mod client;
+pub mod fixture;
+pub mod provider;
+pub mod server;
+
+pub use client::{MockClient, MockClientError};
+pub use fixture::{Fixture, FixtureError, RecordingClient, ReplayClient};
+pub use provider::{MockExtensionsBuilder, MockProvider, MockUserInfoEndpoint};
+pub use server::{MockEndpoint, MockResponse, MockServer, MockServerConfiguration};
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    use futures_executor::block_on;
+    use oauth2_client::{
+        authorization_code_grant::{
+            Flow as AuthorizationCodeGrantFlow, FlowBuildAuthorizationUrlConfiguration,
+            FlowHandleCallbackByQueryConfiguration, FlowHandleCallbackError,
+        },
+        client_credentials_grant::{Flow as ClientCredentialsGrantFlow, FlowExecuteError},
+        device_authorization_grant::{
+            Flow as DeviceAuthorizationGrantFlow, FlowExecuteError as DeviceFlowExecuteError,
+        },
+        jwt_authorization_grant::Flow as JwtAuthorizationGrantFlow,
+        oauth2_core::{
+            access_token_response::ErrorBodyError,
+            utils::{gen_code_challenge, gen_code_verifier, gen_nonce, gen_state},
+        },
+        resource_owner_password_credentials_grant::Flow as ResourceOwnerPasswordCredentialsGrantFlow,
+        RetryPolicy,
+    };
+    use oauth2_signin::web_app::{
+        SigninFlow, SigninFlowBuildAuthorizationUrlConfiguration,
+        SigninFlowHandleCallbackByQueryConfiguration, SigninFlowHandleCallbackRet,
+    };
+
+    fn no_jitter(x: &mut RetryPolicy) {
+        x.jitter = false;
+    }
+
+    #[test]
+    fn test_authorization_code_grant() {
+        let server = MockServer::default();
+        let provider = server.provider();
+        let flow = AuthorizationCodeGrantFlow::new(server.client());
+
+        let state = gen_state(None);
+        let code_verifier = gen_code_verifier(None);
+        let (code_challenge, code_challenge_method) =
+            gen_code_challenge(code_verifier.to_owned(), None);
+
+        let url = flow
+            .build_authorization_url(
+                &provider,
+                vec!["openid".to_owned(), "email".to_owned()],
+                FlowBuildAuthorizationUrlConfiguration::new().configure(|x| {
+                    x.set_state(state.to_owned());
+                    x.set_code_challenge(
+                        code_challenge.to_owned(),
+                        code_challenge_method.to_owned(),
+                    );
+                }),
+            )
+            .unwrap();
+        let redirect_url = server.authorize(&url).unwrap();
+        let query = redirect_url.query().unwrap();
+
+        // Wrong code_verifier
+        let code = redirect_url
+            .query_pairs()
+            .find(|(k, _)| k == "code")
+            .map(|(_, v)| v.to_string())
+            .unwrap();
+        match block_on(flow.handle_callback_by_query(
+            &provider,
+            query,
+            FlowHandleCallbackByQueryConfiguration::new().configure(|x| {
+                x.state = Some(state.to_owned());
+                x.code_verifier = Some(gen_code_verifier(None));
+            }),
+        )) {
+            Err(FlowHandleCallbackError::AccessTokenFailed(body)) => {
+                assert_eq!(body.error, ErrorBodyError::InvalidGrant)
+            }
+            x => panic!("{x:?}"),
+        }
+        // The code was consumed
+        assert!(server.request_bodies(MockEndpoint::Token)[0].contains(&code));
+
+        let redirect_url = server.authorize(&url).unwrap();
+        let body = block_on(flow.handle_callback_by_query(
+            &provider,
+            redirect_url.query().unwrap(),
+            FlowHandleCallbackByQueryConfiguration::new().configure(|x| {
+                x.state = Some(state.to_owned());
+                x.code_verifier = Some(code_verifier.to_owned());
+            }),
+        ))
+        .unwrap();
+        assert!(body.refresh_token.is_some());
+        assert!(body.id_token.is_some());
+    }
+
+    #[test]
+    fn test_authorization_code_grant_with_signin_flow() {
+        let server = MockServer::default();
+        let signin_flow = SigninFlow::new(
+            server.client(),
+            server.provider(),
+            vec!["openid".to_owned(), "profile".to_owned()],
+            MockExtensionsBuilder::new(&server),
+        );
+
+        let state = gen_state(None);
+        let nonce = gen_nonce(None);
+        let code_verifier = gen_code_verifier(None);
+        let (code_challenge, code_challenge_method) =
+            gen_code_challenge(code_verifier.to_owned(), None);
+
+        let url = signin_flow
+            .build_authorization_url(
+                SigninFlowBuildAuthorizationUrlConfiguration::new().configure(|x| {
+                    x.set_state(state.to_owned());
+                    x.set_code_challenge(
+                        code_challenge.to_owned(),
+                        code_challenge_method.to_owned(),
+                    );
+                    x.set_nonce(nonce.to_owned());
+                }),
+            )
+            .unwrap();
+        let redirect_url = server.authorize(&url).unwrap();
+
+        match block_on(signin_flow.handle_callback_by_query(
+            redirect_url.query().unwrap(),
+            SigninFlowHandleCallbackByQueryConfiguration::new().configure(|x| {
+                x.set_state(state.to_owned());
+                x.set_code_verifier(code_verifier.to_owned());
+                x.set_nonce(nonce.to_owned());
+            }),
+        )) {
+            SigninFlowHandleCallbackRet::Ok((_, user_info)) => {
+                assert_eq!(user_info.uid, "mock-user-1");
+                assert_eq!(user_info.email.as_deref(), Some("mock-user@example.com"));
+            }
+            x => panic!("{x:?}"),
+        }
+        assert_eq!(server.request_count(MockEndpoint::UserInfo), 1);
+    }
+
+    #[test]
+    fn test_client_credentials_grant() {
+        let server = MockServer::default();
+        let provider = server.provider();
+        let flow = ClientCredentialsGrantFlow::with_retry(server.client()).configure(|x| {
+            no_jitter(&mut x.retry_policy);
+        });
+
+        let body = block_on(flow.execute(&provider, vec!["read".to_owned()])).unwrap();
+        assert!(body.scope.is_some());
+
+        server.script(MockEndpoint::Token, MockResponse::status(500));
+        server.script_respond_failed(MockEndpoint::Token);
+        block_on(flow.execute(&provider, None)).unwrap();
+        assert_eq!(
+            server.sleeps(),
+            vec![Duration::from_millis(500), Duration::from_secs(1)]
+        );
+
+        server.script(
+            MockEndpoint::Token,
+            MockResponse::error(400, "invalid_scope"),
+        );
+        match block_on(flow.execute(&provider, None)) {
+            Err(FlowExecuteError::AccessTokenFailed(body)) => {
+                assert_eq!(body.error, ErrorBodyError::InvalidScope)
+            }
+            x => panic!("{x:?}"),
+        }
+
+        let server = MockServer::new(
+            MockServerConfiguration::new().configure(|x| x.client_secret = Some("x".to_owned())),
+        );
+        let provider = MockProvider::new(&MockServer::default());
+        match block_on(ClientCredentialsGrantFlow::new(server.client()).execute(&provider, None)) {
+            Err(FlowExecuteError::AccessTokenFailed(body)) => {
+                assert_eq!(body.error, ErrorBodyError::InvalidClient)
+            }
+            x => panic!("{x:?}"),
+        }
+    }
+
+    #[test]
+    fn test_resource_owner_password_credentials_grant() {
+        let server = MockServer::default();
+        let provider = server.provider();
+        let flow = ResourceOwnerPasswordCredentialsGrantFlow::new(server.client());
+
+        block_on(flow.execute(&provider, None, "user", "password")).unwrap();
+
+        match block_on(flow.execute(&provider, None, "user", "wrong")) {
+            Err(err) => assert!(err.to_string().contains("InvalidGrant")),
+            x => panic!("{x:?}"),
+        }
+    }
+
+    #[test]
+    fn test_jwt_authorization_grant() {
+        let server = MockServer::default();
+        let provider = server.provider();
+        let flow = JwtAuthorizationGrantFlow::new(server.client());
+
+        block_on(flow.execute(&provider, None)).unwrap();
+        assert!(server.request_bodies(MockEndpoint::Token)[0]
+            .contains(&format!("assertion={}", provider::ASSERTION)));
+    }
+
+    #[test]
+    fn test_jwt_authorization_grant_with_retry() {
+        use std::sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        };
+
+        use oauth2_client::{
+            re_exports::{ClientId, ClientSecret, Url},
+            Provider, ProviderExtJwtAuthorizationGrant,
+        };
+
+        #[derive(Debug, Clone)]
+        struct JtiProvider {
+            inner: MockProvider,
+            count: Arc<AtomicUsize>,
+        }
+        impl Provider for JtiProvider {
+            type Scope = String;
+
+            fn client_id(&self) -> Option<&ClientId> {
+                self.inner.client_id()
+            }
+
+            fn client_secret(&self) -> Option<&ClientSecret> {
+                self.inner.client_secret()
+            }
+
+            fn token_endpoint_url(&self) -> &Url {
+                self.inner.token_endpoint_url()
+            }
+        }
+        impl ProviderExtJwtAuthorizationGrant for JtiProvider {
+            fn assertion(
+                &self,
+            ) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+                Ok(format!(
+                    "{}-{}",
+                    provider::ASSERTION,
+                    self.count.fetch_add(1, Ordering::SeqCst)
+                ))
+            }
+        }
+
+        let server = MockServer::default();
+        let provider = JtiProvider {
+            inner: server.provider(),
+            count: Default::default(),
+        };
+        let flow = JwtAuthorizationGrantFlow::with_retry(server.client()).configure(|x| {
+            no_jitter(&mut x.retry_policy);
+        });
+
+        server.script(MockEndpoint::Token, MockResponse::status(500));
+        block_on(flow.execute(&provider, None)).unwrap();
+
+        let bodies = server.request_bodies(MockEndpoint::Token);
+        assert_eq!(bodies.len(), 2);
+        assert_ne!(bodies[0], bodies[1]);
+        assert_eq!(server.sleeps(), vec![Duration::from_millis(500)]);
+    }
+
+    #[test]
+    fn test_jwt_authorization_grant_with_cache() {
+        use oauth2_client::{
+            re_exports::{ClientId, ClientSecret, Url},
+            Provider, ProviderExtJwtAuthorizationGrant, TokenCache,
+        };
+
+        #[derive(Debug, Clone)]
+        struct SubProvider {
+            inner: MockProvider,
+            sub: &'static str,
+        }
+        impl Provider for SubProvider {
+            type Scope = String;
+
+            fn client_id(&self) -> Option<&ClientId> {
+                self.inner.client_id()
+            }
+
+            fn client_secret(&self) -> Option<&ClientSecret> {
+                self.inner.client_secret()
+            }
+
+            fn token_endpoint_url(&self) -> &Url {
+                self.inner.token_endpoint_url()
+            }
+
+            fn principal(&self) -> Option<String> {
+                Some(format!("client#{}", self.sub))
+            }
+        }
+        impl ProviderExtJwtAuthorizationGrant for SubProvider {
+            fn assertion(
+                &self,
+            ) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+                self.inner.assertion()
+            }
+        }
+
+        let server = MockServer::default();
+        let flow = JwtAuthorizationGrantFlow::new(server.client());
+        let token_cache = TokenCache::new();
+        let provider_1 = SubProvider {
+            inner: server.provider(),
+            sub: "user_1@example.com",
+        };
+        let provider_2 = SubProvider {
+            sub: "user_2@example.com",
+            ..provider_1.to_owned()
+        };
+
+        let body_1 = block_on(flow.execute_with_cache(&provider_1, None, &token_cache)).unwrap();
+        let body_2 = block_on(flow.execute_with_cache(&provider_2, None, &token_cache)).unwrap();
+        assert_ne!(body_1.access_token, body_2.access_token);
+        assert_eq!(server.request_count(MockEndpoint::Token), 2);
+
+        let body = block_on(flow.execute_with_cache(&provider_1, None, &token_cache)).unwrap();
+        assert_eq!(body.access_token, body_1.access_token);
+        assert_eq!(server.request_count(MockEndpoint::Token), 2);
+
+        // Without a principal
+        let provider = server.provider();
+        let body = block_on(flow.execute_with_cache(&provider, None, &token_cache)).unwrap();
+        assert_ne!(
+            block_on(flow.execute_with_cache(&provider, None, &token_cache))
+                .unwrap()
+                .access_token,
+            body.access_token
+        );
+        assert_eq!(server.request_count(MockEndpoint::Token), 4);
+    }
+
+    #[test]
+    fn test_device_authorization_grant() {
+        let server = MockServer::default();
+        let provider = server.provider();
+        let flow = DeviceAuthorizationGrantFlow::new(server.client(), server.client());
+
+        server.script(
+            MockEndpoint::Token,
+            MockResponse::error(400, "authorization_pending"),
+        );
+        server.script(MockEndpoint::Token, MockResponse::error(400, "slow_down"));
+
+        let approver = server.clone();
+        let body = block_on(flow.execute(
+            &provider,
+            vec!["openid".to_owned()],
+            move |user_code, _, _| {
+                assert!(approver.approve_device(&user_code));
+            },
+        ))
+        .unwrap();
+        assert!(body.id_token.is_some());
+        assert_eq!(server.request_count(MockEndpoint::Token), 3);
+        assert_eq!(
+            server.sleeps(),
+            vec![Duration::from_secs(5), Duration::from_secs(10)]
+        );
+
+        // Not approved, then the device code is unknown
+        server.script(
+            MockEndpoint::Token,
+            MockResponse::error(400, "authorization_pending"),
+        );
+        server.script(
+            MockEndpoint::Token,
+            MockResponse::error(400, "invalid_grant"),
+        );
+        match block_on(flow.execute(&provider, None, |_, _, _| {})) {
+            Err(DeviceFlowExecuteError::DeviceAccessTokenFailed(body)) => {
+                assert_eq!(body.error, ErrorBodyError::InvalidGrant)
+            }
+            x => panic!("{x:?}"),
+        }
+
+        let denier = server.clone();
+        match block_on(flow.execute(&provider, None, move |user_code, _, _| {
+            assert!(denier.deny_device(&user_code));
+        })) {
+            Err(DeviceFlowExecuteError::DeviceAccessTokenFailed(body)) => {
+                assert_eq!(body.error, ErrorBodyError::AccessDenied)
+            }
+            x => panic!("{x:?}"),
+        }
+    }
+}
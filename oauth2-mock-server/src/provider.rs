@@ -0,0 +1,188 @@
+use oauth2_client::{
+    authorization_code_grant::provider_ext::{
+        ProviderExtAuthorizationCodeGrantOidcSupportType,
+        ProviderExtAuthorizationCodeGrantPkceSupportType,
+    },
+    extensions::{
+        AccessTokenResponseSuccessfulBody, Builder, BuilderObtainUserInfoError,
+        BuilderObtainUserInfoOutput, EndpointParseResponseError, EndpointRenderRequestError,
+        GrantInfo, UserInfo,
+    },
+    re_exports::{
+        http::header::{ACCEPT, AUTHORIZATION},
+        serde_json, AccessToken, Body, ClientId, ClientSecret, Endpoint, Map, RedirectUri, Request,
+        Response, Scope, Url, Value, MIME_APPLICATION_JSON,
+    },
+    Provider, ProviderExtAuthorizationCodeGrant, ProviderExtClientCredentialsGrant,
+    ProviderExtDeviceAuthorizationGrant, ProviderExtJwtAuthorizationGrant,
+    ProviderExtResourceOwnerPasswordCredentialsGrant,
+};
+
+use crate::server::{MockEndpoint, MockServer};
+
+pub const ASSERTION: &str = "mock-assertion";
+
+//
+//
+//
+/// Supports every grant, all endpoints point at the [`MockServer`] it was made from.
+#[derive(Debug, Clone)]
+pub struct MockProvider {
+    client_id: ClientId,
+    client_secret: Option<ClientSecret>,
+    redirect_uri: RedirectUri,
+    //
+    token_endpoint_url: Url,
+    authorization_endpoint_url: Url,
+    device_authorization_endpoint_url: Url,
+}
+impl MockProvider {
+    pub fn new(server: &MockServer) -> Self {
+        let config = server.config();
+
+        Self {
+            client_id: config.client_id.to_owned(),
+            client_secret: config.client_secret.to_owned().map(Into::into),
+            redirect_uri: config.redirect_uri.parse().expect("invalid redirect_uri"),
+            token_endpoint_url: server.endpoint_url(MockEndpoint::Token),
+            authorization_endpoint_url: server.endpoint_url(MockEndpoint::Authorize),
+            device_authorization_endpoint_url: server
+                .endpoint_url(MockEndpoint::DeviceAuthorization),
+        }
+    }
+}
+impl Provider for MockProvider {
+    type Scope = String;
+
+    fn client_id(&self) -> Option<&ClientId> {
+        Some(&self.client_id)
+    }
+
+    fn client_secret(&self) -> Option<&ClientSecret> {
+        self.client_secret.as_ref()
+    }
+
+    fn token_endpoint_url(&self) -> &Url {
+        &self.token_endpoint_url
+    }
+
+    fn name(&self) -> &str {
+        "mock"
+    }
+}
+impl ProviderExtAuthorizationCodeGrant for MockProvider {
+    fn redirect_uri(&self) -> Option<&RedirectUri> {
+        Some(&self.redirect_uri)
+    }
+
+    fn oidc_support_type(&self) -> Option<ProviderExtAuthorizationCodeGrantOidcSupportType> {
+        Some(ProviderExtAuthorizationCodeGrantOidcSupportType::Yes)
+    }
+
+    fn pkce_support_type(&self) -> Option<ProviderExtAuthorizationCodeGrantPkceSupportType> {
+        Some(ProviderExtAuthorizationCodeGrantPkceSupportType::Yes)
+    }
+
+    fn authorization_endpoint_url(&self) -> &Url {
+        &self.authorization_endpoint_url
+    }
+}
+impl ProviderExtClientCredentialsGrant for MockProvider {}
+impl ProviderExtResourceOwnerPasswordCredentialsGrant for MockProvider {}
+impl ProviderExtJwtAuthorizationGrant for MockProvider {
+    fn assertion(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        Ok(ASSERTION.to_owned())
+    }
+}
+impl ProviderExtDeviceAuthorizationGrant for MockProvider {
+    fn device_authorization_endpoint_url(&self) -> &Url {
+        &self.device_authorization_endpoint_url
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct MockExtensionsBuilder {
+    user_info_endpoint_url: Url,
+}
+impl MockExtensionsBuilder {
+    pub fn new(server: &MockServer) -> Self {
+        Self {
+            user_info_endpoint_url: server.endpoint_url(MockEndpoint::UserInfo),
+        }
+    }
+}
+
+impl<SCOPE> Builder<SCOPE> for MockExtensionsBuilder
+where
+    SCOPE: Scope,
+{
+    fn obtain_user_info(
+        &self,
+        _grant_info: GrantInfo<SCOPE>,
+        access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
+    ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
+        Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
+            MockUserInfoEndpoint {
+                url: self.user_info_endpoint_url.to_owned(),
+                access_token: access_token.access_token.to_owned(),
+            },
+        )))
+    }
+}
+
+//
+#[derive(Debug, Clone)]
+pub struct MockUserInfoEndpoint {
+    url: Url,
+    access_token: AccessToken,
+}
+
+impl Endpoint for MockUserInfoEndpoint {
+    type RenderRequestError = EndpointRenderRequestError;
+
+    type ParseResponseOutput = UserInfo;
+    type ParseResponseError = EndpointParseResponseError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let request = Request::builder()
+            .uri(self.url.as_str())
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EndpointRenderRequestError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        if !response.status().is_success() {
+            return Err(EndpointParseResponseError::ToOutputFailed(
+                format!("status {}", response.status()).into(),
+            ));
+        }
+
+        let raw = serde_json::from_slice::<Map<String, Value>>(response.body())
+            .map_err(EndpointParseResponseError::DeResponseBodyFailed)?;
+
+        let field = |key: &str| raw.get(key).and_then(|x| x.as_str()).map(ToOwned::to_owned);
+
+        Ok(UserInfo {
+            uid: field("sub")
+                .ok_or_else(|| EndpointParseResponseError::ToOutputFailed("sub missing".into()))?,
+            name: field("name"),
+            email: field("email"),
+            raw,
+            ..Default::default()
+        }
+        .with_standard_claims_from_raw())
+    }
+}
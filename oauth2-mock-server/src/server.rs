@@ -0,0 +1,877 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use base64::{engine::general_purpose, Engine as _};
+use oauth2_client::{
+    oauth2_core::{
+        types::{
+            code_verifier::{CODE_VERIFIER_LEN_MAX, CODE_VERIFIER_LEN_MIN},
+            CodeChallengeMethod,
+        },
+        utils::gen_code_challenge,
+    },
+    re_exports::{
+        http::{
+            header::{AUTHORIZATION, CONTENT_TYPE, LOCATION},
+            Method, StatusCode,
+        },
+        serde_json::{json, Map, Value},
+        serde_urlencoded, Body, Request, Response, Url, MIME_APPLICATION_JSON,
+    },
+};
+
+use crate::{client::MockClient, provider::MockProvider};
+
+pub const BASE_URL: &str = "https://oauth2-mock-server.test";
+
+pub const DEVICE_CODE_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:device_code";
+pub const JWT_BEARER_GRANT_TYPE: &str = "urn:ietf:params:oauth:grant-type:jwt-bearer";
+
+//
+//
+//
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MockEndpoint {
+    Authorize,
+    Token,
+    DeviceAuthorization,
+    UserInfo,
+    Jwks,
+    Revoke,
+    Introspect,
+}
+impl MockEndpoint {
+    pub const ALL: [Self; 7] = [
+        Self::Authorize,
+        Self::Token,
+        Self::DeviceAuthorization,
+        Self::UserInfo,
+        Self::Jwks,
+        Self::Revoke,
+        Self::Introspect,
+    ];
+
+    pub fn path(&self) -> &'static str {
+        match self {
+            Self::Authorize => "/authorize",
+            Self::Token => "/token",
+            Self::DeviceAuthorization => "/device_authorization",
+            Self::UserInfo => "/userinfo",
+            Self::Jwks => "/jwks",
+            Self::Revoke => "/revoke",
+            Self::Introspect => "/introspect",
+        }
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct MockResponse {
+    pub status: StatusCode,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+impl MockResponse {
+    pub fn json(status: u16, body: Value) -> Self {
+        Self {
+            status: StatusCode::from_u16(status).expect("invalid status"),
+            headers: vec![(CONTENT_TYPE.to_string(), MIME_APPLICATION_JSON.to_owned())],
+            body: body.to_string().into_bytes(),
+        }
+    }
+
+    /// e.g. `authorization_pending`, `slow_down`, `invalid_grant`
+    pub fn error(status: u16, error: &str) -> Self {
+        Self::json(status, json!({ "error": error }))
+    }
+
+    pub fn status(status: u16) -> Self {
+        Self {
+            status: StatusCode::from_u16(status).expect("invalid status"),
+            headers: vec![],
+            body: vec![],
+        }
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_owned(), value.to_owned()));
+        self
+    }
+
+    fn into_response(self) -> Response<Body> {
+        let mut builder = Response::builder().status(self.status);
+        for (name, value) in self.headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(self.body).expect("unreachable")
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Scripted {
+    Response(MockResponse),
+    RespondFailed,
+}
+
+//
+//
+//
+#[derive(Debug, Clone)]
+pub struct MockServerConfiguration {
+    pub base_url: Url,
+    pub client_id: String,
+    /// `None` for a public client.
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+    /// username -> password
+    pub users: HashMap<String, String>,
+    pub user_info: Map<String, Value>,
+    pub jwks: Value,
+    pub device_code_interval: u64,
+    pub device_code_expires_in: u64,
+    pub access_token_expires_in: u64,
+}
+impl Default for MockServerConfiguration {
+    fn default() -> Self {
+        Self {
+            base_url: BASE_URL.parse().expect("unreachable"),
+            client_id: "mock-client-id".to_owned(),
+            client_secret: Some("mock-client-secret".to_owned()),
+            redirect_uri: "https://client.example.com/auth/callback".to_owned(),
+            users: HashMap::from([("user".to_owned(), "password".to_owned())]),
+            user_info: json!({
+                "sub": "mock-user-1",
+                "name": "Mock User",
+                "email": "mock-user@example.com",
+                "email_verified": true,
+            })
+            .as_object()
+            .cloned()
+            .expect("unreachable"),
+            jwks: json!({ "keys": [] }),
+            device_code_interval: 5,
+            device_code_expires_in: 1800,
+            access_token_expires_in: 3600,
+        }
+    }
+}
+impl MockServerConfiguration {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+}
+
+//
+//
+//
+#[derive(Debug, Default)]
+struct State {
+    seq: usize,
+    scripted: HashMap<MockEndpoint, VecDeque<Scripted>>,
+    requests: Vec<(MockEndpoint, Request<Body>)>,
+    sleeps: Vec<Duration>,
+    //
+    codes: HashMap<String, IssuedCode>,
+    devices: HashMap<String, IssuedDevice>,
+    access_tokens: HashMap<String, IssuedToken>,
+    refresh_tokens: HashMap<String, IssuedToken>,
+}
+
+#[derive(Debug, Clone)]
+struct IssuedCode {
+    redirect_uri: Option<String>,
+    scope: Option<String>,
+    nonce: Option<String>,
+    code_challenge: Option<(String, CodeChallengeMethod)>,
+}
+
+#[derive(Debug, Clone)]
+struct IssuedDevice {
+    user_code: String,
+    scope: Option<String>,
+    approved: Option<bool>,
+}
+
+#[derive(Debug, Clone)]
+struct IssuedToken {
+    scope: Option<String>,
+    expires_at: SystemTime,
+}
+
+//
+//
+//
+/// In-process authorization server, cheap to clone, clones share state.
+#[derive(Debug, Clone)]
+pub struct MockServer {
+    config: Arc<MockServerConfiguration>,
+    state: Arc<Mutex<State>>,
+}
+impl Default for MockServer {
+    fn default() -> Self {
+        Self::new(MockServerConfiguration::default())
+    }
+}
+impl MockServer {
+    pub fn new(config: MockServerConfiguration) -> Self {
+        Self {
+            config: Arc::new(config),
+            state: Default::default(),
+        }
+    }
+
+    pub fn config(&self) -> &MockServerConfiguration {
+        &self.config
+    }
+
+    pub fn endpoint_url(&self, endpoint: MockEndpoint) -> Url {
+        self.config
+            .base_url
+            .join(endpoint.path())
+            .expect("invalid base_url")
+    }
+
+    pub fn client(&self) -> MockClient {
+        MockClient::new(self.clone())
+    }
+
+    pub fn provider(&self) -> MockProvider {
+        MockProvider::new(self)
+    }
+
+    //
+    /// The next request to `endpoint` gets `response` instead of the default handling.
+    pub fn script(&self, endpoint: MockEndpoint, response: MockResponse) {
+        self.lock()
+            .scripted
+            .entry(endpoint)
+            .or_default()
+            .push_back(Scripted::Response(response));
+    }
+
+    /// The next request to `endpoint` fails like a connection reset.
+    pub fn script_respond_failed(&self, endpoint: MockEndpoint) {
+        self.lock()
+            .scripted
+            .entry(endpoint)
+            .or_default()
+            .push_back(Scripted::RespondFailed);
+    }
+
+    pub fn request_count(&self, endpoint: MockEndpoint) -> usize {
+        self.lock()
+            .requests
+            .iter()
+            .filter(|(x, _)| *x == endpoint)
+            .count()
+    }
+
+    /// Bodies of the requests to `endpoint`, oldest first.
+    pub fn request_bodies(&self, endpoint: MockEndpoint) -> Vec<String> {
+        self.lock()
+            .requests
+            .iter()
+            .filter(|(x, _)| *x == endpoint)
+            .map(|(_, req)| String::from_utf8_lossy(req.body()).to_string())
+            .collect()
+    }
+
+    /// What `MockClient` was asked to sleep, e.g. device polls and retries.
+    pub fn sleeps(&self) -> Vec<Duration> {
+        self.lock().sleeps.to_owned()
+    }
+
+    pub(crate) fn record_sleep(&self, dur: Duration) {
+        self.lock().sleeps.push(dur);
+    }
+
+    //
+    /// Acts as the user consenting in the browser, returns the redirect with `code` and `state`.
+    pub fn authorize(&self, authorization_url: &Url) -> Result<Url, MockResponse> {
+        let request = Request::builder()
+            .method(Method::GET)
+            .uri(authorization_url.as_str())
+            .body(vec![])
+            .expect("unreachable");
+
+        let response = self
+            .handle(request)
+            .ok_or_else(|| MockResponse::status(500))?;
+        match response.headers().get(LOCATION) {
+            Some(location) if response.status() == StatusCode::FOUND => location
+                .to_str()
+                .ok()
+                .and_then(|x| x.parse().ok())
+                .ok_or_else(|| MockResponse::status(500)),
+            _ => Err(MockResponse {
+                status: response.status(),
+                headers: vec![],
+                body: response.into_body(),
+            }),
+        }
+    }
+
+    /// Acts as the user entering `user_code` on the verification page.
+    pub fn approve_device(&self, user_code: &str) -> bool {
+        self.decide_device(user_code, true)
+    }
+
+    pub fn deny_device(&self, user_code: &str) -> bool {
+        self.decide_device(user_code, false)
+    }
+
+    fn decide_device(&self, user_code: &str, approved: bool) -> bool {
+        let mut state = self.lock();
+        match state
+            .devices
+            .values_mut()
+            .find(|x| x.user_code == user_code)
+        {
+            Some(device) => {
+                device.approved = Some(approved);
+                true
+            }
+            None => false,
+        }
+    }
+
+    //
+    /// `None` means the scripted respond failure.
+    pub fn handle(&self, request: Request<Body>) -> Option<Response<Body>> {
+        let endpoint = MockEndpoint::ALL.into_iter().find(|x| {
+            self.endpoint_url(*x).path() == request.uri().path()
+                && request.uri().host() == self.config.base_url.host_str()
+        });
+        let endpoint = match endpoint {
+            Some(x) => x,
+            None => return Some(MockResponse::status(404).into_response()),
+        };
+
+        let scripted = {
+            let mut state = self.lock();
+            state.requests.push((endpoint, clone_request(&request)));
+            state
+                .scripted
+                .get_mut(&endpoint)
+                .and_then(|x| x.pop_front())
+        };
+        match scripted {
+            Some(Scripted::Response(response)) => return Some(response.into_response()),
+            Some(Scripted::RespondFailed) => return None,
+            None => {}
+        }
+
+        let response = match endpoint {
+            MockEndpoint::Authorize => self.handle_authorize(&request),
+            MockEndpoint::Token => self.handle_token(&request),
+            MockEndpoint::DeviceAuthorization => self.handle_device_authorization(&request),
+            MockEndpoint::UserInfo => self.handle_user_info(&request),
+            MockEndpoint::Jwks => MockResponse::json(200, self.config.jwks.to_owned()),
+            MockEndpoint::Revoke => self.handle_revoke(&request),
+            MockEndpoint::Introspect => self.handle_introspect(&request),
+        };
+        Some(response.into_response())
+    }
+
+    fn handle_authorize(&self, request: &Request<Body>) -> MockResponse {
+        let query = parse_form(request.uri().query().unwrap_or_default().as_bytes());
+
+        if query.get("client_id") != Some(&self.config.client_id) {
+            return MockResponse::error(400, "unauthorized_client");
+        }
+        let redirect_uri = match query.get("redirect_uri") {
+            Some(x) if x != &self.config.redirect_uri => {
+                return MockResponse::error(400, "invalid_request")
+            }
+            Some(x) => x.to_owned(),
+            None => self.config.redirect_uri.to_owned(),
+        };
+        let mut redirect_url: Url = match redirect_uri.parse() {
+            Ok(x) => x,
+            Err(_) => return MockResponse::error(400, "invalid_request"),
+        };
+
+        {
+            let mut pairs = redirect_url.query_pairs_mut();
+            if query.get("response_type").map(|x| x.as_str()) == Some("code") {
+                let code_challenge = query.get("code_challenge").map(|x| {
+                    let method = match query.get("code_challenge_method").map(|x| x.as_str()) {
+                        Some("S256") => CodeChallengeMethod::Sha256,
+                        _ => CodeChallengeMethod::Plain,
+                    };
+                    (x.to_owned(), method)
+                });
+
+                let code = self.gen("code");
+                self.lock().codes.insert(
+                    code.to_owned(),
+                    IssuedCode {
+                        redirect_uri: query.get("redirect_uri").cloned(),
+                        scope: query.get("scope").cloned(),
+                        nonce: query.get("nonce").cloned(),
+                        code_challenge,
+                    },
+                );
+                pairs.append_pair("code", &code);
+            } else {
+                pairs.append_pair("error", "unsupported_response_type");
+            }
+            if let Some(state) = query.get("state") {
+                pairs.append_pair("state", state);
+            }
+        }
+
+        MockResponse::status(302).with_header(LOCATION.as_str(), redirect_url.as_str())
+    }
+
+    fn handle_token(&self, request: &Request<Body>) -> MockResponse {
+        let form = parse_form(request.body());
+
+        if let Err(response) = self.authenticate_client(request, &form) {
+            return response;
+        }
+
+        match form.get("grant_type").map(|x| x.as_str()) {
+            Some("authorization_code") => {
+                let issued = match form
+                    .get("code")
+                    .and_then(|code| self.lock().codes.remove(code))
+                {
+                    Some(x) => x,
+                    None => return MockResponse::error(400, "invalid_grant"),
+                };
+                if issued.redirect_uri.is_some()
+                    && issued.redirect_uri != form.get("redirect_uri").cloned()
+                {
+                    return MockResponse::error(400, "invalid_grant");
+                }
+                if let Some((code_challenge, method)) = issued.code_challenge {
+                    let matched = form
+                        .get("code_verifier")
+                        .filter(|x| {
+                            (CODE_VERIFIER_LEN_MIN..=CODE_VERIFIER_LEN_MAX).contains(&x.len())
+                        })
+                        .map(|code_verifier| {
+                            gen_code_challenge(code_verifier.to_owned().into(), method).0
+                                == code_challenge
+                        });
+                    if matched != Some(true) {
+                        return MockResponse::error(400, "invalid_grant");
+                    }
+                }
+                self.issue_token(issued.scope, issued.nonce)
+            }
+            Some("refresh_token") => {
+                match form
+                    .get("refresh_token")
+                    .and_then(|x| self.lock().refresh_tokens.remove(x))
+                {
+                    Some(issued) => self.issue_token(issued.scope, None),
+                    None => MockResponse::error(400, "invalid_grant"),
+                }
+            }
+            Some("client_credentials") => self.issue_token(form.get("scope").cloned(), None),
+            Some("password") => {
+                let username = form.get("username").cloned().unwrap_or_default();
+                if self.config.users.get(&username) != form.get("password") {
+                    return MockResponse::error(400, "invalid_grant");
+                }
+                self.issue_token(form.get("scope").cloned(), None)
+            }
+            Some(DEVICE_CODE_GRANT_TYPE) => {
+                let device_code = form.get("device_code").cloned().unwrap_or_default();
+                let device = match self.lock().devices.get(&device_code).cloned() {
+                    Some(x) => x,
+                    None => return MockResponse::error(400, "invalid_grant"),
+                };
+                match device.approved {
+                    None => MockResponse::error(400, "authorization_pending"),
+                    Some(false) => {
+                        self.lock().devices.remove(&device_code);
+                        MockResponse::error(400, "access_denied")
+                    }
+                    Some(true) => {
+                        self.lock().devices.remove(&device_code);
+                        self.issue_token(device.scope, None)
+                    }
+                }
+            }
+            Some(JWT_BEARER_GRANT_TYPE) => match form.get("assertion") {
+                Some(x) if !x.is_empty() => self.issue_token(form.get("scope").cloned(), None),
+                _ => MockResponse::error(400, "invalid_grant"),
+            },
+            _ => MockResponse::error(400, "unsupported_grant_type"),
+        }
+    }
+
+    fn handle_device_authorization(&self, request: &Request<Body>) -> MockResponse {
+        let form = parse_form(request.body());
+
+        if form.get("client_id") != Some(&self.config.client_id) {
+            return MockResponse::error(400, "invalid_client");
+        }
+
+        let device_code = self.gen("device-code");
+        let user_code = self.gen("USER").to_uppercase();
+        self.lock().devices.insert(
+            device_code.to_owned(),
+            IssuedDevice {
+                user_code: user_code.to_owned(),
+                scope: form.get("scope").cloned(),
+                approved: None,
+            },
+        );
+
+        let verification_uri = self.config.base_url.join("/device").expect("unreachable");
+        MockResponse::json(
+            200,
+            json!({
+                "device_code": device_code,
+                "user_code": user_code,
+                "verification_uri": verification_uri.as_str(),
+                "verification_uri_complete": format!("{verification_uri}?user_code={user_code}"),
+                "expires_in": self.config.device_code_expires_in,
+                "interval": self.config.device_code_interval,
+            }),
+        )
+    }
+
+    fn handle_user_info(&self, request: &Request<Body>) -> MockResponse {
+        let access_token = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.strip_prefix("Bearer "));
+
+        match access_token.and_then(|x| self.active_access_token(x)) {
+            Some(_) => MockResponse::json(200, Value::Object(self.config.user_info.to_owned())),
+            None => MockResponse::error(401, "invalid_token"),
+        }
+    }
+
+    fn handle_revoke(&self, request: &Request<Body>) -> MockResponse {
+        let form = parse_form(request.body());
+
+        if let Err(response) = self.authenticate_client(request, &form) {
+            return response;
+        }
+
+        if let Some(token) = form.get("token") {
+            let mut state = self.lock();
+            state.access_tokens.remove(token);
+            state.refresh_tokens.remove(token);
+        }
+        MockResponse::status(200)
+    }
+
+    fn handle_introspect(&self, request: &Request<Body>) -> MockResponse {
+        let form = parse_form(request.body());
+
+        if let Err(response) = self.authenticate_client(request, &form) {
+            return response;
+        }
+
+        match form.get("token").and_then(|x| self.active_access_token(x)) {
+            Some(issued) => MockResponse::json(
+                200,
+                json!({
+                    "active": true,
+                    "client_id": self.config.client_id,
+                    "scope": issued.scope,
+                    "sub": self.config.user_info.get("sub"),
+                    "exp": unix_timestamp(issued.expires_at),
+                }),
+            ),
+            None => MockResponse::json(200, json!({ "active": false })),
+        }
+    }
+
+    //
+    fn authenticate_client(
+        &self,
+        request: &Request<Body>,
+        form: &HashMap<String, String>,
+    ) -> Result<(), MockResponse> {
+        let basic = request
+            .headers()
+            .get(AUTHORIZATION)
+            .and_then(|x| x.to_str().ok())
+            .and_then(|x| x.strip_prefix("Basic "))
+            .and_then(|x| general_purpose::STANDARD.decode(x).ok())
+            .and_then(|x| String::from_utf8(x).ok())
+            .and_then(|x| {
+                x.split_once(':')
+                    .map(|(id, secret)| (id.to_owned(), Some(secret.to_owned())))
+            });
+
+        let (client_id, client_secret) = match basic {
+            Some(x) => x,
+            None => (
+                form.get("client_id").cloned().unwrap_or_default(),
+                form.get("client_secret").cloned(),
+            ),
+        };
+
+        if client_id != self.config.client_id {
+            return Err(MockResponse::error(401, "invalid_client"));
+        }
+        if let Some(client_secret) = client_secret {
+            if Some(client_secret) != self.config.client_secret {
+                return Err(MockResponse::error(401, "invalid_client"));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn issue_token(&self, scope: Option<String>, nonce: Option<String>) -> MockResponse {
+        let access_token = self.gen("access-token");
+        let refresh_token = self.gen("refresh-token");
+        let expires_at =
+            SystemTime::now() + Duration::from_secs(self.config.access_token_expires_in);
+        let issued = IssuedToken {
+            scope: scope.to_owned(),
+            expires_at,
+        };
+
+        {
+            let mut state = self.lock();
+            state
+                .access_tokens
+                .insert(access_token.to_owned(), issued.to_owned());
+            state
+                .refresh_tokens
+                .insert(refresh_token.to_owned(), issued);
+        }
+
+        let mut body = json!({
+            "access_token": access_token,
+            "token_type": "Bearer",
+            "expires_in": self.config.access_token_expires_in,
+            "refresh_token": refresh_token,
+        });
+        if let Some(scope) = &scope {
+            body["scope"] = scope.to_owned().into();
+
+            if scope.split(' ').any(|x| x == "openid") {
+                body["id_token"] = self.id_token(nonce).into();
+            }
+        }
+
+        MockResponse::json(200, body)
+    }
+
+    /// Unsigned (`alg` `none`), publish keys with `jwks` if the client verifies signatures.
+    fn id_token(&self, nonce: Option<String>) -> String {
+        let now = unix_timestamp(SystemTime::now());
+        let mut claims = self.config.user_info.to_owned();
+        claims.insert("iss".to_owned(), self.config.base_url.as_str().into());
+        claims.insert("aud".to_owned(), self.config.client_id.to_owned().into());
+        claims.insert("iat".to_owned(), now.into());
+        claims.insert(
+            "exp".to_owned(),
+            (now + self.config.access_token_expires_in).into(),
+        );
+        if let Some(nonce) = nonce {
+            claims.insert("nonce".to_owned(), nonce.into());
+        }
+
+        format!(
+            "{}.{}.",
+            general_purpose::URL_SAFE_NO_PAD.encode(r#"{"alg":"none","typ":"JWT"}"#),
+            general_purpose::URL_SAFE_NO_PAD.encode(Value::Object(claims).to_string())
+        )
+    }
+
+    fn active_access_token(&self, access_token: &str) -> Option<IssuedToken> {
+        self.lock()
+            .access_tokens
+            .get(access_token)
+            .filter(|x| x.expires_at > SystemTime::now())
+            .cloned()
+    }
+
+    fn gen(&self, prefix: &str) -> String {
+        let mut state = self.lock();
+        state.seq += 1;
+        format!("{prefix}-{}", state.seq)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().expect("poisoned")
+    }
+}
+
+//
+fn parse_form(bytes: &[u8]) -> HashMap<String, String> {
+    serde_urlencoded::from_bytes::<Vec<(String, String)>>(bytes)
+        .unwrap_or_default()
+        .into_iter()
+        .collect()
+}
+
+fn unix_timestamp(at: SystemTime) -> u64 {
+    at.duration_since(UNIX_EPOCH).unwrap_or_default().as_secs()
+}
+
+fn clone_request(request: &Request<Body>) -> Request<Body> {
+    let mut cloned = Request::new(request.body().to_owned());
+    *cloned.method_mut() = request.method().to_owned();
+    *cloned.uri_mut() = request.uri().to_owned();
+    *cloned.headers_mut() = request.headers().to_owned();
+    cloned
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_client::re_exports::serde_json;
+
+    fn post_form(server: &MockServer, endpoint: MockEndpoint, body: &str) -> Response<Body> {
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(server.endpoint_url(endpoint).as_str())
+            .body(body.as_bytes().to_vec())
+            .unwrap();
+        server.handle(request).unwrap()
+    }
+
+    fn json_body(response: &Response<Body>) -> Value {
+        serde_json::from_slice(response.body()).unwrap()
+    }
+
+    #[test]
+    fn test_token_lifecycle() {
+        let server = MockServer::default();
+        let client = "client_id=mock-client-id&client_secret=mock-client-secret";
+
+        let response = post_form(
+            &server,
+            MockEndpoint::Token,
+            &format!("grant_type=client_credentials&scope=read&{client}"),
+        );
+        assert_eq!(response.status(), StatusCode::OK);
+        let access_token = json_body(&response)["access_token"]
+            .as_str()
+            .unwrap()
+            .to_owned();
+
+        let response = post_form(
+            &server,
+            MockEndpoint::Introspect,
+            &format!("token={access_token}&{client}"),
+        );
+        assert_eq!(json_body(&response)["active"], true);
+        assert_eq!(json_body(&response)["scope"], "read");
+
+        let request = Request::builder()
+            .uri(server.endpoint_url(MockEndpoint::UserInfo).as_str())
+            .header(AUTHORIZATION, format!("Bearer {access_token}"))
+            .body(vec![])
+            .unwrap();
+        let response = server.handle(request).unwrap();
+        assert_eq!(json_body(&response)["sub"], "mock-user-1");
+
+        let response = post_form(
+            &server,
+            MockEndpoint::Revoke,
+            &format!("token={access_token}&{client}"),
+        );
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = post_form(
+            &server,
+            MockEndpoint::Introspect,
+            &format!("token={access_token}&{client}"),
+        );
+        assert_eq!(json_body(&response)["active"], false);
+
+        let response = post_form(
+            &server,
+            MockEndpoint::Token,
+            &format!("grant_type=implicit&{client}"),
+        );
+        assert_eq!(json_body(&response)["error"], "unsupported_grant_type");
+
+        let response = post_form(
+            &server,
+            MockEndpoint::Token,
+            "grant_type=client_credentials",
+        );
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[test]
+    fn test_script() {
+        let server = MockServer::default();
+
+        server.script(
+            MockEndpoint::Jwks,
+            MockResponse::status(503).with_header("Retry-After", "1"),
+        );
+        server.script_respond_failed(MockEndpoint::Jwks);
+
+        let request = || {
+            Request::builder()
+                .uri(server.endpoint_url(MockEndpoint::Jwks).as_str())
+                .body(vec![])
+                .unwrap()
+        };
+        assert_eq!(
+            server.handle(request()).unwrap().status(),
+            StatusCode::SERVICE_UNAVAILABLE
+        );
+        assert!(server.handle(request()).is_none());
+        assert_eq!(
+            json_body(&server.handle(request()).unwrap())["keys"],
+            json!([])
+        );
+        assert_eq!(server.request_count(MockEndpoint::Jwks), 3);
+
+        let request = Request::builder()
+            .uri("https://oauth2-mock-server.test/unknown")
+            .body(vec![])
+            .unwrap();
+        assert_eq!(
+            server.handle(request).unwrap().status(),
+            StatusCode::NOT_FOUND
+        );
+    }
+
+    #[test]
+    fn test_authorize() {
+        let server = MockServer::default();
+
+        let mut url = server.endpoint_url(MockEndpoint::Authorize);
+        url.query_pairs_mut()
+            .append_pair("response_type", "code")
+            .append_pair("client_id", "mock-client-id")
+            .append_pair("state", "STATE");
+        let redirect_url = server.authorize(&url).unwrap();
+        assert!(redirect_url
+            .as_str()
+            .starts_with("https://client.example.com/auth/callback?code="));
+        assert!(redirect_url.as_str().ends_with("&state=STATE"));
+
+        url.query_pairs_mut()
+            .append_pair("redirect_uri", "https://evil.example.com/");
+        assert_eq!(
+            server.authorize(&url).unwrap_err().status,
+            StatusCode::BAD_REQUEST
+        );
+    }
+}
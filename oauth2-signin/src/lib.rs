@@ -1,3 +1,5 @@
 pub use oauth2_client;
 
 pub mod web_app;
+
+pub mod registry;
@@ -0,0 +1,585 @@
+//! One factory per provider crate, each behind its `with-<provider>` feature.
+//!
+//! Provider specific options are read from [`ProviderConfig`], e.g. `tenant` (Microsoft),
+//! `base_url` (GitLab, Mastodon), `domain` (Okta), `region` (Amazon), and from `extra`, e.g.
+//! `access_type` (Google, Zoho), `include_granted_scopes` (Google), `prompt` (Zoho),
+//! `authorization_server_id` (Okta).
+//!
+//! `id_token_user_info` (Google, Microsoft, Okta, Zoho) reads `UserInfo` from the `id_token`
+//! claims instead of requesting the userinfo endpoint, Apple and Yahoo always do.
+
+#[cfg(feature = "with-okta")]
+use oauth2_client::{extensions::IdTokenExtensionsBuilder, DefaultExtensionsBuilder};
+
+#[allow(unused_imports)]
+use super::{ProviderConfig, ProviderFactoryOutput, ProviderRegistry, ProviderRegistryError};
+
+//
+#[allow(unused_variables)]
+pub fn register_all(registry: &mut ProviderRegistry) {
+    #[cfg(feature = "with-amazon")]
+    registry.register_with_capabilities("amazon", oauth2_amazon::CAPABILITIES, amazon);
+    #[cfg(feature = "with-apple")]
+    registry.register_with_capabilities("apple", oauth2_apple::CAPABILITIES, apple);
+    #[cfg(feature = "with-baidu")]
+    registry.register_with_capabilities("baidu", oauth2_baidu::CAPABILITIES, baidu);
+    #[cfg(feature = "with-bitbucket")]
+    registry.register_with_capabilities("bitbucket", oauth2_bitbucket::CAPABILITIES, bitbucket);
+    #[cfg(feature = "with-digitalocean")]
+    registry.register_with_capabilities(
+        "digitalocean",
+        oauth2_digitalocean::CAPABILITIES,
+        digitalocean,
+    );
+    #[cfg(feature = "with-dropbox")]
+    registry.register_with_capabilities("dropbox", oauth2_dropbox::CAPABILITIES, dropbox);
+    #[cfg(feature = "with-facebook")]
+    registry.register_with_capabilities("facebook", oauth2_facebook::CAPABILITIES, facebook);
+    #[cfg(feature = "with-github")]
+    registry.register_with_capabilities("github", oauth2_github::CAPABILITIES, github);
+    #[cfg(feature = "with-gitlab")]
+    registry.register_with_capabilities("gitlab", oauth2_gitlab::CAPABILITIES, gitlab);
+    #[cfg(feature = "with-google")]
+    registry.register_with_capabilities("google", oauth2_google::CAPABILITIES, google);
+    #[cfg(feature = "with-instagram")]
+    registry.register_with_capabilities("instagram", oauth2_instagram::CAPABILITIES, instagram);
+    #[cfg(feature = "with-linkedin")]
+    registry.register_with_capabilities("linkedin", oauth2_linkedin::CAPABILITIES, linkedin);
+    #[cfg(feature = "with-linode")]
+    registry.register_with_capabilities("linode", oauth2_linode::CAPABILITIES, linode);
+    #[cfg(feature = "with-mastodon")]
+    registry.register_with_capabilities("mastodon", oauth2_mastodon::CAPABILITIES, mastodon);
+    #[cfg(feature = "with-microsoft")]
+    registry.register_with_capabilities("microsoft", oauth2_microsoft::CAPABILITIES, microsoft);
+    #[cfg(feature = "with-okta")]
+    registry.register_with_capabilities("okta", oauth2_okta::CAPABILITIES, okta);
+    #[cfg(feature = "with-pinterest")]
+    registry.register_with_capabilities("pinterest", oauth2_pinterest::CAPABILITIES, pinterest);
+    #[cfg(feature = "with-tiktok")]
+    registry.register_with_capabilities("tiktok", oauth2_tiktok::CAPABILITIES, tiktok);
+    #[cfg(feature = "with-twitch")]
+    registry.register_with_capabilities("twitch", oauth2_twitch::CAPABILITIES, twitch);
+    #[cfg(feature = "with-twitter")]
+    registry.register_with_capabilities("twitter", oauth2_twitter::CAPABILITIES, twitter);
+    #[cfg(feature = "with-wechat")]
+    registry.register_with_capabilities("wechat", oauth2_wechat::CAPABILITIES, wechat);
+    #[cfg(feature = "with-yahoo")]
+    registry.register_with_capabilities("yahoo", oauth2_yahoo::CAPABILITIES, yahoo);
+    #[cfg(feature = "with-zoho")]
+    registry.register_with_capabilities("zoho", oauth2_zoho::CAPABILITIES, zoho);
+}
+
+#[allow(dead_code)]
+fn make_provider_failed(
+    err: impl std::error::Error + Send + Sync + 'static,
+) -> ProviderRegistryError {
+    ProviderRegistryError::MakeProviderFailed(Box::new(err))
+}
+
+//
+#[cfg(feature = "with-amazon")]
+pub fn amazon(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_amazon::{
+        AmazonExtensionsBuilder, AmazonProviderWithWebServices, AmazonTokenUrlRegion,
+    };
+
+    let region = match config.region.as_deref() {
+        Some(x) if x.eq_ignore_ascii_case("NA") => Some(AmazonTokenUrlRegion::NA),
+        Some(x) if x.eq_ignore_ascii_case("EU") => Some(AmazonTokenUrlRegion::EU),
+        Some(x) if x.eq_ignore_ascii_case("FE") => Some(AmazonTokenUrlRegion::FE),
+        Some(x) => return Err(ProviderRegistryError::ConfigInvalid("region", x.to_owned())),
+        None => None,
+    };
+
+    Ok(ProviderFactoryOutput::new(
+        AmazonProviderWithWebServices::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+            region,
+        )
+        .map_err(make_provider_failed)?,
+        AmazonExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-apple")]
+pub fn apple(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_apple::{AppleExtensionsBuilder, AppleProviderWithAppleJs};
+
+    Ok(ProviderFactoryOutput::new(
+        AppleProviderWithAppleJs::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        AppleExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-baidu")]
+pub fn baidu(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_baidu::{BaiduExtensionsBuilder, BaiduProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        BaiduProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        BaiduExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-bitbucket")]
+pub fn bitbucket(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_bitbucket::{BitbucketExtensionsBuilder, BitbucketProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        BitbucketProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        BitbucketExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-digitalocean")]
+pub fn digitalocean(
+    config: &ProviderConfig,
+) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_digitalocean::{
+        DigitaloceanExtensionsBuilder, DigitaloceanProviderWithWebApplication,
+    };
+
+    Ok(ProviderFactoryOutput::new(
+        DigitaloceanProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        DigitaloceanExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-dropbox")]
+pub fn dropbox(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_dropbox::{DropboxExtensionsBuilder, DropboxProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        DropboxProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        DropboxExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-facebook")]
+pub fn facebook(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_facebook::{FacebookExtensionsBuilder, FacebookProviderForWebApp};
+
+    Ok(ProviderFactoryOutput::new(
+        FacebookProviderForWebApp::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        FacebookExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-github")]
+pub fn github(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_github::{GithubExtensionsBuilder, GithubProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        GithubProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        GithubExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-gitlab")]
+pub fn gitlab(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_gitlab::{GitlabExtensionsBuilder, GitlabProviderForEndUsers, BASE_URL_GITLAB_COM};
+
+    Ok(ProviderFactoryOutput::new(
+        GitlabProviderForEndUsers::new(
+            config.base_url.as_deref().unwrap_or(BASE_URL_GITLAB_COM),
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        GitlabExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-google")]
+pub fn google(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_google::{
+        GoogleExtensionsBuilder, GoogleProviderForWebServerApps,
+        GoogleProviderForWebServerAppsAccessType,
+    };
+
+    let access_type = match config.extra_str("access_type")? {
+        Some("online") => Some(GoogleProviderForWebServerAppsAccessType::Online),
+        Some("offline") => Some(GoogleProviderForWebServerAppsAccessType::Offline),
+        Some(x) => {
+            return Err(ProviderRegistryError::ConfigInvalid(
+                "access_type",
+                x.to_owned(),
+            ))
+        }
+        None => None,
+    };
+    let include_granted_scopes = config.extra_bool("include_granted_scopes")?;
+
+    let provider = GoogleProviderForWebServerApps::new(
+        config.client_id.to_owned(),
+        config.client_secret_required()?,
+        config.redirect_uri.to_owned(),
+    )
+    .map_err(make_provider_failed)?
+    .configure(|x| {
+        x.access_type = access_type.to_owned();
+        x.include_granted_scopes = include_granted_scopes;
+    });
+
+    Ok(if config.extra_bool("id_token_user_info")? == Some(true) {
+        ProviderFactoryOutput::new(provider, GoogleExtensionsBuilder::with_id_token_fast_path())
+    } else {
+        ProviderFactoryOutput::new(provider, GoogleExtensionsBuilder)
+    })
+}
+
+#[cfg(feature = "with-instagram")]
+pub fn instagram(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_instagram::{InstagramExtensionsBuilder, InstagramProviderForBasicDisplayApi};
+
+    Ok(ProviderFactoryOutput::new(
+        InstagramProviderForBasicDisplayApi::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        InstagramExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-linkedin")]
+pub fn linkedin(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_linkedin::{LinkedinExtensionsBuilder, LinkedinProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        LinkedinProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        LinkedinExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-linode")]
+pub fn linode(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_linode::{LinodeExtensionsBuilder, LinodeProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        LinodeProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        LinodeExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-mastodon")]
+pub fn mastodon(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_mastodon::{
+        MastodonExtensionsBuilder, MastodonProviderForEndUsers, BASE_URL_MASTODON_SOCIAL,
+    };
+
+    Ok(ProviderFactoryOutput::new(
+        MastodonProviderForEndUsers::new(
+            config
+                .base_url
+                .as_deref()
+                .unwrap_or(BASE_URL_MASTODON_SOCIAL),
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        MastodonExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-microsoft")]
+pub fn microsoft(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_microsoft::{
+        MicrosoftExtensionsBuilder, MicrosoftProviderForWebApps, TENANT_COMMON,
+    };
+
+    let provider = MicrosoftProviderForWebApps::new(
+        config.tenant.as_deref().unwrap_or(TENANT_COMMON),
+        config.client_id.to_owned(),
+        config.client_secret_required()?,
+        config.redirect_uri.to_owned(),
+    )
+    .map_err(make_provider_failed)?;
+
+    Ok(if config.extra_bool("id_token_user_info")? == Some(true) {
+        ProviderFactoryOutput::new(
+            provider,
+            MicrosoftExtensionsBuilder::with_id_token_fast_path(),
+        )
+    } else {
+        ProviderFactoryOutput::new(provider, MicrosoftExtensionsBuilder)
+    })
+}
+
+#[cfg(feature = "with-okta")]
+pub fn okta(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_okta::{issuer_url, OktaProviderForWebApplication};
+
+    let domain = config
+        .domain
+        .as_deref()
+        .ok_or(ProviderRegistryError::ConfigMissing("domain"))?;
+    let authorization_server_id = config
+        .extra_str("authorization_server_id")?
+        .map(ToOwned::to_owned);
+
+    let provider = OktaProviderForWebApplication::new(
+        domain,
+        authorization_server_id.to_owned(),
+        config.client_id.to_owned(),
+        config.client_secret_required()?,
+        config.redirect_uri.to_owned(),
+    )
+    .map_err(make_provider_failed)?;
+
+    Ok(if config.extra_bool("id_token_user_info")? == Some(true) {
+        ProviderFactoryOutput::new(
+            provider,
+            IdTokenExtensionsBuilder::new()
+                .with_issuer(issuer_url(domain, authorization_server_id)),
+        )
+    } else {
+        ProviderFactoryOutput::new(provider, DefaultExtensionsBuilder)
+    })
+}
+
+#[cfg(feature = "with-pinterest")]
+pub fn pinterest(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_pinterest::{PinterestExtensionsBuilder, PinterestProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        PinterestProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        PinterestExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-tiktok")]
+pub fn tiktok(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_tiktok::{TiktokExtensionsBuilder, TiktokProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        TiktokProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        TiktokExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-twitch")]
+pub fn twitch(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_twitch::{TwitchExtensionsBuilder, TwitchProviderForWebServerApps};
+
+    Ok(ProviderFactoryOutput::new(
+        TwitchProviderForWebServerApps::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        TwitchExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-twitter")]
+pub fn twitter(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_twitter::{TwitterExtensionsBuilder, TwitterProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        TwitterProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        TwitterExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-wechat")]
+pub fn wechat(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_wechat::{extensions::WechatExtensionsBuilder, WechatProviderWithWebApplication};
+
+    Ok(ProviderFactoryOutput::new(
+        WechatProviderWithWebApplication::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        WechatExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-yahoo")]
+pub fn yahoo(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_yahoo::{YahooExtensionsBuilder, YahooProviderForWebApps};
+
+    Ok(ProviderFactoryOutput::new(
+        YahooProviderForWebApps::new(
+            config.client_id.to_owned(),
+            config.client_secret_required()?,
+            config.redirect_uri.to_owned(),
+        )
+        .map_err(make_provider_failed)?,
+        YahooExtensionsBuilder,
+    ))
+}
+
+#[cfg(feature = "with-zoho")]
+pub fn zoho(config: &ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+    use oauth2_zoho::{
+        ZohoExtensionsBuilder, ZohoProviderForWebServerApps, ZohoProviderForWebServerAppsAccessType,
+    };
+
+    let access_type = match config.extra_str("access_type")? {
+        Some("online") => Some(ZohoProviderForWebServerAppsAccessType::Online),
+        Some("offline") => Some(ZohoProviderForWebServerAppsAccessType::Offline),
+        Some(x) => {
+            return Err(ProviderRegistryError::ConfigInvalid(
+                "access_type",
+                x.to_owned(),
+            ))
+        }
+        None => None,
+    };
+    let prompt = config.extra_str("prompt")?.map(ToOwned::to_owned);
+
+    let provider = ZohoProviderForWebServerApps::new(
+        config.client_id.to_owned(),
+        config.client_secret_required()?,
+        config.redirect_uri.to_owned(),
+    )
+    .map_err(make_provider_failed)?
+    .configure(|x| {
+        x.access_type = access_type.to_owned();
+        x.prompt = prompt.to_owned();
+    });
+
+    Ok(if config.extra_bool("id_token_user_info")? == Some(true) {
+        ProviderFactoryOutput::new(provider, ZohoExtensionsBuilder::with_id_token_fast_path())
+    } else {
+        ProviderFactoryOutput::new(provider, ZohoExtensionsBuilder)
+    })
+}
+
+#[cfg(all(test, feature = "with-all-providers"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_register_all() -> Result<(), Box<dyn std::error::Error>> {
+        let registry = ProviderRegistry::with_builtin();
+        assert_eq!(registry.names().len(), 23);
+        assert!(registry
+            .names()
+            .iter()
+            .all(|x| registry.capabilities(x).is_some()));
+
+        let config = ProviderConfig::new(
+            "client_id".to_owned(),
+            "client_secret".into(),
+            "https://client.example.com/cb".parse()?,
+        )
+        .configure(|x| x.domain = Some("dev-000000.okta.com".to_owned()));
+        for name in registry.names() {
+            registry.build(name, &config)?;
+        }
+
+        let output = registry.build(
+            "microsoft",
+            &config
+                .to_owned()
+                .configure(|x| x.tenant = Some("consumers".to_owned())),
+        )?;
+        assert_eq!(
+            output.provider.token_endpoint_url().as_str(),
+            "https://login.microsoftonline.com/consumers/oauth2/v2.0/token"
+        );
+
+        match registry.build(
+            "amazon",
+            &config
+                .to_owned()
+                .configure(|x| x.region = Some("US".to_owned())),
+        ) {
+            Err(ProviderRegistryError::ConfigInvalid("region", _)) => {}
+            x => panic!("{x:?}"),
+        }
+        match registry.build("okta", &config.to_owned().configure(|x| x.domain = None)) {
+            Err(ProviderRegistryError::ConfigMissing("domain")) => {}
+            x => panic!("{x:?}"),
+        }
+
+        let id_token_config = config.to_owned().configure(|x| {
+            x.extra.insert("id_token_user_info".to_owned(), true.into());
+        });
+        for name in ["google", "microsoft", "okta", "zoho"] {
+            registry.build(name, &id_token_config)?;
+        }
+        match registry.build(
+            "google",
+            &config.to_owned().configure(|x| {
+                x.extra
+                    .insert("id_token_user_info".to_owned(), "yes".into());
+            }),
+        ) {
+            Err(ProviderRegistryError::ConfigInvalid("id_token_user_info", _)) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use oauth2_client::re_exports::{ClientId, ClientSecret, Deserialize, Map, RedirectUri, Value};
+
+use super::ProviderRegistryError;
+
+//
+#[derive(Deserialize, Debug, Clone)]
+pub struct ProviderConfig {
+    /// Registry name, defaults to the key the config is stored under, e.g. `[mastodon-social]` with
+    /// `provider = "mastodon"`.
+    #[serde(default)]
+    pub provider: Option<String>,
+    pub client_id: ClientId,
+    #[serde(default)]
+    pub client_secret: Option<ClientSecret>,
+    pub redirect_uri: RedirectUri,
+    /// Falls back to the provider's `scopes_default`.
+    #[serde(default)]
+    pub scopes: Option<Vec<String>>,
+    // e.g. Microsoft
+    #[serde(default)]
+    pub tenant: Option<String>,
+    // e.g. GitLab, Mastodon
+    #[serde(default)]
+    pub base_url: Option<String>,
+    // e.g. Okta
+    #[serde(default)]
+    pub domain: Option<String>,
+    // e.g. Amazon
+    #[serde(default)]
+    pub region: Option<String>,
+    //
+    #[serde(flatten)]
+    pub extra: Map<String, Value>,
+}
+
+impl ProviderConfig {
+    pub fn new(
+        client_id: ClientId,
+        client_secret: ClientSecret,
+        redirect_uri: RedirectUri,
+    ) -> Self {
+        Self {
+            provider: None,
+            client_id,
+            client_secret: Some(client_secret),
+            redirect_uri,
+            scopes: None,
+            tenant: None,
+            base_url: None,
+            domain: None,
+            region: None,
+            extra: Map::new(),
+        }
+    }
+
+    pub fn configure<F>(mut self, mut f: F) -> Self
+    where
+        F: FnMut(&mut Self),
+    {
+        f(&mut self);
+        self
+    }
+
+    pub fn client_secret_required(&self) -> Result<ClientSecret, ProviderRegistryError> {
+        self.client_secret
+            .to_owned()
+            .ok_or(ProviderRegistryError::ConfigMissing("client_secret"))
+    }
+
+    pub fn extra_str(&self, key: &'static str) -> Result<Option<&str>, ProviderRegistryError> {
+        match self.extra.get(key) {
+            Some(Value::String(s)) => Ok(Some(s)),
+            Some(x) => Err(ProviderRegistryError::ConfigInvalid(key, x.to_string())),
+            None => Ok(None),
+        }
+    }
+
+    pub fn extra_bool(&self, key: &'static str) -> Result<Option<bool>, ProviderRegistryError> {
+        match self.extra.get(key) {
+            Some(Value::Bool(b)) => Ok(Some(*b)),
+            Some(x) => Err(ProviderRegistryError::ConfigInvalid(key, x.to_string())),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,339 @@
+//! Maps provider names to factories so that signin flows can be enabled from TOML/JSON/env.
+//!
+//! Builtin factories are behind the `with-<provider>` features, `with-all-providers` enables
+//! every one of them.
+
+pub mod builtin;
+pub mod config;
+
+pub use config::ProviderConfig;
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::Arc,
+};
+
+use oauth2_client::{
+    authorization_code_grant::provider_ext::ProviderExtAuthorizationCodeGrantStringScopeWrapper,
+    capabilities::GrantType,
+    re_exports::{thiserror, Client},
+    ExtensionsBuilder, ProviderCapabilities, ProviderExtAuthorizationCodeGrant,
+};
+
+use crate::web_app::SigninFlow;
+
+//
+pub struct ProviderFactoryOutput {
+    pub provider: Box<dyn ProviderExtAuthorizationCodeGrant<Scope = String> + Send + Sync>,
+    pub extensions_builder: Box<dyn ExtensionsBuilder<String> + Send + Sync>,
+}
+impl ProviderFactoryOutput {
+    pub fn new<P, EB>(provider: P, extensions_builder: EB) -> Self
+    where
+        P: ProviderExtAuthorizationCodeGrant + Clone + Send + Sync + 'static,
+        EB: ExtensionsBuilder<String> + Send + Sync + 'static,
+    {
+        Self {
+            provider: Box::new(ProviderExtAuthorizationCodeGrantStringScopeWrapper::new(
+                provider,
+            )),
+            extensions_builder: Box::new(extensions_builder),
+        }
+    }
+}
+impl core::fmt::Debug for ProviderFactoryOutput {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ProviderFactoryOutput")
+            .field("provider", &self.provider)
+            .field("extensions_builder", &self.extensions_builder)
+            .finish()
+    }
+}
+
+pub type ProviderFactory = Arc<
+    dyn Fn(&ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError> + Send + Sync,
+>;
+
+//
+#[derive(Clone, Default)]
+pub struct ProviderRegistry {
+    factories: BTreeMap<String, ProviderFactory>,
+    capabilities: BTreeMap<String, ProviderCapabilities>,
+}
+impl core::fmt::Debug for ProviderRegistry {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ProviderRegistry")
+            .field("names", &self.names())
+            .finish()
+    }
+}
+
+impl ProviderRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Every builtin factory enabled by features.
+    pub fn with_builtin() -> Self {
+        let mut this = Self::new();
+        builtin::register_all(&mut this);
+        this
+    }
+
+    pub fn register<F>(&mut self, name: impl Into<String>, factory: F) -> &mut Self
+    where
+        F: Fn(&ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let name = name.into();
+        self.capabilities.remove(&name);
+        self.factories.insert(name, Arc::new(factory));
+        self
+    }
+
+    /// Configs are validated against `capabilities` before the factory is called.
+    pub fn register_with_capabilities<F>(
+        &mut self,
+        name: impl Into<String>,
+        capabilities: ProviderCapabilities,
+        factory: F,
+    ) -> &mut Self
+    where
+        F: Fn(&ProviderConfig) -> Result<ProviderFactoryOutput, ProviderRegistryError>
+            + Send
+            + Sync
+            + 'static,
+    {
+        let name = name.into();
+        self.register(name.to_owned(), factory);
+        self.capabilities.insert(name, capabilities);
+        self
+    }
+
+    pub fn contains(&self, name: impl AsRef<str>) -> bool {
+        self.factories.contains_key(name.as_ref())
+    }
+
+    pub fn names(&self) -> Vec<&str> {
+        self.factories.keys().map(|x| x.as_str()).collect()
+    }
+
+    pub fn capabilities(&self, name: impl AsRef<str>) -> Option<&ProviderCapabilities> {
+        self.capabilities.get(name.as_ref())
+    }
+
+    pub fn validate(
+        &self,
+        name: impl AsRef<str>,
+        config: &ProviderConfig,
+    ) -> Result<(), ProviderRegistryError> {
+        let name = config.provider.as_deref().unwrap_or_else(|| name.as_ref());
+        if !self.contains(name) {
+            return Err(ProviderRegistryError::UnknownProvider(name.to_owned()));
+        }
+
+        let capabilities = match self.capabilities(name) {
+            Some(x) => x,
+            None => return Ok(()),
+        };
+        if !capabilities.supports_grant(GrantType::AuthorizationCode) {
+            return Err(ProviderRegistryError::ConfigInvalid(
+                "provider",
+                format!("{name} does not support {}", GrantType::AuthorizationCode),
+            ));
+        }
+        if let Some(scopes) = &config.scopes {
+            if !capabilities.oidc && scopes.iter().any(|x| x == "openid") {
+                return Err(ProviderRegistryError::ConfigInvalid(
+                    "scopes",
+                    format!("{name} does not support openid"),
+                ));
+            }
+            if let Some(scope) = scopes
+                .iter()
+                .find(|x| x.contains(capabilities.scope_delimiter))
+            {
+                return Err(ProviderRegistryError::ConfigInvalid(
+                    "scopes",
+                    scope.to_owned(),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// `name` is only used when `config.provider` is missing.
+    pub fn build(
+        &self,
+        name: impl AsRef<str>,
+        config: &ProviderConfig,
+    ) -> Result<ProviderFactoryOutput, ProviderRegistryError> {
+        let name = config.provider.as_deref().unwrap_or_else(|| name.as_ref());
+        let factory = self
+            .factories
+            .get(name)
+            .ok_or_else(|| ProviderRegistryError::UnknownProvider(name.to_owned()))?;
+
+        self.validate(name, config)?;
+
+        factory(config)
+    }
+
+    pub fn build_signin_flow<C>(
+        &self,
+        client: C,
+        name: impl AsRef<str>,
+        config: &ProviderConfig,
+    ) -> Result<SigninFlow<C>, ProviderRegistryError>
+    where
+        C: Client + Clone,
+    {
+        let ProviderFactoryOutput {
+            provider,
+            extensions_builder,
+        } = self.build(name, config)?;
+
+        Ok(SigninFlow::new_with_boxed(
+            client,
+            provider,
+            config.scopes.to_owned(),
+            extensions_builder,
+        ))
+    }
+
+    pub fn build_signin_flows<C>(
+        &self,
+        client: C,
+        configs: &HashMap<String, ProviderConfig>,
+    ) -> Result<HashMap<String, SigninFlow<C>>, ProviderRegistryError>
+    where
+        C: Client + Clone,
+    {
+        configs
+            .iter()
+            .map(|(name, config)| {
+                self.build_signin_flow(client.clone(), name, config)
+                    .map(|flow| (name.to_owned(), flow))
+            })
+            .collect()
+    }
+}
+
+//
+#[derive(thiserror::Error, Debug)]
+pub enum ProviderRegistryError {
+    #[error("UnknownProvider {0}")]
+    UnknownProvider(String),
+    #[error("ConfigMissing {0}")]
+    ConfigMissing(&'static str),
+    #[error("ConfigInvalid {0} {1}")]
+    ConfigInvalid(&'static str, String),
+    #[error("MakeProviderFailed {0}")]
+    MakeProviderFailed(Box<dyn std::error::Error + Send + Sync>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_client::re_exports::serde_json;
+    use oauth2_github::{GithubExtensionsBuilder, GithubProviderWithWebApplication};
+
+    use http_api_isahc_client::IsahcClient;
+
+    fn github_registry() -> ProviderRegistry {
+        let mut registry = ProviderRegistry::new();
+        registry.register_with_capabilities("github", oauth2_github::CAPABILITIES, |config| {
+            Ok(ProviderFactoryOutput::new(
+                GithubProviderWithWebApplication::new(
+                    config.client_id.to_owned(),
+                    config.client_secret_required()?,
+                    config.redirect_uri.to_owned(),
+                )
+                .map_err(|err| ProviderRegistryError::MakeProviderFailed(err.into()))?,
+                GithubExtensionsBuilder,
+            ))
+        });
+        registry
+    }
+
+    #[test]
+    fn test_build_signin_flows() -> Result<(), Box<dyn std::error::Error>> {
+        let configs = serde_json::from_str::<HashMap<String, ProviderConfig>>(
+            r#"
+            {
+                "github": {
+                    "client_id": "client_id",
+                    "client_secret": "client_secret",
+                    "redirect_uri": "https://client.example.com/cb",
+                    "scopes": ["user"]
+                },
+                "github-enterprise": {
+                    "provider": "github",
+                    "client_id": "client_id_2",
+                    "client_secret": "client_secret_2",
+                    "redirect_uri": "https://client.example.com/cb2"
+                }
+            }
+            "#,
+        )?;
+
+        let flows = github_registry().build_signin_flows(IsahcClient::new()?, &configs)?;
+        assert_eq!(flows.len(), 2);
+
+        let url = flows["github"].build_authorization_url(None)?;
+        assert_eq!(url.as_str(), "https://github.com/login/oauth/authorize?response_type=code&client_id=client_id&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=user");
+
+        let url = flows["github-enterprise"].build_authorization_url(None)?;
+        assert!(url.as_str().contains("client_id=client_id_2"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_build_err() -> Result<(), Box<dyn std::error::Error>> {
+        let registry = github_registry();
+
+        let config = ProviderConfig::new(
+            "client_id".to_owned(),
+            "client_secret".into(),
+            "https://client.example.com/cb".parse()?,
+        );
+        match registry.build("gitee", &config) {
+            Err(ProviderRegistryError::UnknownProvider(name)) => assert_eq!(name, "gitee"),
+            x => panic!("{x:?}"),
+        }
+
+        match registry.build(
+            "github",
+            &config
+                .to_owned()
+                .configure(|x| x.scopes = Some(vec!["openid".to_owned()])),
+        ) {
+            Err(ProviderRegistryError::ConfigInvalid("scopes", _)) => {}
+            x => panic!("{x:?}"),
+        }
+        match registry.build(
+            "github",
+            &config
+                .to_owned()
+                .configure(|x| x.scopes = Some(vec!["user repo".to_owned()])),
+        ) {
+            Err(ProviderRegistryError::ConfigInvalid("scopes", scope)) => {
+                assert_eq!(scope, "user repo")
+            }
+            x => panic!("{x:?}"),
+        }
+
+        let config = config.configure(|x| x.client_secret = None);
+        match registry.build("github", &config) {
+            Err(ProviderRegistryError::ConfigMissing("client_secret")) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+}
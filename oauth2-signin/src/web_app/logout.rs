@@ -0,0 +1,300 @@
+//! Routes logouts initiated by the OP to the app's own sessions.
+//!
+//! Front-Channel Logout 1.0: the OP loads the app's `frontchannel_logout_uri` in an iframe, with
+//! `iss` and `sid` in the query when `frontchannel_logout_session_required`. Respond with
+//! `Cache-Control: no-store`.
+//!
+//! Back-Channel Logout 1.0 (feature `with-back-channel-logout`): the OP posts a form with a
+//! `logout_token` to the app's `backchannel_logout_uri`. Respond `200` with
+//! `Cache-Control: no-store`, or `400` on error.
+
+use std::{collections::BTreeMap, error::Error, sync::Arc};
+
+#[cfg(feature = "with-back-channel-logout")]
+use oauth2_client::logout::{LogoutTokenError, LogoutTokenVerifier};
+use oauth2_client::re_exports::{
+    serde_urlencoded::{self, de::Error as SerdeUrlencodedDeError},
+    thiserror, Deserialize,
+};
+
+//
+//
+//
+/// What the OP asked to end. Without `sid` nor `sub` the app can only go by its own session
+/// cookie, which the iframe carries unless third-party cookies are blocked.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LogoutRequest {
+    pub provider: String,
+    pub iss: Option<String>,
+    pub sid: Option<String>,
+    pub sub: Option<String>,
+}
+
+pub type SessionInvalidator =
+    Arc<dyn Fn(&LogoutRequest) -> Result<(), Box<dyn Error + Send + Sync>> + Send + Sync>;
+
+//
+//
+//
+#[derive(Clone)]
+pub struct LogoutRouter {
+    invalidator: SessionInvalidator,
+    issuers: BTreeMap<String, Option<String>>,
+    #[cfg(feature = "with-back-channel-logout")]
+    verifiers: BTreeMap<String, LogoutTokenVerifier>,
+}
+impl core::fmt::Debug for LogoutRouter {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("LogoutRouter")
+            .field("issuers", &self.issuers)
+            .finish()
+    }
+}
+
+impl LogoutRouter {
+    pub fn new<F>(invalidator: F) -> Self
+    where
+        F: Fn(&LogoutRequest) -> Result<(), Box<dyn Error + Send + Sync>> + Send + Sync + 'static,
+    {
+        Self {
+            invalidator: Arc::new(invalidator),
+            issuers: BTreeMap::new(),
+            #[cfg(feature = "with-back-channel-logout")]
+            verifiers: BTreeMap::new(),
+        }
+    }
+
+    /// `issuer` is checked against the `iss` of front-channel requests, `None` accepts any.
+    pub fn register(
+        &mut self,
+        provider: impl Into<String>,
+        issuer: impl Into<Option<String>>,
+    ) -> &mut Self {
+        self.issuers.insert(provider.into(), issuer.into());
+        self
+    }
+
+    /// The verifier's `validation` carries the issuer and the `client_id`.
+    #[cfg(feature = "with-back-channel-logout")]
+    pub fn register_back_channel(
+        &mut self,
+        provider: impl Into<String>,
+        verifier: LogoutTokenVerifier,
+    ) -> &mut Self {
+        self.verifiers.insert(provider.into(), verifier);
+        self
+    }
+
+    pub fn contains(&self, provider: impl AsRef<str>) -> bool {
+        self.issuers.contains_key(provider.as_ref())
+    }
+
+    pub fn handle_front_channel_logout_by_query(
+        &self,
+        provider: impl AsRef<str>,
+        query: impl AsRef<str>,
+    ) -> Result<LogoutRequest, LogoutRouterError> {
+        let provider = provider.as_ref();
+        let issuer = self
+            .issuers
+            .get(provider)
+            .ok_or_else(|| LogoutRouterError::UnknownProvider(provider.to_owned()))?;
+
+        let query = serde_urlencoded::from_str::<FrontChannelLogoutQuery>(query.as_ref())
+            .map_err(LogoutRouterError::DeQueryFailed)?;
+
+        // Front-Channel Logout 1.0 section 2, if either is included, both must be.
+        let iss = match (query.iss, &query.sid) {
+            (Some(iss), Some(_)) => Some(iss),
+            (None, None) => None,
+            _ => return Err(LogoutRouterError::IssOrSidMissing),
+        };
+        if let (Some(issuer), Some(iss)) = (issuer, &iss) {
+            if issuer != iss {
+                return Err(LogoutRouterError::IssuerMismatch(iss.to_owned()));
+            }
+        }
+
+        self.invalidate(LogoutRequest {
+            provider: provider.to_owned(),
+            iss,
+            sid: query.sid,
+            sub: None,
+        })
+    }
+
+    #[cfg(feature = "with-back-channel-logout")]
+    pub fn handle_back_channel_logout_by_body(
+        &self,
+        provider: impl AsRef<str>,
+        body: impl AsRef<[u8]>,
+    ) -> Result<LogoutRequest, LogoutRouterError> {
+        let provider = provider.as_ref();
+        let verifier = self
+            .verifiers
+            .get(provider)
+            .ok_or_else(|| LogoutRouterError::UnknownProvider(provider.to_owned()))?;
+
+        let body = serde_urlencoded::from_bytes::<BackChannelLogoutBody>(body.as_ref())
+            .map_err(LogoutRouterError::DeBodyFailed)?;
+
+        let claims = verifier
+            .verify(body.logout_token)
+            .map_err(LogoutRouterError::LogoutTokenError)?;
+
+        self.invalidate(LogoutRequest {
+            provider: provider.to_owned(),
+            iss: Some(claims.iss),
+            sid: claims.sid,
+            sub: claims.sub,
+        })
+    }
+
+    fn invalidate(&self, request: LogoutRequest) -> Result<LogoutRequest, LogoutRouterError> {
+        (self.invalidator)(&request).map_err(LogoutRouterError::InvalidateFailed)?;
+        Ok(request)
+    }
+}
+
+//
+#[derive(Deserialize)]
+struct FrontChannelLogoutQuery {
+    iss: Option<String>,
+    sid: Option<String>,
+}
+
+#[cfg(feature = "with-back-channel-logout")]
+#[derive(Deserialize)]
+struct BackChannelLogoutBody {
+    logout_token: String,
+}
+
+//
+#[derive(thiserror::Error, Debug)]
+pub enum LogoutRouterError {
+    #[error("UnknownProvider {0}")]
+    UnknownProvider(String),
+    #[error("DeQueryFailed {0}")]
+    DeQueryFailed(SerdeUrlencodedDeError),
+    #[error("IssOrSidMissing")]
+    IssOrSidMissing,
+    #[error("IssuerMismatch {0}")]
+    IssuerMismatch(String),
+    #[cfg(feature = "with-back-channel-logout")]
+    #[error("DeBodyFailed {0}")]
+    DeBodyFailed(SerdeUrlencodedDeError),
+    #[cfg(feature = "with-back-channel-logout")]
+    #[error("LogoutTokenError {0}")]
+    LogoutTokenError(LogoutTokenError),
+    #[error("InvalidateFailed {0}")]
+    InvalidateFailed(Box<dyn Error + Send + Sync>),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::sync::Mutex;
+
+    #[test]
+    fn test_front_channel_logout() -> Result<(), Box<dyn std::error::Error>> {
+        let invalidated = Arc::new(Mutex::new(vec![]));
+        let invalidated_cloned = invalidated.clone();
+        let mut router = LogoutRouter::new(move |request: &LogoutRequest| {
+            invalidated_cloned.lock().unwrap().push(request.to_owned());
+            Ok(())
+        });
+        router.register("okta", "https://example.okta.com/oauth2/default".to_owned());
+
+        let request = router.handle_front_channel_logout_by_query(
+            "okta",
+            "iss=https%3A%2F%2Fexample.okta.com%2Foauth2%2Fdefault&sid=SID",
+        )?;
+        assert_eq!(request.sid, Some("SID".to_owned()));
+        assert_eq!(invalidated.lock().unwrap().to_owned(), vec![request]);
+
+        let request = router.handle_front_channel_logout_by_query("okta", "")?;
+        assert_eq!(request.iss, None);
+        assert_eq!(invalidated.lock().unwrap().len(), 2);
+
+        match router.handle_front_channel_logout_by_query("okta", "sid=SID") {
+            Err(LogoutRouterError::IssOrSidMissing) => {}
+            x => panic!("{x:?}"),
+        }
+        match router
+            .handle_front_channel_logout_by_query("okta", "iss=https%3A%2F%2Fevil.com&sid=SID")
+        {
+            Err(LogoutRouterError::IssuerMismatch(iss)) => assert_eq!(iss, "https://evil.com"),
+            x => panic!("{x:?}"),
+        }
+        match router.handle_front_channel_logout_by_query("google", "") {
+            Err(LogoutRouterError::UnknownProvider(_)) => {}
+            x => panic!("{x:?}"),
+        }
+        assert_eq!(invalidated.lock().unwrap().len(), 2);
+
+        let mut router = LogoutRouter::new(|_: &LogoutRequest| Err("foo".into()));
+        router.register("okta", None);
+        match router.handle_front_channel_logout_by_query("okta", "") {
+            Err(LogoutRouterError::InvalidateFailed(_)) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "with-back-channel-logout")]
+    #[test]
+    fn test_back_channel_logout() -> Result<(), Box<dyn std::error::Error>> {
+        use oauth2_client::re_exports::serde_json::json;
+        use std::time::{SystemTime, UNIX_EPOCH};
+
+        use jsonwebtoken::{encode, Algorithm, DecodingKey, EncodingKey, Header};
+
+        let exp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs() + 3600;
+        let logout_token = encode(
+            &Header::new(Algorithm::HS256),
+            &json!({
+                "iss": "https://server.example.com",
+                "aud": "CLIENT_ID",
+                "iat": exp - 3600,
+                "exp": exp,
+                "jti": "bWJq",
+                "sid": "SID",
+                "events": {
+                    "http://schemas.openid.net/event/backchannel-logout": {}
+                }
+            }),
+            &EncodingKey::from_secret(b"SECRET"),
+        )?;
+
+        let mut router = LogoutRouter::new(|_: &LogoutRequest| Ok(()));
+        router.register_back_channel(
+            "okta",
+            LogoutTokenVerifier::new(
+                DecodingKey::from_secret(b"SECRET"),
+                vec![Algorithm::HS256],
+                "CLIENT_ID".to_owned(),
+            )
+            .configure(|x| {
+                x.validation.issuers = vec!["https://server.example.com".to_owned()];
+            }),
+        );
+
+        let request = router
+            .handle_back_channel_logout_by_body("okta", format!("logout_token={logout_token}"))?;
+        assert_eq!(request.sid, Some("SID".to_owned()));
+        assert_eq!(request.sub, None);
+
+        match router.handle_back_channel_logout_by_body("okta", "logout_token=foo") {
+            Err(LogoutRouterError::LogoutTokenError(_)) => {}
+            x => panic!("{x:?}"),
+        }
+        match router.handle_back_channel_logout_by_body("okta", "") {
+            Err(LogoutRouterError::DeBodyFailed(_)) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+}
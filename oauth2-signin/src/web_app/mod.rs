@@ -1,8 +1,17 @@
+pub mod logout;
 pub mod signin_flow;
+pub mod state_store;
 pub mod types;
 
+pub use logout::{LogoutRequest, LogoutRouter, LogoutRouterError, SessionInvalidator};
 pub use signin_flow::SigninFlow;
+#[cfg(feature = "with-cookie-state-store")]
+pub use state_store::CookieSigninStateStore;
+pub use state_store::{
+    MemorySigninStateStore, SigninState, SigninStateStore, SigninStateStoreError,
+};
 pub use types::{
     SigninFlowBuildAuthorizationUrlConfiguration, SigninFlowBuildAuthorizationUrlError,
     SigninFlowHandleCallbackByQueryConfiguration, SigninFlowHandleCallbackRet,
+    SigninFlowSaveStateError, SigninFlowTakeStateError,
 };
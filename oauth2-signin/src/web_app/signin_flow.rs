@@ -1,3 +1,6 @@
+use core::time::Duration;
+use std::sync::Arc;
+
 use oauth2_client::{
     authorization_code_grant::{
         provider_ext::{
@@ -8,16 +11,24 @@ use oauth2_client::{
         Flow,
     },
     extensions::{
-        AuthorizationCodeGrantInfo, BuilderObtainUserInfoOutput, EndpointExecuteError, GrantInfo,
+        AccessTokenResponseSuccessfulBody, AuthorizationCodeGrantInfo, BuilderObtainUserInfoOutput,
+        GrantInfo, UserInfoPipeline,
+    },
+    id_token::{IdTokenClaims, IdTokenError, IdTokenValidation},
+    oauth2_core::{
+        authorization_code_grant::authorization_request::AuthenticationParameters,
+        types::scope::SCOPE_OPENID, utils::gen_code_challenge,
     },
-    oauth2_core::types::scope::SCOPE_OPENID,
-    re_exports::{Client, ClientRespondEndpointError, Url},
+    re_exports::{Client, RetryableClient, Url},
+    utils::{gen_code_verifier, gen_nonce, gen_state},
     ExtensionsBuilder, Provider, ProviderExtAuthorizationCodeGrant,
 };
 
 use super::{
-    SigninFlowBuildAuthorizationUrlConfiguration, SigninFlowBuildAuthorizationUrlError,
-    SigninFlowHandleCallbackByQueryConfiguration, SigninFlowHandleCallbackRet,
+    state_store::TTL_DEFAULT, SigninFlowBuildAuthorizationUrlConfiguration,
+    SigninFlowBuildAuthorizationUrlError, SigninFlowHandleCallbackByQueryConfiguration,
+    SigninFlowHandleCallbackRet, SigninFlowSaveStateError, SigninFlowTakeStateError, SigninState,
+    SigninStateStore,
 };
 
 //
@@ -33,6 +44,8 @@ where
     pub scopes: Option<Vec<String>>,
     pub extensions_builder: Box<dyn ExtensionsBuilder<String> + Send + Sync>,
     pub client_with_user_info: C,
+    pub state_store: Option<Arc<dyn SigninStateStore + Send + Sync>>,
+    pub state_ttl: Duration,
     pkce_enabled: bool,
 }
 impl<C> core::fmt::Debug for SigninFlow<C>
@@ -46,6 +59,8 @@ where
             .field("scopes", &self.scopes)
             .field("extensions_builder", &self.extensions_builder)
             .field("client_with_user_info", &self.client_with_user_info)
+            .field("state_store", &self.state_store)
+            .field("state_ttl", &self.state_ttl)
             .field("pkce_enabled", &self.pkce_enabled)
             .finish()
     }
@@ -76,6 +91,30 @@ where
                 .map(|x| x.iter().map(|y| y.to_string()).collect()),
             extensions_builder: Box::new(extensions_builder),
             client_with_user_info: client,
+            state_store: None,
+            state_ttl: TTL_DEFAULT,
+            pkce_enabled: true,
+        }
+    }
+
+    /// For providers built at runtime, e.g. by [`crate::registry::ProviderRegistry`].
+    pub fn new_with_boxed(
+        client: C,
+        provider: Box<dyn ProviderExtAuthorizationCodeGrant<Scope = String> + Send + Sync>,
+        scopes: impl Into<Option<Vec<String>>>,
+        extensions_builder: Box<dyn ExtensionsBuilder<String> + Send + Sync>,
+    ) -> Self
+    where
+        C: Clone,
+    {
+        Self {
+            flow: Flow::new(client.clone()),
+            provider,
+            scopes: scopes.into(),
+            extensions_builder,
+            client_with_user_info: client,
+            state_store: None,
+            state_ttl: TTL_DEFAULT,
             pkce_enabled: true,
         }
     }
@@ -91,11 +130,22 @@ where
     pub fn disable_pkce(&mut self) {
         self.pkce_enabled = false;
     }
+
+    pub fn set_state_store<S>(&mut self, state_store: S)
+    where
+        S: SigninStateStore + Send + Sync + 'static,
+    {
+        self.state_store = Some(Arc::new(state_store));
+    }
+
+    pub fn set_state_ttl(&mut self, state_ttl: Duration) {
+        self.state_ttl = state_ttl;
+    }
 }
 
 impl<C> SigninFlow<C>
 where
-    C: Client + Send + Sync,
+    C: RetryableClient + Send + Sync,
 {
     pub fn is_oidc_enabled(&self) -> bool {
         if let Some(oidc_support_type) = self.provider.oidc_support_type() {
@@ -137,6 +187,95 @@ where
             .build_authorization_url(self.provider.as_ref(), self.scopes.to_owned(), config)
     }
 
+    /// Generates `state`, plus `nonce` and `code_verifier` when OIDC and PKCE are enabled,
+    /// and saves them to `state_store` along with `max_age` and `acr_values` of `authentication`.
+    ///
+    /// Returns the ticket with the URL, keep it in a cookie for
+    /// [`SigninFlow::handle_callback_by_query_with_state_store`].
+    pub fn build_authorization_url_with_state_store(
+        &self,
+        authentication: impl Into<Option<AuthenticationParameters>>,
+    ) -> Result<(Url, String), SigninFlowSaveStateError> {
+        let state_store = self
+            .state_store
+            .as_ref()
+            .ok_or(SigninFlowSaveStateError::StateStoreMissing)?;
+
+        let authentication: AuthenticationParameters = authentication.into().unwrap_or_default();
+
+        let mut config = SigninFlowBuildAuthorizationUrlConfiguration::new();
+        let mut signin_state = SigninState {
+            state: gen_state(None),
+            code_verifier: None,
+            nonce: None,
+            max_age: authentication.max_age,
+            acr_values: authentication.acr_values.to_owned().map(|x| x.0),
+        };
+        config.set_state(signin_state.state.to_owned());
+        config.set_authentication(authentication);
+
+        if self.is_oidc_enabled() {
+            let nonce = gen_nonce(None);
+            config.set_nonce(nonce.to_owned());
+            signin_state.nonce = Some(nonce);
+        }
+
+        if self.is_pkce_enabled() {
+            let code_verifier = gen_code_verifier(None);
+            let (code_challenge, code_challenge_method) =
+                gen_code_challenge(code_verifier.to_owned(), None);
+            config.set_code_challenge(code_challenge, code_challenge_method);
+            signin_state.code_verifier = Some(code_verifier);
+        }
+
+        let url = self
+            .build_authorization_url(config)
+            .map_err(SigninFlowSaveStateError::BuildAuthorizationUrlError)?;
+
+        let ticket = state_store
+            .save(&signin_state, self.state_ttl)
+            .map_err(SigninFlowSaveStateError::StateStoreError)?;
+
+        Ok((url, ticket))
+    }
+
+    /// Takes what [`SigninFlow::build_authorization_url_with_state_store`] saved, a ticket can
+    /// only be used once.
+    pub async fn handle_callback_by_query_with_state_store(
+        &self,
+        query: impl AsRef<str>,
+        ticket: impl AsRef<str>,
+    ) -> SigninFlowHandleCallbackRet {
+        let state_store = match self.state_store.as_ref() {
+            Some(x) => x,
+            None => {
+                return SigninFlowHandleCallbackRet::TakeStateError(
+                    SigninFlowTakeStateError::StateStoreMissing,
+                )
+            }
+        };
+
+        let signin_state = match state_store.take(ticket.as_ref()) {
+            Ok(Some(x)) => x,
+            Ok(None) => {
+                return SigninFlowHandleCallbackRet::TakeStateError(
+                    SigninFlowTakeStateError::StateNotFound,
+                )
+            }
+            Err(err) => {
+                return SigninFlowHandleCallbackRet::TakeStateError(
+                    SigninFlowTakeStateError::StateStoreError(err),
+                )
+            }
+        };
+
+        self.handle_callback_by_query(
+            query,
+            SigninFlowHandleCallbackByQueryConfiguration::from(signin_state),
+        )
+        .await
+    }
+
     pub async fn handle_callback_by_query(
         &self,
         query: impl AsRef<str>,
@@ -145,6 +284,8 @@ where
         let config: SigninFlowHandleCallbackByQueryConfiguration =
             config.into().unwrap_or_default();
         let nonce = config.nonce.to_owned();
+        let max_age = config.max_age;
+        let acr_values = config.acr_values.to_owned();
 
         let access_token = match self
             .flow
@@ -155,6 +296,25 @@ where
             Err(err) => return SigninFlowHandleCallbackRet::FlowHandleCallbackError(err),
         };
 
+        if max_age.is_some() || acr_values.is_some() {
+            let validation = IdTokenValidation {
+                client_id: self.provider.client_id().cloned(),
+                nonce: nonce.to_owned(),
+                max_age: max_age.map(Duration::from_secs),
+                acr_values: acr_values.unwrap_or_default(),
+                ..Default::default()
+            };
+            if let Err(err) = access_token
+                .id_token
+                .as_ref()
+                .ok_or(IdTokenError::Missing)
+                .and_then(IdTokenClaims::decode)
+                .and_then(|claims| claims.validate(&validation))
+            {
+                return SigninFlowHandleCallbackRet::IdTokenValidateError((access_token, err));
+            }
+        }
+
         let grant_info = GrantInfo::AuthorizationCodeGrant(AuthorizationCodeGrantInfo {
             provider: self.provider.as_ref(),
             authorization_request_scopes: self.scopes.as_ref(),
@@ -172,37 +332,32 @@ where
                 SigninFlowHandleCallbackRet::Ok((access_token, user_info))
             }
             Ok(BuilderObtainUserInfoOutput::Respond(user_info_endpoint)) => {
-                match self
-                    .client_with_user_info
-                    .respond_dyn_endpoint(user_info_endpoint.as_ref())
+                self.execute_user_info_pipeline(access_token, user_info_endpoint.into())
+                    .await
+            }
+            Ok(BuilderObtainUserInfoOutput::Pipeline(user_info_pipeline)) => {
+                self.execute_user_info_pipeline(access_token, user_info_pipeline)
                     .await
-                {
-                    Ok(user_info) => SigninFlowHandleCallbackRet::Ok((access_token, user_info)),
-                    Err(err) => match err {
-                        ClientRespondEndpointError::RespondFailed(err) => {
-                            SigninFlowHandleCallbackRet::OkButUserInfoEndpointExecuteError((
-                                access_token,
-                                EndpointExecuteError::RespondFailed(Box::new(err)),
-                            ))
-                        }
-                        ClientRespondEndpointError::EndpointRenderRequestFailed(err) => {
-                            SigninFlowHandleCallbackRet::OkButUserInfoEndpointExecuteError((
-                                access_token,
-                                EndpointExecuteError::RenderRequestError(err),
-                            ))
-                        }
-                        ClientRespondEndpointError::EndpointParseResponseFailed(err) => {
-                            SigninFlowHandleCallbackRet::OkButUserInfoEndpointExecuteError((
-                                access_token,
-                                EndpointExecuteError::ParseResponseError(err),
-                            ))
-                        }
-                    },
-                }
             }
             Err(err) => SigninFlowHandleCallbackRet::OkButUserInfoObtainError((access_token, err)),
         }
     }
+
+    async fn execute_user_info_pipeline(
+        &self,
+        access_token: AccessTokenResponseSuccessfulBody<String>,
+        user_info_pipeline: UserInfoPipeline,
+    ) -> SigninFlowHandleCallbackRet {
+        match user_info_pipeline
+            .execute(&self.client_with_user_info)
+            .await
+        {
+            Ok(user_info) => SigninFlowHandleCallbackRet::Ok((access_token, user_info)),
+            Err(err) => {
+                SigninFlowHandleCallbackRet::OkButUserInfoEndpointExecuteError((access_token, err))
+            }
+        }
+    }
 }
 
 #[cfg(test)]
@@ -215,6 +370,7 @@ mod tests {
     use oauth2_google::{GoogleExtensionsBuilder, GoogleProviderForWebServerApps, GoogleScope};
 
     use http_api_isahc_client::IsahcClient;
+    use oauth2_client::authorization_code_grant::FlowHandleCallbackError;
 
     #[test]
     fn test_build_authorization_url() -> Result<(), Box<dyn std::error::Error>> {
@@ -226,7 +382,7 @@ mod tests {
                 IsahcClient::new()?,
                 GithubProviderWithWebApplication::new(
                     "client_id".to_owned(),
-                    "client_secret".to_owned(),
+                    "client_secret".into(),
                     "https://client.example.com/cb".parse()?,
                 )?,
                 vec![GithubScope::User],
@@ -239,7 +395,7 @@ mod tests {
                 IsahcClient::new()?,
                 GoogleProviderForWebServerApps::new(
                     "client_id".to_owned(),
-                    "client_secret".to_owned(),
+                    "client_secret".into(),
                     "https://client.example.com/cb".parse()?,
                 )?,
                 vec![GoogleScope::Email],
@@ -268,7 +424,7 @@ mod tests {
                 IsahcClient::new()?,
                 GithubProviderWithWebApplication::new(
                     "client_id".to_owned(),
-                    "client_secret".to_owned(),
+                    "client_secret".into(),
                     "https://client.example.com/cb".parse()?,
                 )?,
                 vec![GithubScope::User],
@@ -284,4 +440,103 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_with_state_store() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::web_app::{MemorySigninStateStore, SigninFlowTakeStateError};
+
+        let state_store = MemorySigninStateStore::new();
+        let flow = SigninFlow::new(
+            IsahcClient::new()?,
+            GoogleProviderForWebServerApps::new(
+                "client_id".to_owned(),
+                "client_secret".into(),
+                "https://client.example.com/cb".parse()?,
+            )?,
+            vec![GoogleScope::Openid, GoogleScope::Email],
+            GoogleExtensionsBuilder,
+        )
+        .configure(|x| x.set_state_store(state_store.to_owned()));
+
+        let (url, ticket) = flow.build_authorization_url_with_state_store(
+            AuthenticationParameters::new().configure(|x| x.max_age = Some(300)),
+        )?;
+        let query = url.query_pairs().collect::<HashMap<_, _>>();
+        assert!(query.contains_key("state"));
+        assert!(query.contains_key("nonce"));
+        assert_eq!(query.get("max_age").map(|x| x.as_ref()), Some("300"));
+        assert_eq!(query.contains_key("code_challenge"), flow.is_pkce_enabled());
+        assert_eq!(state_store.len(), 1);
+
+        match flow
+            .handle_callback_by_query_with_state_store("code=CODE&state=OTHER", &ticket)
+            .await
+        {
+            SigninFlowHandleCallbackRet::FlowHandleCallbackError(
+                FlowHandleCallbackError::StateMismatch,
+            ) => {}
+            x => panic!("{x:?}"),
+        }
+        match flow
+            .handle_callback_by_query_with_state_store("code=CODE&state=OTHER", &ticket)
+            .await
+        {
+            SigninFlowHandleCallbackRet::TakeStateError(
+                SigninFlowTakeStateError::StateNotFound,
+            ) => {}
+            x => panic!("{x:?}"),
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_with_state_store_and_pkce() -> Result<(), Box<dyn std::error::Error>> {
+        use oauth2_client::{oauth2_core::types::CodeVerifier, re_exports::url};
+        use oauth2_mock_server::{MockEndpoint, MockExtensionsBuilder, MockServer};
+
+        use crate::web_app::MemorySigninStateStore;
+
+        let server = MockServer::default();
+        let state_store = MemorySigninStateStore::new();
+        let flow = SigninFlow::new(
+            server.client(),
+            server.provider(),
+            vec!["openid".to_owned(), "profile".to_owned()],
+            MockExtensionsBuilder::new(&server),
+        )
+        .configure(|x| x.set_state_store(state_store.to_owned()));
+        assert!(flow.is_pkce_enabled());
+
+        let (url, ticket) = flow.build_authorization_url_with_state_store(None)?;
+        let code_challenge = url
+            .query_pairs()
+            .find(|(k, _)| k == "code_challenge")
+            .map(|(_, v)| v.to_string())
+            .ok_or("code_challenge missing")?;
+
+        let redirect_url = server.authorize(&url).map_err(|_| "authorize failed")?;
+        match flow
+            .handle_callback_by_query_with_state_store(
+                redirect_url.query().unwrap_or_default(),
+                &ticket,
+            )
+            .await
+        {
+            SigninFlowHandleCallbackRet::Ok(_) => {}
+            x => panic!("{x:?}"),
+        }
+
+        let request_bodies = server.request_bodies(MockEndpoint::Token);
+        let code_verifier = url::form_urlencoded::parse(request_bodies[0].as_bytes())
+            .find(|(k, _)| k == "code_verifier")
+            .map(|(_, v)| v.to_string())
+            .ok_or("code_verifier missing")?;
+        assert_eq!(
+            gen_code_challenge(CodeVerifier::from(code_verifier), None).0,
+            code_challenge
+        );
+
+        Ok(())
+    }
 }
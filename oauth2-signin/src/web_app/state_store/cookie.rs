@@ -0,0 +1,171 @@
+use core::time::Duration;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Key, Nonce,
+};
+use oauth2_client::re_exports::{serde_json, Deserialize, Serialize};
+
+use super::{SigninState, SigninStateStore, SigninStateStoreError};
+
+const NONCE_LEN: usize = 12;
+// Blobs sealed with the same key for another purpose do not open here.
+const AAD: &[u8] = b"oauth2-signin state_store v1";
+
+//
+//
+//
+/// Stateless, the ticket is the `SigninState` itself, sealed with ChaCha20-Poly1305 and carrying
+/// its expiry. Tampered tickets and tickets sealed with another key are taken as unknown ones.
+///
+/// Nothing is kept server side, so a ticket can be taken again until it expires; clear the cookie
+/// on the callback. Replaying it is harmless once the `code` has been exchanged.
+#[derive(Clone)]
+pub struct CookieSigninStateStore {
+    cipher: ChaCha20Poly1305,
+}
+
+impl core::fmt::Debug for CookieSigninStateStore {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CookieSigninStateStore").finish()
+    }
+}
+
+impl CookieSigninStateStore {
+    /// `key` must be random and shared by every instance, e.g. from a secret manager.
+    pub fn new(key: [u8; 32]) -> Self {
+        Self {
+            cipher: ChaCha20Poly1305::new(Key::from_slice(&key)),
+        }
+    }
+
+    fn save_at(
+        &self,
+        signin_state: &SigninState,
+        ttl: Duration,
+        now: Duration,
+    ) -> Result<String, SigninStateStoreError> {
+        let sealed = Sealed {
+            signin_state: signin_state.to_owned(),
+            exp: (now + ttl).as_secs(),
+        };
+        let plaintext = serde_json::to_vec(&sealed)
+            .map_err(|err| SigninStateStoreError::SerFailed(Box::new(err)))?;
+
+        let mut nonce = [0_u8; NONCE_LEN];
+        getrandom::getrandom(&mut nonce)
+            .map_err(|err| SigninStateStoreError::Other(Box::new(err)))?;
+
+        let ciphertext = self
+            .cipher
+            .encrypt(
+                Nonce::from_slice(&nonce),
+                Payload {
+                    msg: &plaintext,
+                    aad: AAD,
+                },
+            )
+            .map_err(|_| SigninStateStoreError::EncryptFailed)?;
+
+        let mut ticket = nonce.to_vec();
+        ticket.extend(ciphertext);
+        Ok(general_purpose::URL_SAFE_NO_PAD.encode(ticket))
+    }
+
+    fn take_at(&self, ticket: &str, now: Duration) -> Option<SigninState> {
+        let ticket = general_purpose::URL_SAFE_NO_PAD.decode(ticket).ok()?;
+        if ticket.len() < NONCE_LEN {
+            return None;
+        }
+        let (nonce, ciphertext) = ticket.split_at(NONCE_LEN);
+
+        let plaintext = self
+            .cipher
+            .decrypt(
+                Nonce::from_slice(nonce),
+                Payload {
+                    msg: ciphertext,
+                    aad: AAD,
+                },
+            )
+            .ok()?;
+        let sealed = serde_json::from_slice::<Sealed>(&plaintext).ok()?;
+
+        if sealed.exp <= now.as_secs() {
+            return None;
+        }
+        Some(sealed.signin_state)
+    }
+}
+
+impl SigninStateStore for CookieSigninStateStore {
+    fn save(
+        &self,
+        signin_state: &SigninState,
+        ttl: Duration,
+    ) -> Result<String, SigninStateStoreError> {
+        self.save_at(signin_state, ttl, unix_now()?)
+    }
+
+    fn take(&self, ticket: &str) -> Result<Option<SigninState>, SigninStateStoreError> {
+        Ok(self.take_at(ticket, unix_now()?))
+    }
+}
+
+fn unix_now() -> Result<Duration, SigninStateStoreError> {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_err(|err| SigninStateStoreError::Other(Box::new(err)))
+}
+
+//
+#[derive(Deserialize, Serialize)]
+struct Sealed {
+    #[serde(rename = "s")]
+    signin_state: SigninState,
+    exp: u64,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_take() -> Result<(), Box<dyn std::error::Error>> {
+        let store = CookieSigninStateStore::new([1; 32]);
+        let signin_state = SigninState {
+            state: "STATE".into(),
+            code_verifier: None,
+            nonce: Some("NONCE".to_owned()),
+            max_age: Some(300),
+            acr_values: None,
+        };
+        let now = Duration::from_secs(1_700_000_000);
+
+        let ticket = store.save_at(&signin_state, Duration::from_secs(60), now)?;
+        assert!(!ticket.contains("STATE") && !ticket.contains("NONCE"));
+        let taken = store.take_at(&ticket, now).unwrap();
+        assert_eq!(taken.state.expose_secret(), "STATE");
+        assert_eq!(taken.nonce, Some("NONCE".to_owned()));
+        assert_eq!(taken.max_age, Some(300));
+
+        assert!(store
+            .take_at(&ticket, now + Duration::from_secs(60))
+            .is_none());
+
+        let mut tampered = general_purpose::URL_SAFE_NO_PAD.decode(&ticket)?;
+        *tampered.last_mut().unwrap() ^= 1;
+        assert!(store
+            .take_at(&general_purpose::URL_SAFE_NO_PAD.encode(tampered), now)
+            .is_none());
+
+        assert!(CookieSigninStateStore::new([2; 32])
+            .take_at(&ticket, now)
+            .is_none());
+        assert!(store.take_at("foo", now).is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+use core::time::Duration;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use oauth2_client::utils::gen_nonce;
+
+use super::{SigninState, SigninStateStore, SigninStateStoreError};
+
+pub const TICKET_LEN: usize = 32;
+
+//
+//
+//
+/// Single process only, use [`super::CookieSigninStateStore`] or a shared backend when running
+/// several instances.
+#[derive(Debug, Clone, Default)]
+pub struct MemorySigninStateStore {
+    entries: Arc<Mutex<HashMap<String, (SigninState, Instant)>>>,
+}
+
+impl MemorySigninStateStore {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().map(|x| x.len()).unwrap_or_default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn save_at(
+        &self,
+        signin_state: &SigninState,
+        ttl: Duration,
+        now: Instant,
+    ) -> Result<String, SigninStateStoreError> {
+        let mut entries = self
+            .entries
+            .lock()
+            .map_err(|_| SigninStateStoreError::LockPoisoned)?;
+
+        // Abandoned signins never reach `take`.
+        entries.retain(|_, (_, expires_at)| *expires_at > now);
+
+        let ticket = gen_nonce(TICKET_LEN);
+        entries.insert(ticket.to_owned(), (signin_state.to_owned(), now + ttl));
+        Ok(ticket)
+    }
+
+    fn take_at(
+        &self,
+        ticket: &str,
+        now: Instant,
+    ) -> Result<Option<SigninState>, SigninStateStoreError> {
+        let mut entries = self
+            .entries
+            .lock()
+            .map_err(|_| SigninStateStoreError::LockPoisoned)?;
+
+        Ok(entries
+            .remove(ticket)
+            .filter(|(_, expires_at)| *expires_at > now)
+            .map(|(signin_state, _)| signin_state))
+    }
+}
+
+impl SigninStateStore for MemorySigninStateStore {
+    fn save(
+        &self,
+        signin_state: &SigninState,
+        ttl: Duration,
+    ) -> Result<String, SigninStateStoreError> {
+        self.save_at(signin_state, ttl, Instant::now())
+    }
+
+    fn take(&self, ticket: &str) -> Result<Option<SigninState>, SigninStateStoreError> {
+        self.take_at(ticket, Instant::now())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_save_and_take() -> Result<(), Box<dyn std::error::Error>> {
+        let store = MemorySigninStateStore::new();
+        let signin_state = SigninState {
+            state: "STATE".into(),
+            code_verifier: Some("CODE_VERIFIER".into()),
+            nonce: None,
+            max_age: None,
+            acr_values: None,
+        };
+        let now = Instant::now();
+
+        let ticket = store.save_at(&signin_state, Duration::from_secs(60), now)?;
+        assert_eq!(ticket.len(), TICKET_LEN);
+        let taken = store.take_at(&ticket, now)?.unwrap();
+        assert_eq!(taken.state.expose_secret(), "STATE");
+        assert_eq!(
+            taken.code_verifier.map(|x| x.expose_secret().to_owned()),
+            Some("CODE_VERIFIER".to_owned())
+        );
+        // Exactly once.
+        assert!(store.take_at(&ticket, now)?.is_none());
+
+        let ticket = store.save_at(&signin_state, Duration::from_secs(60), now)?;
+        assert!(store
+            .take_at(&ticket, now + Duration::from_secs(61))?
+            .is_none());
+
+        store.save_at(&signin_state, Duration::from_secs(60), now)?;
+        store.save_at(
+            &signin_state,
+            Duration::from_secs(60),
+            now + Duration::from_secs(61),
+        )?;
+        assert_eq!(store.len(), 1);
+
+        Ok(())
+    }
+}
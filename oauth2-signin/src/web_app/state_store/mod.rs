@@ -0,0 +1,67 @@
+//! Keeps `state`, `nonce` and the PKCE `code_verifier` between the authorization redirect and
+//! the callback.
+//!
+//! [`SigninStateStore::save`] returns a ticket, the app sets it as a cookie on the redirect and
+//! passes it back on the callback. Binding the values to the browser this way is what makes
+//! `state` a CSRF protection.
+
+#[cfg(feature = "with-cookie-state-store")]
+pub mod cookie;
+pub mod memory;
+
+#[cfg(feature = "with-cookie-state-store")]
+pub use cookie::CookieSigninStateStore;
+pub use memory::MemorySigninStateStore;
+
+use core::time::Duration;
+
+use oauth2_client::{
+    oauth2_core::types::{CodeVerifier, Nonce, State},
+    re_exports::{thiserror, Deserialize, Serialize},
+};
+
+pub const TTL_DEFAULT: Duration = Duration::from_secs(60 * 10);
+
+//
+//
+//
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SigninState {
+    pub state: State,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub code_verifier: Option<CodeVerifier>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<Nonce>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_age: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub acr_values: Option<Vec<String>>,
+}
+
+//
+//
+//
+pub trait SigninStateStore: core::fmt::Debug {
+    /// Returns the ticket to hand back to [`SigninStateStore::take`].
+    fn save(
+        &self,
+        signin_state: &SigninState,
+        ttl: Duration,
+    ) -> Result<String, SigninStateStoreError>;
+
+    /// `Ok(None)` when the ticket is unknown, expired or was already taken.
+    fn take(&self, ticket: &str) -> Result<Option<SigninState>, SigninStateStoreError>;
+}
+
+//
+#[derive(thiserror::Error, Debug)]
+pub enum SigninStateStoreError {
+    #[error("SerFailed {0}")]
+    SerFailed(Box<dyn std::error::Error + Send + Sync>),
+    #[error("EncryptFailed")]
+    EncryptFailed,
+    #[error("LockPoisoned")]
+    LockPoisoned,
+    #[error("Other {0}")]
+    Other(Box<dyn std::error::Error + Send + Sync>),
+}
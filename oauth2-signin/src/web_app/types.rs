@@ -7,9 +7,13 @@ use oauth2_client::{
         AccessTokenResponseSuccessfulBody, BuilderObtainUserInfoError,
         EndpointExecuteError as UserInfoEndpointExecuteError, UserInfo,
     },
+    id_token::IdTokenError,
     oauth2_core::types::{CodeVerifier, Nonce, State},
+    re_exports::thiserror,
 };
 
+use super::{SigninState, SigninStateStoreError};
+
 //
 //
 //
@@ -17,12 +21,38 @@ pub type SigninFlowBuildAuthorizationUrlError = FlowBuildAuthorizationUrlError;
 
 pub type SigninFlowBuildAuthorizationUrlConfiguration = FlowBuildAuthorizationUrlConfiguration;
 
+//
+#[derive(thiserror::Error, Debug)]
+pub enum SigninFlowSaveStateError {
+    #[error("StateStoreMissing")]
+    StateStoreMissing,
+    #[error("StateStoreError {0}")]
+    StateStoreError(SigninStateStoreError),
+    #[error("BuildAuthorizationUrlError {0}")]
+    BuildAuthorizationUrlError(SigninFlowBuildAuthorizationUrlError),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SigninFlowTakeStateError {
+    #[error("StateStoreMissing")]
+    StateStoreMissing,
+    #[error("StateStoreError {0}")]
+    StateStoreError(SigninStateStoreError),
+    /// Unknown, expired or already taken.
+    #[error("StateNotFound")]
+    StateNotFound,
+}
+
 //
 #[derive(Debug, Clone, Default)]
 pub struct SigninFlowHandleCallbackByQueryConfiguration {
     pub state: Option<State>,
     pub code_verifier: Option<CodeVerifier>,
     pub nonce: Option<Nonce>,
+    /// `max_age` of the authorization request, `auth_time` of the ID token is checked against it.
+    pub max_age: Option<u64>,
+    /// `acr_values` of the authorization request, `acr` of the ID token must be one of them.
+    pub acr_values: Option<Vec<String>>,
 }
 impl SigninFlowHandleCallbackByQueryConfiguration {
     pub fn new() -> Self {
@@ -48,6 +78,25 @@ impl SigninFlowHandleCallbackByQueryConfiguration {
     pub fn set_nonce(&mut self, nonce: Nonce) {
         self.nonce = Some(nonce);
     }
+
+    pub fn set_max_age(&mut self, max_age: u64) {
+        self.max_age = Some(max_age);
+    }
+
+    pub fn set_acr_values(&mut self, acr_values: Vec<String>) {
+        self.acr_values = Some(acr_values);
+    }
+}
+impl From<SigninState> for SigninFlowHandleCallbackByQueryConfiguration {
+    fn from(signin_state: SigninState) -> Self {
+        Self {
+            state: Some(signin_state.state),
+            code_verifier: signin_state.code_verifier,
+            nonce: signin_state.nonce,
+            max_age: signin_state.max_age,
+            acr_values: signin_state.acr_values,
+        }
+    }
 }
 impl From<SigninFlowHandleCallbackByQueryConfiguration> for FlowHandleCallbackByQueryConfiguration {
     fn from(c: SigninFlowHandleCallbackByQueryConfiguration) -> Self {
@@ -60,6 +109,7 @@ impl From<SigninFlowHandleCallbackByQueryConfiguration> for FlowHandleCallbackBy
 
 //
 #[derive(Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum SigninFlowHandleCallbackRet {
     Ok((AccessTokenResponseSuccessfulBody<String>, UserInfo)),
     OkButUserInfoNone(AccessTokenResponseSuccessfulBody<String>),
@@ -76,4 +126,6 @@ pub enum SigninFlowHandleCallbackRet {
         ),
     ),
     FlowHandleCallbackError(FlowHandleCallbackError),
+    IdTokenValidateError((AccessTokenResponseSuccessfulBody<String>, IdTokenError)),
+    TakeStateError(SigninFlowTakeStateError),
 }
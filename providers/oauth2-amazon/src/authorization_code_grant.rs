@@ -45,6 +45,10 @@ impl Provider for AmazonProviderWithWebServices {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "amazon"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for AmazonProviderWithWebServices {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -68,16 +72,18 @@ impl ProviderExtAuthorizationCodeGrant for AmazonProviderWithWebServices {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = AmazonProviderWithWebServices::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
             None,
         )?;
@@ -86,7 +92,7 @@ mod tests {
             &provider,
             vec![AmazonScope::Profile, AmazonScope::PostalCode],
         )
-        .configure(|x| x.state = Some("STATE".to_owned()))
+        .configure(|x| x.state = Some("STATE".into()))
         .render_request()?;
 
         assert_eq!(request.uri(), "https://www.amazon.com/ap/oa?response_type=code&client_id=CLIENT_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=profile+postal_code&state=STATE");
@@ -98,12 +104,12 @@ mod tests {
     fn access_token_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = AmazonProviderWithWebServices::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
             None,
         )?;
 
-        let request = AccessTokenEndpoint::new(&provider, "CODE".to_owned()).render_request()?;
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
 
         assert_eq!(request.body(), b"grant_type=authorization_code&code=CODE&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&client_id=CLIENT_ID&client_secret=CLIENT_SECRET");
 
@@ -114,16 +120,15 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = AmazonProviderWithWebServices::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
             None,
         )?;
 
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(_body) => {}
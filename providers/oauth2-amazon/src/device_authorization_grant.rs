@@ -43,6 +43,10 @@ impl Provider for AmazonProviderWithDevices {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "amazon"
+    }
 }
 impl ProviderExtDeviceAuthorizationGrant for AmazonProviderWithDevices {
     fn device_authorization_endpoint_url(&self) -> &Url {
@@ -101,10 +105,12 @@ pub enum DeviceAccessTokenRequestRenderingError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         device_authorization_grant::DeviceAccessTokenEndpoint,
         oauth2_core::re_exports::AccessTokenResponseErrorBodyError,
-        re_exports::{Response, RetryableEndpoint as _},
+        re_exports::RetryableEndpoint as _,
     };
 
     #[test]
@@ -123,13 +129,11 @@ mod tests {
         );
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/device_access_token_err_when_unsupported_grant_type.json"
-        );
-        let body_ret = endpoint.parse_response(
-            Response::builder().body(response_body.as_bytes().to_vec())?,
-            None,
-        )?;
+        let client = ReplayClient::open(fixture_path!(
+            "device_access_token_err_when_unsupported_grant_type.json"
+        ))?;
+        let body_ret = endpoint
+            .parse_response(client.replay_request(endpoint.render_request(None)?)?, None)?;
         match body_ret {
             Ok(Ok(body)) => panic!("{body:?}"),
             Ok(Err(body)) => {
@@ -1,6 +1,6 @@
 use oauth2_client::re_exports::{
-    http::header::ACCEPT, serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request,
-    Response, SerdeJsonError, Serialize, MIME_APPLICATION_JSON,
+    http::header::ACCEPT, serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint,
+    HttpError, Request, Response, SerdeJsonError, Serialize, MIME_APPLICATION_JSON,
 };
 
 // Ref https://developer.amazon.com/docs/login-with-amazon/obtain-customer-profile.html
@@ -9,12 +9,12 @@ pub const URL: &str = "https://api.amazon.com/user/profile";
 //
 #[derive(Debug, Clone)]
 pub struct UserEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl UserEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -28,7 +28,7 @@ impl Endpoint for UserEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header("x-amz-access-token", &self.access_token)
+            .header("x-amz-access-token", self.access_token.expose_secret())
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(UserEndpointError::MakeRequestFailed)?;
@@ -67,15 +67,19 @@ pub enum UserEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/user.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("user.json"))?
+            .replay(&UserEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(user.user_id, "amzn1.account.AGH4ZOFGBUJ5KSAFB5DVL5KTBU3Q");
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "with-tracing")]
+use oauth2_client::trace;
 use oauth2_client::{
     extensions::{EndpointParseResponseError, EndpointRenderRequestError, UserInfo},
     re_exports::{serde_json, Body, Endpoint, Request, Response},
@@ -25,13 +27,26 @@ impl Endpoint for AmazonUserInfoEndpoint {
     type ParseResponseError = EndpointParseResponseError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
-        self.inner.render_request().map_err(Into::into)
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span("amazon", None, trace::endpoint::USER_INFO);
+
+        let request = self.inner.render_request()?;
+
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
+
+        Ok(request)
     }
 
     fn parse_response(
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span("amazon", None, trace::endpoint::USER_INFO);
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         UserInfo::try_from(self.inner.parse_response(response)?)
             .map_err(EndpointParseResponseError::ToOutputFailed)
     }
@@ -67,6 +82,7 @@ impl TryFrom<User> for UserInfo {
             raw: serde_json::to_value(user)
                 .map(|x| x.as_object().cloned())?
                 .ok_or_else(|| "unreachable".to_owned())?,
+            ..Default::default()
         })
     }
 }
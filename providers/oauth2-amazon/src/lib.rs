@@ -1,3 +1,4 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const TOKEN_URL_NA: &str = "https://api.amazon.com/auth/o2/token";
@@ -6,6 +7,17 @@ pub const TOKEN_URL_FE: &str = "https://api.amazon.co.jp/auth/o2/token";
 pub const AUTHORIZATION_URL: &str = "https://www.amazon.com/ap/oa";
 pub const DEVICE_AUTHORIZATION_URL: &str = "https://api.amazon.com/auth/o2/create/codepair";
 
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode, GrantType::DeviceCode],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost, ClientAuthMethod::None],
+    pkce: true,
+    oidc: false,
+    refresh_token: true,
+    revocation: false,
+    user_info: true,
+    scope_delimiter: ' ',
+};
+
 pub mod authorization_code_grant;
 pub mod device_authorization_grant;
 
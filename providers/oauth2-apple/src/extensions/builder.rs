@@ -0,0 +1,29 @@
+use oauth2_client::{
+    extensions::{
+        AccessTokenResponseSuccessfulBody, Builder, BuilderObtainUserInfoError,
+        BuilderObtainUserInfoOutput, GrantInfo, IdTokenExtensionsBuilder,
+    },
+    re_exports::Scope,
+};
+
+use crate::ISSUER;
+
+//
+/// Apple has no userinfo endpoint, `name` is only posted to the redirect uri on first sign in.
+#[derive(Debug, Clone)]
+pub struct AppleExtensionsBuilder;
+
+impl<SCOPE> Builder<SCOPE> for AppleExtensionsBuilder
+where
+    SCOPE: Scope,
+{
+    fn obtain_user_info(
+        &self,
+        grant_info: GrantInfo<SCOPE>,
+        access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
+    ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
+        IdTokenExtensionsBuilder::new()
+            .with_issuer(ISSUER)
+            .obtain_user_info(grant_info, access_token)
+    }
+}
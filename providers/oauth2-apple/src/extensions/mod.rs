@@ -0,0 +1,3 @@
+pub mod builder;
+
+pub use builder::AppleExtensionsBuilder;
@@ -1,8 +1,25 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const TOKEN_URL: &str = "https://appleid.apple.com/auth/token";
 pub const AUTHORIZATION_URL: &str = "https://appleid.apple.com/auth/authorize";
 pub const OAUTH2_TOKEN_URL: &str = "https://appleid.apple.com/auth/oauth2/token";
+pub const ISSUER: &str = "https://appleid.apple.com";
+
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode, GrantType::ClientCredentials],
+    client_auth_methods: &[
+        ClientAuthMethod::ClientSecretPost,
+        ClientAuthMethod::ClientSecretBasic,
+        ClientAuthMethod::ClientSecretQuery,
+    ],
+    pkce: false,
+    oidc: true,
+    refresh_token: true,
+    revocation: true,
+    user_info: true,
+    scope_delimiter: ' ',
+};
 
 pub mod authorization_code_grant;
 pub mod client_credentials_grant;
@@ -10,6 +27,9 @@ pub mod client_credentials_grant;
 pub use authorization_code_grant::AppleProviderWithAppleJs;
 pub use client_credentials_grant::AppleProviderForSearchAdsApi;
 
+pub mod extensions;
+pub use extensions::AppleExtensionsBuilder;
+
 #[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
 pub enum AppleScope {
     //
@@ -1,6 +1,6 @@
 use oauth2_client::{
     authorization_code_grant::provider_ext::AccessTokenRequestBody,
-    oauth2_core::access_token_request::GRANT_TYPE_WITH_AUTHORIZATION_CODE_GRANT,
+    oauth2_core::{access_token_request::GRANT_TYPE_WITH_AUTHORIZATION_CODE_GRANT, types::Code},
     re_exports::{
         serde_qs, thiserror, Body, ClientId, ClientSecret, Deserialize, HttpError, RedirectUri,
         Request, SerdeQsError, Serialize, Url, UrlParseError,
@@ -48,6 +48,10 @@ impl Provider for BaiduProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "baidu"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for BaiduProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -74,7 +78,7 @@ impl ProviderExtAuthorizationCodeGrant for BaiduProviderWithWebApplication {
                 grant_type: GRANT_TYPE_WITH_AUTHORIZATION_CODE_GRANT.to_owned(),
                 code: body.code.to_owned(),
                 client_id: this.client_id.to_string(),
-                client_secret: this.client_secret.to_string(),
+                client_secret: this.client_secret.to_owned(),
                 redirect_uri: this.redirect_uri.to_string(),
             };
 
@@ -100,9 +104,9 @@ impl ProviderExtAuthorizationCodeGrant for BaiduProviderWithWebApplication {
 #[derive(Serialize, Deserialize)]
 pub struct BaiduAccessTokenRequestBody {
     pub grant_type: String,
-    pub code: String,
+    pub code: Code,
     pub client_id: String,
-    pub client_secret: String,
+    pub client_secret: ClientSecret,
     pub redirect_uri: String,
 }
 
@@ -118,22 +122,24 @@ pub enum AccessTokenRequestRenderingError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = BaiduProviderWithWebApplication::new(
             "APP_KEY".to_owned(),
-            "SECRET_KEY".to_owned(),
+            "SECRET_KEY".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         let request =
             AuthorizationEndpoint::new(&provider, vec![BaiduScope::Basic, BaiduScope::Netdisk])
-                .configure(|x| x.state = Some("STATE".to_owned()))
+                .configure(|x| x.state = Some("STATE".into()))
                 .render_request()?;
 
         assert_eq!(request.uri(), "http://openapi.baidu.com/oauth/2.0/authorize?response_type=code&client_id=APP_KEY&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=basic+netdisk&state=STATE");
@@ -145,11 +151,11 @@ mod tests {
     fn access_token_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = BaiduProviderWithWebApplication::new(
             "APP_KEY".to_owned(),
-            "SECRET_KEY".to_owned(),
+            "SECRET_KEY".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let request = AccessTokenEndpoint::new(&provider, "CODE".to_owned()).render_request()?;
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
 
         assert_eq!(request.uri().query().unwrap(), "grant_type=authorization_code&code=CODE&client_id=APP_KEY&client_secret=SECRET_KEY&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb");
 
@@ -160,15 +166,14 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = BaiduProviderWithWebApplication::new(
             "APP_KEY".to_owned(),
-            "SECRET_KEY".to_owned(),
+            "SECRET_KEY".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(_body) => {}
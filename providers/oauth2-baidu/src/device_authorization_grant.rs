@@ -48,6 +48,10 @@ impl Provider for BaiduProviderWithDevice {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "baidu"
+    }
 }
 impl ProviderExtDeviceAuthorizationGrant for BaiduProviderWithDevice {
     fn device_authorization_endpoint_url(&self) -> &Url {
@@ -152,7 +156,7 @@ pub struct BaiduDeviceAccessTokenRequestQuery {
     pub grant_type: String,
     pub code: String,
     pub client_id: String,
-    pub client_secret: String,
+    pub client_secret: ClientSecret,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -28,7 +28,7 @@ where
                 .contains(BaiduScope::Netdisk.to_string().as_str())
             {
                 return Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-                    BaiduUserInfoEndpoint::new(&access_token.access_token),
+                    BaiduUserInfoEndpoint::new(access_token.access_token.expose_secret()),
                 )));
             }
         }
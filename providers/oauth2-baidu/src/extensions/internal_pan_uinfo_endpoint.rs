@@ -1,8 +1,9 @@
 // [获取用户信息](https://pan.baidu.com/union/doc/pksg0s9ns)
 
 use oauth2_client::re_exports::{
-    http::header::ACCEPT, serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request,
-    Response, SerdeJsonError, Serialize, Url, UrlParseError, MIME_APPLICATION_JSON,
+    http::header::ACCEPT, serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint,
+    HttpError, Request, Response, SerdeJsonError, Serialize, Url, UrlParseError,
+    MIME_APPLICATION_JSON,
 };
 
 pub const URL: &str = "https://pan.baidu.com/rest/2.0/xpan/nas?method=uinfo";
@@ -10,12 +11,12 @@ pub const URL: &str = "https://pan.baidu.com/rest/2.0/xpan/nas?method=uinfo";
 //
 #[derive(Debug, Clone)]
 pub struct PanUinfoEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl PanUinfoEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -29,7 +30,7 @@ impl Endpoint for PanUinfoEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let mut url = Url::parse(URL).map_err(PanUinfoEndpointError::UrlParseFailed)?;
         url.query_pairs_mut()
-            .append_pair("access_token", &self.access_token);
+            .append_pair("access_token", self.access_token.expose_secret());
 
         let request = Request::builder()
             .uri(url.as_str())
@@ -76,15 +77,19 @@ pub enum PanUinfoEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<Uinfo>(include_str!(
-            "../../tests/response_body_json_files/pan_uinfo.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("pan_uinfo.json"))?
+            .replay(&PanUinfoEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user_info) => {
                 assert_eq!(user_info.baidu_name, "153*****036");
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "with-tracing")]
+use oauth2_client::trace;
 use oauth2_client::{
     extensions::{EndpointParseResponseError, EndpointRenderRequestError, UserInfo},
     re_exports::{serde_json, Body, Endpoint, Request, Response},
@@ -25,13 +27,26 @@ impl Endpoint for BaiduUserInfoEndpoint {
     type ParseResponseError = EndpointParseResponseError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
-        self.inner.render_request().map_err(Into::into)
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span("baidu", None, trace::endpoint::USER_INFO);
+
+        let request = self.inner.render_request()?;
+
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
+
+        Ok(request)
     }
 
     fn parse_response(
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span("baidu", None, trace::endpoint::USER_INFO);
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         UserInfoWrapper::try_from(self.inner.parse_response(response)?)
             .map(|x| x.0)
             .map_err(EndpointParseResponseError::ToOutputFailed)
@@ -68,9 +83,12 @@ impl TryFrom<Uinfo> for UserInfoWrapper {
             uid: user_info.uk.to_string(),
             name: Some(user_info.baidu_name.to_owned()),
             email: None,
+            preferred_username: Some(user_info.netdisk_name.to_owned()),
+            picture: Some(user_info.avatar_url.to_owned()),
             raw: serde_json::to_value(user_info)
                 .map(|x| x.as_object().cloned())?
                 .ok_or_else(|| "unreachable".to_owned())?,
+            ..Default::default()
         }))
     }
 }
@@ -1,9 +1,21 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const TOKEN_URL: &str = "https://openapi.baidu.com/oauth/2.0/token";
 pub const AUTHORIZATION_URL: &str = "http://openapi.baidu.com/oauth/2.0/authorize";
 pub const DEVICE_AUTHORIZATION_URL: &str = "https://openapi.baidu.com/oauth/2.0/device/code";
 
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode, GrantType::DeviceCode],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretQuery],
+    pkce: false,
+    oidc: false,
+    refresh_token: true,
+    revocation: false,
+    user_info: true,
+    scope_delimiter: ' ',
+};
+
 pub mod authorization_code_grant;
 pub mod device_authorization_grant;
 
@@ -22,7 +22,7 @@ where
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            BitbucketUserInfoEndpoint::new(&access_token.access_token),
+            BitbucketUserInfoEndpoint::new(access_token.access_token.expose_secret()),
         )))
     }
 }
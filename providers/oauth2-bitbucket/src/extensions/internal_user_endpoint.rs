@@ -1,7 +1,7 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Map, Request, Response,
-    SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 
 // https://developer.atlassian.com/cloud/bitbucket/rest/api-group-users/#api-user-get
@@ -10,12 +10,12 @@ pub const URL: &str = "https://api.bitbucket.org/2.0/user";
 //
 #[derive(Debug, Clone)]
 pub struct UserEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl UserEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -29,7 +29,10 @@ impl Endpoint for UserEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(UserEndpointError::MakeRequestFailed)?;
@@ -62,6 +65,7 @@ pub struct User {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct UserLinks {
     pub avatar: UserLink,
+    pub html: Option<UserLink>,
 }
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct UserLink {
@@ -81,11 +85,13 @@ pub enum UserEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/user.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("user.json"))?
+            .replay(&UserEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(
                     user.account_id,
@@ -94,5 +100,7 @@ mod tests {
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "with-tracing")]
+use oauth2_client::trace;
 use oauth2_client::{
     extensions::{EndpointParseResponseError, EndpointRenderRequestError, UserInfo},
     re_exports::{serde_json, Body, Endpoint, Request, Response},
@@ -25,13 +27,26 @@ impl Endpoint for BitbucketUserInfoEndpoint {
     type ParseResponseError = EndpointParseResponseError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
-        self.inner.render_request().map_err(Into::into)
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span("bitbucket", None, trace::endpoint::USER_INFO);
+
+        let request = self.inner.render_request()?;
+
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
+
+        Ok(request)
     }
 
     fn parse_response(
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span("bitbucket", None, trace::endpoint::USER_INFO);
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         UserInfo::try_from(self.inner.parse_response(response)?)
             .map_err(EndpointParseResponseError::ToOutputFailed)
     }
@@ -64,9 +79,13 @@ impl TryFrom<User> for UserInfo {
             uid: user.account_id.to_owned(),
             name: Some(user.username.to_owned()),
             email: None,
+            preferred_username: Some(user.username.to_owned()),
+            picture: Some(user.links.avatar.href.to_owned()),
+            profile: user.links.html.as_ref().map(|x| x.href.to_owned()),
             raw: serde_json::to_value(user)
                 .map(|x| x.as_object().cloned())?
                 .ok_or_else(|| "unreachable".to_owned())?,
+            ..Default::default()
         })
     }
 }
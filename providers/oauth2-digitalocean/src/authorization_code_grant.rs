@@ -1,6 +1,8 @@
 use oauth2_client::{
     authorization_code_grant::provider_ext::ProviderExtAuthorizationCodeGrantPkceSupportType,
-    re_exports::{ClientId, ClientSecret, RedirectUri, Url, UrlParseError},
+    re_exports::{
+        ClientId, ClientSecret, Deserialize, Map, RedirectUri, Serialize, Url, UrlParseError, Value,
+    },
     Provider, ProviderExtAuthorizationCodeGrant,
 };
 
@@ -44,6 +46,10 @@ impl Provider for DigitaloceanProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "digitalocean"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for DigitaloceanProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -62,3 +68,55 @@ impl ProviderExtAuthorizationCodeGrant for DigitaloceanProviderWithWebApplicatio
         &self.authorization_endpoint_url
     }
 }
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DigitaloceanAccessTokenResponseExtra {
+    pub info: DigitaloceanAccessTokenResponseExtraInfo,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DigitaloceanAccessTokenResponseExtraInfo {
+    pub name: String,
+    pub email: String,
+    pub uuid: String,
+
+    #[serde(flatten)]
+    pub _extra: Map<String, Value>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::authorization_code_grant::AccessTokenEndpoint;
+
+    #[test]
+    fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = DigitaloceanProviderWithWebApplication::new(
+            "CLIENT_ID".to_owned(),
+            "CLIENT_SECRET".into(),
+            RedirectUri::new("https://client.example.com/cb")?,
+        )?;
+
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
+
+        match body_ret {
+            Ok(body) => {
+                let body = body.try_cast_extra::<DigitaloceanAccessTokenResponseExtra>()?;
+                assert_eq!(
+                    body.extra().map(|x| x.info.uuid.as_str()),
+                    Some("9e323321e52ae626628c6db045a9f27a69474f40")
+                );
+            }
+            Err(body) => panic!("{body:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -6,6 +6,8 @@ use oauth2_client::{
     re_exports::Scope,
 };
 
+use crate::authorization_code_grant::DigitaloceanAccessTokenResponseExtra;
+
 //
 #[derive(Debug, Clone)]
 pub struct DigitaloceanExtensionsBuilder;
@@ -19,7 +21,7 @@ where
         _grant_info: GrantInfo<SCOPE>,
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
-        let info = access_token
+        let raw = access_token
             .extra()
             .ok_or("extra missing")
             .map_err(BuilderObtainUserInfoError::Unreachable)?
@@ -28,40 +30,25 @@ where
             .map_err(BuilderObtainUserInfoError::Unreachable)?
             .as_object()
             .ok_or("info mismatch")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?;
-
-        let uid = info
-            .get("uuid")
-            .ok_or("uuid missing")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .as_str()
-            .ok_or("uuid mismatch")
             .map_err(BuilderObtainUserInfoError::Unreachable)?
             .to_owned();
 
-        let name = info
-            .get("name")
-            .ok_or("name missing")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .as_str()
-            .ok_or("name mismatch")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .to_owned();
-
-        let email = info
-            .get("email")
-            .ok_or("email missing")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .as_str()
-            .ok_or("email mismatch")
+        let access_token = access_token
+            .to_owned()
+            .try_cast_extra::<DigitaloceanAccessTokenResponseExtra>()
+            .map_err(|_| BuilderObtainUserInfoError::Unreachable("extra mismatch"))?;
+        let info = &access_token
+            .extra()
+            .ok_or("extra missing")
             .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .to_owned();
+            .info;
 
         Ok(BuilderObtainUserInfoOutput::Static(UserInfo {
-            uid,
-            name: Some(name),
-            email: Some(email),
-            raw: info.to_owned(),
+            uid: info.uuid.to_owned(),
+            name: Some(info.name.to_owned()),
+            email: Some(info.email.to_owned()),
+            raw,
+            ..Default::default()
         }))
     }
 }
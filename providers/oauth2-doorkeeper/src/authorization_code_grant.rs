@@ -58,6 +58,10 @@ where
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "doorkeeper"
+    }
 }
 impl<SCOPE> ProviderExtAuthorizationCodeGrant for DoorkeeperProviderWithAuthorizationCodeFlow<SCOPE>
 where
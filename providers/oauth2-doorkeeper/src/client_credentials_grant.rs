@@ -52,6 +52,10 @@ where
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "doorkeeper"
+    }
 }
 impl<SCOPE> ProviderExtClientCredentialsGrant for DoorkeeperProviderWithClientCredentials<SCOPE> where
     SCOPE: Scope
@@ -1,3 +1,20 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
+
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[
+        GrantType::AuthorizationCode,
+        GrantType::ClientCredentials,
+        GrantType::Password,
+    ],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost],
+    pkce: false,
+    oidc: false,
+    refresh_token: true,
+    revocation: true,
+    user_info: false,
+    scope_delimiter: ' ',
+};
+
 pub mod authorization_code_grant;
 pub mod client_credentials_grant;
 pub mod resource_owner_password_credentials_grant;
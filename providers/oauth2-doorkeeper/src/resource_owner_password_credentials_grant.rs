@@ -52,6 +52,10 @@ where
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "doorkeeper"
+    }
 }
 impl<SCOPE> ProviderExtResourceOwnerPasswordCredentialsGrant
     for DoorkeeperProviderWithResourceOwnerPasswordCredentials<SCOPE>
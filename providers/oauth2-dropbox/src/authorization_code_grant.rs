@@ -1,6 +1,6 @@
 use oauth2_client::{
     authorization_code_grant::provider_ext::ProviderExtAuthorizationCodeGrantPkceSupportType,
-    re_exports::{ClientId, ClientSecret, RedirectUri, Url, UrlParseError},
+    re_exports::{ClientId, ClientSecret, Deserialize, RedirectUri, Serialize, Url, UrlParseError},
     Provider, ProviderExtAuthorizationCodeGrant,
 };
 
@@ -44,6 +44,10 @@ impl Provider for DropboxProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "dropbox"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for DropboxProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -65,3 +69,46 @@ impl ProviderExtAuthorizationCodeGrant for DropboxProviderWithWebApplication {
         &self.authorization_endpoint_url
     }
 }
+
+//
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DropboxAccessTokenResponseExtra {
+    pub uid: String,
+    pub account_id: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::authorization_code_grant::AccessTokenEndpoint;
+
+    #[test]
+    fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = DropboxProviderWithWebApplication::new(
+            "CLIENT_ID".to_owned(),
+            "CLIENT_SECRET".into(),
+            RedirectUri::new("https://client.example.com/cb")?,
+        )?;
+
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
+
+        match body_ret {
+            Ok(body) => {
+                let body = body.try_cast_extra::<DropboxAccessTokenResponseExtra>()?;
+                assert_eq!(
+                    body.extra().map(|x| x.account_id.as_str()),
+                    Some("dbid:AADMB_j_i3y-F9qhVBv68H3eRnzSJ3bZ9Nw")
+                );
+            }
+            Err(body) => panic!("{body:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -7,7 +7,7 @@ use oauth2_client::{
     re_exports::Scope,
 };
 
-use crate::DropboxScope;
+use crate::{authorization_code_grant::DropboxAccessTokenResponseExtra, DropboxScope};
 
 use super::DropboxUserInfoEndpoint;
 
@@ -24,19 +24,22 @@ where
         _grant_info: GrantInfo<SCOPE>,
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
+        let raw = access_token
+            .extra()
+            .ok_or("extra missing")
+            .map_err(BuilderObtainUserInfoError::Unreachable)?
+            .to_owned();
+
+        let access_token = access_token
+            .to_owned()
+            .try_cast_extra::<DropboxAccessTokenResponseExtra>()
+            .map_err(|_| BuilderObtainUserInfoError::Unreachable("extra mismatch"))?;
         let extra = access_token
             .extra()
             .ok_or("extra missing")
             .map_err(BuilderObtainUserInfoError::Unreachable)?;
 
-        let uid = extra
-            .get("uid")
-            .ok_or("uid missing")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .as_str()
-            .ok_or("uid mismatch")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .to_owned();
+        let uid = extra.uid.to_owned();
 
         let scopes = access_token
             .scope
@@ -45,17 +48,14 @@ where
             .unwrap_or_default();
 
         if scopes.contains(&DropboxScope::SharingRead.to_string()) {
-            let account_id = extra
-                .get("account_id")
-                .ok_or("account_id missing")
-                .map_err(BuilderObtainUserInfoError::Unreachable)?
-                .as_str()
-                .ok_or("account_id mismatch")
-                .map_err(BuilderObtainUserInfoError::Unreachable)?
-                .to_owned();
+            let account_id = extra.account_id.to_owned();
 
             return Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-                DropboxUserInfoEndpoint::new(&access_token.access_token, account_id, uid),
+                DropboxUserInfoEndpoint::new(
+                    access_token.access_token.expose_secret(),
+                    account_id,
+                    uid,
+                ),
             )));
         }
 
@@ -63,7 +63,8 @@ where
             uid,
             name: None,
             email: None,
-            raw: extra.to_owned(),
+            raw,
+            ..Default::default()
         }))
     }
 }
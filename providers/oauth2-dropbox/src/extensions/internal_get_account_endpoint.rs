@@ -3,8 +3,8 @@ use oauth2_client::re_exports::{
         header::{ACCEPT, AUTHORIZATION, CONTENT_TYPE},
         Method,
     },
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Map, Request, Response,
-    SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 
 // https://www.dropbox.com/developers/documentation/http/documentation#users-get_account
@@ -13,13 +13,13 @@ pub const URL: &str = "https://api.dropboxapi.com/2/users/get_account";
 //
 #[derive(Debug, Clone)]
 pub struct GetAccountEndpoint {
-    access_token: String,
+    access_token: AccessToken,
     account_id: String,
 }
 impl GetAccountEndpoint {
     pub fn new(access_token: impl AsRef<str>, account_id: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
             account_id: account_id.as_ref().to_owned(),
         }
     }
@@ -41,7 +41,10 @@ impl Endpoint for GetAccountEndpoint {
         let request = Request::builder()
             .uri(URL)
             .method(Method::POST)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(CONTENT_TYPE, MIME_APPLICATION_JSON)
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(body_str.as_bytes().to_vec())
@@ -70,6 +73,18 @@ pub struct GetAccountEndpointRequestBody {
 pub struct Account {
     pub account_id: String,
     pub email: Option<String>,
+    pub email_verified: Option<bool>,
+    pub name: Option<AccountName>,
+    //
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub _extra: Option<Map<String, Value>>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct AccountName {
+    pub display_name: String,
+    pub given_name: String,
+    pub surname: String,
     //
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub _extra: Option<Map<String, Value>>,
@@ -90,11 +105,13 @@ pub enum GetAccountEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_account() {
-        match serde_json::from_str::<Account>(include_str!(
-            "../../tests/response_body_json_files/get_account.json"
-        )) {
+    fn de_account() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("get_account.json"))?.replay(
+            &GetAccountEndpoint::new("ACCESS_TOKEN", "dbid:AADMB_j_i3y-F9qhVBv68H3eRnzSJ3bZ9Nw"),
+        ) {
             Ok(account) => {
                 assert_eq!(
                     account.account_id,
@@ -103,5 +120,7 @@ mod tests {
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "with-tracing")]
+use oauth2_client::trace;
 use oauth2_client::{
     extensions::{EndpointParseResponseError, EndpointRenderRequestError, UserInfo},
     re_exports::{serde_json, Body, Endpoint, Request, Response},
@@ -33,13 +35,26 @@ impl Endpoint for DropboxUserInfoEndpoint {
     type ParseResponseError = EndpointParseResponseError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
-        self.inner.render_request().map_err(Into::into)
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span("dropbox", None, trace::endpoint::USER_INFO);
+
+        let request = self.inner.render_request()?;
+
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
+
+        Ok(request)
     }
 
     fn parse_response(
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span("dropbox", None, trace::endpoint::USER_INFO);
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         UserInfoWrapper::try_from((self.uid.to_owned(), self.inner.parse_response(response)?))
             .map(|x| x.0)
             .map_err(EndpointParseResponseError::ToOutputFailed)
@@ -76,9 +91,13 @@ impl TryFrom<(Uid, Account)> for UserInfoWrapper {
             uid,
             name: None,
             email: account.email.to_owned(),
+            given_name: account.name.as_ref().map(|x| x.given_name.to_owned()),
+            family_name: account.name.as_ref().map(|x| x.surname.to_owned()),
+            email_verified: account.email_verified,
             raw: serde_json::to_value(account)
                 .map(|x| x.as_object().cloned())?
                 .ok_or_else(|| "unreachable".to_owned())?,
+            ..Default::default()
         }))
     }
 }
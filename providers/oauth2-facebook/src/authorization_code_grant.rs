@@ -43,6 +43,10 @@ impl Provider for FacebookProviderForWebApp {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "facebook"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for FacebookProviderForWebApp {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
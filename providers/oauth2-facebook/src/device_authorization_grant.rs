@@ -9,6 +9,7 @@ use oauth2_client::{
             DeviceCode, UserCode, VerificationUri, VerificationUriComplete,
         },
         re_exports::AccessTokenResponseErrorBodyError,
+        types::AccessToken,
     },
     re_exports::{
         serde_json, thiserror, Body, ClientId, ClientSecret, Deserialize, Map, Response,
@@ -21,7 +22,7 @@ use crate::{FacebookScope, DEVICE_AUTHORIZATION_URL, DEVICE_TOKEN_URL};
 
 #[derive(Debug, Clone)]
 pub struct FacebookProviderForDevices {
-    client_access_token: String,
+    client_access_token: AccessToken,
     pub redirect_uri: Option<String>,
     //
     token_endpoint_url: Url,
@@ -30,7 +31,7 @@ pub struct FacebookProviderForDevices {
 impl FacebookProviderForDevices {
     pub fn new(app_id: String, client_token: String) -> Result<Self, UrlParseError> {
         Ok(Self {
-            client_access_token: format!("{app_id}|{client_token}"),
+            client_access_token: format!("{app_id}|{client_token}").into(),
             redirect_uri: None,
             token_endpoint_url: DEVICE_TOKEN_URL.parse()?,
             device_authorization_endpoint_url: DEVICE_AUTHORIZATION_URL.parse()?,
@@ -59,6 +60,10 @@ impl Provider for FacebookProviderForDevices {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "facebook"
+    }
 }
 impl ProviderExtDeviceAuthorizationGrant for FacebookProviderForDevices {
     fn device_authorization_endpoint_url(&self) -> &Url {
@@ -70,7 +75,7 @@ impl ProviderExtDeviceAuthorizationGrant for FacebookProviderForDevices {
 
         map.insert(
             "access_token".to_owned(),
-            Value::String(self.client_access_token.to_owned()),
+            Value::String(self.client_access_token.expose_secret().to_owned()),
         );
 
         if let Some(redirect_uri) = &self.redirect_uri {
@@ -140,7 +145,7 @@ impl ProviderExtDeviceAuthorizationGrant for FacebookProviderForDevices {
 
         map.insert(
             "access_token".to_owned(),
-            Value::String(self.client_access_token.to_owned()),
+            Value::String(self.client_access_token.expose_secret().to_owned()),
         );
 
         map.insert(
@@ -341,6 +346,8 @@ pub enum DeviceAccessTokenResponseParsingError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         device_authorization_grant::{DeviceAccessTokenEndpoint, DeviceAuthorizationEndpoint},
         re_exports::{Endpoint as _, RetryableEndpoint as _},
@@ -376,10 +383,8 @@ mod tests {
         );
 
         //
-        let response_body =
-            include_str!("../tests/response_body_json_files/device_authorization.json");
-        let body_ret = endpoint
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret =
+            ReplayClient::open(fixture_path!("device_authorization.json"))?.replay(&endpoint)?;
         match body_ret {
             Ok(body) => {
                 assert_eq!(body.device_code, "4c7c240847a4c10bf6850802c51dde1e")
@@ -388,11 +393,10 @@ mod tests {
         }
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/device_authorization_err_when_no_access_token.json"
-        );
-        let body_ret = endpoint
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "device_authorization_err_when_no_access_token.json"
+        ))?
+        .replay(&endpoint)?;
         match body_ret {
             Ok(body) => panic!("{body:?}"),
             Err(body) => assert_eq!(
@@ -445,13 +449,11 @@ mod tests {
         );
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_device_authorization_grant.json"
-        );
-        let body_ret = endpoint.parse_response(
-            Response::builder().body(response_body.as_bytes().to_vec())?,
-            None,
-        )?;
+        let client = ReplayClient::open(fixture_path!(
+            "access_token_with_device_authorization_grant.json"
+        ))?;
+        let body_ret = endpoint
+            .parse_response(client.replay_request(endpoint.render_request(None)?)?, None)?;
         match body_ret {
             Ok(Ok(body)) => {
                 let map = body.extra().unwrap();
@@ -465,13 +467,10 @@ mod tests {
         }
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/device_access_token_err_when_1349174.json"
-        );
-        let body_ret = endpoint.parse_response(
-            Response::builder().body(response_body.as_bytes().to_vec())?,
-            None,
-        )?;
+        let client =
+            ReplayClient::open(fixture_path!("device_access_token_err_when_1349174.json"))?;
+        let body_ret = endpoint
+            .parse_response(client.replay_request(endpoint.render_request(None)?)?, None)?;
         match body_ret {
             Ok(Ok(body)) => panic!("{body:?}"),
             Ok(Err(body)) => panic!("{body:?}"),
@@ -22,7 +22,7 @@ where
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            FacebookUserInfoEndpoint::new(&access_token.access_token),
+            FacebookUserInfoEndpoint::new(access_token.access_token.expose_secret()),
         )))
     }
 }
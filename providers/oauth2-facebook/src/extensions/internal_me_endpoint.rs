@@ -1,6 +1,6 @@
 use oauth2_client::re_exports::{
-    http::header::ACCEPT, serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Map,
-    Request, Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    http::header::ACCEPT, serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint,
+    HttpError, Map, Request, Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 use serde_aux::field_attributes::deserialize_number_from_string;
 
@@ -9,12 +9,12 @@ pub const URL: &str = "https://graph.facebook.com/v15.0/me/";
 //
 #[derive(Debug, Clone)]
 pub struct MeEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl MeEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -30,7 +30,9 @@ impl Endpoint for MeEndpoint {
             "email,id,first_name,last_name,middle_name,name,name_format,picture,short_name";
         let url = format!(
             "{}?fields={}&access_token={}",
-            URL, fields, self.access_token
+            URL,
+            fields,
+            self.access_token.expose_secret()
         );
 
         let request = Request::builder()
@@ -80,15 +82,18 @@ pub enum MeEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/me.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("me.json"))?.replay(&MeEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user_info) => {
                 assert_eq!(user_info.id, 2199743876856949);
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "with-tracing")]
+use oauth2_client::trace;
 use oauth2_client::{
     extensions::{EndpointParseResponseError, EndpointRenderRequestError, UserInfo},
     re_exports::{serde_json, Body, Endpoint, Request, Response},
@@ -25,13 +27,26 @@ impl Endpoint for FacebookUserInfoEndpoint {
     type ParseResponseError = EndpointParseResponseError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
-        self.inner.render_request().map_err(Into::into)
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span("facebook", None, trace::endpoint::USER_INFO);
+
+        let request = self.inner.render_request()?;
+
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
+
+        Ok(request)
     }
 
     fn parse_response(
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span("facebook", None, trace::endpoint::USER_INFO);
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         UserInfo::try_from(self.inner.parse_response(response)?)
             .map_err(EndpointParseResponseError::ToOutputFailed)
     }
@@ -64,9 +79,19 @@ impl TryFrom<User> for UserInfo {
             uid: user.id.to_string(),
             name: user.name.to_owned(),
             email: user.email.to_owned(),
+            given_name: user.first_name.to_owned(),
+            family_name: user.last_name.to_owned(),
+            picture: user
+                .picture
+                .as_ref()
+                .and_then(|x| x.get("data"))
+                .and_then(|x| x.get("url"))
+                .and_then(|x| x.as_str())
+                .map(ToOwned::to_owned),
             raw: serde_json::to_value(user)
                 .map(|x| x.as_object().cloned())?
                 .ok_or_else(|| "unreachable".to_owned())?,
+            ..Default::default()
         })
     }
 }
@@ -1,3 +1,4 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const TOKEN_URL: &str = "https://graph.facebook.com/v15.0/oauth/access_token";
@@ -5,6 +6,17 @@ pub const AUTHORIZATION_URL: &str = "https://www.facebook.com/v15.0/dialog/oauth
 pub const DEVICE_TOKEN_URL: &str = "https://graph.facebook.com/v15.0/device/login_status";
 pub const DEVICE_AUTHORIZATION_URL: &str = "https://graph.facebook.com/v15.0/device/login";
 
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode, GrantType::DeviceCode],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost],
+    pkce: false,
+    oidc: false,
+    refresh_token: false,
+    revocation: false,
+    user_info: true,
+    scope_delimiter: ' ',
+};
+
 pub mod authorization_code_grant;
 pub mod device_authorization_grant;
 
@@ -43,6 +43,10 @@ impl Provider for GithubProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "github"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for GithubProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -62,21 +66,23 @@ impl ProviderExtAuthorizationCodeGrant for GithubProviderWithWebApplication {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = GithubProviderWithWebApplication::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         let request = AuthorizationEndpoint::new(&provider, vec![GithubScope::UserEmail])
-            .configure(|x| x.state = Some("STATE".to_owned()))
+            .configure(|x| x.state = Some("STATE".into()))
             .render_request()?;
 
         assert_eq!(request.uri(), "https://github.com/login/oauth/authorize?response_type=code&client_id=CLIENT_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=user%3Aemail&state=STATE");
@@ -88,11 +94,11 @@ mod tests {
     fn access_token_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = GithubProviderWithWebApplication::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let request = AccessTokenEndpoint::new(&provider, "CODE".to_owned()).render_request()?;
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
 
         assert_eq!(request.body(), b"grant_type=authorization_code&code=CODE&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&client_id=CLIENT_ID&client_secret=CLIENT_SECRET");
 
@@ -103,15 +109,14 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = GithubProviderWithWebApplication::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(_body) => {}
@@ -35,6 +35,10 @@ impl Provider for GithubProviderWithDevice {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "github"
+    }
 }
 impl ProviderExtDeviceAuthorizationGrant for GithubProviderWithDevice {
     fn device_authorization_endpoint_url(&self) -> &Url {
@@ -1,12 +1,12 @@
 use oauth2_client::{
     extensions::{
         AccessTokenResponseSuccessfulBody, Builder, BuilderObtainUserInfoError,
-        BuilderObtainUserInfoOutput, GrantInfo,
+        BuilderObtainUserInfoOutput, GrantInfo, UserInfoPipeline,
     },
     re_exports::Scope,
 };
 
-use super::GithubUserInfoEndpoint;
+use super::{GithubUserEmailsStep, GithubUserInfoEndpoint};
 
 //
 #[derive(Debug, Clone)]
@@ -21,8 +21,11 @@ where
         _grant_info: GrantInfo<SCOPE>,
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
-        Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            GithubUserInfoEndpoint::new(&access_token.access_token),
-        )))
+        let access_token = access_token.access_token.expose_secret();
+
+        Ok(BuilderObtainUserInfoOutput::Pipeline(
+            UserInfoPipeline::new(Box::new(GithubUserInfoEndpoint::new(access_token)))
+                .with_step(GithubUserEmailsStep::new(access_token)),
+        ))
     }
 }
@@ -0,0 +1,94 @@
+use oauth2_client::re_exports::{
+    http::header::{ACCEPT, AUTHORIZATION},
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+};
+
+// Ref https://docs.github.com/en/rest/users/emails#list-email-addresses-for-the-authenticated-user
+pub const URL: &str = "https://api.github.com/user/emails";
+
+//
+#[derive(Debug, Clone)]
+pub struct UserEmailsEndpoint {
+    access_token: AccessToken,
+}
+impl UserEmailsEndpoint {
+    pub fn new(access_token: impl AsRef<str>) -> Self {
+        Self {
+            access_token: access_token.as_ref().into(),
+        }
+    }
+}
+
+impl Endpoint for UserEmailsEndpoint {
+    type RenderRequestError = UserEmailsEndpointError;
+
+    type ParseResponseOutput = Vec<UserEmail>;
+    type ParseResponseError = UserEmailsEndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let request = Request::builder()
+            .uri(URL)
+            .header(
+                AUTHORIZATION,
+                format!("token {}", self.access_token.expose_secret()),
+            )
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(UserEmailsEndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let body = serde_json::from_slice::<Vec<UserEmail>>(response.body())
+            .map_err(UserEmailsEndpointError::DeResponseBodyFailed)?;
+
+        Ok(body)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct UserEmail {
+    pub email: String,
+    pub primary: bool,
+    pub verified: bool,
+    pub visibility: Option<String>,
+    //
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub _extra: Option<Map<String, Value>>,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum UserEmailsEndpointError {
+    #[error("MakeRequestFailed {0}")]
+    MakeRequestFailed(HttpError),
+    //
+    #[error("DeResponseBodyFailed {0}")]
+    DeResponseBodyFailed(SerdeJsonError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    #[test]
+    fn de_user_emails() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("user_emails.json"))?
+            .replay(&UserEmailsEndpoint::new("ACCESS_TOKEN"))
+        {
+            Ok(user_emails) => {
+                assert_eq!(user_emails.len(), 2);
+                assert!(user_emails[0].primary);
+            }
+            Err(err) => panic!("{err}"),
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Map, Request, Response,
-    SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 
 pub const URL: &str = "https://api.github.com/user";
@@ -9,12 +9,12 @@ pub const URL: &str = "https://api.github.com/user";
 //
 #[derive(Debug, Clone)]
 pub struct UserEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl UserEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -28,7 +28,10 @@ impl Endpoint for UserEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("token {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("token {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(UserEndpointError::MakeRequestFailed)?;
@@ -52,8 +55,10 @@ pub struct User {
     pub login: String,
     pub id: usize,
     pub avatar_url: String,
-    pub name: String,
-    pub email: String,
+    pub name: Option<String>,
+    /// `null` when private, see `GithubUserEmailsStep`.
+    pub email: Option<String>,
+    pub html_url: Option<String>,
     //
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub _extra: Option<Map<String, Value>>,
@@ -72,15 +77,31 @@ pub enum UserEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_client::extensions::UserInfo;
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/user.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("user.json"))?
+            .replay(&UserEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(user.id, 610852);
+
+                let user_info = UserInfo::try_from(user).map_err(|err| err.to_string())?;
+                assert_eq!(user_info.preferred_username, Some("vkill".to_owned()));
+                assert_eq!(
+                    user_info.picture,
+                    Some("https://avatars.githubusercontent.com/u/610852?v=4".to_owned())
+                );
+                assert_eq!(
+                    user_info.profile,
+                    Some("https://github.com/vkill".to_owned())
+                );
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
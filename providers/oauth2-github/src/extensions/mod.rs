@@ -1,6 +1,9 @@
 pub mod builder;
+pub mod internal_user_emails_endpoint;
 pub mod internal_user_endpoint;
 pub mod user_info_endpoint;
+pub mod user_info_pipeline;
 
 pub use builder::GithubExtensionsBuilder;
 pub use user_info_endpoint::GithubUserInfoEndpoint;
+pub use user_info_pipeline::GithubUserEmailsStep;
@@ -0,0 +1,119 @@
+use oauth2_client::{
+    extensions::{
+        EndpointParseResponseError, EndpointRenderRequestError, UserInfo, UserInfoPipelineStep,
+    },
+    re_exports::{Body, Endpoint, Request, Response},
+};
+
+use super::internal_user_emails_endpoint::{UserEmailsEndpoint, UserEmailsEndpointError};
+
+//
+/// Fills in the primary verified email, `/user` returns `null` when the email is private
+/// and does not tell whether a public email is verified.
+///
+/// Requires the `user:email` or `user` scope, the step is a no-op when the request is rejected.
+#[derive(Debug, Clone)]
+pub struct GithubUserEmailsStep {
+    inner: UserEmailsEndpoint,
+}
+impl GithubUserEmailsStep {
+    pub fn new(access_token: impl AsRef<str>) -> Self {
+        Self {
+            inner: UserEmailsEndpoint::new(access_token),
+        }
+    }
+}
+
+impl UserInfoPipelineStep for GithubUserEmailsStep {
+    fn render_request(
+        &self,
+        user_info: &UserInfo,
+    ) -> Result<Option<Request<Body>>, EndpointRenderRequestError> {
+        if user_info.email.is_some() && user_info.email_verified == Some(true) {
+            return Ok(None);
+        }
+
+        Ok(Some(self.inner.render_request()?))
+    }
+
+    fn merge_response(
+        &self,
+        response: Response<Body>,
+        user_info: &mut UserInfo,
+    ) -> Result<(), EndpointParseResponseError> {
+        if !response.status().is_success() {
+            return Ok(());
+        }
+
+        if let Some(user_email) = self
+            .inner
+            .parse_response(response)?
+            .into_iter()
+            .find(|x| x.primary && x.verified)
+        {
+            user_info.email = Some(user_email.email);
+            user_info.email_verified = Some(true);
+        }
+
+        Ok(())
+    }
+}
+
+//
+impl From<UserEmailsEndpointError> for EndpointRenderRequestError {
+    fn from(err: UserEmailsEndpointError) -> Self {
+        match err {
+            UserEmailsEndpointError::MakeRequestFailed(err) => Self::MakeRequestFailed(err),
+            UserEmailsEndpointError::DeResponseBodyFailed(err) => Self::Other(Box::new(err)),
+        }
+    }
+}
+impl From<UserEmailsEndpointError> for EndpointParseResponseError {
+    fn from(err: UserEmailsEndpointError) -> Self {
+        match err {
+            UserEmailsEndpointError::MakeRequestFailed(err) => Self::Other(Box::new(err)),
+            UserEmailsEndpointError::DeResponseBodyFailed(err) => Self::DeResponseBodyFailed(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    #[test]
+    fn test_merge_response() -> Result<(), Box<dyn std::error::Error>> {
+        let step = GithubUserEmailsStep::new("ACCESS_TOKEN");
+        let mut user_info = UserInfo {
+            uid: "610852".to_owned(),
+            ..Default::default()
+        };
+
+        let request = step.render_request(&user_info)?.ok_or("request missing")?;
+        let response =
+            ReplayClient::open(fixture_path!("user_emails.json"))?.replay_request(request)?;
+        step.merge_response(response, &mut user_info)?;
+        assert_eq!(user_info.email, Some("vkill.net@gmail.com".to_owned()));
+        assert_eq!(user_info.email_verified, Some(true));
+
+        assert!(step.render_request(&user_info)?.is_none());
+
+        //
+        let mut user_info = UserInfo {
+            uid: "610852".to_owned(),
+            email: Some("public@example.com".to_owned()),
+            ..Default::default()
+        };
+
+        let request = step.render_request(&user_info)?.ok_or("request missing")?;
+        let response =
+            ReplayClient::open(fixture_path!("user_emails.json"))?.replay_request(request)?;
+        step.merge_response(response, &mut user_info)?;
+        assert_eq!(user_info.email, Some("vkill.net@gmail.com".to_owned()));
+        assert_eq!(user_info.email_verified, Some(true));
+
+        Ok(())
+    }
+}
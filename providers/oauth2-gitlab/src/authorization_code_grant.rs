@@ -49,6 +49,10 @@ impl Provider for GitlabProviderForEndUsers {
         self.inner.token_endpoint_url()
     }
 
+    fn name(&self) -> &str {
+        "gitlab"
+    }
+
     fn extra(&self) -> Option<Map<String, Value>> {
         let mut map = Map::new();
         map.insert(
@@ -88,25 +92,23 @@ impl ProviderExtAuthorizationCodeGrant for GitlabProviderForEndUsers {
 mod tests {
     use super::*;
 
-    use oauth2_client::{
-        authorization_code_grant::AccessTokenEndpoint,
-        re_exports::{Endpoint as _, Response},
-    };
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::authorization_code_grant::AccessTokenEndpoint;
 
     #[test]
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = GitlabProviderForEndUsers::new(
             "https://gitlab.com/",
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
@@ -32,7 +32,7 @@ where
 
         if scopes.contains(&GitlabScope::ReadUser.to_string()) {
             return Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-                GitlabUserInfoEndpoint::new(&access_token.access_token),
+                GitlabUserInfoEndpoint::new(access_token.access_token.expose_secret()),
             )));
         }
 
@@ -1,7 +1,7 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Map, Request, Response,
-    SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 
 pub const URL: &str = "https://gitlab.com/api/v4/user";
@@ -9,12 +9,12 @@ pub const URL: &str = "https://gitlab.com/api/v4/user";
 //
 #[derive(Debug, Clone)]
 pub struct UserEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl UserEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -28,7 +28,10 @@ impl Endpoint for UserEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(UserEndpointError::MakeRequestFailed)?;
@@ -54,6 +57,7 @@ pub struct User {
     pub username: String,
     pub avatar_url: String,
     pub email: String,
+    pub web_url: Option<String>,
     //
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub _extra: Option<Map<String, Value>>,
@@ -72,15 +76,19 @@ pub enum UserEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/user.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("user.json"))?
+            .replay(&UserEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(user.id, 446862);
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
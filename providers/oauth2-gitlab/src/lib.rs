@@ -1,9 +1,21 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{
     Deserialize_enum_str, Scope, Serialize_enum_str, Url, UrlParseError,
 };
 
 pub const BASE_URL_GITLAB_COM: &str = "https://gitlab.com/";
 
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost],
+    pkce: true,
+    oidc: true,
+    refresh_token: true,
+    revocation: true,
+    user_info: true,
+    scope_delimiter: ' ',
+};
+
 pub mod authorization_code_grant;
 
 pub use authorization_code_grant::GitlabProviderForEndUsers;
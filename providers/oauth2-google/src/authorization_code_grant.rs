@@ -1,5 +1,8 @@
 use oauth2_client::{
-    authorization_code_grant::provider_ext::ProviderExtAuthorizationCodeGrantOidcSupportType,
+    authorization_code_grant::provider_ext::{
+        ProviderExtAuthorizationCodeGrantOidcSupportType,
+        ProviderExtAuthorizationCodeGrantPkceSupportType,
+    },
     re_exports::{
         thiserror, ClientId, ClientSecret, Map, RedirectUri, Serialize_enum_str, Url,
         UrlParseError, Value,
@@ -38,7 +41,10 @@ impl GoogleProviderForWebServerApps {
         client_secret: ClientSecret,
         redirect_uri: RedirectUri,
     ) -> Result<Self, GoogleProviderForWebServerAppsNewError> {
-        if !matches!(redirect_uri, RedirectUri::Url(_)) {
+        if !matches!(
+            redirect_uri,
+            RedirectUri::Url(_) | RedirectUri::LoopbackIp(_)
+        ) {
             return Err(GoogleProviderForWebServerAppsNewError::RedirectUriShouldBeAUrl);
         }
 
@@ -85,6 +91,10 @@ impl Provider for GoogleProviderForWebServerApps {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "google"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for GoogleProviderForWebServerApps {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -95,6 +105,10 @@ impl ProviderExtAuthorizationCodeGrant for GoogleProviderForWebServerApps {
         Some(ProviderExtAuthorizationCodeGrantOidcSupportType::Yes)
     }
 
+    fn pkce_support_type(&self) -> Option<ProviderExtAuthorizationCodeGrantPkceSupportType> {
+        Some(ProviderExtAuthorizationCodeGrantPkceSupportType::Yes)
+    }
+
     fn scopes_default(&self) -> Option<Vec<<Self as Provider>::Scope>> {
         Some(vec![
             GoogleScope::Profile,
@@ -151,7 +165,11 @@ impl GoogleProviderForDesktopApps {
         client_id: ClientId,
         client_secret: ClientSecret,
         redirect_uri: RedirectUri,
-    ) -> Result<Self, UrlParseError> {
+    ) -> Result<Self, GoogleProviderForDesktopAppsNewError> {
+        if !redirect_uri.is_allowed_for_native_apps() {
+            return Err(GoogleProviderForDesktopAppsNewError::RedirectUriNotAllowedForNativeApps);
+        }
+
         Ok(Self {
             client_id,
             client_secret,
@@ -170,6 +188,15 @@ impl GoogleProviderForDesktopApps {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum GoogleProviderForDesktopAppsNewError {
+    #[error("UrlParseError {0}")]
+    UrlParseError(#[from] UrlParseError),
+    //
+    #[error("RedirectUriNotAllowedForNativeApps")]
+    RedirectUriNotAllowedForNativeApps,
+}
+
 impl Provider for GoogleProviderForDesktopApps {
     type Scope = GoogleScope;
 
@@ -184,12 +211,20 @@ impl Provider for GoogleProviderForDesktopApps {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "google"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for GoogleProviderForDesktopApps {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
         Some(&self.redirect_uri)
     }
 
+    fn pkce_support_type(&self) -> Option<ProviderExtAuthorizationCodeGrantPkceSupportType> {
+        Some(ProviderExtAuthorizationCodeGrantPkceSupportType::Yes)
+    }
+
     fn scopes_default(&self) -> Option<Vec<<Self as Provider>::Scope>> {
         Some(vec![
             GoogleScope::Email,
@@ -207,29 +242,55 @@ impl ProviderExtAuthorizationCodeGrant for GoogleProviderForDesktopApps {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn test_new() {
         match GoogleProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::Oob,
         ) {
             Ok(p) => panic!("{p:?}"),
             Err(GoogleProviderForWebServerAppsNewError::RedirectUriShouldBeAUrl) => {}
             Err(err) => panic!("{err}"),
         }
+
+        for redirect_uri in [
+            RedirectUri::Oob,
+            "http://127.0.0.1:8080/".parse().unwrap(),
+            "com.example.app:/oauth2redirect".parse().unwrap(),
+        ] {
+            match GoogleProviderForDesktopApps::new(
+                "CLIENT_ID".to_owned(),
+                "CLIENT_SECRET".into(),
+                redirect_uri,
+            ) {
+                Ok(_) => {}
+                Err(err) => panic!("{err}"),
+            }
+        }
+        match GoogleProviderForDesktopApps::new(
+            "CLIENT_ID".to_owned(),
+            "CLIENT_SECRET".into(),
+            "myapp://cb".parse().unwrap(),
+        ) {
+            Ok(p) => panic!("{p:?}"),
+            Err(GoogleProviderForDesktopAppsNewError::RedirectUriNotAllowedForNativeApps) => {}
+            Err(err) => panic!("{err}"),
+        }
     }
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = GoogleProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?
         .configure(|x| {
@@ -238,7 +299,7 @@ mod tests {
         });
 
         let request = AuthorizationEndpoint::new(&provider, vec![GoogleScope::Email])
-            .configure(|x| x.state = Some("STATE".to_owned()))
+            .configure(|x| x.state = Some("STATE".into()))
             .render_request()?;
 
         assert_eq!(request.uri(), "https://accounts.google.com/o/oauth2/v2/auth?response_type=code&client_id=CLIENT_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=email&state=STATE&access_type=offline&include_granted_scopes=true");
@@ -250,11 +311,11 @@ mod tests {
     fn access_token_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = GoogleProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let request = AccessTokenEndpoint::new(&provider, "CODE".to_owned()).render_request()?;
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
 
         assert_eq!(request.body(), b"grant_type=authorization_code&code=CODE&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&client_id=CLIENT_ID&client_secret=CLIENT_SECRET");
 
@@ -265,15 +326,14 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = GoogleProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
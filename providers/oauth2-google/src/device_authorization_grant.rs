@@ -37,6 +37,10 @@ impl Provider for GoogleProviderForTvAndDeviceApps {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "google"
+    }
 }
 impl ProviderExtDeviceAuthorizationGrant for GoogleProviderForTvAndDeviceApps {
     fn device_authorization_endpoint_url(&self) -> &Url {
@@ -57,10 +61,8 @@ mod tests {
 
     #[test]
     fn access_token_request() -> Result<(), Box<dyn std::error::Error>> {
-        let provider = GoogleProviderForTvAndDeviceApps::new(
-            "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
-        )?;
+        let provider =
+            GoogleProviderForTvAndDeviceApps::new("CLIENT_ID".to_owned(), "CLIENT_SECRET".into())?;
 
         let endpoint = DeviceAccessTokenEndpoint::new(
             &provider,
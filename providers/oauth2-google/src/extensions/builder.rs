@@ -1,13 +1,13 @@
 use oauth2_client::{
     extensions::{
         AccessTokenResponseSuccessfulBody, Builder, BuilderObtainUserInfoError,
-        BuilderObtainUserInfoOutput, GrantInfo,
+        BuilderObtainUserInfoOutput, GrantInfo, IdTokenExtensionsBuilder,
     },
     oauth2_core::types::ScopeParameter,
     re_exports::Scope,
 };
 
-use crate::GoogleScope;
+use crate::{GoogleScope, ISSUERS};
 
 use super::GoogleUserInfoEndpoint;
 
@@ -15,6 +15,18 @@ use super::GoogleUserInfoEndpoint;
 #[derive(Debug, Clone)]
 pub struct GoogleExtensionsBuilder;
 
+impl GoogleExtensionsBuilder {
+    /// Reads `UserInfo` from the `id_token` when there is one, saving the userinfo request.
+    pub fn with_id_token_fast_path() -> IdTokenExtensionsBuilder<Self> {
+        ISSUERS
+            .iter()
+            .fold(IdTokenExtensionsBuilder::new(), |builder, issuer| {
+                builder.with_issuer(*issuer)
+            })
+            .with_fallback(Self)
+    }
+}
+
 impl<SCOPE> Builder<SCOPE> for GoogleExtensionsBuilder
 where
     SCOPE: Scope,
@@ -26,7 +38,7 @@ where
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
             GoogleUserInfoEndpoint::new(
-                &access_token.access_token,
+                access_token.access_token.expose_secret(),
                 access_token.scope.as_ref().map(|x| {
                     ScopeParameter::<String>::from(x)
                         .0
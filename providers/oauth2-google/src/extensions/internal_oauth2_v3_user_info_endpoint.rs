@@ -1,6 +1,6 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request, Response,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Request, Response,
     SerdeJsonError, Serialize, MIME_APPLICATION_JSON,
 };
 
@@ -9,12 +9,12 @@ pub const URL: &str = "https://www.googleapis.com/oauth2/v3/userinfo";
 //
 #[derive(Debug, Clone)]
 pub struct Oauth2V3UserInfoEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl Oauth2V3UserInfoEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -28,7 +28,10 @@ impl Endpoint for Oauth2V3UserInfoEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(Oauth2V3UserInfoEndpointError::MakeRequestFailed)?;
@@ -68,15 +71,19 @@ pub enum Oauth2V3UserInfoEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user_info() {
-        match serde_json::from_str::<Oauth2V3UserInfo>(include_str!(
-            "../../tests/response_body_json_files/oauth2_v3_userinfo.json"
-        )) {
+    fn de_user_info() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("oauth2_v3_userinfo.json"))?
+            .replay(&Oauth2V3UserInfoEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user_info) => {
                 assert_eq!(user_info.sub, "110578243643543721809");
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "with-tracing")]
+use oauth2_client::trace;
 use oauth2_client::{
     extensions::{EndpointParseResponseError, EndpointRenderRequestError, UserInfo},
     re_exports::{serde_json, Body, Endpoint, Request, Response},
@@ -41,16 +43,29 @@ impl Endpoint for GoogleUserInfoEndpoint {
     type ParseResponseError = EndpointParseResponseError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
-        match &self.inner {
-            Inner::Oauth2V3UserInfoEndpoint(ep) => ep.render_request().map_err(Into::into),
-            Inner::OidcV1UserInfoEndpoint(ep) => ep.render_request().map_err(Into::into),
-        }
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span("google", None, trace::endpoint::USER_INFO);
+
+        let request = match &self.inner {
+            Inner::Oauth2V3UserInfoEndpoint(ep) => ep.render_request()?,
+            Inner::OidcV1UserInfoEndpoint(ep) => ep.render_request()?,
+        };
+
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
+
+        Ok(request)
     }
 
     fn parse_response(
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span("google", None, trace::endpoint::USER_INFO);
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         match &self.inner {
             Inner::Oauth2V3UserInfoEndpoint(ep) => UserInfo::try_from(ep.parse_response(response)?)
                 .map_err(EndpointParseResponseError::ToOutputFailed),
@@ -89,9 +104,12 @@ impl TryFrom<Oauth2V3UserInfo> for UserInfo {
             uid: user_info.sub.to_owned(),
             name: None,
             email: user_info.email.to_owned(),
+            picture: user_info.picture.to_owned(),
+            email_verified: user_info.email_verified,
             raw: serde_json::to_value(user_info)
                 .map(|x| x.as_object().cloned())?
                 .ok_or_else(|| "unreachable".to_owned())?,
+            ..Default::default()
         })
     }
 }
@@ -125,9 +143,15 @@ impl TryFrom<OidcV1UserInfo> for UserInfo {
             uid: user_info.sub.to_owned(),
             name: user_info.name.to_owned(),
             email: user_info.email.to_owned(),
+            picture: user_info.picture.to_owned(),
+            email_verified: user_info.email_verified,
+            given_name: user_info.given_name.to_owned(),
+            family_name: user_info.family_name.to_owned(),
+            locale: user_info.locale.to_owned(),
             raw: serde_json::to_value(user_info)
                 .map(|x| x.as_object().cloned())?
                 .ok_or_else(|| "unreachable".to_owned())?,
+            ..Default::default()
         })
     }
 }
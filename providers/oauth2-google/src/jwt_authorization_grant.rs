@@ -1,24 +1,107 @@
 use oauth2_client::{
+    id_token::decode_payload,
     re_exports::{ClientId, ClientSecret, Url, UrlParseError},
     Provider, ProviderExtJwtAuthorizationGrant,
 };
+#[cfg(feature = "with-service-account")]
+use oauth2_client::{
+    jwt_authorization_grant::{
+        JwtAssertionSigner, JwtAssertionSignerError, JwtAssertionSigningAlgorithm,
+    },
+    re_exports::{thiserror, Value},
+};
 
 use crate::{GoogleScope, TOKEN_URL};
+#[cfg(feature = "with-service-account")]
+use crate::{GoogleServiceAccountKey, GoogleServiceAccountKeyError};
 
 #[derive(Debug, Clone)]
 pub struct GoogleProviderForServerToServerApps {
-    assertion: String,
+    assertion: GoogleProviderForServerToServerAppsAssertion,
     //
     token_endpoint_url: Url,
 }
+
+#[derive(Debug, Clone)]
+enum GoogleProviderForServerToServerAppsAssertion {
+    Signed(String),
+    #[cfg(feature = "with-service-account")]
+    Signer(Box<JwtAssertionSigner>),
+}
+
 impl GoogleProviderForServerToServerApps {
     pub fn new(assertion: String) -> Result<Self, UrlParseError> {
         Ok(Self {
-            assertion,
+            assertion: GoogleProviderForServerToServerAppsAssertion::Signed(assertion),
             token_endpoint_url: TOKEN_URL.parse()?,
         })
     }
 }
+
+// https://developers.google.com/identity/protocols/oauth2/service-account#authorizingrequests
+#[cfg(feature = "with-service-account")]
+impl GoogleProviderForServerToServerApps {
+    /// `sub` is the user to impersonate with domain-wide delegation.
+    ///
+    /// A new assertion is signed for every token request.
+    pub fn from_service_account_key(
+        key: &GoogleServiceAccountKey,
+        scopes: Vec<GoogleScope>,
+        sub: Option<String>,
+    ) -> Result<Self, GoogleProviderForServerToServerAppsNewError> {
+        let token_endpoint_url = key.token_uri.parse()?;
+
+        let signer = JwtAssertionSigner::from_pem(
+            JwtAssertionSigningAlgorithm::Rs256,
+            key.private_key.expose_secret().as_bytes(),
+            key.client_email.to_owned(),
+            key.token_uri.to_owned(),
+        )?
+        .configure(|x| {
+            x.set_kid(key.private_key_id.to_owned());
+            x.set_with_jti(false);
+            x.set_extra_claim(
+                "scope",
+                Value::String(
+                    scopes
+                        .iter()
+                        .map(|x| x.to_string())
+                        .collect::<Vec<_>>()
+                        .join(" "),
+                ),
+            );
+            if let Some(sub) = &sub {
+                x.set_sub(sub.to_owned());
+            }
+        });
+
+        Ok(Self {
+            assertion: GoogleProviderForServerToServerAppsAssertion::Signer(Box::new(signer)),
+            token_endpoint_url,
+        })
+    }
+
+    pub fn from_service_account_json_key(
+        json: impl AsRef<[u8]>,
+        scopes: Vec<GoogleScope>,
+        sub: Option<String>,
+    ) -> Result<Self, GoogleProviderForServerToServerAppsNewError> {
+        let key = GoogleServiceAccountKey::from_json(json)?;
+        Self::from_service_account_key(&key, scopes, sub)
+    }
+}
+
+#[cfg(feature = "with-service-account")]
+#[derive(thiserror::Error, Debug)]
+pub enum GoogleProviderForServerToServerAppsNewError {
+    #[error("UrlParseError {0}")]
+    UrlParseError(#[from] UrlParseError),
+    #[error("GoogleServiceAccountKeyError {0}")]
+    GoogleServiceAccountKeyError(#[from] GoogleServiceAccountKeyError),
+    #[error("JwtAssertionSignerError {0}")]
+    JwtAssertionSignerError(#[from] JwtAssertionSignerError),
+}
+
 impl Provider for GoogleProviderForServerToServerApps {
     type Scope = GoogleScope;
 
@@ -33,10 +116,62 @@ impl Provider for GoogleProviderForServerToServerApps {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "google"
+    }
+
+    /// The service account, the impersonated user and the sorted `scope` claim, so one
+    /// `TokenCache` can be shared across `sub` and scopes. Pre-signed assertions are read without
+    /// verification.
+    fn principal(&self) -> Option<String> {
+        match &self.assertion {
+            GoogleProviderForServerToServerAppsAssertion::Signed(assertion) => {
+                let payload = decode_payload(assertion).ok()?;
+                Some(principal(
+                    payload.get("iss")?.as_str()?,
+                    payload.get("sub").and_then(|x| x.as_str()),
+                    payload.get("scope").and_then(|x| x.as_str()),
+                ))
+            }
+            #[cfg(feature = "with-service-account")]
+            GoogleProviderForServerToServerAppsAssertion::Signer(signer) => Some(principal(
+                &signer.iss,
+                signer.sub.as_deref(),
+                signer.extra.get("scope").and_then(|x| x.as_str()),
+            )),
+        }
+    }
+}
+fn principal(iss: &str, sub: Option<&str>, scope: Option<&str>) -> String {
+    let mut principal = match sub {
+        Some(sub) => format!("{iss}#{sub}"),
+        None => iss.to_owned(),
+    };
+    if let Some(scope) = scope {
+        let mut scopes = scope
+            .split(' ')
+            .filter(|x| !x.is_empty())
+            .collect::<Vec<_>>();
+        scopes.sort_unstable();
+        scopes.dedup();
+        principal.push(' ');
+        principal.push_str(&scopes.join(" "));
+    }
+    principal
 }
+
 impl ProviderExtJwtAuthorizationGrant for GoogleProviderForServerToServerApps {
-    fn assertion(&self) -> &str {
-        &self.assertion
+    fn assertion(&self) -> Result<String, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        match &self.assertion {
+            GoogleProviderForServerToServerAppsAssertion::Signed(assertion) => {
+                Ok(assertion.to_owned())
+            }
+            #[cfg(feature = "with-service-account")]
+            GoogleProviderForServerToServerAppsAssertion::Signer(signer) => {
+                signer.sign_now().map_err(Into::into)
+            }
+        }
     }
 }
 
@@ -61,4 +196,146 @@ mod tests {
 
         Ok(())
     }
+
+    #[cfg(feature = "with-service-account")]
+    #[test]
+    fn access_token_request_with_service_account_key() -> Result<(), Box<dyn std::error::Error>> {
+        use jsonwebtoken::{decode, decode_header, Algorithm, DecodingKey, Validation};
+        use oauth2_client::re_exports::{serde_json, Map};
+
+        let provider = GoogleProviderForServerToServerApps::from_service_account_json_key(
+            include_str!("../tests/service_account_json_key_files/throwaway.json"),
+            vec![
+                GoogleScope::AndroidPublisher,
+                GoogleScope::Other("https://www.googleapis.com/auth/admin.directory.user".into()),
+            ],
+            Some("admin@example.com".to_owned()),
+        )?;
+        assert_eq!(
+            provider.token_endpoint_url().as_str(),
+            "https://oauth2.googleapis.com/token"
+        );
+
+        let endpoint = AccessTokenEndpoint::new(&provider, None);
+        let request = endpoint.render_request()?;
+
+        let body = String::from_utf8(request.body().to_owned())?;
+        let assertion = body
+            .split('&')
+            .find_map(|x| x.strip_prefix("assertion="))
+            .ok_or("assertion missing")?;
+
+        assert_eq!(
+            decode_header(assertion)?.kid,
+            Some("0123456789abcdef0123456789abcdef01234567".to_owned())
+        );
+
+        let mut validation = Validation::new(Algorithm::RS256);
+        validation.set_audience(&["https://oauth2.googleapis.com/token"]);
+        let claims = decode::<Map<String, serde_json::Value>>(
+            assertion,
+            &DecodingKey::from_rsa_pem(include_bytes!(
+                "../tests/service_account_json_key_files/throwaway_public_key.pem"
+            ))?,
+            &validation,
+        )?
+        .claims;
+        assert_eq!(
+            claims.get("iss"),
+            Some(&"throwaway@project-123456.iam.gserviceaccount.com".into())
+        );
+        assert_eq!(claims.get("sub"), Some(&"admin@example.com".into()));
+        assert_eq!(
+            claims.get("scope"),
+            Some(&"https://www.googleapis.com/auth/androidpublisher https://www.googleapis.com/auth/admin.directory.user".into())
+        );
+        assert_eq!(
+            claims.get("exp").and_then(|x| x.as_u64()),
+            claims.get("iat").and_then(|x| x.as_u64()).map(|x| x + 3600)
+        );
+
+        Ok(())
+    }
+
+    #[cfg(feature = "with-service-account")]
+    #[test]
+    fn token_cache_key_with_service_account_key() -> Result<(), Box<dyn std::error::Error>> {
+        use oauth2_client::token_cache::TokenCacheKey;
+
+        let json = include_str!("../tests/service_account_json_key_files/throwaway.json");
+        let key = |sub: Option<&str>| -> Result<TokenCacheKey, Box<dyn std::error::Error>> {
+            let provider = GoogleProviderForServerToServerApps::from_service_account_json_key(
+                json,
+                vec![GoogleScope::AndroidPublisher],
+                sub.map(ToOwned::to_owned),
+            )?;
+            Ok(TokenCacheKey::with_provider(&provider, None))
+        };
+
+        assert_eq!(
+            key(Some("user_1@example.com"))?.principal,
+            Some("throwaway@project-123456.iam.gserviceaccount.com#user_1@example.com https://www.googleapis.com/auth/androidpublisher".to_owned())
+        );
+        assert_ne!(
+            key(Some("user_1@example.com"))?,
+            key(Some("user_2@example.com"))?
+        );
+        assert_ne!(key(Some("user_1@example.com"))?, key(None)?);
+
+        Ok(())
+    }
+    #[cfg(feature = "with-service-account")]
+    #[test]
+    fn token_cache_with_service_account_key_and_scopes() -> Result<(), Box<dyn std::error::Error>> {
+        use oauth2_client::{
+            oauth2_core::{access_token_response::SuccessfulBody, types::AccessTokenType},
+            token_cache::TokenCacheKey,
+            TokenCache,
+        };
+
+        let json = include_str!("../tests/service_account_json_key_files/throwaway.json");
+        let key = |scopes: Vec<GoogleScope>| -> Result<TokenCacheKey, Box<dyn std::error::Error>> {
+            let provider = GoogleProviderForServerToServerApps::from_service_account_json_key(
+                json, scopes, None,
+            )?;
+            Ok(TokenCacheKey::with_provider(&provider, None))
+        };
+        let admin_directory_user =
+            GoogleScope::Other("https://www.googleapis.com/auth/admin.directory.user".into());
+
+        let token_cache = TokenCache::new();
+        token_cache.insert(
+            &key(vec![GoogleScope::AndroidPublisher])?,
+            &SuccessfulBody::<GoogleScope>::new(
+                "ACCESS_TOKEN".into(),
+                AccessTokenType::Bearer,
+                Some(3600),
+                None,
+                None,
+            ),
+        );
+
+        assert!(token_cache
+            .get::<GoogleScope>(&key(vec![GoogleScope::AndroidPublisher])?)
+            .is_some());
+        assert!(token_cache
+            .get::<GoogleScope>(&key(vec![admin_directory_user.to_owned()])?)
+            .is_none());
+        assert!(token_cache
+            .get::<GoogleScope>(&key(vec![
+                GoogleScope::AndroidPublisher,
+                admin_directory_user.to_owned()
+            ])?)
+            .is_none());
+
+        assert_eq!(
+            key(vec![
+                GoogleScope::AndroidPublisher,
+                admin_directory_user.to_owned()
+            ])?,
+            key(vec![admin_directory_user, GoogleScope::AndroidPublisher])?
+        );
+
+        Ok(())
+    }
 }
@@ -1,8 +1,27 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const TOKEN_URL: &str = "https://oauth2.googleapis.com/token";
 pub const AUTHORIZATION_URL: &str = "https://accounts.google.com/o/oauth2/v2/auth";
 pub const DEVICE_AUTHORIZATION_URL: &str = "https://oauth2.googleapis.com/device/code";
+// Ref https://developers.google.com/identity/openid-connect/openid-connect#validatinganidtoken
+pub const ISSUERS: &[&str] = &["https://accounts.google.com", "accounts.google.com"];
+// No `end_session_endpoint`, signing out of the app only ends the app's own session.
+
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[
+        GrantType::AuthorizationCode,
+        GrantType::DeviceCode,
+        GrantType::JwtBearer,
+    ],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost],
+    pkce: true,
+    oidc: true,
+    refresh_token: true,
+    revocation: true,
+    user_info: true,
+    scope_delimiter: ' ',
+};
 
 pub mod authorization_code_grant;
 pub mod device_authorization_grant;
@@ -14,6 +33,13 @@ pub use authorization_code_grant::{
 };
 pub use device_authorization_grant::GoogleProviderForTvAndDeviceApps;
 pub use jwt_authorization_grant::GoogleProviderForServerToServerApps;
+#[cfg(feature = "with-service-account")]
+pub use jwt_authorization_grant::GoogleProviderForServerToServerAppsNewError;
+
+#[cfg(feature = "with-service-account")]
+pub mod service_account_key;
+#[cfg(feature = "with-service-account")]
+pub use service_account_key::{GoogleServiceAccountKey, GoogleServiceAccountKeyError};
 
 pub mod extensions;
 pub use extensions::GoogleExtensionsBuilder;
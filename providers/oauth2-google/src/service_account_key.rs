@@ -0,0 +1,46 @@
+//! https://cloud.google.com/iam/docs/keys-create-delete
+
+use std::{fs, io::Error as IoError, path::Path};
+
+use oauth2_client::re_exports::{serde_json, thiserror, Deserialize, PrivateKey, SerdeJsonError};
+
+use crate::TOKEN_URL;
+
+//
+//
+//
+#[derive(Deserialize, Debug, Clone)]
+pub struct GoogleServiceAccountKey {
+    #[serde(rename = "type")]
+    pub r#type: Option<String>,
+    pub project_id: Option<String>,
+    pub private_key_id: String,
+    pub private_key: PrivateKey,
+    pub client_email: String,
+    pub client_id: Option<String>,
+    #[serde(default = "token_uri_default")]
+    pub token_uri: String,
+}
+
+fn token_uri_default() -> String {
+    TOKEN_URL.to_owned()
+}
+
+impl GoogleServiceAccountKey {
+    pub fn from_json(json: impl AsRef<[u8]>) -> Result<Self, GoogleServiceAccountKeyError> {
+        serde_json::from_slice(json.as_ref()).map_err(GoogleServiceAccountKeyError::DeFailed)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, GoogleServiceAccountKeyError> {
+        let json = fs::read(path).map_err(GoogleServiceAccountKeyError::ReadFailed)?;
+        Self::from_json(json)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum GoogleServiceAccountKeyError {
+    #[error("ReadFailed {0}")]
+    ReadFailed(IoError),
+    #[error("DeFailed {0}")]
+    DeFailed(SerdeJsonError),
+}
@@ -1,5 +1,5 @@
 use oauth2_client::{
-    re_exports::{ClientId, ClientSecret, RedirectUri, Url, UrlParseError},
+    re_exports::{ClientId, ClientSecret, Deserialize, RedirectUri, Serialize, Url, UrlParseError},
     Provider, ProviderExtAuthorizationCodeGrant,
 };
 
@@ -43,6 +43,10 @@ impl Provider for InstagramProviderForBasicDisplayApi {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "instagram"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for InstagramProviderForBasicDisplayApi {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -58,37 +62,38 @@ impl ProviderExtAuthorizationCodeGrant for InstagramProviderForBasicDisplayApi {
     }
 }
 
+//
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct InstagramAccessTokenResponseExtra {
+    pub user_id: u64,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    use oauth2_client::{
-        authorization_code_grant::AccessTokenEndpoint,
-        re_exports::{Endpoint as _, Response},
-    };
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::authorization_code_grant::AccessTokenEndpoint;
 
     #[test]
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = InstagramProviderForBasicDisplayApi::new(
             "APP_ID".to_owned(),
-            "APP_SECRET".to_owned(),
+            "APP_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
-                let map = body.extra().unwrap();
-                assert_eq!(
-                    map.get("user_id").unwrap().as_u64(),
-                    Some(17841403401953170)
-                );
+                let body = body.try_cast_extra::<InstagramAccessTokenResponseExtra>()?;
+                assert_eq!(body.extra().unwrap().user_id, 17841403401953170);
             }
             Err(body) => panic!("{body:?}"),
         }
@@ -7,6 +7,7 @@ use oauth2_client::{
 };
 
 use super::InstagramUserInfoEndpoint;
+use crate::authorization_code_grant::InstagramAccessTokenResponseExtra;
 
 //
 #[derive(Debug, Clone)]
@@ -22,19 +23,16 @@ where
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
         let ig_user_id = access_token
+            .to_owned()
+            .try_cast_extra::<InstagramAccessTokenResponseExtra>()
+            .map_err(|_| BuilderObtainUserInfoError::Unreachable("user_id mismatch"))?
             .extra()
-            .map(|x| x.get("user_id").cloned())
             .ok_or("user_id missing")
             .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .ok_or("user_id missing")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .as_u64()
-            .ok_or("user_id mismatch")
-            .map_err(BuilderObtainUserInfoError::Unreachable)?
-            .to_owned();
+            .user_id;
 
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            InstagramUserInfoEndpoint::new(&access_token.access_token, ig_user_id),
+            InstagramUserInfoEndpoint::new(access_token.access_token.expose_secret(), ig_user_id),
         )))
     }
 }
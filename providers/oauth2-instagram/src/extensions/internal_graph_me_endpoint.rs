@@ -1,6 +1,6 @@
 use oauth2_client::re_exports::{
-    http::header::ACCEPT, serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request,
-    Response, SerdeJsonError, Serialize, MIME_APPLICATION_JSON,
+    http::header::ACCEPT, serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint,
+    HttpError, Request, Response, SerdeJsonError, Serialize, MIME_APPLICATION_JSON,
 };
 
 pub const URL: &str = "https://graph.instagram.com/v15.0/me";
@@ -8,12 +8,12 @@ pub const URL: &str = "https://graph.instagram.com/v15.0/me";
 //
 #[derive(Debug, Clone)]
 pub struct GraphMeEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl GraphMeEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -29,7 +29,8 @@ impl Endpoint for GraphMeEndpoint {
             .uri(
                 format!(
                     "{}?fields=account_type,id,username&access_token={}",
-                    URL, self.access_token
+                    URL,
+                    self.access_token.expose_secret()
                 )
                 .as_str(),
             )
@@ -71,15 +72,19 @@ pub enum GraphMeEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/graph_me.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("graph_me.json"))?
+            .replay(&GraphMeEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(user.id, "4242566562536072");
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -43,6 +43,10 @@ impl Provider for LinkedinProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "linkedin"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for LinkedinProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -60,3 +64,33 @@ impl ProviderExtAuthorizationCodeGrant for LinkedinProviderWithWebApplication {
         &self.authorization_endpoint_url
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::authorization_code_grant::AccessTokenEndpoint;
+
+    #[test]
+    fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = LinkedinProviderWithWebApplication::new(
+            "CLIENT_ID".to_owned(),
+            "CLIENT_SECRET".into(),
+            RedirectUri::new("https://client.example.com/cb")?,
+        )?;
+
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
+
+        match body_ret {
+            Ok(_body) => {}
+            Err(body) => panic!("{body:?}"),
+        }
+
+        Ok(())
+    }
+}
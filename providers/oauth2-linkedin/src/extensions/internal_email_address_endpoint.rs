@@ -0,0 +1,113 @@
+use oauth2_client::re_exports::{
+    http::header::{ACCEPT, AUTHORIZATION},
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+};
+
+// Ref https://docs.microsoft.com/en-us/linkedin/consumer/integrations/self-serve/sign-in-with-linkedin#retrieving-member-email-address
+pub const URL: &str =
+    "https://api.linkedin.com/v2/emailAddress?q=members&projection=(elements*(handle~))";
+
+//
+#[derive(Debug, Clone)]
+pub struct EmailAddressEndpoint {
+    access_token: AccessToken,
+}
+impl EmailAddressEndpoint {
+    pub fn new(access_token: impl AsRef<str>) -> Self {
+        Self {
+            access_token: access_token.as_ref().into(),
+        }
+    }
+}
+
+impl Endpoint for EmailAddressEndpoint {
+    type RenderRequestError = EmailAddressEndpointError;
+
+    type ParseResponseOutput = EmailAddresses;
+    type ParseResponseError = EmailAddressEndpointError;
+
+    fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
+        let request = Request::builder()
+            .uri(URL)
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
+            .header(ACCEPT, MIME_APPLICATION_JSON)
+            .body(vec![])
+            .map_err(EmailAddressEndpointError::MakeRequestFailed)?;
+
+        Ok(request)
+    }
+
+    fn parse_response(
+        &self,
+        response: Response<Body>,
+    ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        let body = serde_json::from_slice::<EmailAddresses>(response.body())
+            .map_err(EmailAddressEndpointError::DeResponseBodyFailed)?;
+
+        Ok(body)
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EmailAddresses {
+    pub elements: Vec<EmailAddressElement>,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EmailAddressElement {
+    pub handle: String,
+    #[serde(rename = "handle~")]
+    pub handle_tilde: EmailAddressHandle,
+}
+
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct EmailAddressHandle {
+    #[serde(rename = "emailAddress")]
+    pub email_address: String,
+    //
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub _extra: Option<Map<String, Value>>,
+}
+
+impl EmailAddresses {
+    /// The API only returns the primary email address.
+    pub fn primary(&self) -> Option<&str> {
+        self.elements
+            .first()
+            .map(|x| x.handle_tilde.email_address.as_str())
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum EmailAddressEndpointError {
+    #[error("MakeRequestFailed {0}")]
+    MakeRequestFailed(HttpError),
+    //
+    #[error("DeResponseBodyFailed {0}")]
+    DeResponseBodyFailed(SerdeJsonError),
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    #[test]
+    fn de_email_address() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("email_address.json"))?
+            .replay(&EmailAddressEndpoint::new("ACCESS_TOKEN"))
+        {
+            Ok(email_addresses) => {
+                assert_eq!(email_addresses.primary(), Some("vkill.net@gmail.com"));
+            }
+            Err(err) => panic!("{err}"),
+        }
+
+        Ok(())
+    }
+}
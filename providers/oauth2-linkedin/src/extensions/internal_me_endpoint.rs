@@ -1,7 +1,7 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Map, Request, Response,
-    SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 
 // Ref https://docs.microsoft.com/en-us/linkedin/consumer/integrations/self-serve/sign-in-with-linkedin
@@ -10,12 +10,12 @@ pub const URL: &str = "https://api.linkedin.com/v2/me";
 //
 #[derive(Debug, Clone)]
 pub struct MeEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl MeEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -29,7 +29,10 @@ impl Endpoint for MeEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(MeEndpointError::MakeRequestFailed)?;
@@ -51,6 +54,10 @@ impl Endpoint for MeEndpoint {
 #[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct User {
     pub id: String,
+    #[serde(rename = "localizedFirstName")]
+    pub localized_first_name: Option<String>,
+    #[serde(rename = "localizedLastName")]
+    pub localized_last_name: Option<String>,
     //
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub _extra: Option<Map<String, Value>>,
@@ -69,15 +76,18 @@ pub enum MeEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/me.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("me.json"))?.replay(&MeEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(user.id, "6gD6NigTM7");
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
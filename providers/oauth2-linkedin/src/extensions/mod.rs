@@ -1,6 +1,9 @@
 pub mod builder;
+pub mod internal_email_address_endpoint;
 pub mod internal_me_endpoint;
 pub mod user_info_endpoint;
+pub mod user_info_pipeline;
 
 pub use builder::LinkedinExtensionsBuilder;
 pub use user_info_endpoint::LinkedinUserInfoEndpoint;
+pub use user_info_pipeline::LinkedinEmailAddressStep;
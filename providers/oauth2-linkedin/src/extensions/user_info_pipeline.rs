@@ -0,0 +1,100 @@
+use oauth2_client::{
+    extensions::{
+        EndpointParseResponseError, EndpointRenderRequestError, UserInfo, UserInfoPipelineStep,
+    },
+    re_exports::{Body, Endpoint, Request, Response},
+};
+
+use super::internal_email_address_endpoint::{EmailAddressEndpoint, EmailAddressEndpointError};
+
+//
+/// Fills in the primary email, `/v2/me` never returns it.
+///
+/// Requires the `r_emailaddress` scope, the step is a no-op when the request is rejected.
+#[derive(Debug, Clone)]
+pub struct LinkedinEmailAddressStep {
+    inner: EmailAddressEndpoint,
+}
+impl LinkedinEmailAddressStep {
+    pub fn new(access_token: impl AsRef<str>) -> Self {
+        Self {
+            inner: EmailAddressEndpoint::new(access_token),
+        }
+    }
+}
+
+impl UserInfoPipelineStep for LinkedinEmailAddressStep {
+    fn render_request(
+        &self,
+        user_info: &UserInfo,
+    ) -> Result<Option<Request<Body>>, EndpointRenderRequestError> {
+        if user_info.email.is_some() {
+            return Ok(None);
+        }
+
+        Ok(Some(self.inner.render_request()?))
+    }
+
+    fn merge_response(
+        &self,
+        response: Response<Body>,
+        user_info: &mut UserInfo,
+    ) -> Result<(), EndpointParseResponseError> {
+        if !response.status().is_success() {
+            return Ok(());
+        }
+
+        // LinkedIn doesn't report whether it is verified.
+        user_info.email = self
+            .inner
+            .parse_response(response)?
+            .primary()
+            .map(ToOwned::to_owned);
+
+        Ok(())
+    }
+}
+
+//
+impl From<EmailAddressEndpointError> for EndpointRenderRequestError {
+    fn from(err: EmailAddressEndpointError) -> Self {
+        match err {
+            EmailAddressEndpointError::MakeRequestFailed(err) => Self::MakeRequestFailed(err),
+            EmailAddressEndpointError::DeResponseBodyFailed(err) => Self::Other(Box::new(err)),
+        }
+    }
+}
+impl From<EmailAddressEndpointError> for EndpointParseResponseError {
+    fn from(err: EmailAddressEndpointError) -> Self {
+        match err {
+            EmailAddressEndpointError::MakeRequestFailed(err) => Self::Other(Box::new(err)),
+            EmailAddressEndpointError::DeResponseBodyFailed(err) => Self::DeResponseBodyFailed(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    #[test]
+    fn test_merge_response() -> Result<(), Box<dyn std::error::Error>> {
+        let step = LinkedinEmailAddressStep::new("ACCESS_TOKEN");
+        let mut user_info = UserInfo {
+            uid: "6gD6NigTM7".to_owned(),
+            ..Default::default()
+        };
+
+        let request = step.render_request(&user_info)?.ok_or("request missing")?;
+        let response =
+            ReplayClient::open(fixture_path!("email_address.json"))?.replay_request(request)?;
+        step.merge_response(response, &mut user_info)?;
+        assert_eq!(user_info.email, Some("vkill.net@gmail.com".to_owned()));
+
+        assert!(step.render_request(&user_info)?.is_none());
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use oauth2_client::{
     oauth2_core::{
         re_exports::{AccessTokenResponseErrorBody, AccessTokenResponseSuccessfulBody},
-        types::{AccessTokenType, ScopeParameter},
+        types::{AccessToken, AccessTokenType, RefreshToken, ScopeParameter},
     },
     re_exports::{
         serde_json, thiserror, Body, ClientId, ClientSecret, RedirectUri, Response, SerdeJsonError,
@@ -51,6 +51,10 @@ impl Provider for LinodeProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "linode"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for LinodeProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -94,10 +98,10 @@ impl ProviderExtAuthorizationCodeGrant for LinodeProviderWithWebApplication {
 
 #[derive(Serialize, Deserialize)]
 pub struct LinodeAccessTokenResponseBody {
-    pub access_token: String,
+    pub access_token: AccessToken,
     pub token_type: AccessTokenType,
     pub expires_in: Option<usize>,
-    pub refresh_token: Option<String>,
+    pub refresh_token: Option<RefreshToken>,
     pub scope: Option<ScopeParameter<LinodeScope>>,
 }
 
@@ -106,10 +110,10 @@ impl From<LinodeAccessTokenResponseBody>
 {
     fn from(body: LinodeAccessTokenResponseBody) -> Self {
         Ok(AccessTokenResponseSuccessfulBody {
-            access_token: body.access_token.to_owned(),
+            access_token: body.access_token,
             token_type: body.token_type,
             expires_in: body.expires_in,
-            refresh_token: body.refresh_token.to_owned(),
+            refresh_token: body.refresh_token,
             scope: body.scope,
             id_token: None,
             _extra: None,
@@ -128,24 +132,22 @@ pub enum AccessTokenResponseParsingError {
 mod tests {
     use super::*;
 
-    use oauth2_client::{
-        authorization_code_grant::AccessTokenEndpoint,
-        re_exports::{Endpoint as _, Response},
-    };
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::authorization_code_grant::AccessTokenEndpoint;
 
     #[test]
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = LinodeProviderWithWebApplication::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(_body) => {}
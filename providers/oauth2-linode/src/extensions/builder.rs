@@ -22,7 +22,7 @@ where
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            LinodeUserInfoEndpoint::new(&access_token.access_token),
+            LinodeUserInfoEndpoint::new(access_token.access_token.expose_secret()),
         )))
     }
 }
@@ -1,7 +1,7 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Map, Request, Response,
-    SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 
 // Ref https://www.linode.com/docs/api/profile/#profile-view
@@ -10,12 +10,12 @@ pub const URL: &str = "https://api.linode.com/v4/profile";
 //
 #[derive(Debug, Clone)]
 pub struct MeEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl MeEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -29,7 +29,10 @@ impl Endpoint for MeEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(MeEndpointError::MakeRequestFailed)?;
@@ -53,6 +56,8 @@ pub struct User {
     pub uid: u64,
     pub email: String,
     pub username: String,
+    pub timezone: Option<String>,
+    pub verified_phone_number: Option<String>,
     //
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub _extra: Option<Map<String, Value>>,
@@ -71,15 +76,19 @@ pub enum MeEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/v4_profile.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("v4_profile.json"))?
+            .replay(&MeEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(user.uid, 1234);
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
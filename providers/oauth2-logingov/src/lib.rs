@@ -1 +1,13 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 
+/// Grants are not implemented yet, this describes what Login.gov supports.
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode],
+    client_auth_methods: &[ClientAuthMethod::PrivateKeyJwt, ClientAuthMethod::None],
+    pkce: true,
+    oidc: true,
+    refresh_token: false,
+    revocation: false,
+    user_info: false,
+    scope_delimiter: ' ',
+};
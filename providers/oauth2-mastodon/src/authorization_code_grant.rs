@@ -1,5 +1,5 @@
 use oauth2_client::{
-    re_exports::{ClientId, ClientSecret, Map, RedirectUri, Url, UrlParseError, Value},
+    re_exports::{thiserror, ClientId, ClientSecret, Map, RedirectUri, Url, UrlParseError, Value},
     Provider, ProviderExtAuthorizationCodeGrant,
 };
 use oauth2_doorkeeper::DoorkeeperProviderWithAuthorizationCodeFlow;
@@ -17,7 +17,19 @@ impl MastodonProviderForEndUsers {
         client_id: ClientId,
         client_secret: ClientSecret,
         redirect_uri: RedirectUri,
-    ) -> Result<Self, UrlParseError> {
+    ) -> Result<Self, MastodonProviderForEndUsersNewError> {
+        // Private-use scheme uris for desktop/mobile apps must be valid, see RedirectUri::new_private_use_scheme
+        match &redirect_uri {
+            RedirectUri::LoopbackIp(_) | RedirectUri::PrivateUseScheme(_) | RedirectUri::Oob => {}
+            RedirectUri::Url(url) if url.scheme() == "https" => {}
+            RedirectUri::Other(s) if s == "urn:ietf:wg:oauth:2.0:oob" => {}
+            redirect_uri => {
+                return Err(MastodonProviderForEndUsersNewError::RedirectUriInvalid(
+                    redirect_uri.to_string(),
+                ))
+            }
+        }
+
         Ok(Self {
             inner: DoorkeeperProviderWithAuthorizationCodeFlow::<MastodonScope>::new(
                 client_id,
@@ -30,6 +42,16 @@ impl MastodonProviderForEndUsers {
         })
     }
 }
+
+#[derive(thiserror::Error, Debug)]
+pub enum MastodonProviderForEndUsersNewError {
+    #[error("UrlParseError {0}")]
+    UrlParseError(#[from] UrlParseError),
+    //
+    #[error("RedirectUriInvalid {0}")]
+    RedirectUriInvalid(String),
+}
+
 impl Provider for MastodonProviderForEndUsers {
     type Scope = MastodonScope;
 
@@ -45,6 +67,10 @@ impl Provider for MastodonProviderForEndUsers {
         self.inner.token_endpoint_url()
     }
 
+    fn name(&self) -> &str {
+        "mastodon"
+    }
+
     fn extra(&self) -> Option<Map<String, Value>> {
         let mut map = Map::new();
         map.insert(
@@ -72,23 +98,68 @@ impl ProviderExtAuthorizationCodeGrant for MastodonProviderForEndUsers {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
+    #[test]
+    fn test_new() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = MastodonProviderForEndUsers::new(
+            "https://mastodon.social/",
+            "CLIENT_ID".to_owned(),
+            "CLIENT_SECRET".into(),
+            RedirectUri::new("com.example.app:/oauth2redirect")?,
+        )?;
+        assert!(matches!(
+            provider.redirect_uri(),
+            Some(RedirectUri::PrivateUseScheme(_))
+        ));
+
+        let provider = MastodonProviderForEndUsers::new(
+            "https://mastodon.social/",
+            "CLIENT_ID".to_owned(),
+            "CLIENT_SECRET".into(),
+            RedirectUri::Other("urn:ietf:wg:oauth:2.0:oob".to_owned()),
+        )?;
+        assert_eq!(
+            provider.redirect_uri().map(|x| x.to_string()),
+            Some("urn:ietf:wg:oauth:2.0:oob".to_owned())
+        );
+
+        for redirect_uri in [
+            RedirectUri::new("com.example.app://oauth2redirect")?,
+            RedirectUri::new("http://client.example.com/cb")?,
+        ] {
+            match MastodonProviderForEndUsers::new(
+                "https://mastodon.social/",
+                "CLIENT_ID".to_owned(),
+                "CLIENT_SECRET".into(),
+                redirect_uri,
+            ) {
+                Ok(p) => panic!("{p:?}"),
+                Err(MastodonProviderForEndUsersNewError::RedirectUriInvalid(_)) => {}
+                Err(err) => panic!("{err}"),
+            }
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = MastodonProviderForEndUsers::new(
             "https://mastodon.social/",
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         let request =
             AuthorizationEndpoint::new(&provider, vec![MastodonScope::Read, MastodonScope::Write])
-                .configure(|x| x.state = Some("STATE".to_owned()))
+                .configure(|x| x.state = Some("STATE".into()))
                 .render_request()?;
 
         assert_eq!(request.uri(), "https://mastodon.social/oauth/authorize?response_type=code&client_id=CLIENT_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=read+write&state=STATE");
@@ -101,11 +172,11 @@ mod tests {
         let provider = MastodonProviderForEndUsers::new(
             "https://mastodon.social/",
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let request = AccessTokenEndpoint::new(&provider, "CODE".to_owned()).render_request()?;
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
 
         assert_eq!(request.body(), b"grant_type=authorization_code&code=CODE&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&client_id=CLIENT_ID&client_secret=CLIENT_SECRET");
 
@@ -117,15 +188,14 @@ mod tests {
         let provider = MastodonProviderForEndUsers::new(
             "https://mastodon.social/",
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
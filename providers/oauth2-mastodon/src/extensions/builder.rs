@@ -38,7 +38,7 @@ where
             .to_owned();
 
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            MastodonUserInfoEndpoint::new(base_url, &access_token.access_token)
+            MastodonUserInfoEndpoint::new(base_url, access_token.access_token.expose_secret())
                 .map_err(|err| BuilderObtainUserInfoError::Other(Box::new(err)))?,
         )))
     }
@@ -1,6 +1,6 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request, Response,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Request, Response,
     SerdeJsonError, Serialize, Url, UrlParseError, MIME_APPLICATION_JSON,
 };
 
@@ -8,7 +8,7 @@ use oauth2_client::re_exports::{
 #[derive(Debug, Clone)]
 pub struct AccountsVerifyCredentialsEndpoint {
     url: Url,
-    access_token: String,
+    access_token: AccessToken,
 }
 impl AccountsVerifyCredentialsEndpoint {
     pub fn new(
@@ -17,7 +17,7 @@ impl AccountsVerifyCredentialsEndpoint {
     ) -> Result<Self, UrlParseError> {
         Ok(Self {
             url: Url::parse(base_url.as_ref())?.join("/api/v1/accounts/verify_credentials")?,
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         })
     }
 }
@@ -31,7 +31,10 @@ impl Endpoint for AccountsVerifyCredentialsEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(self.url.as_str())
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(AccountsVerifyCredentialsEndpointError::MakeRequestFailed)?;
@@ -54,6 +57,9 @@ impl Endpoint for AccountsVerifyCredentialsEndpoint {
 pub struct Account {
     pub id: String,
     pub username: String,
+    pub display_name: Option<String>,
+    pub avatar: Option<String>,
+    pub url: Option<String>,
     // TODO
 }
 
@@ -70,15 +76,19 @@ pub enum AccountsVerifyCredentialsEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_account() {
-        match serde_json::from_str::<Account>(include_str!(
-            "../../tests/response_body_json_files/accounts_verify_credentials.json"
-        )) {
+    fn de_account() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("accounts_verify_credentials.json"))?.replay(
+            &AccountsVerifyCredentialsEndpoint::new("https://mastodon.social/", "ACCESS_TOKEN")?,
+        ) {
             Ok(account) => {
                 assert_eq!(account.id, "107241446397110525");
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
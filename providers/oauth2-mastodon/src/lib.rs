@@ -1,9 +1,25 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{
     Deserialize_enum_str, Scope, Serialize_enum_str, Url, UrlParseError,
 };
 
 pub const BASE_URL_MASTODON_SOCIAL: &str = "https://mastodon.social/";
 
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[
+        GrantType::AuthorizationCode,
+        GrantType::ClientCredentials,
+        GrantType::Password,
+    ],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost],
+    pkce: false,
+    oidc: false,
+    refresh_token: false,
+    revocation: true,
+    user_info: true,
+    scope_delimiter: ' ',
+};
+
 pub mod authorization_code_grant;
 pub mod client_credentials_grant;
 pub mod resource_owner_password_credentials_grant;
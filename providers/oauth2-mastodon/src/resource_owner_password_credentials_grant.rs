@@ -42,6 +42,10 @@ impl Provider for MastodonProviderForBots {
         self.inner.token_endpoint_url()
     }
 
+    fn name(&self) -> &str {
+        "mastodon"
+    }
+
     fn extra(&self) -> Option<Map<String, Value>> {
         let mut map = Map::new();
         map.insert(
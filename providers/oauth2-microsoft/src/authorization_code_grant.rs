@@ -48,6 +48,10 @@ impl Provider for MicrosoftProviderForWebApps {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "microsoft"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for MicrosoftProviderForWebApps {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -74,3 +78,52 @@ impl ProviderExtAuthorizationCodeGrant for MicrosoftProviderForWebApps {
         &self.authorization_endpoint_url
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::{
+        authorization_code_grant::AccessTokenEndpoint,
+        oauth2_core::re_exports::AccessTokenResponseErrorBodyError,
+    };
+
+    #[test]
+    fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = MicrosoftProviderForWebApps::new(
+            "common",
+            "CLIENT_ID".to_owned(),
+            "CLIENT_SECRET".into(),
+            RedirectUri::new("https://client.example.com/cb")?,
+        )?;
+        let endpoint = AccessTokenEndpoint::new(&provider, "CODE".into());
+
+        //
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&endpoint)?;
+        match body_ret {
+            Ok(body) => {
+                assert!(body.id_token.is_some());
+            }
+            Err(body) => panic!("{body:?}"),
+        }
+
+        //
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant_err.json"
+        ))?
+        .replay(&endpoint)?;
+        match body_ret {
+            Ok(body) => panic!("{body:?}"),
+            Err(body) => {
+                assert_eq!(body.error, AccessTokenResponseErrorBodyError::InvalidClient);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -35,9 +35,59 @@ impl Provider for MicrosoftProviderForDevices {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "microsoft"
+    }
 }
 impl ProviderExtDeviceAuthorizationGrant for MicrosoftProviderForDevices {
     fn device_authorization_endpoint_url(&self) -> &Url {
         &self.device_authorization_endpoint_url
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::{
+        device_authorization_grant::{
+            provider_ext::DeviceAuthorizationResponseSuccessfulBody, DeviceAccessTokenEndpoint,
+        },
+        re_exports::RetryableEndpoint as _,
+    };
+
+    #[test]
+    fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = MicrosoftProviderForDevices::new("common", "CLIENT_ID".to_owned())?;
+        let endpoint = DeviceAccessTokenEndpoint::new(
+            &provider,
+            DeviceAuthorizationResponseSuccessfulBody::new(
+                "DEVICE_CODE".to_owned(),
+                "".to_owned(),
+                "https://example.com".parse()?,
+                None,
+                0,
+                Some(5),
+            ),
+        );
+
+        //
+        let client = ReplayClient::open(fixture_path!(
+            "access_token_with_device_authorization_grant.json"
+        ))?;
+        let body_ret = endpoint
+            .parse_response(client.replay_request(endpoint.render_request(None)?)?, None)?;
+        match body_ret {
+            Ok(Ok(body)) => {
+                assert!(body.id_token.is_some());
+            }
+            Ok(Err(body)) => panic!("{body:?}"),
+            Err(reason) => panic!("{reason:?}"),
+        }
+
+        Ok(())
+    }
+}
@@ -1,7 +1,7 @@
 use oauth2_client::{
     extensions::{
         AccessTokenResponseSuccessfulBody, Builder, BuilderObtainUserInfoError,
-        BuilderObtainUserInfoOutput, GrantInfo,
+        BuilderObtainUserInfoOutput, GrantInfo, IdTokenExtensionsBuilder,
     },
     re_exports::Scope,
 };
@@ -12,6 +12,16 @@ use super::MicrosoftUserInfoEndpoint;
 #[derive(Debug, Clone)]
 pub struct MicrosoftExtensionsBuilder;
 
+impl MicrosoftExtensionsBuilder {
+    /// Reads `UserInfo` from the `id_token` when there is one, saving the Graph `/me` request.
+    ///
+    /// The issuer is not checked, it contains the tenant id which differs per user with the
+    /// `common`, `organizations` and `consumers` tenants.
+    pub fn with_id_token_fast_path() -> IdTokenExtensionsBuilder<Self> {
+        IdTokenExtensionsBuilder::new().with_fallback(Self)
+    }
+}
+
 impl<SCOPE> Builder<SCOPE> for MicrosoftExtensionsBuilder
 where
     SCOPE: Scope,
@@ -22,7 +32,7 @@ where
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            MicrosoftUserInfoEndpoint::new(&access_token.access_token),
+            MicrosoftUserInfoEndpoint::new(access_token.access_token.expose_secret()),
         )))
     }
 }
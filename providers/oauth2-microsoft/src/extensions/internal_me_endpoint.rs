@@ -1,7 +1,7 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Map, Request, Response,
-    SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Map, Request,
+    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 
 // Ref https://docs.microsoft.com/en-us/graph/api/user-get
@@ -10,12 +10,12 @@ pub const URL: &str = "https://graph.microsoft.com/v1.0/me";
 //
 #[derive(Debug, Clone)]
 pub struct MeEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl MeEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -29,7 +29,10 @@ impl Endpoint for MeEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(MeEndpointError::MakeRequestFailed)?;
@@ -54,6 +57,15 @@ pub struct User {
     #[serde(rename = "userPrincipalName")]
     pub user_principal_name: String,
     pub mail: Option<String>,
+    #[serde(rename = "displayName")]
+    pub display_name: Option<String>,
+    #[serde(rename = "givenName")]
+    pub given_name: Option<String>,
+    pub surname: Option<String>,
+    #[serde(rename = "preferredLanguage")]
+    pub preferred_language: Option<String>,
+    #[serde(rename = "mobilePhone")]
+    pub mobile_phone: Option<String>,
     //
     #[serde(flatten, skip_serializing_if = "Option::is_none")]
     pub _extra: Option<Map<String, Value>>,
@@ -72,15 +84,18 @@ pub enum MeEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<User>(include_str!(
-            "../../tests/response_body_json_files/me.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("me.json"))?.replay(&MeEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(user.id, "f26b4f162cf4fb1f");
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -1,7 +1,19 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const TENANT_COMMON: &str = "common";
 
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode, GrantType::DeviceCode],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost, ClientAuthMethod::None],
+    pkce: true,
+    oidc: true,
+    refresh_token: true,
+    revocation: false,
+    user_info: true,
+    scope_delimiter: ' ',
+};
+
 pub mod authorization_code_grant;
 pub mod device_authorization_grant;
 
@@ -29,6 +41,13 @@ pub fn authorization_url(tenant: impl AsRef<str>) -> String {
         tenant.as_ref()
     )
 }
+/// For `oauth2_client::logout::EndSessionRequest`.
+pub fn end_session_url(tenant: impl AsRef<str>) -> String {
+    format!(
+        "https://login.microsoftonline.com/{}/oauth2/v2.0/logout",
+        tenant.as_ref()
+    )
+}
 
 // Ref https://docs.microsoft.com/en-us/azure/active-directory/develop/v2-permissions-and-consent
 #[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
@@ -53,6 +53,10 @@ impl Provider for OktaProviderForWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "okta"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for OktaProviderForWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -79,3 +83,35 @@ impl ProviderExtAuthorizationCodeGrant for OktaProviderForWebApplication {
         &self.authorization_endpoint_url
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
+    use oauth2_client::authorization_code_grant::AccessTokenEndpoint;
+
+    #[test]
+    fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
+        let provider = OktaProviderForWebApplication::new(
+            "dev-000000.okta.com",
+            None,
+            "CLIENT_ID".to_owned(),
+            "CLIENT_SECRET".into(),
+            RedirectUri::new("https://client.example.com/cb")?,
+        )?;
+
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
+
+        match body_ret {
+            Ok(_body) => {}
+            Err(body) => panic!("{body:?}"),
+        }
+
+        Ok(())
+    }
+}
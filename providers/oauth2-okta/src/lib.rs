@@ -1,11 +1,36 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const AUTHORIZATION_SERVER_ID_DEFAULT: &str = "default";
 
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost],
+    pkce: true,
+    oidc: true,
+    refresh_token: true,
+    revocation: true,
+    user_info: true,
+    scope_delimiter: ' ',
+};
+
 pub mod authorization_code_grant;
 
 pub use authorization_code_grant::OktaProviderForWebApplication;
 
+/// The `iss` of ID tokens, e.g. for `oauth2_client::extensions::IdTokenExtensionsBuilder`.
+pub fn issuer_url(
+    domain: impl AsRef<str>,
+    authorization_server_id: impl Into<Option<String>>,
+) -> String {
+    format!(
+        "https://{}/oauth2/{}",
+        domain.as_ref(),
+        authorization_server_id
+            .into()
+            .unwrap_or_else(|| AUTHORIZATION_SERVER_ID_DEFAULT.to_owned())
+    )
+}
 pub fn token_url(
     domain: impl AsRef<str>,
     authorization_server_id: impl Into<Option<String>>,
@@ -30,6 +55,19 @@ pub fn authorization_url(
             .unwrap_or_else(|| AUTHORIZATION_SERVER_ID_DEFAULT.to_owned())
     )
 }
+/// For `oauth2_client::logout::EndSessionRequest`.
+pub fn end_session_url(
+    domain: impl AsRef<str>,
+    authorization_server_id: impl Into<Option<String>>,
+) -> String {
+    format!(
+        "https://{}/oauth2/{}/v1/logout",
+        domain.as_ref(),
+        authorization_server_id
+            .into()
+            .unwrap_or_else(|| AUTHORIZATION_SERVER_ID_DEFAULT.to_owned())
+    )
+}
 
 // Ref https://developer.okta.com/docs/reference/api/oidc/#access-token-scopes-and-claims
 #[derive(Deserialize_enum_str, Serialize_enum_str, Debug, Clone, PartialEq, Eq)]
@@ -50,6 +50,10 @@ impl Provider for PinterestProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "pinterest"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for PinterestProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -103,7 +107,7 @@ impl ProviderExtAuthorizationCodeGrant for PinterestProviderWithWebApplication {
         ) -> Result<Request<Body>, Box<dyn std::error::Error + Send + Sync + 'static>> {
             let body = PinterestAccessTokenRequestBody {
                 grant_type: GRANT_TYPE_WITH_AUTHORIZATION_CODE_GRANT.to_owned(),
-                code: body.code.to_owned(),
+                code: body.code.expose_secret().to_owned(),
                 redirect_uri: this.redirect_uri.to_string(),
             };
             let body_str = serde_urlencoded::to_string(body)
@@ -116,8 +120,11 @@ impl ProviderExtAuthorizationCodeGrant for PinterestProviderWithWebApplication {
                 .uri(url.as_str())
                 .header(
                     AUTHORIZATION,
-                    http_authentication::Credentials::basic(&this.client_id, &this.client_secret)
-                        .to_string(),
+                    http_authentication::Credentials::basic(
+                        &this.client_id,
+                        this.client_secret.expose_secret(),
+                    )
+                    .to_string(),
                 )
                 .body(body_str.as_bytes().to_vec())
                 .map_err(AccessTokenRequestRenderingError::MakeRequestFailed)?;
@@ -149,16 +156,18 @@ pub enum AccessTokenRequestRenderingError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = PinterestProviderWithWebApplication::new(
             "APP_ID".to_owned(),
-            "APP_SECRET_KEY".to_owned(),
+            "APP_SECRET_KEY".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
@@ -166,7 +175,7 @@ mod tests {
             &provider,
             vec![PinterestScope::BoardsRead, PinterestScope::PinsRead],
         )
-        .configure(|x| x.state = Some("STATE".to_owned()))
+        .configure(|x| x.state = Some("STATE".into()))
         .render_request()?;
 
         assert_eq!(request.uri(), "https://www.pinterest.com/oauth/?response_type=code&client_id=APP_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=boards%3Aread%2Cpins%3Aread&state=STATE");
@@ -178,16 +187,15 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = PinterestProviderWithWebApplication::new(
             "APP_ID".to_owned(),
-            "APP_SECRET_KEY".to_owned(),
+            "APP_SECRET_KEY".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
@@ -2,7 +2,7 @@
 
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request, Response,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Request, Response,
     SerdeJsonError, Serialize, MIME_APPLICATION_JSON,
 };
 
@@ -11,12 +11,12 @@ pub const URL: &str = "https://api.pinterest.com/v5/user_account";
 //
 #[derive(Debug, Clone)]
 pub struct GetUserAccountEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl GetUserAccountEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -30,7 +30,10 @@ impl Endpoint for GetUserAccountEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(GetUserAccountEndpointError::MakeRequestFailed)?;
@@ -71,15 +74,19 @@ pub enum GetUserAccountEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_user() {
-        match serde_json::from_str::<UserAccount>(include_str!(
-            "../../tests/response_body_json_files/get_user_account.json"
-        )) {
+    fn de_user() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("get_user_account.json"))?
+            .replay(&GetUserAccountEndpoint::new("ACCESS_TOKEN"))
+        {
             Ok(user) => {
                 assert_eq!(user.username, "xxx");
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
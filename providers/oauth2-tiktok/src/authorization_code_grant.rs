@@ -6,7 +6,7 @@ use oauth2_client::{
             AccessTokenResponseErrorBody, AccessTokenResponseErrorBodyError,
             AccessTokenResponseSuccessfulBody,
         },
-        types::AccessTokenType,
+        types::{AccessToken, AccessTokenType, Code, RefreshToken},
     },
     re_exports::{
         http::Method, serde_json, serde_qs, thiserror, Body, ClientId, ClientSecret, HttpError,
@@ -15,7 +15,7 @@ use oauth2_client::{
     },
     Provider, ProviderExtAuthorizationCodeGrant,
 };
-use serde::{Deserialize, Serialize};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 use crate::{TiktokScope, AUTHORIZATION_URL, TOKEN_URL};
 
@@ -45,6 +45,24 @@ impl TiktokProviderWithWebApplication {
             authorization_endpoint_url: AUTHORIZATION_URL.parse()?,
         })
     }
+
+    /// What `access_token_response_parsing` does, with the extra typed as any `EXTRA` that can
+    /// be built from [`TiktokAccessTokenResponseExtra`], e.g. itself.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_access_token_response<EXTRA>(
+        response: &Response<Body>,
+    ) -> Result<
+        Result<AccessTokenResponseSuccessfulBody<TiktokScope, EXTRA>, AccessTokenResponseErrorBody>,
+        AccessTokenResponseParsingError,
+    >
+    where
+        EXTRA: From<TiktokAccessTokenResponseExtra> + Serialize + DeserializeOwned,
+    {
+        let body = serde_json::from_slice::<TiktokAccessTokenResponseBody>(response.body())
+            .map_err(AccessTokenResponseParsingError::DeResponseBodyFailed)?;
+
+        Ok(body.into())
+    }
 }
 impl Provider for TiktokProviderWithWebApplication {
     type Scope = TiktokScope;
@@ -60,6 +78,10 @@ impl Provider for TiktokProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "tiktok"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for TiktokProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -146,17 +168,7 @@ impl ProviderExtAuthorizationCodeGrant for TiktokProviderWithWebApplication {
             Box<dyn std::error::Error + Send + Sync + 'static>,
         >,
     > {
-        fn doing(
-            response: &Response<Body>,
-        ) -> Result<TiktokAccessTokenResponseBody, Box<dyn std::error::Error + Send + Sync + 'static>>
-        {
-            let body = serde_json::from_slice::<TiktokAccessTokenResponseBody>(response.body())
-                .map_err(AccessTokenResponseParsingError::DeResponseBodyFailed)?;
-
-            Ok(body)
-        }
-
-        Some(doing(response).map(Into::into))
+        Some(Self::parse_access_token_response(response).map_err(Into::into))
     }
 }
 
@@ -164,8 +176,8 @@ impl ProviderExtAuthorizationCodeGrant for TiktokProviderWithWebApplication {
 #[derive(Serialize, Deserialize)]
 pub struct TiktokAccessTokenRequestQuery {
     pub client_key: String,
-    pub client_secret: String,
-    pub code: String,
+    pub client_secret: ClientSecret,
+    pub code: Code,
     pub grant_type: String,
 }
 
@@ -191,9 +203,9 @@ pub enum TiktokAccessTokenResponseBody {
 pub struct TiktokAccessTokenResponseBodySuccessfulData {
     pub open_id: String,
     pub scope: String,
-    pub access_token: String,
+    pub access_token: AccessToken,
     pub expires_in: i64,
-    pub refresh_token: String,
+    pub refresh_token: RefreshToken,
     pub refresh_expires_in: i64,
 }
 
@@ -205,8 +217,28 @@ pub struct TiktokAccessTokenResponseBodyErrorData {
     pub error_code: i64,
 }
 
-impl From<TiktokAccessTokenResponseBody>
-    for Result<AccessTokenResponseSuccessfulBody<TiktokScope>, AccessTokenResponseErrorBody>
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct TiktokAccessTokenResponseExtra {
+    pub open_id: String,
+    pub refresh_expires_in: i64,
+}
+
+impl From<TiktokAccessTokenResponseExtra> for Map<String, Value> {
+    fn from(extra: TiktokAccessTokenResponseExtra) -> Self {
+        let mut map = Map::new();
+        map.insert(KEY_OPENID.to_owned(), Value::String(extra.open_id));
+        map.insert(
+            "refresh_expires_in".to_owned(),
+            Value::from(extra.refresh_expires_in),
+        );
+        map
+    }
+}
+
+impl<EXTRA> From<TiktokAccessTokenResponseBody>
+    for Result<AccessTokenResponseSuccessfulBody<TiktokScope, EXTRA>, AccessTokenResponseErrorBody>
+where
+    EXTRA: From<TiktokAccessTokenResponseExtra> + Serialize + DeserializeOwned,
 {
     fn from(body: TiktokAccessTokenResponseBody) -> Self {
         match body {
@@ -220,28 +252,23 @@ impl From<TiktokAccessTokenResponseBody>
                     })
                     .collect();
 
-                let mut map = Map::new();
-                map.insert(
-                    KEY_OPENID.to_owned(),
-                    Value::String(body.open_id.to_owned()),
-                );
-                map.insert(
-                    "refresh_expires_in".to_owned(),
-                    Value::Number(body.refresh_expires_in.into()),
-                );
+                let extra = TiktokAccessTokenResponseExtra {
+                    open_id: body.open_id.to_owned(),
+                    refresh_expires_in: body.refresh_expires_in,
+                };
 
-                let mut body = AccessTokenResponseSuccessfulBody::<TiktokScope>::new(
+                let mut body = AccessTokenResponseSuccessfulBody::new(
                     body.access_token.to_owned(),
                     AccessTokenType::Bearer,
                     Some(body.expires_in as usize),
-                    Some(body.refresh_token),
+                    Some(body.refresh_token.to_owned()),
                     if scope.is_empty() {
                         None
                     } else {
                         Some(scope.into())
                     },
                 );
-                body.set_extra(map);
+                body.set_extra(extra.into());
 
                 Ok(body)
             }
@@ -269,16 +296,18 @@ pub enum AccessTokenResponseParsingError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = TiktokProviderWithWebApplication::new(
             "CLIENT_KEY".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
@@ -286,7 +315,7 @@ mod tests {
             &provider,
             vec![TiktokScope::UserInfoBasic, TiktokScope::VideoList],
         )
-        .configure(|x| x.state = Some("STATE".to_owned()))
+        .configure(|x| x.state = Some("STATE".into()))
         .render_request()?;
 
         assert_eq!(request.uri(), "https://www.tiktok.com/auth/authorize/?response_type=code&client_key=CLIENT_KEY&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=user.info.basic%2Cvideo.list&state=STATE");
@@ -298,34 +327,45 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = TiktokProviderWithWebApplication::new(
             "CLIENT_KEY".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
-            Ok(body) => {
-                let map = body.extra().unwrap();
-                assert_eq!(
-                    map.get("open_id").unwrap().as_str(),
-                    Some("_000fwZ23Mw4RY9cB4lDQyKCgQg4Ft6SyTuE")
-                );
-            }
+            Ok(body) => assert_eq!(
+                body.extra().and_then(|x| x.get(KEY_OPENID)),
+                Some(&"_000fwZ23Mw4RY9cB4lDQyKCgQg4Ft6SyTuE".into())
+            ),
+            Err(body) => panic!("{body:?}"),
+        }
+
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
+        let response = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay_request(request)?;
+        match TiktokProviderWithWebApplication::parse_access_token_response::<
+            TiktokAccessTokenResponseExtra,
+        >(&response)?
+        {
+            Ok(body) => assert_eq!(
+                body.extra().map(|x| x.open_id.as_str()),
+                Some("_000fwZ23Mw4RY9cB4lDQyKCgQg4Ft6SyTuE")
+            ),
             Err(body) => panic!("{body:?}"),
         }
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_failed_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_failed_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
@@ -28,7 +28,7 @@ where
                 .contains(TiktokScope::UserInfoBasic.to_string().as_str())
             {
                 return Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-                    TiktokUserInfoEndpoint::new(&access_token.access_token),
+                    TiktokUserInfoEndpoint::new(access_token.access_token.expose_secret()),
                 )));
             }
         }
@@ -4,7 +4,7 @@ use std::collections::HashMap;
 
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request, Response,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Request, Response,
     SerdeJsonError, Serialize, Url, UrlParseError, MIME_APPLICATION_JSON,
 };
 
@@ -13,12 +13,12 @@ pub const URL: &str = "https://open.tiktokapis.com/v2/user/info/";
 //
 #[derive(Debug, Clone)]
 pub struct V2UserInfoEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl V2UserInfoEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -36,7 +36,10 @@ impl Endpoint for V2UserInfoEndpoint {
 
         let request = Request::builder()
             .uri(url.as_str())
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(V2UserInfoEndpointError::MakeRequestFailed)?;
@@ -117,21 +120,19 @@ pub enum V2UserInfoEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_response_body() {
-        match serde_json::from_str::<ResponseBody>(include_str!(
-            "../../tests/response_body_json_files/v2_user_info.json"
-        )) {
-            Ok(body) => {
-                assert_eq!(
-                    body.data
-                        .get("user")
-                        .map(|x| x.display_name.as_deref())
-                        .unwrap(),
-                    Some("122755990")
-                );
+    fn de_response_body() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("v2_user_info.json"))?
+            .replay(&V2UserInfoEndpoint::new("ACCESS_TOKEN"))
+        {
+            Ok(user) => {
+                assert_eq!(user.display_name.as_deref(), Some("122755990"));
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
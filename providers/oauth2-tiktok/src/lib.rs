@@ -1,8 +1,20 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const TOKEN_URL: &str = "https://open-api.tiktok.com/oauth/access_token/";
 pub const AUTHORIZATION_URL: &str = "https://www.tiktok.com/auth/authorize/";
 
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretQuery],
+    pkce: false,
+    oidc: false,
+    refresh_token: true,
+    revocation: true,
+    user_info: true,
+    scope_delimiter: ',',
+};
+
 pub mod authorization_code_grant;
 
 pub use authorization_code_grant::TiktokProviderWithWebApplication;
@@ -61,6 +61,10 @@ impl Provider for TwitchProviderForWebServerApps {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "twitch"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for TwitchProviderForWebServerApps {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -91,7 +95,7 @@ impl ProviderExtAuthorizationCodeGrant for TwitchProviderForWebServerApps {
         }
     }
 
-    #[allow(clippy::type_complexity)]
+    #[allow(clippy::type_complexity, clippy::result_large_err)]
     fn access_token_response_parsing(
         &self,
         response: &Response<Body>,
@@ -136,7 +140,7 @@ impl ProviderExtAuthorizationCodeGrant for TwitchProviderForWebServerApps {
             Ok(Err(body))
         }
 
-        Some(doing(response).map(|ret| ret.map(Into::into).map_err(Into::into)))
+        Some(doing(response).map(|ret| ret.map_err(Into::into)))
     }
 }
 
@@ -168,6 +172,8 @@ pub enum AccessTokenResponseParsingError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
         re_exports::{http::StatusCode, Endpoint as _},
@@ -177,7 +183,7 @@ mod tests {
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = TwitchProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?
         .configure(|x| {
@@ -185,7 +191,7 @@ mod tests {
         });
 
         let request = AuthorizationEndpoint::new(&provider, vec![TwitchScope::UserReadEmail])
-            .configure(|x| x.state = Some("STATE".to_owned()))
+            .configure(|x| x.state = Some("STATE".into()))
             .render_request()?;
 
         assert_eq!(request.uri(), "https://id.twitch.tv/oauth2/authorize?response_type=code&client_id=CLIENT_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=user%3Aread%3Aemail&state=STATE&force_verify=true");
@@ -197,11 +203,11 @@ mod tests {
     fn access_token_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = TwitchProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let request = AccessTokenEndpoint::new(&provider, "CODE".to_owned()).render_request()?;
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
 
         assert_eq!(request.body(), b"grant_type=authorization_code&code=CODE&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&client_id=CLIENT_ID&client_secret=CLIENT_SECRET");
 
@@ -212,13 +218,13 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = TwitchProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         //
         let response_body = r#"{"status":400, "message":"Invalid authorization code"}"#;
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned()).parse_response(
+        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".into()).parse_response(
             Response::builder()
                 .status(StatusCode::BAD_REQUEST)
                 .body(response_body.as_bytes().to_vec())?,
@@ -239,11 +245,10 @@ mod tests {
         }
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
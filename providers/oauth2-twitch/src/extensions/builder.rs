@@ -30,7 +30,7 @@ where
             .map_err(BuilderObtainUserInfoError::Unreachable)?;
 
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            TwitchUserInfoEndpoint::new(&access_token.access_token, client_id),
+            TwitchUserInfoEndpoint::new(access_token.access_token.expose_secret(), client_id),
         )))
     }
 }
@@ -1,6 +1,6 @@
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request, Response,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Request, Response,
     SerdeJsonError, Serialize, MIME_APPLICATION_JSON,
 };
 
@@ -9,13 +9,13 @@ pub const URL: &str = "https://api.twitch.tv/helix/users";
 //
 #[derive(Debug, Clone)]
 pub struct UsersEndpoint {
-    access_token: String,
+    access_token: AccessToken,
     client_id: String,
 }
 impl UsersEndpoint {
     pub fn new(access_token: impl AsRef<str>, client_id: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
             client_id: client_id.as_ref().to_owned(),
         }
     }
@@ -30,7 +30,10 @@ impl Endpoint for UsersEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let request = Request::builder()
             .uri(URL)
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header("Client-Id", &self.client_id)
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
@@ -59,6 +62,8 @@ pub struct User {
     pub id: String,
     pub login: String,
     pub email: Option<String>,
+    pub display_name: Option<String>,
+    pub profile_image_url: Option<String>,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -74,15 +79,27 @@ pub enum UsersEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_client::extensions::UserInfo;
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_users() {
-        match serde_json::from_str::<Users>(include_str!(
-            "../../tests/response_body_json_files/users.json"
-        )) {
+    fn de_users() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("users.json"))?
+            .replay(&UsersEndpoint::new("ACCESS_TOKEN", "CLIENT_ID"))
+        {
             Ok(users) => {
                 assert_eq!(users.data.len(), 1);
+
+                let user_info = UserInfo::try_from(users).map_err(|err| err.to_string())?;
+                assert_eq!(user_info.preferred_username, Some("heyongpeng".to_owned()));
+                assert!(user_info
+                    .picture
+                    .unwrap()
+                    .ends_with("profile_image-300x300.png"));
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -53,6 +53,10 @@ impl Provider for TwitterProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "twitter"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for TwitterProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -86,11 +90,12 @@ impl ProviderExtAuthorizationCodeGrant for TwitterProviderWithWebApplication {
         ) -> Result<Request<Body>, Box<dyn std::error::Error + Send + Sync + 'static>> {
             let body = TwitterAccessTokenRequestBody {
                 grant_type: GRANT_TYPE_WITH_AUTHORIZATION_CODE_GRANT.to_owned(),
-                code: body.code.to_owned(),
+                code: body.code.expose_secret().to_owned(),
                 redirect_uri: this.redirect_uri.to_string(),
                 code_verifier: body
                     .code_verifier
-                    .to_owned()
+                    .as_ref()
+                    .map(|x| x.expose_secret().to_owned())
                     .ok_or(AccessTokenRequestRenderingError::CodeVerifierMissing)?,
             };
             let body_str = serde_urlencoded::to_string(body)
@@ -103,8 +108,11 @@ impl ProviderExtAuthorizationCodeGrant for TwitterProviderWithWebApplication {
                 .uri(url.as_str())
                 .header(
                     AUTHORIZATION,
-                    http_authentication::Credentials::basic(&this.client_id, &this.client_secret)
-                        .to_string(),
+                    http_authentication::Credentials::basic(
+                        &this.client_id,
+                        this.client_secret.expose_secret(),
+                    )
+                    .to_string(),
                 )
                 .body(body_str.as_bytes().to_vec())
                 .map_err(AccessTokenRequestRenderingError::MakeRequestFailed)?;
@@ -139,16 +147,18 @@ pub enum AccessTokenRequestRenderingError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = TwitterProviderWithWebApplication::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
@@ -156,7 +166,7 @@ mod tests {
             &provider,
             vec![TwitterScope::TweetRead, TwitterScope::UsersRead],
         )
-        .configure(|x| x.state = Some("STATE".to_owned()))
+        .configure(|x| x.state = Some("STATE".into()))
         .render_request()?;
 
         assert_eq!(request.uri(), "https://twitter.com/i/oauth2/authorize?response_type=code&client_id=CLIENT_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=tweet.read+users.read&state=STATE");
@@ -168,16 +178,18 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = TwitterProviderWithWebApplication::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(
+            &AccessTokenEndpoint::new(&provider, "CODE".into())
+                .configure(|x| x.set_code_verifier("CODE_VERIFIER".into())),
+        )?;
 
         match body_ret {
             Ok(body) => {
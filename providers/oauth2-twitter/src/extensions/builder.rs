@@ -31,7 +31,7 @@ where
                     .contains(TwitterScope::TweetRead.to_string().as_str())
             {
                 return Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-                    TwitterUserInfoEndpoint::new(&access_token.access_token),
+                    TwitterUserInfoEndpoint::new(access_token.access_token.expose_secret()),
                 )));
             }
         }
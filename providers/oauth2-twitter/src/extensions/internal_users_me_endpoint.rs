@@ -2,7 +2,7 @@
 
 use oauth2_client::re_exports::{
     http::header::{ACCEPT, AUTHORIZATION},
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request, Response,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Request, Response,
     SerdeJsonError, Serialize, Url, UrlParseError, MIME_APPLICATION_JSON,
 };
 
@@ -11,12 +11,12 @@ pub const URL: &str = "https://api.twitter.com/2/users/me";
 //
 #[derive(Debug, Clone)]
 pub struct UsersMeEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl UsersMeEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -34,7 +34,10 @@ impl Endpoint for UsersMeEndpoint {
 
         let request = Request::builder()
             .uri(url.as_str())
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(UsersMeEndpointError::MakeRequestFailed)?;
@@ -108,22 +111,28 @@ pub enum UsersMeEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_client::re_exports::ClientRespondEndpointError;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_response_body() {
-        match serde_json::from_str::<ResponseOkBody>(include_str!(
-            "../../tests/response_body_json_files/users_me.json"
-        )) {
-            Ok(body) => {
-                assert_eq!(body.data.name, "HAWE");
+    fn de_response_body() -> Result<(), Box<dyn std::error::Error>> {
+        let endpoint = UsersMeEndpoint::new("ACCESS_TOKEN");
+
+        match ReplayClient::open(fixture_path!("users_me.json"))?.replay(&endpoint) {
+            Ok(user) => {
+                assert_eq!(user.name, "HAWE");
             }
             Err(err) => panic!("{err}"),
         }
 
-        match serde_json::from_str::<ResponseFailBody>(include_str!(
-            "../../tests/response_body_json_files/users_me_fail.json"
-        )) {
-            Ok(_) => {}
-            Err(err) => panic!("{err}"),
+        match ReplayClient::open(fixture_path!("users_me_fail.json"))?.replay(&endpoint) {
+            Err(ClientRespondEndpointError::EndpointParseResponseFailed(
+                UsersMeEndpointError::ResponseBodyIsFail(_),
+            )) => {}
+            ret => panic!("{ret:?}"),
         }
+
+        Ok(())
     }
 }
@@ -5,12 +5,13 @@ use oauth2_client::{
     },
     oauth2_core::{
         access_token_request::GRANT_TYPE_WITH_AUTHORIZATION_CODE_GRANT,
-        re_exports::AccessTokenResponseErrorBodyError, types::AccessTokenType,
+        re_exports::AccessTokenResponseErrorBodyError,
+        types::{AccessToken, AccessTokenType, Code, RefreshToken},
     },
     re_exports::{
-        serde_json, serde_qs, thiserror, Body, ClientId, ClientSecret, Deserialize, HttpError, Map,
-        RedirectUri, Request, Response, SerdeJsonError, SerdeQsError, Serialize, Url,
-        UrlParseError, Value,
+        serde::de::DeserializeOwned, serde_json, serde_qs, thiserror, Body, ClientId, ClientSecret,
+        Deserialize, HttpError, Map, RedirectUri, Request, Response, SerdeJsonError, SerdeQsError,
+        Serialize, Url, UrlParseError, Value,
     },
     Provider, ProviderExtAuthorizationCodeGrant,
 };
@@ -18,6 +19,7 @@ use oauth2_client::{
 use crate::{WechatScope, AUTHORIZATION_URL, TOKEN_URL};
 
 pub const KEY_OPENID: &str = "openid";
+pub const KEY_UNIONID: &str = "unionid";
 
 #[derive(Debug, Clone)]
 pub struct WechatProviderWithWebApplication {
@@ -52,6 +54,36 @@ impl WechatProviderWithWebApplication {
         f(&mut self);
         self
     }
+
+    /// What `access_token_response_parsing` does, with the extra typed as any `EXTRA` that can
+    /// be built from [`WechatAccessTokenResponseExtra`], e.g. itself.
+    #[allow(clippy::type_complexity)]
+    pub fn parse_access_token_response<EXTRA>(
+        response: &Response<Body>,
+    ) -> Result<
+        Result<AccessTokenResponseSuccessfulBody<WechatScope, EXTRA>, AccessTokenResponseErrorBody>,
+        AccessTokenResponseParsingError,
+    >
+    where
+        EXTRA: From<WechatAccessTokenResponseExtra> + Serialize + DeserializeOwned,
+    {
+        if response.status().is_success() {
+            let map = serde_json::from_slice::<Map<String, Value>>(response.body())
+                .map_err(AccessTokenResponseParsingError::DeResponseBodyFailed)?;
+            if !map.contains_key("errcode") {
+                let body = serde_json::from_slice::<WechatAccessTokenResponseSuccessfulBody>(
+                    response.body(),
+                )
+                .map_err(AccessTokenResponseParsingError::DeResponseBodyFailed)?;
+
+                return Ok(Ok(body.into()));
+            }
+        }
+
+        let body = serde_json::from_slice::<WechatAccessTokenResponseErrorBody>(response.body())
+            .map_err(AccessTokenResponseParsingError::DeResponseBodyFailed)?;
+        Ok(Err(body.into()))
+    }
 }
 impl Provider for WechatProviderWithWebApplication {
     type Scope = WechatScope;
@@ -67,6 +99,10 @@ impl Provider for WechatProviderWithWebApplication {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "wechat"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for WechatProviderWithWebApplication {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -112,7 +148,7 @@ impl ProviderExtAuthorizationCodeGrant for WechatProviderWithWebApplication {
                 redirect_uri,
                 response_type: query.response_type.to_owned(),
                 scope,
-                state: query.state.to_owned(),
+                state: query.state.as_ref().map(|x| x.expose_secret().to_owned()),
             };
 
             let query_str = serde_qs::to_string(&query)
@@ -161,7 +197,7 @@ impl ProviderExtAuthorizationCodeGrant for WechatProviderWithWebApplication {
         Some(doing(self, body))
     }
 
-    #[allow(clippy::type_complexity)]
+    #[allow(clippy::type_complexity, clippy::result_large_err)]
     fn access_token_response_parsing(
         &self,
         response: &Response<Body>,
@@ -174,32 +210,7 @@ impl ProviderExtAuthorizationCodeGrant for WechatProviderWithWebApplication {
             Box<dyn std::error::Error + Send + Sync + 'static>,
         >,
     > {
-        fn doing(
-            response: &Response<Body>,
-        ) -> Result<
-            Result<WechatAccessTokenResponseSuccessfulBody, WechatAccessTokenResponseErrorBody>,
-            Box<dyn std::error::Error + Send + Sync + 'static>,
-        > {
-            if response.status().is_success() {
-                let map = serde_json::from_slice::<Map<String, Value>>(response.body())
-                    .map_err(AccessTokenResponseParsingError::DeResponseBodyFailed)?;
-                if !map.contains_key("errcode") {
-                    let body = serde_json::from_slice::<WechatAccessTokenResponseSuccessfulBody>(
-                        response.body(),
-                    )
-                    .map_err(AccessTokenResponseParsingError::DeResponseBodyFailed)?;
-
-                    return Ok(Ok(body));
-                }
-            }
-
-            let body =
-                serde_json::from_slice::<WechatAccessTokenResponseErrorBody>(response.body())
-                    .map_err(AccessTokenResponseParsingError::DeResponseBodyFailed)?;
-            Ok(Err(body))
-        }
-
-        Some(doing(response).map(|ret| ret.map(Into::into).map_err(Into::into)))
+        Some(Self::parse_access_token_response(response).map_err(Into::into))
     }
 }
 
@@ -228,8 +239,8 @@ pub enum AuthorizationRequestQuerySerializingError {
 #[derive(Serialize, Deserialize)]
 pub struct WechatAccessTokenRequestQuery {
     pub appid: String,
-    pub secret: String,
-    pub code: String,
+    pub secret: ClientSecret,
+    pub code: Code,
     pub grant_type: String,
 }
 
@@ -244,14 +255,56 @@ pub enum AccessTokenRequestRenderingError {
 //
 #[derive(Serialize, Deserialize)]
 pub struct WechatAccessTokenResponseSuccessfulBody {
-    pub access_token: String,
+    pub access_token: AccessToken,
     pub expires_in: usize,
-    pub refresh_token: String,
+    pub refresh_token: RefreshToken,
     pub openid: String,
     pub scope: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unionid: Option<String>,
 }
-impl From<WechatAccessTokenResponseSuccessfulBody>
-    for AccessTokenResponseSuccessfulBody<WechatScope>
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WechatAccessTokenResponseExtra {
+    pub openid: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub unionid: Option<String>,
+}
+
+impl From<WechatAccessTokenResponseExtra> for Map<String, Value> {
+    fn from(extra: WechatAccessTokenResponseExtra) -> Self {
+        let mut map = Map::new();
+        map.insert(KEY_OPENID.to_owned(), Value::String(extra.openid));
+        if let Some(unionid) = extra.unionid {
+            map.insert(KEY_UNIONID.to_owned(), Value::String(unionid));
+        }
+        map
+    }
+}
+
+impl TryFrom<&Map<String, Value>> for WechatAccessTokenResponseExtra {
+    type Error = &'static str;
+
+    fn try_from(map: &Map<String, Value>) -> Result<Self, Self::Error> {
+        Ok(Self {
+            openid: map
+                .get(KEY_OPENID)
+                .ok_or("openid missing")?
+                .as_str()
+                .ok_or("openid mismatch")?
+                .to_owned(),
+            unionid: map
+                .get(KEY_UNIONID)
+                .and_then(|x| x.as_str())
+                .map(ToOwned::to_owned),
+        })
+    }
+}
+
+impl<EXTRA> From<WechatAccessTokenResponseSuccessfulBody>
+    for AccessTokenResponseSuccessfulBody<WechatScope, EXTRA>
+where
+    EXTRA: From<WechatAccessTokenResponseExtra> + Serialize + DeserializeOwned,
 {
     fn from(body: WechatAccessTokenResponseSuccessfulBody) -> Self {
         let scope: Vec<_> = body
@@ -263,21 +316,23 @@ impl From<WechatAccessTokenResponseSuccessfulBody>
             })
             .collect();
 
-        let mut map = Map::new();
-        map.insert(KEY_OPENID.to_owned(), Value::String(body.openid.to_owned()));
+        let extra = WechatAccessTokenResponseExtra {
+            openid: body.openid.to_owned(),
+            unionid: body.unionid.to_owned(),
+        };
 
         let mut body = Self::new(
             body.access_token.to_owned(),
             AccessTokenType::Bearer,
             Some(body.expires_in),
-            Some(body.refresh_token),
+            Some(body.refresh_token.to_owned()),
             if scope.is_empty() {
                 None
             } else {
                 Some(scope.into())
             },
         );
-        body.set_extra(map);
+        body.set_extra(extra.into());
 
         body
     }
@@ -309,16 +364,18 @@ pub enum AccessTokenResponseParsingError {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = WechatProviderWithWebApplication::new(
             "APPID".to_owned(),
-            "SECRET".to_owned(),
+            "SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?
         .configure(|x| {
@@ -326,7 +383,7 @@ mod tests {
         });
 
         let request = AuthorizationEndpoint::new(&provider, vec![WechatScope::SnsapiLogin])
-            .configure(|x| x.state = Some("3d6be0a4035d839573b04816624a415e".to_owned()))
+            .configure(|x| x.state = Some("3d6be0a4035d839573b04816624a415e".into()))
             .render_request()?;
 
         assert_eq!(request.uri(), "https://open.weixin.qq.com/connect/oauth2/authorize?appid=APPID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&response_type=code&scope=snsapi_login&state=3d6be0a4035d839573b04816624a415e#wechat_redirect");
@@ -338,11 +395,11 @@ mod tests {
     fn access_token_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = WechatProviderWithWebApplication::new(
             "APPID".to_owned(),
-            "SECRET".to_owned(),
+            "SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let request = AccessTokenEndpoint::new(&provider, "CODE".to_owned()).render_request()?;
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
 
         assert_eq!(request.method(), "GET");
         assert_eq!(request.uri(), "https://api.weixin.qq.com/sns/oauth2/access_token?appid=APPID&secret=SECRET&code=CODE&grant_type=authorization_code");
@@ -354,32 +411,45 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = WechatProviderWithWebApplication::new(
             "APPID".to_owned(),
-            "SECRET".to_owned(),
+            "SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
                 assert_eq!(body.access_token, "ACCESS_TOKEN");
                 assert_eq!(body.token_type, AccessTokenType::Bearer);
                 assert_eq!(body.expires_in, Some(7200));
-                assert_eq!(body.refresh_token, Some("REFRESH_TOKEN".to_owned()));
+                assert_eq!(body.refresh_token, Some("REFRESH_TOKEN".into()));
                 assert_eq!(
                     body.scope,
                     Some(vec![WechatScope::Other("SCOPE".to_owned())].into())
                 );
-                let map = body.extra().unwrap();
-                assert_eq!(map.get("openid").unwrap(), "OPENID");
+                let extra = WechatAccessTokenResponseExtra::try_from(body.extra().unwrap())?;
+                assert_eq!(extra.openid, "OPENID");
+                assert_eq!(extra.unionid, None);
             }
             Err(body) => panic!("{body:?}"),
         }
 
+        let request = AccessTokenEndpoint::new(&provider, "CODE".into()).render_request()?;
+        let response = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay_request(request)?;
+        match WechatProviderWithWebApplication::parse_access_token_response::<
+            WechatAccessTokenResponseExtra,
+        >(&response)?
+        {
+            Ok(body) => assert_eq!(body.extra().map(|x| x.openid.as_str()), Some("OPENID")),
+            Err(body) => panic!("{body:?}"),
+        }
+
         Ok(())
     }
 }
@@ -7,7 +7,7 @@ use oauth2_client::{
     re_exports::Scope,
 };
 
-use crate::{authorization_code_grant::KEY_OPENID, WechatScope};
+use crate::{authorization_code_grant::WechatAccessTokenResponseExtra, WechatScope};
 
 use super::WechatUserInfoEndpoint;
 
@@ -24,6 +24,14 @@ where
         grant_info: GrantInfo<SCOPE>,
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
+        let extra = || {
+            access_token
+                .extra()
+                .ok_or("extra missing")
+                .and_then(WechatAccessTokenResponseExtra::try_from)
+                .map_err(BuilderObtainUserInfoError::Unreachable)
+        };
+
         let has_snsapi_login_scope = access_token.scope.as_ref().map(|x| {
             ScopeParameter::<String>::from(x)
                 .0
@@ -31,42 +39,21 @@ where
         }) == Some(true);
 
         if has_snsapi_login_scope {
-            let openid = access_token
-                .extra()
-                .ok_or("extra missing")
-                .map_err(BuilderObtainUserInfoError::Unreachable)?
-                .get(KEY_OPENID)
-                .ok_or("openid missing")
-                .map_err(BuilderObtainUserInfoError::Unreachable)?
-                .as_str()
-                .ok_or("openid mismatch")
-                .map_err(BuilderObtainUserInfoError::Unreachable)?
-                .to_owned();
-
             return Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-                WechatUserInfoEndpoint::new(&access_token.access_token, openid),
+                WechatUserInfoEndpoint::new(
+                    access_token.access_token.expose_secret(),
+                    extra()?.openid,
+                ),
             )));
         }
 
         match grant_info {
             GrantInfo::AuthorizationCodeGrant(_) => {
-                let uid = access_token
-                    .extra()
-                    .ok_or("extra missing")
-                    .map_err(BuilderObtainUserInfoError::Unreachable)?
-                    .get(KEY_OPENID)
-                    .ok_or("openid missing")
-                    .map_err(BuilderObtainUserInfoError::Unreachable)?
-                    .as_str()
-                    .ok_or("openid mismatch")
-                    .map_err(BuilderObtainUserInfoError::Unreachable)?
-                    .to_owned();
-
                 return Ok(BuilderObtainUserInfoOutput::Static(UserInfo {
-                    uid,
+                    uid: extra()?.openid,
                     name: None,
                     email: None,
-                    raw: Default::default(),
+                    ..Default::default()
                 }));
             }
             GrantInfo::DeviceAuthorizationGrant(_) => {
@@ -1,6 +1,6 @@
 use oauth2_client::re_exports::{
-    http::header::ACCEPT, serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request,
-    Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
+    http::header::ACCEPT, serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint,
+    HttpError, Request, Response, SerdeJsonError, Serialize, Value, MIME_APPLICATION_JSON,
 };
 
 pub const URL: &str = "https://api.weixin.qq.com/sns/userinfo";
@@ -8,13 +8,13 @@ pub const URL: &str = "https://api.weixin.qq.com/sns/userinfo";
 //
 #[derive(Debug, Clone)]
 pub struct SnsUserinfoEndpoint {
-    access_token: String,
+    access_token: AccessToken,
     openid: String,
 }
 impl SnsUserinfoEndpoint {
     pub fn new(access_token: impl AsRef<str>, openid: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
             openid: openid.as_ref().to_owned(),
         }
     }
@@ -29,7 +29,9 @@ impl Endpoint for SnsUserinfoEndpoint {
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
         let url = format!(
             "{}?access_token={}&openid={}&lang=zh_CN",
-            URL, self.access_token, self.openid
+            URL,
+            self.access_token.expose_secret(),
+            self.openid
         );
 
         let request = Request::builder()
@@ -78,15 +80,23 @@ pub enum SnsUserinfoEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_client::extensions::UserInfo;
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_sns_userinfo() {
-        match serde_json::from_str::<SnsUserinfo>(include_str!(
-            "../../tests/response_body_json_files/sns_userinfo.json"
-        )) {
+    fn de_sns_userinfo() -> Result<(), Box<dyn std::error::Error>> {
+        match ReplayClient::open(fixture_path!("sns_userinfo.json"))?
+            .replay(&SnsUserinfoEndpoint::new("ACCESS_TOKEN", "OPENID"))
+        {
             Ok(user) => {
                 assert_eq!(user.openid, "OPENID");
+
+                let user_info = UserInfo::try_from(user).map_err(|err| err.to_string())?;
+                assert!(user_info.picture.is_some());
             }
             Err(err) => panic!("{err}"),
         }
+
+        Ok(())
     }
 }
@@ -1,12 +1,13 @@
 use oauth2_client::{
     extensions::{
         AccessTokenResponseSuccessfulBody, Builder, BuilderObtainUserInfoError,
-        BuilderObtainUserInfoOutput, GrantInfo,
+        BuilderObtainUserInfoOutput, GrantInfo, IdTokenExtensionsBuilder,
     },
     re_exports::Scope,
 };
 
 use super::YahooUserInfoEndpoint;
+use crate::ISSUER;
 
 //
 #[derive(Debug, Clone)]
@@ -18,15 +19,17 @@ where
 {
     fn obtain_user_info(
         &self,
-        _grant_info: GrantInfo<SCOPE>,
+        grant_info: GrantInfo<SCOPE>,
         access_token: &AccessTokenResponseSuccessfulBody<SCOPE>,
     ) -> Result<BuilderObtainUserInfoOutput, BuilderObtainUserInfoError> {
-        if let Some(_id_token) = &access_token.id_token {
-            // TODO
+        if access_token.id_token.is_some() {
+            return IdTokenExtensionsBuilder::new()
+                .with_issuer(ISSUER)
+                .obtain_user_info(grant_info, access_token);
         }
 
         Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-            YahooUserInfoEndpoint::new(&access_token.access_token),
+            YahooUserInfoEndpoint::new(access_token.access_token.expose_secret()),
         )))
     }
 }
@@ -71,6 +71,10 @@ impl Provider for ZohoProviderForWebServerApps {
     fn token_endpoint_url(&self) -> &Url {
         &self.token_endpoint_url
     }
+
+    fn name(&self) -> &str {
+        "zoho"
+    }
 }
 impl ProviderExtAuthorizationCodeGrant for ZohoProviderForWebServerApps {
     fn redirect_uri(&self) -> Option<&RedirectUri> {
@@ -114,16 +118,19 @@ impl ProviderExtAuthorizationCodeGrant for ZohoProviderForWebServerApps {
 mod tests {
     use super::*;
 
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     use oauth2_client::{
         authorization_code_grant::{AccessTokenEndpoint, AuthorizationEndpoint},
-        re_exports::{Endpoint as _, Response},
+        oauth2_core::types::Prompt,
+        re_exports::Endpoint as _,
     };
 
     #[test]
     fn authorization_request() -> Result<(), Box<dyn std::error::Error>> {
         let provider = ZohoProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?
         .configure(|x| {
@@ -132,11 +139,20 @@ mod tests {
         });
 
         let request = AuthorizationEndpoint::new(&provider, vec![ZohoScope::Site24x7AdminAll])
-            .configure(|x| x.state = Some("STATE".to_owned()))
+            .configure(|x| x.state = Some("STATE".into()))
             .render_request()?;
 
         assert_eq!(request.uri(), "https://accounts.zoho.com/oauth/v2/auth?response_type=code&client_id=CLIENT_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=Site24x7.Admin.All&state=STATE&access_type=offline&prompt=consent");
 
+        let request = AuthorizationEndpoint::new(&provider, vec![ZohoScope::Site24x7AdminAll])
+            .configure(|x| {
+                x.state = Some("STATE".into());
+                x.authentication.prompt = Some(vec![Prompt::Login].into());
+            })
+            .render_request()?;
+
+        assert_eq!(request.uri(), "https://accounts.zoho.com/oauth/v2/auth?response_type=code&client_id=CLIENT_ID&redirect_uri=https%3A%2F%2Fclient.example.com%2Fcb&scope=Site24x7.Admin.All&state=STATE&prompt=login&access_type=offline");
+
         Ok(())
     }
 
@@ -144,16 +160,15 @@ mod tests {
     fn access_token_response() -> Result<(), Box<dyn std::error::Error>> {
         let provider = ZohoProviderForWebServerApps::new(
             "CLIENT_ID".to_owned(),
-            "CLIENT_SECRET".to_owned(),
+            "CLIENT_SECRET".into(),
             RedirectUri::new("https://client.example.com/cb")?,
         )?;
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
@@ -167,11 +182,10 @@ mod tests {
         }
 
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant_and_offline.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant_and_offline.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
@@ -184,11 +198,10 @@ mod tests {
         When scopes include email or profile
         */
         //
-        let response_body = include_str!(
-            "../tests/response_body_json_files/access_token_with_authorization_code_grant_and_id_token.json"
-        );
-        let body_ret = AccessTokenEndpoint::new(&provider, "CODE".to_owned())
-            .parse_response(Response::builder().body(response_body.as_bytes().to_vec())?)?;
+        let body_ret = ReplayClient::open(fixture_path!(
+            "access_token_with_authorization_code_grant_and_id_token.json"
+        ))?
+        .replay(&AccessTokenEndpoint::new(&provider, "CODE".into()))?;
 
         match body_ret {
             Ok(body) => {
@@ -1,19 +1,28 @@
 use oauth2_client::{
     extensions::{
         AccessTokenResponseSuccessfulBody, Builder, BuilderObtainUserInfoError,
-        BuilderObtainUserInfoOutput, GrantInfo,
+        BuilderObtainUserInfoOutput, GrantInfo, IdTokenExtensionsBuilder,
     },
     oauth2_core::types::ScopeParameter,
     re_exports::{serde_json, Scope},
 };
 
 use super::ZohoUserInfoEndpoint;
-use crate::ZohoScope;
+use crate::{ZohoScope, ISSUER};
 
 //
 #[derive(Debug, Clone)]
 pub struct ZohoExtensionsBuilder;
 
+impl ZohoExtensionsBuilder {
+    /// Reads `UserInfo` from the `id_token` when there is one, saving the userinfo request.
+    pub fn with_id_token_fast_path() -> IdTokenExtensionsBuilder<Self> {
+        IdTokenExtensionsBuilder::new()
+            .with_issuer(ISSUER)
+            .with_fallback(Self)
+    }
+}
+
 impl<SCOPE> Builder<SCOPE> for ZohoExtensionsBuilder
 where
     SCOPE: Scope,
@@ -34,7 +43,7 @@ where
                 && !scope.0.contains(&ZohoScope::Profile)
             {
                 return Ok(BuilderObtainUserInfoOutput::Respond(Box::new(
-                    ZohoUserInfoEndpoint::new(&access_token.access_token),
+                    ZohoUserInfoEndpoint::new(access_token.access_token.expose_secret()),
                 )));
             }
         }
@@ -6,7 +6,7 @@ use oauth2_client::re_exports::{
         header::{ACCEPT, AUTHORIZATION},
         StatusCode,
     },
-    serde_json, thiserror, Body, Deserialize, Endpoint, HttpError, Request, Response,
+    serde_json, thiserror, AccessToken, Body, Deserialize, Endpoint, HttpError, Request, Response,
     SerdeJsonError, Serialize, MIME_APPLICATION_JSON,
 };
 
@@ -15,12 +15,12 @@ pub const URL: &str = "https://accounts.zoho.com/oauth/user/info";
 //
 #[derive(Debug, Clone)]
 pub struct OauthUserInfoEndpoint {
-    access_token: String,
+    access_token: AccessToken,
 }
 impl OauthUserInfoEndpoint {
     pub fn new(access_token: impl AsRef<str>) -> Self {
         Self {
-            access_token: access_token.as_ref().to_owned(),
+            access_token: access_token.as_ref().into(),
         }
     }
 }
@@ -35,7 +35,10 @@ impl Endpoint for OauthUserInfoEndpoint {
         let request = Request::builder()
             .uri(URL)
             // Or "Zoho-oauthtoken {}"
-            .header(AUTHORIZATION, format!("Bearer {}", &self.access_token))
+            .header(
+                AUTHORIZATION,
+                format!("Bearer {}", self.access_token.expose_secret()),
+            )
             .header(ACCEPT, MIME_APPLICATION_JSON)
             .body(vec![])
             .map_err(OauthUserInfoEndpointError::MakeRequestFailed)?;
@@ -115,12 +118,16 @@ pub enum OauthUserInfoEndpointError {
 mod tests {
     use super::*;
 
+    use oauth2_client::re_exports::ClientRespondEndpointError;
+
+    use oauth2_mock_server::{fixture_path, ReplayClient};
+
     #[test]
-    fn de_response_body() {
+    fn de_response_body() -> Result<(), Box<dyn std::error::Error>> {
+        let endpoint = OauthUserInfoEndpoint::new("ACCESS_TOKEN");
+
         //
-        match serde_json::from_str::<OauthUserInfoResponseBodyOkJson>(include_str!(
-            "../../tests/response_body_json_files/oauth_user_info.json"
-        )) {
+        match ReplayClient::open(fixture_path!("oauth_user_info.json"))?.replay(&endpoint) {
             Ok(ok_json) => {
                 assert_eq!(ok_json.zuid, 795542386);
             }
@@ -132,13 +139,16 @@ mod tests {
         When both AaaServer.profile.READ scope and profile scope are authorized.
         401
         */
-        match serde_json::from_str::<OauthUserInfoResponseBodyErrJson>(include_str!(
-            "../../tests/response_body_json_files/oauth_user_info_err.json"
-        )) {
-            Ok(err_json) => {
+        match ReplayClient::open(fixture_path!("oauth_user_info_err.json"))?.replay(&endpoint) {
+            Err(ClientRespondEndpointError::EndpointParseResponseFailed(
+                OauthUserInfoEndpointError::ResponseBodyError(status, Ok(err_json)),
+            )) => {
+                assert_eq!(status, StatusCode::UNAUTHORIZED);
                 assert_eq!(err_json.cause, "INVALID_OAUTHSCOPE");
             }
-            Err(err) => panic!("{err}"),
+            ret => panic!("{ret:?}"),
         }
+
+        Ok(())
     }
 }
@@ -1,3 +1,5 @@
+#[cfg(feature = "with-tracing")]
+use oauth2_client::trace;
 use oauth2_client::{
     extensions::{EndpointParseResponseError, EndpointRenderRequestError, UserInfo},
     re_exports::{serde_json, Body, Endpoint, Request, Response},
@@ -27,13 +29,26 @@ impl Endpoint for ZohoUserInfoEndpoint {
     type ParseResponseError = EndpointParseResponseError;
 
     fn render_request(&self) -> Result<Request<Body>, Self::RenderRequestError> {
-        self.inner.render_request().map_err(Into::into)
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::render_request_span("zoho", None, trace::endpoint::USER_INFO);
+
+        let request = self.inner.render_request()?;
+
+        #[cfg(feature = "with-tracing")]
+        trace::request_rendered(&request);
+
+        Ok(request)
     }
 
     fn parse_response(
         &self,
         response: Response<Body>,
     ) -> Result<Self::ParseResponseOutput, Self::ParseResponseError> {
+        #[cfg(feature = "with-tracing")]
+        let _span = trace::parse_response_span("zoho", None, trace::endpoint::USER_INFO);
+        #[cfg(feature = "with-tracing")]
+        trace::response_received(&response);
+
         UserInfoWrapper::try_from(self.inner.parse_response(response)?)
             .map(|x| x.0)
             .map_err(EndpointParseResponseError::ToOutputFailed)
@@ -78,9 +93,12 @@ impl TryFrom<OauthUserInfoResponseBodyOkJson> for UserInfoWrapper {
             uid: ok_json.zuid.to_string(),
             name: Some(ok_json.display_name.to_owned()),
             email: Some(ok_json.email.to_owned()),
+            given_name: Some(ok_json.first_name.to_owned()),
+            family_name: Some(ok_json.last_name.to_owned()),
             raw: serde_json::to_value(ok_json)
                 .map(|x| x.as_object().cloned())?
                 .ok_or_else(|| "unreachable".to_owned())?,
+            ..Default::default()
         }))
     }
 }
@@ -1,7 +1,22 @@
+use oauth2_client::capabilities::{ClientAuthMethod, GrantType, ProviderCapabilities};
 use oauth2_client::re_exports::{Deserialize_enum_str, Scope, Serialize_enum_str};
 
 pub const TOKEN_URL: &str = "https://accounts.zoho.com/oauth/v2/token";
 pub const AUTHORIZATION_URL: &str = "https://accounts.zoho.com/oauth/v2/auth";
+pub const ISSUER: &str = "https://accounts.zoho.com";
+/// For `oauth2_client::logout::EndSessionRequest`.
+pub const END_SESSION_URL: &str = "https://accounts.zoho.com/oauth/v2/logout";
+
+pub const CAPABILITIES: ProviderCapabilities = ProviderCapabilities {
+    grants: &[GrantType::AuthorizationCode],
+    client_auth_methods: &[ClientAuthMethod::ClientSecretPost],
+    pkce: false,
+    oidc: true,
+    refresh_token: true,
+    revocation: true,
+    user_info: true,
+    scope_delimiter: ' ',
+};
 
 pub mod authorization_code_grant;
 